@@ -11,6 +11,8 @@ use std::collections::HashMap;
 
 pub mod material;
 pub use self::material::Material;
+pub mod properties;
+pub use self::properties::{Properties, SoundGroup, Tool, ToolTier};
 
 pub use self::Block::*;
 use parking_lot::RwLock;
@@ -108,6 +110,7 @@ macro_rules! define_blocks {
                 $(variant $variant:expr,)?
                 $(tint $tint:expr,)?
                 $(collision $collision:expr,)?
+                $(outline $outline:expr,)?
                 $(update_state ($world:ident, $pos:ident) => $update_state:expr,)?
                 $(multipart ($mkey:ident, $mval:ident) => $multipart:expr,)?
             }
@@ -273,6 +276,22 @@ macro_rules! define_blocks {
                 }
             }
 
+            /// Returns the boxes outlined when the block is looked at, the
+            /// same as its collision unless the block says otherwise.
+            #[allow(unused_variables, unreachable_code)]
+            pub fn get_outline_boxes(&self) -> Vec<Aabb3<f64>> {
+                match *self {
+                    $(
+                        Block::$name {
+                            $($fname,)?
+                        } => {
+                            $(return $outline;)?
+                            self.get_collision_boxes()
+                        }
+                    )+
+                }
+            }
+
             #[allow(unused_variables, unreachable_code)]
             pub fn update_state<W: WorldAccess>(&self, world: &W, pos: Position) -> Block {
                 match *self {
@@ -636,6 +655,7 @@ define_blocks! {
         model { ("minecraft", format!("{}_sapling", variant.as_string()) ) },
         variant format!("stage={}", stage),
         collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 12.0, 14.0)],
     }
     Bedrock {
         props {},
@@ -947,6 +967,7 @@ define_blocks! {
         model { ("minecraft", "golden_rail") },
         variant format!("powered={},shape={}", powered, shape.as_string()),
         collision vec![],
+        outline rail_outline(shape),
     }
     DetectorRail {
         props {
@@ -966,6 +987,7 @@ define_blocks! {
         model { ("minecraft", "detector_rail") },
         variant format!("powered={},shape={}", powered, shape.as_string()),
         collision vec![],
+        outline rail_outline(shape),
     }
     StickyPiston {
         props {
@@ -994,6 +1016,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "web") },
         collision vec![],
+        outline full_cube(),
     }
     TallGrass {
         props {
@@ -1009,6 +1032,7 @@ define_blocks! {
         model { ("minecraft", variant.as_string() ) },
         tint TintType::Grass,
         collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 13.0, 14.0)],
     }
     Seagrass {
         props {},
@@ -1017,6 +1041,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "seagrass") },
         collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 12.0, 14.0)],
     }
     TallSeagrass {
         props {
@@ -1030,6 +1055,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "tall_seagrass") },
         collision vec![],
+        outline full_cube(),
     }
     DeadBush {
         props {},
@@ -1037,6 +1063,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "dead_bush") },
         collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 13.0, 14.0)],
     }
     Piston {
         props {
@@ -1143,6 +1170,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "dandelion") },
         collision vec![],
+        outline vec![cuboid(5.0, 0.0, 5.0, 11.0, 10.0, 11.0)],
     }
     RedFlower {
         props {
@@ -1166,6 +1194,7 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", variant.as_string()) },
         collision vec![],
+        outline vec![cuboid(5.0, 0.0, 5.0, 11.0, 10.0, 11.0)],
     }
     BrownMushroom {
         props {},
@@ -1175,12 +1204,14 @@ define_blocks! {
         },
         model { ("minecraft", "brown_mushroom") },
         collision vec![],
+        outline vec![cuboid(5.0, 0.0, 5.0, 11.0, 6.0, 11.0)],
     }
     RedMushroom {
         props {},
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "red_mushroom") },
         collision vec![],
+        outline vec![cuboid(5.0, 0.0, 5.0, 11.0, 6.0, 11.0)],
     }
     GoldBlock {
         props {},
@@ -1304,6 +1335,7 @@ define_blocks! {
         model { ("minecraft", "torch") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline torch_outline(facing),
     }
     Fire {
         props {
@@ -1406,6 +1438,7 @@ define_blocks! {
             facing.horizontal_offset() * (2 * 3)),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "chest") },
+        collision chest_collision(facing, type_),
     }
     RedstoneWire {
         props {
@@ -1433,6 +1466,7 @@ define_blocks! {
         model { ("minecraft", "redstone_wire") },
         tint TintType::Color{r: ((255.0 / 30.0) * (f64::from(power)) + 14.0) as u8, g: 0, b: 0},
         collision vec![],
+        outline vec![cuboid(0.0, 0.0, 0.0, 16.0, 1.0, 16.0)],
         update_state (world, pos) => Block::RedstoneWire {
             north: can_connect_redstone(world, pos, Direction::North),
             south: can_connect_redstone(world, pos, Direction::South),
@@ -1469,6 +1503,7 @@ define_blocks! {
         model { ("minecraft", "wheat") },
         variant format!("age={}", age),
         collision vec![],
+        outline crop_outline(age),
     }
     Farmland {
         props {
@@ -1562,6 +1597,7 @@ define_blocks! {
         material material::INTERACTABLE,
         model { ("minecraft", "standing_sign") },
         collision vec![],
+        outline vec![cuboid(4.0, 0.0, 4.0, 12.0, 16.0, 12.0)],
     }
     WoodenDoor {
         props {
@@ -1602,6 +1638,7 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "ladder") },
         variant format!("facing={}", facing.as_string()),
+        collision ladder_collision(facing),
     }
     Rail {
         props {
@@ -1623,6 +1660,7 @@ define_blocks! {
         model { ("minecraft", "rail") },
         variant format!("shape={}", shape.as_string()),
         collision vec![],
+        outline rail_outline(shape),
     }
     StoneStairs {
         props {
@@ -1685,6 +1723,7 @@ define_blocks! {
         model { ("minecraft", "wall_sign") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline wall_sign_outline(facing),
     }
     Lever {
         props {
@@ -1707,6 +1746,7 @@ define_blocks! {
         model { ("minecraft", "lever") },
         variant format!("facing={},powered={}", face.variant_with_facing(facing), powered),
         collision vec![],
+        outline lever_outline(face, facing),
     }
     StonePressurePlate {
         props {
@@ -1718,6 +1758,7 @@ define_blocks! {
         model { ("minecraft", "stone_pressure_plate") },
         variant format!("powered={}", powered),
         collision vec![],
+        outline pressure_plate_outline(powered),
     }
     IronDoor {
         props {
@@ -1761,6 +1802,7 @@ define_blocks! {
         model { ("minecraft", "wooden_pressure_plate") },
         variant format!("powered={}", powered),
         collision vec![],
+        outline pressure_plate_outline(powered),
     }
     RedstoneOre {
         props {
@@ -1804,6 +1846,7 @@ define_blocks! {
         model { ("minecraft", "unlit_redstone_torch") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline torch_outline(facing),
     }
     RedstoneTorchLit {
         props {
@@ -1833,6 +1876,7 @@ define_blocks! {
         model { ("minecraft", "redstone_torch") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline torch_outline(facing),
     }
     RedstoneTorchStanding {
         props {
@@ -1844,6 +1888,7 @@ define_blocks! {
         model { ("minecraft", if lit { "redstone_torch" } else { "unlit_redstone_torch" }) },
         variant "facing=up",
         collision vec![],
+        outline torch_outline(Direction::Up),
     }
     RedstoneTorchWall {
         props {
@@ -1864,6 +1909,7 @@ define_blocks! {
         model { ("minecraft", if lit { "redstone_torch" } else { "unlit_redstone_torch" }) },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline torch_outline(facing),
     }
     StoneButton {
         props {
@@ -1885,6 +1931,8 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "stone_button") },
         variant format!("facing={},powered={}", face.variant_with_facing(facing), powered),
+        collision vec![],
+        outline button_outline(face, facing, powered),
     }
     SnowLayer {
         props {
@@ -1894,10 +1942,12 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "snow_layer") },
         variant format!("layers={}", layers),
-        collision vec![Aabb3::new(
-            Point3::new(0.0, 0.0, 0.0),
-            Point3::new(1.0, (f64::from(layers) - 1.0)/8.0, 1.0),
-        )],
+        collision if layers == 1 {
+            vec![]
+        } else {
+            vec![cuboid(0.0, 0.0, 0.0, 16.0, f64::from(layers - 1) * 2.0, 16.0)]
+        },
+        outline vec![cuboid(0.0, 0.0, 0.0, 16.0, f64::from(layers) * 2.0, 16.0)],
     }
     Ice {
         props {},
@@ -1922,6 +1972,7 @@ define_blocks! {
             Point3::new(1.0/16.0, 0.0, 1.0/16.0),
             Point3::new(1.0 - (1.0/16.0), 1.0 - (1.0/16.0), 1.0 - (1.0/16.0))
         )],
+        outline vec![cuboid(1.0, 0.0, 1.0, 15.0, 16.0, 15.0)],
     }
     Clay {
         props {},
@@ -1936,6 +1987,7 @@ define_blocks! {
         model { ("minecraft", "reeds") },
         tint TintType::Foliage,
         collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 16.0, 14.0)],
     }
     Jukebox {
         props {
@@ -1961,7 +2013,8 @@ define_blocks! {
             if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::Fence{north, south, west, east, waterlogged}
@@ -2007,6 +2060,7 @@ define_blocks! {
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 7.0/8.0, 1.0)
         )],
+        outline full_cube(),
     }
     SoulSoil {
         props {},
@@ -2047,6 +2101,8 @@ define_blocks! {
         data None,
         offsets |protocol_version| { if protocol_version >= 735 { Some(0) } else { None } },
         model { ("minecraft", "soul_torch") },
+        collision vec![],
+        outline torch_outline(Direction::Up),
     }
     SoulWallTorch {
         props {
@@ -2060,6 +2116,8 @@ define_blocks! {
         data None,
         offsets |protocol_version| { if protocol_version >= 735 { Some(facing.offset()) } else { None } },
         model { ("minecraft", "soul_wall_torch") },
+        collision vec![],
+        outline torch_outline(facing),
     }
     Glowstone {
         props {},
@@ -2082,6 +2140,7 @@ define_blocks! {
         model { ("minecraft", "portal") },
         variant format!("axis={}", axis.as_string()),
         collision vec![],
+        outline portal_outline(axis),
     }
     PumpkinCarved {
         props {
@@ -2373,6 +2432,7 @@ define_blocks! {
             }
         },
         model { ("minecraft", "chain") },
+        collision chain_collision(axis),
     }
     GlassPane {
         props {
@@ -2422,6 +2482,7 @@ define_blocks! {
         model { ("minecraft", "pumpkin_stem") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline attached_stem_outline(facing),
         update_state (world, pos) => {
             let facing = match (world.get_block(pos.shift(Direction::East)), world.get_block(pos.shift(Direction::West)),
                                 world.get_block(pos.shift(Direction::North)), world.get_block(pos.shift(Direction::South))) {
@@ -2450,6 +2511,7 @@ define_blocks! {
         model { ("minecraft", "melon_stem") },
         variant format!("facing={}", facing.as_string()),
         collision vec![],
+        outline attached_stem_outline(facing),
         update_state (world, pos) => {
             let facing = match (world.get_block(pos.shift(Direction::East)), world.get_block(pos.shift(Direction::West)),
                                 world.get_block(pos.shift(Direction::North)), world.get_block(pos.shift(Direction::South))) {
@@ -2486,6 +2548,7 @@ define_blocks! {
         },
         tint TintType::Color{r: age as u8 * 32, g: 255 - (age as u8 * 8), b: age as u8 * 4},
         collision vec![],
+        outline stem_outline(age, facing),
         update_state (world, pos) => {
             let facing = match (world.get_block(pos.shift(Direction::East)), world.get_block(pos.shift(Direction::West)),
                                 world.get_block(pos.shift(Direction::North)), world.get_block(pos.shift(Direction::South))) {
@@ -2522,6 +2585,7 @@ define_blocks! {
         },
         tint TintType::Color{r: age as u8 * 32, g: 255 - (age as u8 * 8), b: age as u8 * 4},
         collision vec![],
+        outline stem_outline(age, facing),
         update_state (world, pos) => {
             let facing = match (world.get_block(pos.shift(Direction::East)), world.get_block(pos.shift(Direction::West)),
                                 world.get_block(pos.shift(Direction::North)), world.get_block(pos.shift(Direction::South))) {
@@ -2561,6 +2625,7 @@ define_blocks! {
         variant format!("east={},north={},south={},up={},west={}", east, north, south, up, west),
         tint TintType::Foliage,
         collision vec![],
+        outline vine_outline(up, north, south, west, east),
         update_state (world, pos) => {
             let mat = world.get_block(pos.shift(Direction::Up)).get_material();
             let up = mat.renderable && (mat.should_cull_against || mat.never_cull /* Because leaves */);
@@ -2584,7 +2649,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::FenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -2685,7 +2751,8 @@ define_blocks! {
             if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "nether_brick_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let f = |block| matches!(block, Block::NetherBrickFence{..} |
                 Block::FenceGate{..} |
@@ -2741,6 +2808,7 @@ define_blocks! {
         model { ("minecraft", "nether_wart") },
         variant format!("age={}", age),
         collision vec![],
+        outline vec![cuboid(0.0, 0.0, 0.0, 16.0, 5.0 + f64::from(age) * 3.0, 16.0)],
     }
     EnchantingTable {
         props {},
@@ -2768,6 +2836,7 @@ define_blocks! {
             ..material::PARTIALLY_SOLID
         },
         model { ("minecraft", "brewing_stand") },
+        collision vec![cuboid(1.0, 0.0, 1.0, 15.0, 2.0, 15.0), cuboid(7.0, 0.0, 7.0, 9.0, 14.0, 9.0)],
         multipart (key, val) => match key {
             "has_bottle_0" => (val == "true") == has_bottle_0,
             "has_bottle_1" => (val == "true") == has_bottle_1,
@@ -2783,6 +2852,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "cauldron") },
         variant format!("level={}", level),
+        collision cauldron_collision(),
+        outline full_cube(),
     }
     EndPortal {
         props {},
@@ -2792,6 +2863,7 @@ define_blocks! {
         },
         model { ("minecraft", "end_portal") },
         collision vec![],
+        outline vec![cuboid(0.0, 0.0, 0.0, 16.0, 12.0, 16.0)],
     }
     EndPortalFrame {
         props {
@@ -2972,10 +3044,7 @@ define_blocks! {
         },
         model { ("minecraft", "ender_chest") },
         variant format!("facing={}", facing.as_string()),
-        collision vec![Aabb3::new(
-            Point3::new(1.0/16.0, 0.0, 1.0/16.0),
-            Point3::new(15.0/16.0, 7.0/8.0, 15.0/16.0)
-        )],
+        collision vec![cuboid(1.0, 0.0, 1.0, 15.0, 14.0, 15.0)],
     }
     TripwireHook {
         props {
@@ -2998,6 +3067,7 @@ define_blocks! {
         model { ("minecraft", "tripwire_hook") },
         variant format!("attached={},facing={},powered={}", attached, facing.as_string(), powered),
         collision vec![],
+        outline tripwire_hook_outline(facing),
     }
     Tripwire {
         props {
@@ -3033,6 +3103,11 @@ define_blocks! {
         model { ("minecraft", "tripwire") },
         variant format!("attached={},east={},north={},south={},west={}", attached, east, north, south, west),
         collision vec![],
+        outline if attached {
+            vec![cuboid(0.0, 1.0, 0.0, 16.0, 2.5, 16.0)]
+        } else {
+            vec![cuboid(0.0, 0.0, 0.0, 16.0, 8.0, 16.0)]
+        },
         update_state (world, pos) => {
             let f = |dir| {
                 match world.get_block(pos.shift(dir)) {
@@ -3184,6 +3259,8 @@ define_blocks! {
                     if variant == CobblestoneWallVariant::Normal { 0 } else { 1<<6 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", format!("{}_wall", variant.as_string())) },
+        collision wall_shape(up, north, south, west, east, 24.0, 24.0),
+        outline wall_shape(up, north, south, west, east, 16.0, 14.0),
         update_state (world, pos) => {
             let f = |block| matches!(block, Block::CobblestoneWall{..} |
                 Block::FenceGate{..} |
@@ -3244,6 +3321,7 @@ define_blocks! {
         },
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "flower_pot") },
+        collision vec![cuboid(5.0, 0.0, 5.0, 11.0, 6.0, 11.0)],
     }
     Carrots {
         props {
@@ -3254,6 +3332,7 @@ define_blocks! {
         model { ("minecraft", "carrots") },
         variant format!("age={}", age),
         collision vec![],
+        outline crop_outline(age),
     }
     Potatoes {
         props {
@@ -3264,6 +3343,7 @@ define_blocks! {
         model { ("minecraft", "potatoes") },
         variant format!("age={}", age),
         collision vec![],
+        outline crop_outline(age),
     }
     WoodenButton {
         props {
@@ -3293,6 +3373,8 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "wooden_button") },
         variant format!("facing={},powered={}", face.variant_with_facing(facing), powered),
+        collision vec![],
+        outline button_outline(face, facing, powered),
     }
     SkullSkeletonWall {
         props {
@@ -3310,21 +3392,7 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "skull") },
         variant format!("facing={},nodrop={}", facing.as_string(), nodrop),
-        collision {
-            let (min_x, min_y, min_z, max_x, max_y, max_z) = match facing {
-                Direction::Up => (0.25, 0.0, 0.25, 0.75, 0.5, 0.75),
-                Direction::North => (0.25, 0.25, 0.5, 0.75, 0.75, 1.0),
-                Direction::South => (0.25, 0.25, 0.0, 0.75, 0.75, 0.5),
-                Direction::West => (0.5, 0.25, 0.25, 1.0, 0.75, 0.75),
-                Direction::East => (0.0, 0.25, 0.25, 0.5, 0.75, 0.75),
-                _ => unreachable!(),
-            };
-
-            vec![Aabb3::new(
-                Point3::new(min_x, min_y, min_z),
-                Point3::new(max_x, max_y, max_z)
-            )]
-        },
+        collision skull_collision(facing),
     }
     SkullSkeleton
     {
@@ -3357,20 +3425,7 @@ define_blocks! {
         offset Some(facing.horizontal_offset()),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "skull") },
-        collision {
-            let (min_x, min_y, min_z, max_x, max_y, max_z) = match facing {
-                Direction::North => (0.25, 0.25, 0.5, 0.75, 0.75, 1.0),
-                Direction::South => (0.25, 0.25, 0.0, 0.75, 0.75, 0.5),
-                Direction::West => (0.5, 0.25, 0.25, 1.0, 0.75, 0.75),
-                Direction::East => (0.0, 0.25, 0.25, 0.5, 0.75, 0.75),
-                _ => unreachable!(),
-            };
-
-            vec![Aabb3::new(
-                Point3::new(min_x, min_y, min_z),
-                Point3::new(max_x, max_y, max_z)
-            )]
-        },
+        collision skull_collision(facing),
     }
     SkullWitherSkeleton {
         props {
@@ -3380,14 +3435,7 @@ define_blocks! {
         offset Some(rotation as usize),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "skull") },
-        collision {
-            let (min_x, min_y, min_z, max_x, max_y, max_z) = (0.25, 0.0, 0.25, 0.75, 0.5, 0.75);
-
-            vec![Aabb3::new(
-                Point3::new(min_x, min_y, min_z),
-                Point3::new(max_x, max_y, max_z)
-            )]
-        },
+        collision skull_collision(Direction::Up),
     }
     ZombieWallHead {
         props {
@@ -3402,6 +3450,7 @@ define_blocks! {
         offset Some(facing.horizontal_offset()),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "zombie_wall_head") },
+        collision skull_collision(facing),
     }
     ZombieHead {
         props {
@@ -3411,6 +3460,7 @@ define_blocks! {
         offset Some(rotation as usize),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "zombie_head") },
+        collision skull_collision(Direction::Up),
     }
     PlayerWallHead {
         props {
@@ -3425,6 +3475,7 @@ define_blocks! {
         offset Some(facing.horizontal_offset()),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "player_wall_head") },
+        collision skull_collision(facing),
     }
     PlayerHead {
         props {
@@ -3434,6 +3485,7 @@ define_blocks! {
         offset Some(rotation as usize),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "player_head") },
+        collision skull_collision(Direction::Up),
     }
     CreeperWallHead {
         props {
@@ -3448,6 +3500,7 @@ define_blocks! {
         offset Some(facing.horizontal_offset()),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "creeper_wall_head") },
+        collision skull_collision(facing),
     }
     CreeperHead {
         props {
@@ -3457,6 +3510,7 @@ define_blocks! {
         offset Some(rotation as usize),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "creeper_head") },
+        collision skull_collision(Direction::Up),
     }
     DragonWallHead {
         props {
@@ -3471,6 +3525,7 @@ define_blocks! {
         offset Some(facing.horizontal_offset()),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "dragon_wall_head") },
+        collision skull_collision(facing),
     }
     DragonHead {
         props {
@@ -3480,6 +3535,7 @@ define_blocks! {
         offset Some(rotation as usize),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "dragon_head") },
+        collision skull_collision(Direction::Up),
     }
     Anvil {
         props {
@@ -3530,10 +3586,7 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "trapped_chest") },
         variant format!("facing={}", facing.as_string()),
-        collision vec![Aabb3::new(
-            Point3::new(1.0/16.0, 0.0, 1.0/16.0),
-            Point3::new(15.0/16.0, 7.0/8.0, 15.0/16.0)
-        )],
+        collision chest_collision(facing, type_),
     }
     LightWeightedPressurePlate {
         props {
@@ -3544,6 +3597,7 @@ define_blocks! {
         model { ("minecraft", "light_weighted_pressure_plate") },
         variant format!("power={}", power),
         collision vec![],
+        outline pressure_plate_outline(power > 0),
     }
     HeavyWeightedPressurePlate {
         props {
@@ -3554,6 +3608,7 @@ define_blocks! {
         model { ("minecraft", "heavy_weighted_pressure_plate") },
         variant format!("power={}", power),
         collision vec![],
+        outline pressure_plate_outline(power > 0),
     }
     ComparatorUnpowered {
         props {
@@ -3649,6 +3704,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "hopper") },
         variant format!("facing={}", facing.as_string()),
+        collision hopper_collision(),
+        outline full_cube(),
     }
     QuartzBlock {
         props {
@@ -3714,6 +3771,7 @@ define_blocks! {
         model { ("minecraft", "activator_rail") },
         variant format!("powered={},shape={}", powered, shape.as_string()),
         collision vec![],
+        outline rail_outline(shape),
     }
     Dropper {
         props {
@@ -4074,6 +4132,7 @@ define_blocks! {
         variant format!("half={}", half.as_string()),
         tint TintType::Foliage,
         collision vec![],
+        outline full_cube(),
         update_state (world, pos) => {
             let (half, variant) = update_double_plant_state(world, pos, half, variant);
             Block::DoublePlant{half, variant}
@@ -4123,6 +4182,8 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "standing_banner") },
         variant format!("rotation={}", rotation.as_string()),
+        collision vec![],
+        outline vec![cuboid(4.0, 0.0, 4.0, 12.0, 16.0, 12.0)],
     }
     WallBanner {
         props {
@@ -4156,6 +4217,8 @@ define_blocks! {
         material material::NON_SOLID,
         model { ("minecraft", "wall_banner") },
         variant format!("facing={}", facing.as_string()),
+        collision vec![],
+        outline wall_banner_outline(facing),
     }
     DaylightDetectorInverted {
         props {
@@ -4322,7 +4385,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "spruce_fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::SpruceFenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -4347,7 +4411,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "birch_fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::BirchFenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -4372,7 +4437,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "jungle_fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::JungleFenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -4397,7 +4463,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "dark_oak_fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::DarkOakFenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -4422,7 +4489,8 @@ define_blocks! {
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "acacia_fence_gate") },
         variant format!("facing={},in_wall={},open={}", facing.as_string(), in_wall, open),
-        collision fence_gate_collision(facing, in_wall, open),
+        collision fence_gate_collision(facing, open),
+        outline fence_gate_outline(facing, in_wall),
         update_state (world, pos) => Block::AcaciaFenceGate{
             facing,
             in_wall: fence_gate_update_state(world, pos, facing),
@@ -4446,7 +4514,8 @@ define_blocks! {
                     if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "spruce_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::SpruceFence{north, south, west, east, waterlogged}
@@ -4475,7 +4544,8 @@ define_blocks! {
                     if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "birch_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::BirchFence{north, south, west, east, waterlogged}
@@ -4504,7 +4574,8 @@ define_blocks! {
                     if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "jungle_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::JungleFence{north, south, west, east, waterlogged}
@@ -4533,7 +4604,8 @@ define_blocks! {
                     if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "dark_oak_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::DarkOakFence{north, south, west, east, waterlogged}
@@ -4562,7 +4634,8 @@ define_blocks! {
                     if east { 0 } else { 1<<4 }),
         material material::PARTIALLY_SOLID,
         model { ("minecraft", "acacia_fence") },
-        collision fence_collision(north, south, west, east),
+        collision fence_shape(north, south, west, east, 24.0),
+        outline fence_shape(north, south, west, east, 16.0),
         update_state (world, pos) => {
             let (north, south, west, east) = can_connect_sides(world, pos, &can_connect_fence);
             Block::AcaciaFence{north, south, west, east, waterlogged}
@@ -4898,6 +4971,7 @@ define_blocks! {
         model { ("minecraft", "beetroots") },
         variant format!("age={}", age),
         collision vec![],
+        outline crop_outline(age),
     }
     GrassPath {
         props {},
@@ -4984,8 +5058,8 @@ define_blocks! {
             .. material::INVISIBLE
         },
         model { ("minecraft", "structure_void") },
-        // TODO: a small hit box but no collision
         collision vec![],
+        outline vec![cuboid(5.0, 5.0, 5.0, 11.0, 11.0, 11.0)],
     }
     Observer {
         props {
@@ -5541,12 +5615,16 @@ define_blocks! {
         data None::<usize>,
         offset Some(age as usize),
         model { ("minecraft", "kelp") },
+        collision vec![],
+        outline vec![cuboid(0.0, 0.0, 0.0, 16.0, 9.0, 16.0)],
     }
     KelpPlant {
         props {},
         data None::<usize>,
         offset Some(0),
         model { ("minecraft", "kelp_plant") },
+        collision vec![],
+        outline full_cube(),
     }
     DriedKelpBlock {
         props {},
@@ -5562,6 +5640,11 @@ define_blocks! {
         data None::<usize>,
         offset Some((hatch as usize) + ((age - 1) as usize) * 3),
         model { ("minecraft", "turtle_egg") },
+        collision if age == 1 {
+            vec![cuboid(3.0, 0.0, 3.0, 12.0, 7.0, 12.0)]
+        } else {
+            vec![cuboid(1.0, 0.0, 1.0, 15.0, 7.0, 15.0)]
+        },
     }
     CoralBlock {
         props {
@@ -5601,6 +5684,8 @@ define_blocks! {
         data None::<usize>,
         offset Some(if waterlogged { 0 } else { 1 } + variant.offset() * 2),
         model { ("minecraft", variant.as_string()) },
+        collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 15.0, 14.0)],
     }
     CoralWallFan {
         props {
@@ -5629,6 +5714,8 @@ define_blocks! {
                     facing.horizontal_offset() * 2 +
                     variant.offset() * (2 * 4)),
         model { ("minecraft", format!("{}_wall_fan", variant.as_string())) },
+        collision vec![],
+        outline rotate_shape(vec![cuboid(0.0, 4.0, 5.0, 16.0, 12.0, 16.0)], facing),
     }
     CoralFan {
         props {
@@ -5650,6 +5737,8 @@ define_blocks! {
         offset Some(if waterlogged { 0 } else { 1 } +
                     variant.offset() * 2),
         model { ("minecraft", format!("{}_fan", variant.as_string())) },
+        collision vec![],
+        outline vec![cuboid(2.0, 0.0, 2.0, 14.0, 4.0, 14.0)],
     }
     SeaPickle {
         props {
@@ -5661,6 +5750,7 @@ define_blocks! {
                     ((age - 1) as usize) * 2),
         model { ("minecraft", "sea_pickle") },
         variant format!("age={}", age),
+        collision sea_pickle_collision(age),
     }
     BlueIce {
         props {},
@@ -5676,6 +5766,7 @@ define_blocks! {
         offset Some(if waterlogged { 0 } else { 1 }),
         material material::NON_SOLID,
         model { ("minecraft", "conduit") },
+        collision vec![cuboid(5.0, 5.0, 5.0, 11.0, 11.0, 11.0)],
     }
     VoidAir {
         props {},
@@ -5706,6 +5797,7 @@ define_blocks! {
         data None::<usize>,
         offset Some(if drag { 0 } else { 1 }),
         model { ("minecraft", "bubble_column") },
+        collision vec![],
     }
     Missing253 {
         props {},
@@ -5751,13 +5843,13 @@ mod tests {
     fn hier_1_12_2() {
         let id_map = VanillaIDMap::new(340);
         assert_eq!(
-            id_map.by_vanilla_id(255 << 4, &HashMap::new()),
+            id_map.by_vanilla_id(255 << 4, Arc::new(RwLock::new(HashMap::new()))),
             StructureBlock {
                 mode: StructureBlockMode::Save
             }
         );
         assert_eq!(
-            id_map.by_vanilla_id((255 << 4) | 3, &HashMap::new()),
+            id_map.by_vanilla_id((255 << 4) | 3, Arc::new(RwLock::new(HashMap::new()))),
             StructureBlock {
                 mode: StructureBlockMode::Data
             }
@@ -5768,13 +5860,13 @@ mod tests {
     fn flat_1_13_2() {
         let id_map = VanillaIDMap::new(404);
         assert_eq!(
-            id_map.by_vanilla_id(8595, &HashMap::new()),
+            id_map.by_vanilla_id(8595, Arc::new(RwLock::new(HashMap::new()))),
             StructureBlock {
                 mode: StructureBlockMode::Save
             }
         );
         assert_eq!(
-            id_map.by_vanilla_id(8598, &HashMap::new()),
+            id_map.by_vanilla_id(8598, Arc::new(RwLock::new(HashMap::new()))),
             StructureBlock {
                 mode: StructureBlockMode::Data
             }
@@ -5785,11 +5877,11 @@ mod tests {
     fn flat_1_14_4() {
         let id_map = VanillaIDMap::new(477);
         assert_eq!(
-            id_map.by_vanilla_id(9113, &HashMap::new()),
+            id_map.by_vanilla_id(9113, Arc::new(RwLock::new(HashMap::new()))),
             Conduit { waterlogged: true }
         );
         assert_eq!(
-            id_map.by_vanilla_id(9114, &HashMap::new()),
+            id_map.by_vanilla_id(9114, Arc::new(RwLock::new(HashMap::new()))),
             Conduit { waterlogged: false }
         );
     }
@@ -5798,11 +5890,11 @@ mod tests {
     fn flat_1_15_1() {
         let id_map = VanillaIDMap::new(575);
         assert_eq!(
-            id_map.by_vanilla_id(9113, &HashMap::new()),
+            id_map.by_vanilla_id(9113, Arc::new(RwLock::new(HashMap::new()))),
             Conduit { waterlogged: true }
         );
         assert_eq!(
-            id_map.by_vanilla_id(9114, &HashMap::new()),
+            id_map.by_vanilla_id(9114, Arc::new(RwLock::new(HashMap::new()))),
             Conduit { waterlogged: false }
         );
     }
@@ -5811,7 +5903,7 @@ mod tests {
     fn flat_1_16() {
         let id_map = VanillaIDMap::new(735);
         assert_eq!(
-            id_map.by_vanilla_id(1048, &HashMap::new()),
+            id_map.by_vanilla_id(1048, Arc::new(RwLock::new(HashMap::new()))),
             NoteBlock {
                 instrument: NoteBlockInstrument::Pling,
                 note: 24,
@@ -5824,7 +5916,7 @@ mod tests {
     fn flat_1_16_2() {
         let id_map = VanillaIDMap::new(751);
         assert_eq!(
-            id_map.by_vanilla_id(1048, &HashMap::new()),
+            id_map.by_vanilla_id(1048, Arc::new(RwLock::new(HashMap::new()))),
             NoteBlock {
                 instrument: NoteBlockInstrument::Pling,
                 note: 24,
@@ -5832,6 +5924,355 @@ mod tests {
             }
         );
     }
+
+    /// Checks the shape against vanilla's boxes, given in pixels.
+    fn assert_shape(actual: Vec<Aabb3<f64>>, expected: &[[f64; 6]]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for e in expected {
+            let e = cuboid(e[0], e[1], e[2], e[3], e[4], e[5]);
+            assert!(
+                actual
+                    .iter()
+                    .any(|a| (0..3).all(|i| (a.min[i] - e.min[i]).abs() < 1e-9
+                        && (a.max[i] - e.max[i]).abs() < 1e-9)),
+                "{:?} not in {:?}",
+                e,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn full_blocks() {
+        let stone = Stone {
+            variant: StoneVariant::Normal,
+        };
+        assert_shape(
+            stone.get_collision_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+        assert_shape(
+            stone.get_outline_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+        assert_shape(Air {}.get_outline_boxes(), &[]);
+        assert_shape(Water { level: 0 }.get_outline_boxes(), &[]);
+    }
+
+    #[test]
+    fn plants_outline_without_collision() {
+        assert_shape(YellowFlower {}.get_collision_boxes(), &[]);
+        assert_shape(
+            YellowFlower {}.get_outline_boxes(),
+            &[[5.0, 0.0, 5.0, 11.0, 10.0, 11.0]],
+        );
+        assert_shape(
+            Wheat { age: 3 }.get_outline_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 8.0, 16.0]],
+        );
+        assert_shape(Web {}.get_collision_boxes(), &[]);
+        assert_shape(
+            Web {}.get_outline_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+    }
+
+    #[test]
+    fn stairs() {
+        let stairs = |facing, half, shape| OakStairs {
+            facing,
+            half,
+            shape,
+            waterlogged: false,
+        };
+        assert_shape(
+            stairs(Direction::North, BlockHalf::Bottom, StairShape::Straight).get_collision_boxes(),
+            &[
+                [0.0, 0.0, 0.0, 16.0, 8.0, 16.0],
+                [0.0, 8.0, 0.0, 16.0, 16.0, 8.0],
+            ],
+        );
+        assert_shape(
+            stairs(Direction::East, BlockHalf::Top, StairShape::Straight).get_collision_boxes(),
+            &[
+                [0.0, 8.0, 0.0, 16.0, 16.0, 16.0],
+                [8.0, 0.0, 0.0, 16.0, 8.0, 16.0],
+            ],
+        );
+        assert_shape(
+            stairs(Direction::South, BlockHalf::Bottom, StairShape::OuterRight)
+                .get_collision_boxes(),
+            &[
+                [0.0, 0.0, 0.0, 16.0, 8.0, 16.0],
+                [0.0, 8.0, 8.0, 8.0, 16.0, 16.0],
+            ],
+        );
+        assert_shape(
+            stairs(Direction::West, BlockHalf::Bottom, StairShape::InnerLeft).get_collision_boxes(),
+            &[
+                [0.0, 0.0, 0.0, 16.0, 8.0, 16.0],
+                [0.0, 8.0, 0.0, 8.0, 16.0, 16.0],
+                [8.0, 8.0, 8.0, 16.0, 16.0, 16.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn fences_are_taller_than_they_look() {
+        let fence = Fence {
+            north: true,
+            south: false,
+            west: false,
+            east: false,
+            waterlogged: false,
+        };
+        assert_shape(
+            fence.get_collision_boxes(),
+            &[
+                [6.0, 0.0, 6.0, 10.0, 24.0, 10.0],
+                [6.0, 0.0, 0.0, 10.0, 24.0, 6.0],
+            ],
+        );
+        assert_shape(
+            fence.get_outline_boxes(),
+            &[
+                [6.0, 0.0, 6.0, 10.0, 16.0, 10.0],
+                [6.0, 0.0, 0.0, 10.0, 16.0, 6.0],
+            ],
+        );
+
+        let gate = |in_wall, open| FenceGate {
+            facing: Direction::North,
+            in_wall,
+            open,
+            powered: false,
+        };
+        assert_shape(
+            gate(true, false).get_collision_boxes(),
+            &[[0.0, 0.0, 6.0, 16.0, 24.0, 10.0]],
+        );
+        assert_shape(
+            gate(true, false).get_outline_boxes(),
+            &[[0.0, 0.0, 6.0, 16.0, 13.0, 10.0]],
+        );
+        assert_shape(gate(false, true).get_collision_boxes(), &[]);
+        assert_shape(
+            gate(false, true).get_outline_boxes(),
+            &[[0.0, 0.0, 6.0, 16.0, 16.0, 10.0]],
+        );
+    }
+
+    #[test]
+    fn walls() {
+        let wall = |up| CobblestoneWall {
+            up,
+            north: true,
+            south: true,
+            west: false,
+            east: false,
+            variant: CobblestoneWallVariant::Normal,
+            waterlogged: false,
+        };
+        assert_shape(
+            wall(false).get_collision_boxes(),
+            &[
+                [5.0, 0.0, 0.0, 11.0, 24.0, 11.0],
+                [5.0, 0.0, 5.0, 11.0, 24.0, 16.0],
+            ],
+        );
+        assert_shape(
+            wall(true).get_outline_boxes(),
+            &[
+                [4.0, 0.0, 4.0, 12.0, 16.0, 12.0],
+                [5.0, 0.0, 0.0, 11.0, 14.0, 11.0],
+                [5.0, 0.0, 5.0, 11.0, 14.0, 16.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn panes() {
+        let pane = GlassPane {
+            north: true,
+            south: false,
+            west: false,
+            east: true,
+            waterlogged: false,
+        };
+        assert_shape(
+            pane.get_collision_boxes(),
+            &[
+                [7.0, 0.0, 7.0, 9.0, 16.0, 9.0],
+                [7.0, 0.0, 0.0, 9.0, 16.0, 9.0],
+                [7.0, 0.0, 7.0, 16.0, 16.0, 9.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn doors_and_trapdoors() {
+        let door = |hinge, open| WoodenDoor {
+            facing: Direction::East,
+            half: DoorHalf::Lower,
+            hinge,
+            open,
+            powered: false,
+        };
+        assert_shape(
+            door(Side::Left, false).get_collision_boxes(),
+            &[[0.0, 0.0, 0.0, 3.0, 16.0, 16.0]],
+        );
+        assert_shape(
+            door(Side::Right, true).get_collision_boxes(),
+            &[[0.0, 0.0, 13.0, 16.0, 16.0, 16.0]],
+        );
+        assert_shape(
+            door(Side::Left, true).get_collision_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 16.0, 3.0]],
+        );
+
+        let trapdoor = |half, open| TrapDoor {
+            facing: Direction::North,
+            half,
+            open,
+            waterlogged: false,
+            powered: false,
+            wood: TreeVariant::Oak,
+        };
+        assert_shape(
+            trapdoor(BlockHalf::Top, false).get_collision_boxes(),
+            &[[0.0, 13.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+        assert_shape(
+            trapdoor(BlockHalf::Bottom, false).get_collision_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 3.0, 16.0]],
+        );
+        assert_shape(
+            trapdoor(BlockHalf::Bottom, true).get_collision_boxes(),
+            &[[0.0, 0.0, 13.0, 16.0, 16.0, 16.0]],
+        );
+    }
+
+    #[test]
+    fn snow_layers() {
+        assert_shape(SnowLayer { layers: 1 }.get_collision_boxes(), &[]);
+        assert_shape(
+            SnowLayer { layers: 3 }.get_collision_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 4.0, 16.0]],
+        );
+        assert_shape(
+            SnowLayer { layers: 3 }.get_outline_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 6.0, 16.0]],
+        );
+    }
+
+    #[test]
+    fn hollow_containers() {
+        let walls = [
+            [0.0, 0.0, 0.0, 16.0, 16.0, 2.0],
+            [0.0, 0.0, 14.0, 16.0, 16.0, 16.0],
+            [0.0, 0.0, 0.0, 2.0, 16.0, 16.0],
+            [14.0, 0.0, 0.0, 16.0, 16.0, 16.0],
+        ];
+        let mut cauldron = vec![[0.0, 0.0, 0.0, 16.0, 5.0, 16.0]];
+        cauldron.extend_from_slice(&walls);
+        assert_shape(Cauldron { level: 0 }.get_collision_boxes(), &cauldron);
+        assert_shape(
+            Cauldron { level: 0 }.get_outline_boxes(),
+            &[[0.0, 0.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+
+        let hopper = Hopper {
+            enabled: true,
+            facing: Direction::Down,
+        };
+        let mut shape = vec![[0.0, 0.0, 0.0, 16.0, 10.0, 16.0]];
+        shape.extend_from_slice(&walls);
+        assert_shape(hopper.get_collision_boxes(), &shape);
+    }
+
+    #[test]
+    fn chests() {
+        let chest = |type_| Chest {
+            facing: Direction::North,
+            type_,
+            waterlogged: false,
+        };
+        assert_shape(
+            chest(ChestType::Single).get_collision_boxes(),
+            &[[1.0, 0.0, 1.0, 15.0, 14.0, 15.0]],
+        );
+        assert_shape(
+            chest(ChestType::Left).get_collision_boxes(),
+            &[[1.0, 0.0, 1.0, 16.0, 14.0, 15.0]],
+        );
+        assert_shape(
+            chest(ChestType::Right).get_collision_boxes(),
+            &[[0.0, 0.0, 1.0, 15.0, 14.0, 15.0]],
+        );
+    }
+
+    #[test]
+    fn attached_blocks() {
+        let button = |powered| StoneButton {
+            face: AttachedFace::Wall,
+            facing: Direction::North,
+            powered,
+        };
+        assert_shape(button(false).get_collision_boxes(), &[]);
+        assert_shape(
+            button(false).get_outline_boxes(),
+            &[[5.0, 6.0, 14.0, 11.0, 10.0, 16.0]],
+        );
+        assert_shape(
+            button(true).get_outline_boxes(),
+            &[[5.0, 6.0, 15.0, 11.0, 10.0, 16.0]],
+        );
+        assert_shape(
+            Torch {
+                facing: Direction::East,
+            }
+            .get_outline_boxes(),
+            &[[0.0, 3.0, 5.5, 5.0, 13.0, 10.5]],
+        );
+        assert_shape(
+            Ladder {
+                facing: Direction::West,
+                waterlogged: false,
+            }
+            .get_collision_boxes(),
+            &[[13.0, 0.0, 0.0, 16.0, 16.0, 16.0]],
+        );
+    }
+
+    #[test]
+    fn properties() {
+        let obsidian = Obsidian {}.get_properties();
+        assert_eq!(obsidian.hardness, 50.0);
+        assert_eq!(obsidian.blast_resistance, 1200.0);
+        assert_eq!(obsidian.tool, Some(Tool::Pickaxe));
+        assert!(!obsidian.can_harvest(None));
+        assert!(!obsidian.can_harvest(Some((Tool::Pickaxe, ToolTier::Iron))));
+        assert!(obsidian.can_harvest(Some((Tool::Pickaxe, ToolTier::Diamond))));
+
+        let ore = DiamondOre {}.get_properties();
+        assert!(!ore.can_harvest(Some((Tool::Pickaxe, ToolTier::Gold))));
+        assert!(!ore.can_harvest(Some((Tool::Axe, ToolTier::Diamond))));
+        assert!(ore.can_harvest(Some((Tool::Pickaxe, ToolTier::Iron))));
+
+        let dirt = Dirt {
+            snowy: false,
+            variant: DirtVariant::Normal,
+        }
+        .get_properties();
+        assert_eq!(dirt.tool, Some(Tool::Shovel));
+        assert!(dirt.can_harvest(None));
+        assert_eq!(dirt.sound.break_sound(), "block.gravel.break");
+
+        assert!(Bedrock {}.get_properties().is_unbreakable());
+        assert_eq!(Glowstone {}.get_properties().emitted_light, 15);
+        assert_eq!(Obsidian {}.get_properties().light_opacity, 15);
+    }
 }
 
 fn can_burn<W: WorldAccess>(world: &W, pos: Position) -> bool {
@@ -5971,29 +6412,25 @@ fn fence_gate_offset(facing: Direction, in_wall: bool, open: bool, powered: bool
     )
 }
 
-fn fence_gate_collision(facing: Direction, in_wall: bool, open: bool) -> Vec<Aabb3<f64>> {
+fn fence_gate_collision(facing: Direction, open: bool) -> Vec<Aabb3<f64>> {
     if open {
         return vec![];
     }
+    // Tall enough that nothing jumps over it
+    match facing.axis() {
+        Axis::Z => vec![cuboid(0.0, 0.0, 6.0, 16.0, 24.0, 10.0)],
+        Axis::X => vec![cuboid(6.0, 0.0, 0.0, 10.0, 24.0, 16.0)],
+        _ => unreachable!(),
+    }
+}
 
-    let (min_x, min_y, min_z, max_x, max_y, max_z) = if in_wall {
-        match facing.axis() {
-            Axis::Z => (0.0, 0.0, 3.0 / 8.0, 1.0, 13.0 / 16.0, 5.0 / 8.0),
-            Axis::X => (3.0 / 8.0, 0.0, 0.0, 5.0 / 8.0, 13.0 / 16.0, 1.0),
-            _ => unreachable!(),
-        }
-    } else {
-        match facing.axis() {
-            Axis::Z => (0.0, 0.0, 3.0 / 8.0, 1.0, 1.0, 5.0 / 8.0),
-            Axis::X => (3.0 / 8.0, 0.0, 0.0, 5.0 / 8.0, 1.0, 1.0),
-            _ => unreachable!(),
-        }
-    };
-
-    vec![Aabb3::new(
-        Point3::new(min_x, min_y, min_z),
-        Point3::new(max_x, max_y, max_z),
-    )]
+fn fence_gate_outline(facing: Direction, in_wall: bool) -> Vec<Aabb3<f64>> {
+    let height = if in_wall { 13.0 } else { 16.0 };
+    match facing.axis() {
+        Axis::Z => vec![cuboid(0.0, 0.0, 6.0, 16.0, height, 10.0)],
+        Axis::X => vec![cuboid(6.0, 0.0, 0.0, 10.0, height, 16.0)],
+        _ => unreachable!(),
+    }
 }
 
 fn fence_gate_update_state<W: WorldAccess>(world: &W, pos: Position, facing: Direction) -> bool {
@@ -6128,37 +6565,13 @@ fn update_door_state<W: WorldAccess>(
 }
 
 fn door_collision(facing: Direction, hinge: Side, open: bool) -> Vec<Aabb3<f64>> {
-    use std::f64::consts::PI;
-    let mut bounds = Aabb3::new(
-        Point3::new(0.0, 0.0, 0.0),
-        Point3::new(1.0, 1.0, 3.0 / 16.0),
-    );
-    let mut angle = match facing {
-        Direction::South => 0.0,
-        Direction::West => PI * 0.5,
-        Direction::North => PI,
-        Direction::East => PI * 1.5,
-        _ => 0.0,
+    // Open doors swing towards the side away from their hinge
+    let facing = match (open, hinge) {
+        (false, _) => facing,
+        (true, Side::Left) => facing.clockwise(),
+        (true, Side::Right) => facing.counter_clockwise(),
     };
-    angle += if open { PI * 0.5 } else { 0.0 }
-        * match hinge {
-            Side::Left => 1.0,
-            Side::Right => -1.0,
-        };
-
-    let c = angle.cos();
-    let s = angle.sin();
-
-    let x = bounds.min.x - 0.5;
-    let z = bounds.min.z - 0.5;
-    bounds.min.x = 0.5 + (x * c - z * s);
-    bounds.min.z = 0.5 + (z * c + x * s);
-    let x = bounds.max.x - 0.5;
-    let z = bounds.max.z - 0.5;
-    bounds.max.x = 0.5 + (x * c - z * s);
-    bounds.max.z = 0.5 + (z * c + x * s);
-
-    vec![bounds]
+    rotate_shape(vec![cuboid(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)], facing)
 }
 
 fn update_repeater_state<W: WorldAccess>(world: &W, pos: Position, facing: Direction) -> bool {
@@ -6210,63 +6623,33 @@ fn piston_collision(extended: bool, facing: Direction) -> Vec<Aabb3<f64>> {
 }
 
 fn trapdoor_collision(facing: Direction, half: BlockHalf, open: bool) -> Vec<Aabb3<f64>> {
-    let (min_x, min_y, min_z, max_x, max_y, max_z) = if open {
-        match facing {
-            Direction::North => (0.0, 0.0, 3.0 / 16.0, 1.0, 1.0, 1.0),
-            Direction::South => (0.0, 0.0, 0.0, 1.0, 1.0, 3.0 / 16.0),
-            Direction::West => (3.0 / 16.0, 0.0, 0.0, 1.0, 1.0, 1.0),
-            Direction::East => (0.0, 0.0, 0.0, 3.0 / 16.0, 1.0, 1.0),
-            _ => unreachable!(),
-        }
-    } else {
-        match half {
-            BlockHalf::Bottom => (0.0, 0.0, 0.0, 1.0, 3.0 / 16.0, 1.0),
-            BlockHalf::Top => (0.0, 3.0 / 16.0, 0.0, 1.0, 1.0, 1.0),
-            _ => unreachable!(),
-        }
-    };
-
-    vec![Aabb3::new(
-        Point3::new(min_x, min_y, min_z),
-        Point3::new(max_x, max_y, max_z),
-    )]
+    if open {
+        return rotate_shape(vec![cuboid(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)], facing);
+    }
+    match half {
+        BlockHalf::Bottom => vec![cuboid(0.0, 0.0, 0.0, 16.0, 3.0, 16.0)],
+        BlockHalf::Top => vec![cuboid(0.0, 13.0, 0.0, 16.0, 16.0, 16.0)],
+        _ => unreachable!(),
+    }
 }
 
-fn fence_collision(north: bool, south: bool, west: bool, east: bool) -> Vec<Aabb3<f64>> {
-    let mut collision = vec![Aabb3::new(
-        Point3::new(3.0 / 8.0, 0.0, 3.0 / 8.0),
-        Point3::new(5.0 / 8.0, 1.5, 5.0 / 8.0),
-    )];
-
+/// Fences are a post with bars towards their connections. Their collision
+/// is taller than they look so they can't be jumped over.
+fn fence_shape(north: bool, south: bool, west: bool, east: bool, height: f64) -> Vec<Aabb3<f64>> {
+    let mut shape = vec![cuboid(6.0, 0.0, 6.0, 10.0, height, 10.0)];
     if north {
-        collision.push(Aabb3::new(
-            Point3::new(3.0 / 8.0, 0.0, 0.0),
-            Point3::new(5.0 / 8.0, 1.5, 3.0 / 8.0),
-        ));
+        shape.push(cuboid(6.0, 0.0, 0.0, 10.0, height, 6.0));
     }
-
     if south {
-        collision.push(Aabb3::new(
-            Point3::new(3.0 / 8.0, 0.0, 5.0 / 8.0),
-            Point3::new(5.0 / 8.0, 1.5, 1.0),
-        ));
+        shape.push(cuboid(6.0, 0.0, 10.0, 10.0, height, 16.0));
     }
-
     if west {
-        collision.push(Aabb3::new(
-            Point3::new(0.0, 0.0, 3.0 / 8.0),
-            Point3::new(3.0 / 8.0, 1.5, 5.0 / 8.0),
-        ));
+        shape.push(cuboid(0.0, 0.0, 6.0, 6.0, height, 10.0));
     }
-
     if east {
-        collision.push(Aabb3::new(
-            Point3::new(5.0 / 8.0, 0.0, 3.0 / 8.0),
-            Point3::new(1.0, 1.5, 5.0 / 8.0),
-        ));
+        shape.push(cuboid(10.0, 0.0, 6.0, 16.0, height, 10.0));
     }
-
-    collision
+    shape
 }
 
 fn pane_collision(north: bool, south: bool, east: bool, west: bool) -> Vec<Aabb3<f64>> {
@@ -6306,6 +6689,36 @@ fn pane_collision(north: bool, south: bool, east: bool, west: bool) -> Vec<Aabb3
     collision
 }
 
+/// Walls only have a post where something rests on them or they turn,
+/// their sides are lower than the post.
+fn wall_shape(
+    up: bool,
+    north: bool,
+    south: bool,
+    west: bool,
+    east: bool,
+    post_height: f64,
+    side_height: f64,
+) -> Vec<Aabb3<f64>> {
+    let mut shape = vec![];
+    if up {
+        shape.push(cuboid(4.0, 0.0, 4.0, 12.0, post_height, 12.0));
+    }
+    if north {
+        shape.push(cuboid(5.0, 0.0, 0.0, 11.0, side_height, 11.0));
+    }
+    if south {
+        shape.push(cuboid(5.0, 0.0, 5.0, 11.0, side_height, 16.0));
+    }
+    if west {
+        shape.push(cuboid(0.0, 0.0, 5.0, 11.0, side_height, 11.0));
+    }
+    if east {
+        shape.push(cuboid(5.0, 0.0, 5.0, 16.0, side_height, 11.0));
+    }
+    shape
+}
+
 fn get_stair_info<W: WorldAccess>(world: &W, pos: Position) -> Option<(Direction, BlockHalf)> {
     match world.get_block(pos) {
         Block::OakStairs { facing, half, .. }
@@ -6380,76 +6793,81 @@ fn stair_offset(
     )
 }
 
-#[allow(clippy::many_single_char_names)]
+/// Builds a box from coordinates in pixels, sixteenths of a block, the
+/// way vanilla describes its block shapes.
+fn cuboid(min_x: f64, min_y: f64, min_z: f64, max_x: f64, max_y: f64, max_z: f64) -> Aabb3<f64> {
+    Aabb3::new(
+        Point3::new(min_x / 16.0, min_y / 16.0, min_z / 16.0),
+        Point3::new(max_x / 16.0, max_y / 16.0, max_z / 16.0),
+    )
+}
+
+fn full_cube() -> Vec<Aabb3<f64>> {
+    vec![cuboid(0.0, 0.0, 0.0, 16.0, 16.0, 16.0)]
+}
+
+/// Turns the boxes of a block facing north around the vertical axis so
+/// they face the given direction instead.
+fn rotate_shape(boxes: Vec<Aabb3<f64>>, facing: Direction) -> Vec<Aabb3<f64>> {
+    let turns = match facing {
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3,
+        _ => 0,
+    };
+    boxes
+        .into_iter()
+        .map(|mut bb| {
+            for _ in 0..turns {
+                bb = Aabb3::new(
+                    Point3::new(1.0 - bb.min.z, bb.min.y, bb.min.x),
+                    Point3::new(1.0 - bb.max.z, bb.max.y, bb.max.x),
+                );
+            }
+            bb
+        })
+        .collect()
+}
+
+/// Turns the boxes upside down, for the top halves of stairs.
+fn flip_shape(boxes: Vec<Aabb3<f64>>) -> Vec<Aabb3<f64>> {
+    boxes
+        .into_iter()
+        .map(|bb| {
+            Aabb3::new(
+                Point3::new(bb.min.x, 1.0 - bb.min.y, bb.min.z),
+                Point3::new(bb.max.x, 1.0 - bb.max.y, bb.max.z),
+            )
+        })
+        .collect()
+}
+
 fn stair_collision(facing: Direction, shape: StairShape, half: BlockHalf) -> Vec<Aabb3<f64>> {
-    use std::f64::consts::PI;
-    let mut bounds = match shape {
-        StairShape::Straight => vec![
-            Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.5, 1.0)),
-            Aabb3::new(Point3::new(0.0, 0.5, 0.0), Point3::new(1.0, 1.0, 0.5)),
-        ],
+    // The steps on top of the slab, for stairs facing north
+    let steps = match shape {
+        StairShape::Straight => vec![cuboid(0.0, 8.0, 0.0, 16.0, 16.0, 8.0)],
         StairShape::InnerLeft => vec![
-            Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.5, 1.0)),
-            Aabb3::new(Point3::new(0.0, 0.5, 0.0), Point3::new(1.0, 1.0, 0.5)),
-            Aabb3::new(Point3::new(0.0, 0.5, 0.5), Point3::new(0.5, 1.0, 1.0)),
+            cuboid(0.0, 8.0, 0.0, 16.0, 16.0, 8.0),
+            cuboid(0.0, 8.0, 8.0, 8.0, 16.0, 16.0),
         ],
         StairShape::InnerRight => vec![
-            Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.5, 1.0)),
-            Aabb3::new(Point3::new(0.0, 0.5, 0.0), Point3::new(1.0, 1.0, 0.5)),
-            Aabb3::new(Point3::new(0.5, 0.5, 0.5), Point3::new(1.0, 1.0, 1.0)),
-        ],
-        StairShape::OuterLeft => vec![
-            Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.5, 1.0)),
-            Aabb3::new(Point3::new(0.0, 0.5, 0.0), Point3::new(0.5, 1.0, 0.5)),
+            cuboid(0.0, 8.0, 0.0, 16.0, 16.0, 8.0),
+            cuboid(8.0, 8.0, 8.0, 16.0, 16.0, 16.0),
         ],
-        StairShape::OuterRight => vec![
-            Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.5, 1.0)),
-            Aabb3::new(Point3::new(0.5, 0.5, 0.0), Point3::new(1.0, 1.0, 0.5)),
-        ],
-    };
-    let mut angle = match facing {
-        Direction::North => 0.0,
-        Direction::East => PI * 0.5,
-        Direction::South => PI,
-        Direction::West => PI * 1.5,
-        _ => 0.0,
+        StairShape::OuterLeft => vec![cuboid(0.0, 8.0, 0.0, 8.0, 16.0, 8.0)],
+        StairShape::OuterRight => vec![cuboid(8.0, 8.0, 0.0, 16.0, 16.0, 8.0)],
     };
-
+    let mut bounds = vec![cuboid(0.0, 0.0, 0.0, 16.0, 8.0, 16.0)];
+    bounds.extend(rotate_shape(steps, facing));
     if half == BlockHalf::Top {
-        angle -= PI;
-    }
-
-    let c = angle.cos();
-    let s = angle.sin();
-
-    for bound in &mut bounds {
-        let x = bound.min.x - 0.5;
-        let z = bound.min.z - 0.5;
-        bound.min.x = 0.5 + (x * c - z * s);
-        bound.min.z = 0.5 + (z * c + x * s);
-        let x = bound.max.x - 0.5;
-        let z = bound.max.z - 0.5;
-        bound.max.x = 0.5 + (x * c - z * s);
-        bound.max.z = 0.5 + (z * c + x * s);
-
-        if half == BlockHalf::Top {
-            let c = PI.cos();
-            let s = PI.sin();
-            let z = bound.min.z - 0.5;
-            let y = bound.min.y - 0.5;
-            bound.min.z = 0.5 + (z * c - y * s);
-            bound.min.y = 0.5 + (y * c + z * s);
-            let z = bound.max.z - 0.5;
-            let y = bound.max.y - 0.5;
-            bound.max.z = 0.5 + (z * c - y * s);
-            bound.max.y = 0.5 + (y * c + z * s);
-
-            bound.min.x = 1.0 - bound.min.x;
-            bound.max.x = 1.0 - bound.max.x;
-        }
+        flip_shape(bounds)
+    } else {
+        bounds
     }
+}
 
-    bounds
+fn ladder_collision(facing: Direction) -> Vec<Aabb3<f64>> {
+    rotate_shape(vec![cuboid(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)], facing)
 }
 
 fn slab_collision(half: BlockHalf) -> Vec<Aabb3<f64>> {
@@ -6466,6 +6884,171 @@ fn slab_collision(half: BlockHalf) -> Vec<Aabb3<f64>> {
     )]
 }
 
+fn torch_outline(facing: Direction) -> Vec<Aabb3<f64>> {
+    match facing {
+        Direction::Up => vec![cuboid(6.0, 0.0, 6.0, 10.0, 10.0, 10.0)],
+        _ => rotate_shape(vec![cuboid(5.5, 3.0, 11.0, 10.5, 13.0, 16.0)], facing),
+    }
+}
+
+fn lever_outline(face: AttachedFace, facing: Direction) -> Vec<Aabb3<f64>> {
+    match (face, facing.axis()) {
+        (AttachedFace::Floor, Axis::Z) => vec![cuboid(5.0, 0.0, 4.0, 11.0, 6.0, 12.0)],
+        (AttachedFace::Floor, _) => vec![cuboid(4.0, 0.0, 5.0, 12.0, 6.0, 11.0)],
+        (AttachedFace::Ceiling, Axis::Z) => vec![cuboid(5.0, 10.0, 4.0, 11.0, 16.0, 12.0)],
+        (AttachedFace::Ceiling, _) => vec![cuboid(4.0, 10.0, 5.0, 12.0, 16.0, 11.0)],
+        (AttachedFace::Wall, _) => {
+            rotate_shape(vec![cuboid(5.0, 4.0, 10.0, 11.0, 12.0, 16.0)], facing)
+        }
+    }
+}
+
+/// Buttons sink into what they are attached to while pressed.
+fn button_outline(face: AttachedFace, facing: Direction, powered: bool) -> Vec<Aabb3<f64>> {
+    let depth = if powered { 1.0 } else { 2.0 };
+    match (face, facing.axis()) {
+        (AttachedFace::Floor, Axis::Z) => vec![cuboid(5.0, 0.0, 6.0, 11.0, depth, 10.0)],
+        (AttachedFace::Floor, _) => vec![cuboid(6.0, 0.0, 5.0, 10.0, depth, 11.0)],
+        (AttachedFace::Ceiling, Axis::Z) => {
+            vec![cuboid(5.0, 16.0 - depth, 6.0, 11.0, 16.0, 10.0)]
+        }
+        (AttachedFace::Ceiling, _) => vec![cuboid(6.0, 16.0 - depth, 5.0, 10.0, 16.0, 11.0)],
+        (AttachedFace::Wall, _) => rotate_shape(
+            vec![cuboid(5.0, 6.0, 16.0 - depth, 11.0, 10.0, 16.0)],
+            facing,
+        ),
+    }
+}
+
+fn pressure_plate_outline(pressed: bool) -> Vec<Aabb3<f64>> {
+    let height = if pressed { 0.5 } else { 1.0 };
+    vec![cuboid(1.0, 0.0, 1.0, 15.0, height, 15.0)]
+}
+
+fn rail_outline(shape: RailShape) -> Vec<Aabb3<f64>> {
+    match shape {
+        RailShape::AscendingNorth
+        | RailShape::AscendingSouth
+        | RailShape::AscendingEast
+        | RailShape::AscendingWest => vec![cuboid(0.0, 0.0, 0.0, 16.0, 8.0, 16.0)],
+        _ => vec![cuboid(0.0, 0.0, 0.0, 16.0, 2.0, 16.0)],
+    }
+}
+
+/// The outline of crops, which grow by two pixels every stage.
+fn crop_outline(age: u8) -> Vec<Aabb3<f64>> {
+    vec![cuboid(0.0, 0.0, 0.0, 16.0, f64::from(age + 1) * 2.0, 16.0)]
+}
+
+fn stem_outline(age: u8, facing: Direction) -> Vec<Aabb3<f64>> {
+    match facing {
+        Direction::Up => vec![cuboid(7.0, 0.0, 7.0, 9.0, f64::from(age + 1) * 2.0, 9.0)],
+        _ => attached_stem_outline(facing),
+    }
+}
+
+fn attached_stem_outline(facing: Direction) -> Vec<Aabb3<f64>> {
+    match facing {
+        Direction::Up => vec![cuboid(6.0, 0.0, 6.0, 10.0, 10.0, 10.0)],
+        _ => rotate_shape(vec![cuboid(6.0, 0.0, 0.0, 10.0, 10.0, 10.0)], facing),
+    }
+}
+
+fn vine_outline(up: bool, north: bool, south: bool, west: bool, east: bool) -> Vec<Aabb3<f64>> {
+    let mut shape = vec![];
+    if up {
+        shape.push(cuboid(0.0, 15.0, 0.0, 16.0, 16.0, 16.0));
+    }
+    if north {
+        shape.push(cuboid(0.0, 0.0, 0.0, 16.0, 16.0, 1.0));
+    }
+    if south {
+        shape.push(cuboid(0.0, 0.0, 15.0, 16.0, 16.0, 16.0));
+    }
+    if west {
+        shape.push(cuboid(0.0, 0.0, 0.0, 1.0, 16.0, 16.0));
+    }
+    if east {
+        shape.push(cuboid(15.0, 0.0, 0.0, 16.0, 16.0, 16.0));
+    }
+    shape
+}
+
+fn wall_sign_outline(facing: Direction) -> Vec<Aabb3<f64>> {
+    rotate_shape(vec![cuboid(0.0, 4.5, 14.0, 16.0, 12.5, 16.0)], facing)
+}
+
+fn wall_banner_outline(facing: Direction) -> Vec<Aabb3<f64>> {
+    rotate_shape(vec![cuboid(0.0, 0.0, 14.0, 16.0, 12.5, 16.0)], facing)
+}
+
+fn skull_collision(facing: Direction) -> Vec<Aabb3<f64>> {
+    match facing {
+        Direction::Up => vec![cuboid(4.0, 0.0, 4.0, 12.0, 8.0, 12.0)],
+        _ => rotate_shape(vec![cuboid(4.0, 4.0, 8.0, 12.0, 12.0, 16.0)], facing),
+    }
+}
+
+fn portal_outline(axis: Axis) -> Vec<Aabb3<f64>> {
+    match axis {
+        Axis::X => vec![cuboid(0.0, 0.0, 6.0, 16.0, 16.0, 10.0)],
+        _ => vec![cuboid(6.0, 0.0, 0.0, 10.0, 16.0, 16.0)],
+    }
+}
+
+fn tripwire_hook_outline(facing: Direction) -> Vec<Aabb3<f64>> {
+    rotate_shape(vec![cuboid(5.0, 0.0, 10.0, 11.0, 10.0, 16.0)], facing)
+}
+
+/// Double chests reach into the block of their other half.
+fn chest_collision(facing: Direction, type_: ChestType) -> Vec<Aabb3<f64>> {
+    let other = match type_ {
+        ChestType::Single => return vec![cuboid(1.0, 0.0, 1.0, 15.0, 14.0, 15.0)],
+        ChestType::Left => facing.clockwise(),
+        ChestType::Right => facing.counter_clockwise(),
+    };
+    rotate_shape(vec![cuboid(1.0, 0.0, 0.0, 15.0, 14.0, 15.0)], other)
+}
+
+/// The inside of cauldrons is hollow down to the legs.
+fn cauldron_collision() -> Vec<Aabb3<f64>> {
+    vec![
+        cuboid(0.0, 0.0, 0.0, 16.0, 5.0, 16.0),
+        cuboid(0.0, 0.0, 0.0, 16.0, 16.0, 2.0),
+        cuboid(0.0, 0.0, 14.0, 16.0, 16.0, 16.0),
+        cuboid(0.0, 0.0, 0.0, 2.0, 16.0, 16.0),
+        cuboid(14.0, 0.0, 0.0, 16.0, 16.0, 16.0),
+    ]
+}
+
+/// Hoppers are hollow above their funnel so items fall into them.
+fn hopper_collision() -> Vec<Aabb3<f64>> {
+    vec![
+        cuboid(0.0, 0.0, 0.0, 16.0, 10.0, 16.0),
+        cuboid(0.0, 0.0, 0.0, 16.0, 16.0, 2.0),
+        cuboid(0.0, 0.0, 14.0, 16.0, 16.0, 16.0),
+        cuboid(0.0, 0.0, 0.0, 2.0, 16.0, 16.0),
+        cuboid(14.0, 0.0, 0.0, 16.0, 16.0, 16.0),
+    ]
+}
+
+fn sea_pickle_collision(pickles: u8) -> Vec<Aabb3<f64>> {
+    vec![match pickles {
+        1 => cuboid(6.0, 0.0, 6.0, 10.0, 6.0, 10.0),
+        2 => cuboid(3.0, 0.0, 3.0, 13.0, 6.0, 13.0),
+        3 => cuboid(2.0, 0.0, 2.0, 14.0, 6.0, 14.0),
+        _ => cuboid(2.0, 0.0, 2.0, 14.0, 7.0, 14.0),
+    }]
+}
+
+fn chain_collision(axis: Axis) -> Vec<Aabb3<f64>> {
+    vec![match axis {
+        Axis::X => cuboid(0.0, 6.5, 6.5, 16.0, 9.5, 9.5),
+        Axis::Y => cuboid(6.5, 0.0, 6.5, 9.5, 16.0, 9.5),
+        _ => cuboid(6.5, 6.5, 0.0, 9.5, 9.5, 16.0),
+    }]
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StoneVariant {
     Normal,
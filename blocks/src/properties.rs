@@ -0,0 +1,518 @@
+use crate::Block;
+use crate::Block::*;
+
+/// The gameplay side of a block: how long it takes to break, what it
+/// takes to harvest, how it treats light and which sounds it makes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Properties {
+    /// Negative for blocks that can't be broken.
+    pub hardness: f32,
+    pub blast_resistance: f32,
+    /// The tool that breaks the block faster than a bare hand.
+    pub tool: Option<Tool>,
+    /// The lowest tier of `tool` that makes the block drop anything,
+    /// `None` if it drops regardless of what breaks it.
+    pub harvest_tier: Option<ToolTier>,
+    pub emitted_light: u8,
+    pub light_opacity: u8,
+    pub sound: SoundGroup,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tool {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+    Sword,
+    Shears,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolTier {
+    Wood,
+    Gold,
+    Stone,
+    Iron,
+    Diamond,
+    Netherite,
+}
+
+impl ToolTier {
+    /// The vanilla harvest level, gold tools harvest no more than
+    /// wooden ones despite being faster.
+    pub fn harvest_level(self) -> u8 {
+        match self {
+            ToolTier::Wood | ToolTier::Gold => 0,
+            ToolTier::Stone => 1,
+            ToolTier::Iron => 2,
+            ToolTier::Diamond => 3,
+            ToolTier::Netherite => 4,
+        }
+    }
+
+    /// How many times faster than a hand the tier breaks the blocks its
+    /// tool is meant for.
+    pub fn speed(self) -> f32 {
+        match self {
+            ToolTier::Wood => 2.0,
+            ToolTier::Stone => 4.0,
+            ToolTier::Iron => 6.0,
+            ToolTier::Diamond => 8.0,
+            ToolTier::Netherite => 9.0,
+            ToolTier::Gold => 12.0,
+        }
+    }
+}
+
+/// The sound types of 1.12, newer blocks use the closest match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundGroup {
+    Wood,
+    Gravel,
+    Grass,
+    Stone,
+    Metal,
+    Glass,
+    Cloth,
+    Sand,
+    Snow,
+    Ladder,
+    Anvil,
+    Slime,
+}
+
+impl SoundGroup {
+    fn name(self) -> &'static str {
+        match self {
+            SoundGroup::Wood => "wood",
+            SoundGroup::Gravel => "gravel",
+            SoundGroup::Grass => "grass",
+            SoundGroup::Stone => "stone",
+            SoundGroup::Metal => "metal",
+            SoundGroup::Glass => "glass",
+            SoundGroup::Cloth => "cloth",
+            SoundGroup::Sand => "sand",
+            SoundGroup::Snow => "snow",
+            SoundGroup::Ladder => "ladder",
+            SoundGroup::Anvil => "anvil",
+            SoundGroup::Slime => "slime",
+        }
+    }
+
+    pub fn break_sound(self) -> String {
+        match self {
+            SoundGroup::Ladder => "block.wood.break".to_owned(),
+            SoundGroup::Anvil => "block.anvil.destroy".to_owned(),
+            _ => format!("block.{}.break", self.name()),
+        }
+    }
+
+    pub fn place_sound(self) -> String {
+        match self {
+            SoundGroup::Ladder => "block.wood.place".to_owned(),
+            _ => format!("block.{}.place", self.name()),
+        }
+    }
+
+    pub fn step_sound(self) -> String {
+        format!("block.{}.step", self.name())
+    }
+
+    pub fn hit_sound(self) -> String {
+        format!("block.{}.hit", self.name())
+    }
+
+    pub fn fall_sound(self) -> String {
+        format!("block.{}.fall", self.name())
+    }
+
+    pub fn volume(self) -> f32 {
+        match self {
+            SoundGroup::Anvil => 0.3,
+            _ => 1.0,
+        }
+    }
+}
+
+impl Properties {
+    fn new(hardness: f32, blast_resistance: f32, sound: SoundGroup) -> Properties {
+        Properties {
+            hardness,
+            blast_resistance,
+            tool: None,
+            harvest_tier: None,
+            emitted_light: 0,
+            light_opacity: 0,
+            sound,
+        }
+    }
+
+    fn unbreakable() -> Properties {
+        Properties::new(-1.0, 3_600_000.0, SoundGroup::Stone)
+    }
+
+    fn instant(sound: SoundGroup) -> Properties {
+        Properties::new(0.0, 0.0, sound)
+    }
+
+    fn tool(self, tool: Tool) -> Properties {
+        Properties {
+            tool: Some(tool),
+            ..self
+        }
+    }
+
+    fn requires(self, tool: Tool, tier: ToolTier) -> Properties {
+        Properties {
+            tool: Some(tool),
+            harvest_tier: Some(tier),
+            ..self
+        }
+    }
+
+    pub fn is_unbreakable(&self) -> bool {
+        self.hardness < 0.0
+    }
+
+    /// Whether breaking the block with `held` (the tool and its tier, if
+    /// any) drops it.
+    pub fn can_harvest(&self, held: Option<(Tool, ToolTier)>) -> bool {
+        match (self.harvest_tier, self.tool, held) {
+            (None, _, _) => true,
+            (Some(tier), Some(tool), Some((held, held_tier))) => {
+                held == tool && held_tier.harvest_level() >= tier.harvest_level()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Block {
+    /// Looks the block up in the property table, with the light values
+    /// taken from its material.
+    pub fn get_properties(&self) -> Properties {
+        use self::SoundGroup as S;
+        use self::Tool::*;
+        use self::ToolTier::{Diamond, Iron, Stone as StoneTier, Wood as WoodTier};
+
+        let stone = |hardness, blast_resistance| {
+            Properties::new(hardness, blast_resistance, S::Stone).requires(Pickaxe, WoodTier)
+        };
+        let wood = |hardness, blast_resistance| {
+            Properties::new(hardness, blast_resistance, S::Wood).tool(Axe)
+        };
+        let ore = |tier| Properties::new(3.0, 3.0, S::Stone).requires(Pickaxe, tier);
+        let metal = |tier| Properties::new(5.0, 6.0, S::Metal).requires(Pickaxe, tier);
+
+        let props = match *self {
+            Air { .. }
+            | VoidAir { .. }
+            | CaveAir { .. }
+            | StructureVoid { .. }
+            | BubbleColumn { .. } => Properties::instant(S::Stone),
+            Bedrock { .. }
+            | Barrier { .. }
+            | EndPortal { .. }
+            | EndPortalFrame { .. }
+            | EndGateway { .. }
+            | CommandBlock { .. }
+            | RepeatingCommandBlock { .. }
+            | ChainCommandBlock { .. }
+            | StructureBlock { .. } => Properties::unbreakable(),
+            Portal { .. } => Properties {
+                blast_resistance: 0.0,
+                sound: S::Glass,
+                ..Properties::unbreakable()
+            },
+            FlowingWater { .. } | Water { .. } | FlowingLava { .. } | Lava { .. } => {
+                Properties::new(100.0, 100.0, S::Stone)
+            }
+
+            Stone { .. }
+            | StoneBrick { .. }
+            | StoneBrickStairs { .. }
+            | Prismarine { .. }
+            | PrismarineStairs { .. }
+            | PrismarineSlab { .. }
+            | PurpurBlock { .. }
+            | PurpurPillar { .. }
+            | PurpurStairs { .. }
+            | CoralBlock { .. } => stone(1.5, 6.0),
+            Cobblestone { .. }
+            | MossyCobblestone { .. }
+            | CobblestoneWall { .. }
+            | StoneStairs { .. }
+            | DoubleStoneSlab { .. }
+            | StoneSlab { .. }
+            | DoubleStoneSlab2 { .. }
+            | StoneSlab2 { .. }
+            | StoneSlabFlat { .. }
+            | SmoothStone { .. }
+            | PurpurDoubleSlab { .. }
+            | PurpurSlab { .. }
+            | BrickBlock { .. }
+            | BrickStairs { .. }
+            | NetherBrick { .. }
+            | NetherBrickFence { .. }
+            | NetherBrickStairs { .. }
+            | RedNetherBrick { .. } => stone(2.0, 6.0),
+            Sandstone { .. }
+            | SandstoneStairs { .. }
+            | RedSandstone { .. }
+            | RedSandstoneStairs { .. }
+            | QuartzBlock { .. }
+            | QuartzStairs { .. } => stone(0.8, 0.8),
+            Netherrack { .. } => stone(0.4, 0.4),
+            Basalt { .. } | PolishedBasalt { .. } => stone(1.25, 4.2),
+            HardenedClay { .. } | StainedHardenedClay { .. } => stone(1.25, 4.2),
+            WhiteGlazedTerracotta { .. }
+            | OrangeGlazedTerracotta { .. }
+            | MagentaGlazedTerracotta { .. }
+            | LightBlueGlazedTerracotta { .. }
+            | YellowGlazedTerracotta { .. }
+            | LimeGlazedTerracotta { .. }
+            | PinkGlazedTerracotta { .. }
+            | GrayGlazedTerracotta { .. }
+            | LightGrayGlazedTerracotta { .. }
+            | CyanGlazedTerracotta { .. }
+            | PurpleGlazedTerracotta { .. }
+            | BlueGlazedTerracotta { .. }
+            | BrownGlazedTerracotta { .. }
+            | GreenGlazedTerracotta { .. }
+            | RedGlazedTerracotta { .. }
+            | BlackGlazedTerracotta { .. } => stone(1.4, 1.4),
+            Concrete { .. } => stone(1.8, 1.8),
+            EndStone { .. } | EndBricks { .. } => stone(3.0, 9.0),
+            Obsidian { .. } => stone(50.0, 1200.0).requires(Pickaxe, Diamond),
+            MonsterEgg { .. } => Properties::new(0.75, 0.75, S::Stone),
+            MagmaBlock { .. } => stone(0.5, 0.5),
+            BoneBlock { .. } => stone(2.0, 2.0),
+            Dispenser { .. } | Dropper { .. } | Furnace { .. } | FurnaceLit { .. } => {
+                stone(3.5, 3.5)
+            }
+            Observer { .. } => stone(3.0, 3.0),
+            MobSpawner { .. } => Properties {
+                sound: S::Metal,
+                ..stone(5.0, 5.0)
+            },
+            EnchantingTable { .. } => stone(5.0, 1200.0),
+            EnderChest { .. } => stone(22.5, 600.0),
+            BrewingStand { .. } => Properties {
+                sound: S::Metal,
+                ..stone(0.5, 0.5)
+            },
+            Cauldron { .. } => stone(2.0, 2.0),
+            StonePressurePlate { .. } => stone(0.5, 0.5),
+            StoneButton { .. } => Properties::new(0.5, 0.5, S::Stone).tool(Pickaxe),
+            Piston { .. } | StickyPiston { .. } | PistonHead { .. } | PistonExtension { .. } => {
+                Properties::new(1.5, 1.5, S::Stone).tool(Pickaxe)
+            }
+            ShulkerBox { .. }
+            | WhiteShulkerBox { .. }
+            | OrangeShulkerBox { .. }
+            | MagentaShulkerBox { .. }
+            | LightBlueShulkerBox { .. }
+            | YellowShulkerBox { .. }
+            | LimeShulkerBox { .. }
+            | PinkShulkerBox { .. }
+            | GrayShulkerBox { .. }
+            | LightGrayShulkerBox { .. }
+            | CyanShulkerBox { .. }
+            | PurpleShulkerBox { .. }
+            | BlueShulkerBox { .. }
+            | BrownShulkerBox { .. }
+            | GreenShulkerBox { .. }
+            | RedShulkerBox { .. }
+            | BlackShulkerBox { .. } => Properties::new(2.0, 2.0, S::Stone).tool(Pickaxe),
+
+            CoalOre { .. } | NetherGoldOre { .. } | QuartzOre { .. } => ore(WoodTier),
+            IronOre { .. } | LapisOre { .. } => ore(StoneTier),
+            GoldOre { .. }
+            | DiamondOre { .. }
+            | EmeraldOre { .. }
+            | RedstoneOre { .. }
+            | RedstoneOreLit { .. } => ore(Iron),
+            LapisBlock { .. } => Properties::new(3.0, 3.0, S::Stone).requires(Pickaxe, StoneTier),
+            CoalBlock { .. } | RedstoneBlock { .. } => stone(5.0, 6.0),
+            IronBlock { .. } => metal(StoneTier),
+            GoldBlock { .. } | DiamondBlock { .. } | EmeraldBlock { .. } => metal(Iron),
+            IronBars { .. } | Chain { .. } => metal(WoodTier),
+            IronDoor { .. } | IronTrapDoor { .. } => Properties {
+                blast_resistance: 5.0,
+                ..metal(WoodTier)
+            },
+            Hopper { .. } => Properties::new(3.0, 4.8, S::Metal).requires(Pickaxe, WoodTier),
+            LightWeightedPressurePlate { .. } | HeavyWeightedPressurePlate { .. } => {
+                Properties::new(0.5, 0.5, S::Metal).requires(Pickaxe, WoodTier)
+            }
+            Anvil { .. } => Properties::new(5.0, 1200.0, S::Anvil).requires(Pickaxe, WoodTier),
+            Rail { .. } | GoldenRail { .. } | DetectorRail { .. } | ActivatorRail { .. } => {
+                Properties::new(0.7, 0.7, S::Metal).tool(Pickaxe)
+            }
+            Ice { .. } | PackedIce { .. } | FrostedIce { .. } => {
+                Properties::new(0.5, 0.5, S::Glass).tool(Pickaxe)
+            }
+            BlueIce { .. } => Properties::new(2.8, 2.8, S::Glass).tool(Pickaxe),
+            Conduit { .. } => Properties::new(3.0, 3.0, S::Glass).tool(Pickaxe),
+
+            Planks { .. }
+            | Fence { .. }
+            | SpruceFence { .. }
+            | BirchFence { .. }
+            | JungleFence { .. }
+            | DarkOakFence { .. }
+            | AcaciaFence { .. }
+            | FenceGate { .. }
+            | SpruceFenceGate { .. }
+            | BirchFenceGate { .. }
+            | JungleFenceGate { .. }
+            | DarkOakFenceGate { .. }
+            | AcaciaFenceGate { .. }
+            | OakStairs { .. }
+            | SpruceStairs { .. }
+            | BirchStairs { .. }
+            | JungleStairs { .. }
+            | AcaciaStairs { .. }
+            | DarkOakStairs { .. }
+            | DoubleWoodenSlab { .. }
+            | WoodenSlab { .. }
+            | WoodenSlabFlat { .. } => wood(2.0, 3.0),
+            Log { .. } | Log2 { .. } | Wood { .. } => wood(2.0, 2.0),
+            WoodenDoor { .. }
+            | SpruceDoor { .. }
+            | BirchDoor { .. }
+            | JungleDoor { .. }
+            | AcaciaDoor { .. }
+            | DarkOakDoor { .. }
+            | TrapDoor { .. } => wood(3.0, 3.0),
+            Chest { .. } | TrappedChest { .. } | CraftingTable { .. } => wood(2.5, 2.5),
+            BookShelf { .. } => wood(1.5, 1.5),
+            NoteBlock { .. } => wood(0.8, 0.8),
+            Jukebox { .. } => wood(2.0, 6.0),
+            StandingSign { .. } | WallSign { .. } | StandingBanner { .. } | WallBanner { .. } => {
+                wood(1.0, 1.0)
+            }
+            WoodenPressurePlate { .. } => wood(0.5, 0.5),
+            WoodenButton { .. } => wood(0.5, 0.5),
+            Ladder { .. } => Properties::new(0.4, 0.4, S::Ladder).tool(Axe),
+            DaylightDetector { .. } | DaylightDetectorInverted { .. } => wood(0.2, 0.2),
+            BrownMushroomBlock { .. } | RedMushroomBlock { .. } | MushroomStem { .. } => {
+                wood(0.2, 0.2)
+            }
+            Pumpkin { .. }
+            | PumpkinFace { .. }
+            | PumpkinCarved { .. }
+            | PumpkinLit { .. }
+            | MelonBlock { .. } => wood(1.0, 1.0),
+            Cocoa { .. } => wood(0.2, 3.0),
+            ChorusPlant { .. } | ChorusFlower { .. } => wood(0.4, 0.4),
+
+            Grass { .. } | Mycelium { .. } => Properties::new(0.6, 0.6, S::Grass).tool(Shovel),
+            Dirt { .. } => Properties::new(0.5, 0.5, S::Gravel).tool(Shovel),
+            Farmland { .. } => Properties::new(0.6, 0.6, S::Gravel).tool(Shovel),
+            GrassPath { .. } => Properties::new(0.65, 0.65, S::Grass).tool(Shovel),
+            Gravel { .. } | Clay { .. } => Properties::new(0.6, 0.6, S::Gravel).tool(Shovel),
+            Sand { .. } | SoulSand { .. } | SoulSoil { .. } | ConcretePowder { .. } => {
+                Properties::new(0.5, 0.5, S::Sand).tool(Shovel)
+            }
+            SnowLayer { .. } => Properties::new(0.1, 0.1, S::Snow).requires(Shovel, WoodTier),
+            Snow { .. } => Properties::new(0.2, 0.2, S::Snow).requires(Shovel, WoodTier),
+
+            Leaves { .. } | Leaves2 { .. } => Properties::new(0.2, 0.2, S::Grass).tool(Shears),
+            Vine { .. } => Properties::new(0.2, 0.2, S::Grass).tool(Shears),
+            Web { .. } => Properties::new(4.0, 4.0, S::Stone).requires(Sword, WoodTier),
+            Wool { .. } => Properties::new(0.8, 0.8, S::Cloth).tool(Shears),
+            Carpet { .. } => Properties::new(0.1, 0.1, S::Cloth),
+            HayBlock { .. } => Properties::new(0.5, 0.5, S::Grass).tool(Hoe),
+            NetherWartBlock { .. } => Properties::new(1.0, 1.0, S::Wood).tool(Hoe),
+            DriedKelpBlock { .. } => Properties::new(0.5, 2.5, S::Grass).tool(Hoe),
+            Sponge { .. } => Properties::new(0.6, 0.6, S::Grass).tool(Hoe),
+
+            Glass { .. }
+            | StainedGlass { .. }
+            | GlassPane { .. }
+            | StainedGlassPane { .. }
+            | Glowstone { .. }
+            | RedstoneLamp { .. }
+            | RedstoneLampLit { .. }
+            | SeaLantern { .. } => Properties::new(0.3, 0.3, S::Glass),
+            Beacon { .. } => Properties::new(3.0, 3.0, S::Glass),
+            Bed { .. } => Properties::new(0.2, 0.2, S::Wood),
+            Cactus { .. } => Properties::new(0.4, 0.4, S::Cloth),
+            Cake { .. } => Properties::new(0.5, 0.5, S::Cloth),
+            DragonEgg { .. } => Properties::new(3.0, 9.0, S::Stone),
+            Lever { .. } => Properties::new(0.5, 0.5, S::Wood),
+            TurtleEgg { .. } => Properties::new(0.5, 0.5, S::Metal),
+            SkullSkeleton { .. }
+            | SkullSkeletonWall { .. }
+            | SkullWitherSkeletonWall { .. }
+            | SkullWitherSkeleton { .. }
+            | ZombieWallHead { .. }
+            | ZombieHead { .. }
+            | PlayerWallHead { .. }
+            | PlayerHead { .. }
+            | CreeperWallHead { .. }
+            | CreeperHead { .. }
+            | DragonWallHead { .. }
+            | DragonHead { .. } => Properties::new(1.0, 1.0, S::Stone),
+            Slime { .. } => Properties::instant(S::Slime),
+            TNT { .. } => Properties::instant(S::Grass),
+
+            Sapling { .. }
+            | TallGrass { .. }
+            | Seagrass { .. }
+            | TallSeagrass { .. }
+            | DeadBush { .. }
+            | YellowFlower { .. }
+            | RedFlower { .. }
+            | BrownMushroom { .. }
+            | RedMushroom { .. }
+            | DoublePlant { .. }
+            | Wheat { .. }
+            | Carrots { .. }
+            | Potatoes { .. }
+            | Beetroots { .. }
+            | Reeds { .. }
+            | NetherWart { .. }
+            | PumpkinStem { .. }
+            | MelonStem { .. }
+            | AttachedPumpkinStem { .. }
+            | AttachedMelonStem { .. }
+            | Waterlily { .. }
+            | Kelp { .. }
+            | KelpPlant { .. }
+            | Coral { .. }
+            | CoralWallFan { .. }
+            | CoralFan { .. } => Properties::instant(S::Grass),
+            SeaPickle { .. } => Properties::instant(S::Slime),
+            Torch { .. }
+            | SoulTorch { .. }
+            | SoulWallTorch { .. }
+            | RedstoneTorchUnlit { .. }
+            | RedstoneTorchLit { .. }
+            | RedstoneTorchStanding { .. }
+            | RedstoneTorchWall { .. }
+            | RedstoneWire { .. }
+            | Repeater { .. }
+            | RepeaterPowered { .. }
+            | ComparatorUnpowered { .. }
+            | ComparatorPowered { .. }
+            | TripwireHook { .. }
+            | Tripwire { .. }
+            | FlowerPot { .. }
+            | EndRod { .. } => Properties::instant(S::Wood),
+            Fire { .. } | SoulFire { .. } => Properties::instant(S::Cloth),
+
+            Missing253 { .. } | Missing254 { .. } | Missing { .. } => {
+                Properties::new(1.0, 1.0, S::Stone)
+            }
+        };
+        let material = self.get_material();
+        Properties {
+            emitted_light: material.emitted_light,
+            light_opacity: material.absorbed_light,
+            ..props
+        }
+    }
+}
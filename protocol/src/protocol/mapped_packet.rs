@@ -2511,18 +2511,26 @@ impl MappablePacket for packet::Packet {
                 z: maps.z,
                 data: maps.data.map(|x| x.data),
             }),
-            packet::Packet::Maps_NoTracking_Data(maps) => mapped_packet::MappedPacket::Maps(Maps {
-                item_damage: maps.item_damage.0,
-                scale: None,
-                tracking_position: None,
-                locked: None,
-                icons: None,
-                columns: None,
-                rows: None,
-                x: None,
-                z: None,
-                data: None,
-            }),
+            packet::Packet::Maps_NoTracking_Data(maps) => {
+                // The first byte says what the rest is, a column of the map
+                // starts with its position
+                let column = match maps.data.data.split_first() {
+                    Some((0, column)) if column.len() >= 2 => Some(column),
+                    _ => None,
+                };
+                mapped_packet::MappedPacket::Maps(Maps {
+                    item_damage: maps.item_damage.0,
+                    scale: None,
+                    tracking_position: None,
+                    locked: None,
+                    icons: None,
+                    columns: column.map(|_| 1),
+                    rows: column.map(|column| (column.len() - 2) as u8),
+                    x: column.map(|column| column[0]),
+                    z: column.map(|column| column[1]),
+                    data: column.map(|column| column[2..].to_vec()),
+                })
+            }
             packet::Packet::MultiBlockChange_Packed(block_change) => {
                 let sx = (block_change.chunk_section_pos >> 42) as i32;
                 let sy = ((block_change.chunk_section_pos << 44) >> 44) as i32;
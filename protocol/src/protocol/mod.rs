@@ -616,10 +616,10 @@ impl Default for Biomes3D {
 
 impl Serializable for Biomes3D {
     fn read_from<R: io::Read>(buf: &mut R) -> Result<Biomes3D, Error> {
-        let data: [i32; 1024] = [0; 1024];
+        let mut data: [i32; 1024] = [0; 1024];
 
         // Non-length-prefixed three-dimensional biome data
-        for item in &mut data.to_vec() {
+        for item in data.iter_mut() {
             let b: i32 = Serializable::read_from(buf)?;
             *item = b;
         }
@@ -3342,6 +3342,31 @@ impl From<i32> for DigType {
     }
 }
 
+/// What the player does to the entity they click.
+pub enum UseEntityType {
+    Interact,
+    Attack,
+    /// Interacting with a point on the entity, relative to its position
+    InteractAt(Vector3<f64>),
+}
+
+impl UseEntityType {
+    pub fn ordinal(&self) -> i32 {
+        match self {
+            UseEntityType::Interact => 0,
+            UseEntityType::Attack => 1,
+            UseEntityType::InteractAt(_) => 2,
+        }
+    }
+
+    fn target(&self) -> Vector3<f32> {
+        match self {
+            UseEntityType::InteractAt(at) => at.cast().unwrap(),
+            _ => Vector3::new(0.0, 0.0, 0.0),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Hand {
     MainHand,
@@ -3519,6 +3544,54 @@ pub fn send_block_place(
     }
 }
 
+pub fn send_use_entity(
+    conn: &mut Conn,
+    version: Version,
+    target_id: i32,
+    ty: UseEntityType,
+    hand: Hand,
+    sneaking: bool,
+) -> Result<(), Error> {
+    let target = ty.target();
+    if version < Version::V1_8 {
+        // There is nothing to say where the entity was clicked yet
+        if let UseEntityType::InteractAt(_) = ty {
+            return Ok(());
+        }
+        conn.write_packet(packet::play::serverbound::UseEntity_Handsfree_i32 {
+            target_id,
+            ty: ty.ordinal() as u8,
+        })
+    } else if version < Version::V1_9 {
+        conn.write_packet(packet::play::serverbound::UseEntity_Handsfree {
+            target_id: VarInt(target_id),
+            ty: VarInt(ty.ordinal()),
+            target_x: target.x,
+            target_y: target.y,
+            target_z: target.z,
+        })
+    } else if version < Version::V1_16 {
+        conn.write_packet(packet::play::serverbound::UseEntity_Hand {
+            target_id: VarInt(target_id),
+            ty: VarInt(ty.ordinal()),
+            target_x: target.x,
+            target_y: target.y,
+            target_z: target.z,
+            hand: VarInt(hand.ordinal()),
+        })
+    } else {
+        conn.write_packet(packet::play::serverbound::UseEntity_Sneakflag {
+            target_id: VarInt(target_id),
+            ty: VarInt(ty.ordinal()),
+            target_x: target.x,
+            target_y: target.y,
+            target_z: target.z,
+            hand: VarInt(hand.ordinal()),
+            sneaking,
+        })
+    }
+}
+
 pub fn send_client_settings(
     conn: &mut Conn,
     version: Version,
@@ -47,7 +47,7 @@ protocol_packet_ids!(
             0x01 => SpawnExperienceOrb
             0x02 => SpawnGlobalEntity
             0x03 => SpawnMob_WithMeta
-            0x04 => SpawnPainting_VarInt
+            0x04 => SpawnPainting_String
             0x05 => SpawnPlayer_f64
             0x06 => Animation
             0x07 => Statistics
@@ -58,6 +58,12 @@ impl Metadata {
         self.map.insert(key.index, val.wrap());
     }
 
+    /// Returns the value at the index without checking its type, as the
+    /// type of a field can differ between versions.
+    pub fn get_raw(&self, index: i32) -> Option<&Value> {
+        self.map.get(&index)
+    }
+
     fn put_raw<T: MetaValue>(&mut self, index: i32, val: T) {
         self.map.insert(index, val.wrap());
     }
@@ -679,7 +685,7 @@ impl Serializable for VillagerData {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PoseData {
     Standing,
     FallFlying,
@@ -35,6 +35,28 @@ impl Direction {
         }
     }
 
+    pub fn from_index(val: i32) -> Direction {
+        match val {
+            0 => Direction::Down,
+            1 => Direction::Up,
+            2 => Direction::North,
+            3 => Direction::South,
+            4 => Direction::West,
+            5 => Direction::East,
+            _ => Direction::Invalid,
+        }
+    }
+
+    pub fn from_horizontal_index(val: i32) -> Direction {
+        match val {
+            0 => Direction::South,
+            1 => Direction::West,
+            2 => Direction::North,
+            3 => Direction::East,
+            _ => Direction::Invalid,
+        }
+    }
+
     pub fn opposite(&self) -> Direction {
         match *self {
             Direction::Down => Direction::Up,
@@ -1,6 +1,7 @@
+use crate::console;
 use crate::model;
 use crate::render;
-use crate::resources;
+use crate::settings;
 use crate::shared::Direction;
 use crate::types::bit::Set;
 use crate::world;
@@ -21,15 +22,12 @@ pub struct ChunkBuilder {
 
     models: Arc<RwLock<model::Factory>>,
     resource_version: usize,
+    smooth_lighting: bool,
+    biome_blend: i32,
 }
 
 impl ChunkBuilder {
-    pub fn new(
-        resources: Arc<RwLock<resources::Manager>>,
-        textures: Arc<RwLock<render::TextureManager>>,
-    ) -> Self {
-        let models = Arc::new(RwLock::new(model::Factory::new(resources, textures)));
-
+    pub fn new(models: Arc<RwLock<model::Factory>>) -> Self {
         let mut threads = vec![];
         let mut free = vec![];
         let (built_send, built_recv) = unbounded();
@@ -50,6 +48,8 @@ impl ChunkBuilder {
             built_recv,
             models,
             resource_version: 0xFFFF,
+            smooth_lighting: true,
+            biome_blend: 1,
         }
     }
 
@@ -58,11 +58,22 @@ impl ChunkBuilder {
         world: Arc<World>,
         renderer: Arc<RwLock<render::Renderer>>,
         version: usize,
+        vars: &console::Vars,
     ) {
         if version != self.resource_version {
             self.resource_version = version;
             self.models.write().version_change();
         }
+        let smooth_lighting = *vars.get(settings::R_SMOOTH_LIGHTING);
+        let biome_blend = (*vars.get(settings::R_BIOME_BLEND)).clamp(0, 7) as i32;
+        if smooth_lighting != self.smooth_lighting || biome_blend != self.biome_blend {
+            self.smooth_lighting = smooth_lighting;
+            self.biome_blend = biome_blend;
+            let mut models = self.models.write();
+            models.set_smooth_lighting(smooth_lighting);
+            models.set_biome_blend(biome_blend);
+            world.flag_dirty_all();
+        }
 
         let mut renderer = renderer.write();
         while let Ok((id, mut val)) = self.built_recv.try_recv() {
@@ -221,10 +232,14 @@ fn build_func_1(models: Arc<RwLock<model::Factory>>, work: BuildReq) -> BuildRep
 
                 match block {
                     block::Block::Water { .. } | block::Block::FlowingWater { .. } => {
-                        let tex = models.read().textures.clone();
+                        let (tex, biome_blend) = {
+                            let models = models.read();
+                            (models.textures.clone(), models.biome_blend())
+                        };
                         trans_count += model::liquid::render_liquid(
                             tex,
                             false,
+                            biome_blend,
                             &snapshot,
                             x,
                             y,
@@ -234,10 +249,14 @@ fn build_func_1(models: Arc<RwLock<model::Factory>>, work: BuildReq) -> BuildRep
                         continue;
                     }
                     block::Block::Lava { .. } | block::Block::FlowingLava { .. } => {
-                        let tex = models.read().textures.clone();
+                        let (tex, biome_blend) = {
+                            let models = models.read();
+                            (models.textures.clone(), models.biome_blend())
+                        };
                         solid_count += model::liquid::render_liquid(
                             tex,
                             true,
+                            biome_blend,
                             &snapshot,
                             x,
                             y,
@@ -0,0 +1,656 @@
+//! Entity models described by json files in `assets/leafish/models/entity`
+//! instead of code.
+//!
+//! A definition lists the parts of a model. Every part has a pivot, an
+//! optional rotation and the cuboids it is made of. Positions, sizes and
+//! texture offsets use the same pixel units as vanilla's models: y points
+//! down with the feet at 24 and the front of the entity faces -z. Parts
+//! can be animated by channels, which add to the rotation of the part:
+//!
+//! ```json
+//! {
+//!     "texture": "minecraft:entity/pig/pig",
+//!     "texture_size": [64, 32],
+//!     "height": 0.9,
+//!     "parts": [
+//!         {
+//!             "name": "head",
+//!             "pivot": [0, 12, -6],
+//!             "animations": [{"channel": "head_pitch"}],
+//!             "cubes": [{"origin": [-4, -4, -8], "size": [8, 8, 8], "uv": [0, 0]}]
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! A definition may name a `parent` to take any missing fields from.
+
+use super::metadata::EntityState;
+use super::{CustomEntityRenderer, EntityType, GameInfo, Light, Position, Rotation};
+use crate::ecs;
+use crate::format;
+use crate::render::model::{self, FormatState};
+use crate::render::{self, Renderer, Texture};
+use crate::resources;
+use crate::world;
+use cgmath::{Decomposed, Matrix4, Quaternion, Rad, Rotation3, Vector3, Vector4};
+use log::error;
+use std::f32::consts::PI;
+
+/// The entity types drawn from a definition and the name of their
+/// definition.
+pub const DEFINITIONS: &[(EntityType, &str)] = &[
+    (EntityType::Zombie, "zombie"),
+    (EntityType::Husk, "husk"),
+    (EntityType::PigZombie, "zombie_pigman"),
+    (EntityType::ZombifiedPiglin, "zombie_pigman"),
+    (EntityType::Skeleton, "skeleton"),
+    (EntityType::Stray, "stray"),
+    (EntityType::WitherSkeleton, "wither_skeleton"),
+    (EntityType::Creeper, "creeper"),
+    (EntityType::Spider, "spider"),
+    (EntityType::CaveSpider, "cave_spider"),
+    (EntityType::Enderman, "enderman"),
+    (EntityType::Pig, "pig"),
+    (EntityType::Cow, "cow"),
+    (EntityType::MushroomCow, "mooshroom"),
+    (EntityType::Sheep, "sheep"),
+    (EntityType::Chicken, "chicken"),
+    (EntityType::Slime, "slime"),
+];
+
+/// The number of matrices a model can use, see `modelMatrix` in the
+/// model shader.
+const MAX_PARTS: usize = 12;
+
+/// Values that drive the rotation of animated parts.
+#[derive(Clone, Copy, Debug)]
+enum Channel {
+    /// Swings back and forth while the entity walks
+    Walk,
+    /// The pitch of the entity's head
+    HeadPitch,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Clone, Debug)]
+struct Animation {
+    channel: Channel,
+    axis: Axis,
+    amplitude: f32,
+    /// Offset of walking animations, in radians
+    phase: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Cube {
+    origin: [f32; 3],
+    size: [f32; 3],
+    uv: [f32; 2],
+    inflate: f32,
+    /// Replaces the texture of the model for this cube
+    texture: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct Part {
+    pivot: [f32; 3],
+    /// In radians, applied in z, y, x order like vanilla
+    rotation: [f32; 3],
+    cubes: Vec<Cube>,
+    animations: Vec<Animation>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ModelDefinition {
+    texture: String,
+    texture_size: [f32; 2],
+    scale: f32,
+    /// The height of the model in blocks, before scaling
+    height: f32,
+    parts: Vec<Part>,
+}
+
+impl ModelDefinition {
+    pub fn load(res: &resources::Manager, name: &str) -> Option<ModelDefinition> {
+        ModelDefinition::load_json(res, name).and_then(|mdl| {
+            let def = ModelDefinition::parse(res, name, &mdl)?;
+            let animated = def
+                .parts
+                .iter()
+                .filter(|part| !part.animations.is_empty())
+                .count();
+            // One extra for the static parts and one for the name tag
+            if animated + 2 > MAX_PARTS {
+                error!("Entity model {} has too many animated parts", name);
+                return None;
+            }
+            Some(def)
+        })
+    }
+
+    fn load_json(res: &resources::Manager, name: &str) -> Option<serde_json::Value> {
+        let file = match res.open("leafish", &format!("models/entity/{}.json", name)) {
+            Some(val) => val,
+            None => {
+                error!("Error missing entity model {}", name);
+                return None;
+            }
+        };
+        match serde_json::from_reader(file) {
+            Ok(val) => Some(val),
+            Err(err) => {
+                error!("Error loading entity model {}: {:?}", name, err);
+                None
+            }
+        }
+    }
+
+    fn parse(
+        res: &resources::Manager,
+        name: &str,
+        mdl: &serde_json::Value,
+    ) -> Option<ModelDefinition> {
+        let parent = match mdl.get("parent").and_then(|v| v.as_str()) {
+            Some(parent) => Some(ModelDefinition::parse(
+                res,
+                parent,
+                &ModelDefinition::load_json(res, parent)?,
+            )?),
+            None => None,
+        };
+        ModelDefinition::parse_with_parent(name, mdl, parent.as_ref())
+    }
+
+    /// Parses a definition, taking the fields it leaves out from the
+    /// parent.
+    fn parse_with_parent(
+        name: &str,
+        mdl: &serde_json::Value,
+        parent: Option<&ModelDefinition>,
+    ) -> Option<ModelDefinition> {
+        let texture = mdl
+            .get("texture")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_owned())
+            .or_else(|| parent.map(|p| p.texture.clone()));
+        let parts = match mdl.get("parts").and_then(|v| v.as_array()) {
+            Some(parts) => Some(
+                parts
+                    .iter()
+                    .map(|part| Part::parse(name, part))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => parent.map(|p| p.parts.clone()),
+        };
+        let (texture, parts) = match (texture, parts) {
+            (Some(texture), Some(parts)) => (texture, parts),
+            _ => {
+                error!("Entity model {} is missing its texture or parts", name);
+                return None;
+            }
+        };
+        Some(ModelDefinition {
+            texture,
+            texture_size: parse_array(mdl.get("texture_size"))
+                .or_else(|| parent.map(|p| p.texture_size))
+                .unwrap_or([64.0, 32.0]),
+            scale: parse_float(mdl.get("scale"))
+                .or_else(|| parent.map(|p| p.scale))
+                .unwrap_or(1.0),
+            height: parse_float(mdl.get("height"))
+                .or_else(|| parent.map(|p| p.height))
+                .unwrap_or(1.0),
+            parts,
+        })
+    }
+}
+
+impl Part {
+    fn parse(model: &str, part: &serde_json::Value) -> Option<Part> {
+        let rotation: [f32; 3] = parse_array(part.get("rotation")).unwrap_or([0.0; 3]);
+        let cubes = part
+            .get("cubes")
+            .and_then(|v| v.as_array())
+            .map_or(&[][..], |v| &v[..])
+            .iter()
+            .map(|cube| {
+                let cube = Cube {
+                    origin: parse_array(cube.get("origin"))?,
+                    size: parse_array(cube.get("size"))?,
+                    uv: parse_array(cube.get("uv")).unwrap_or([0.0; 2]),
+                    inflate: parse_float(cube.get("inflate")).unwrap_or(0.0),
+                    texture: cube
+                        .get("texture")
+                        .and_then(|v| v.as_str())
+                        .map(|v| v.to_owned()),
+                };
+                Some(cube)
+            })
+            .collect::<Option<Vec<_>>>();
+        let cubes = match cubes {
+            Some(cubes) => cubes,
+            None => {
+                error!(
+                    "Entity model {} has a cube without an origin or size",
+                    model
+                );
+                return None;
+            }
+        };
+        let animations = part
+            .get("animations")
+            .and_then(|v| v.as_array())
+            .map_or(&[][..], |v| &v[..])
+            .iter()
+            .map(|anim| Animation::parse(model, anim))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Part {
+            pivot: parse_array(part.get("pivot")).unwrap_or([0.0; 3]),
+            rotation: [
+                rotation[0].to_radians(),
+                rotation[1].to_radians(),
+                rotation[2].to_radians(),
+            ],
+            cubes,
+            animations,
+        })
+    }
+
+    /// Returns the transform of the part for the given extra rotation,
+    /// converted from vanilla's model space.
+    fn matrix(&self, extra: [f32; 3]) -> Matrix4<f32> {
+        // x is mirrored compared to vanilla, which flips the rotations
+        // around the other axes
+        Matrix4::from_translation(Vector3::new(
+            -self.pivot[0] / 16.0,
+            (self.pivot[1] - 24.0) / 16.0,
+            self.pivot[2] / 16.0,
+        )) * Matrix4::from(Quaternion::from_angle_z(
+            Rad(-(self.rotation[2] + extra[2])),
+        )) * Matrix4::from(Quaternion::from_angle_y(
+            Rad(-(self.rotation[1] + extra[1])),
+        )) * Matrix4::from(Quaternion::from_angle_x(Rad(self.rotation[0] + extra[0])))
+    }
+
+    fn append_verts(
+        &self,
+        verts: &mut Vec<model::Vertex>,
+        renderer: &Renderer,
+        tex: &Texture,
+        texture_size: [f32; 2],
+    ) {
+        for cube in &self.cubes {
+            let tex = match cube.texture.as_ref() {
+                Some(name) => Renderer::get_texture(renderer.get_textures_ref(), name),
+                None => tex.clone(),
+            };
+            let [w, h, d] = cube.size;
+            let [u, v] = cube.uv;
+            let rel = |x: f32, y: f32, w: f32, h: f32| {
+                Some(tex.relative(
+                    x / texture_size[0],
+                    y / texture_size[1],
+                    w / texture_size[0],
+                    h / texture_size[1],
+                ))
+            };
+            let inflate = cube.inflate;
+            model::append_box(
+                verts,
+                -(cube.origin[0] + w + inflate) / 16.0,
+                -(cube.origin[1] + h + inflate) / 16.0,
+                (cube.origin[2] - inflate) / 16.0,
+                (w + inflate * 2.0) / 16.0,
+                (h + inflate * 2.0) / 16.0,
+                (d + inflate * 2.0) / 16.0,
+                [
+                    rel(u + d + w, v, w, d),           // Down
+                    rel(u + d, v, w, d),               // Up
+                    rel(u + d, v + d, w, h),           // North
+                    rel(u + d * 2.0 + w, v + d, w, h), // South
+                    rel(u + d + w, v + d, d, h),       // West
+                    rel(u, v + d, d, h),               // East
+                ],
+            );
+        }
+    }
+}
+
+impl Animation {
+    fn parse(model: &str, anim: &serde_json::Value) -> Option<Animation> {
+        let channel = match anim.get("channel").and_then(|v| v.as_str()) {
+            Some("walk") => Channel::Walk,
+            Some("head_pitch") => Channel::HeadPitch,
+            channel => {
+                error!(
+                    "Entity model {} uses unknown animation channel {:?}",
+                    model, channel
+                );
+                return None;
+            }
+        };
+        let axis = match anim.get("axis").and_then(|v| v.as_str()) {
+            Some("y") => Axis::Y,
+            Some("z") => Axis::Z,
+            _ => Axis::X,
+        };
+        Some(Animation {
+            channel,
+            axis,
+            amplitude: parse_float(anim.get("amplitude")).unwrap_or(1.0),
+            phase: parse_float(anim.get("phase")).unwrap_or(0.0).to_radians(),
+        })
+    }
+}
+
+fn parse_float(val: Option<&serde_json::Value>) -> Option<f32> {
+    val.and_then(|v| v.as_f64()).map(|v| v as f32)
+}
+
+fn parse_array<const N: usize>(val: Option<&serde_json::Value>) -> Option<[f32; N]> {
+    let arr = val.and_then(|v| v.as_array())?;
+    if arr.len() != N {
+        return None;
+    }
+    let mut ret = [0.0; N];
+    for (i, v) in arr.iter().enumerate() {
+        ret[i] = v.as_f64()? as f32;
+    }
+    Some(ret)
+}
+
+/// The model of an entity drawn from a definition.
+#[derive(Default)]
+pub struct DefinedModel {
+    model: Option<model::ModelKey>,
+    /// How far the limbs have swung, like vanilla's limb swing
+    walk_time: f32,
+    /// How far the limbs swing, between 0 and 1
+    walk_amount: f32,
+}
+
+impl DefinedModel {
+    pub fn new() -> DefinedModel {
+        Default::default()
+    }
+}
+
+pub struct DefinedModelRenderer {
+    definition: ModelDefinition,
+    /// The indices of the model parts of animated parts, static parts
+    /// are merged into the first model part
+    animated: Vec<(usize, usize)>,
+    name_tag: usize,
+    defined_model: ecs::Key<DefinedModel>,
+    position: ecs::Key<Position>,
+    rotation: ecs::Key<Rotation>,
+    game_info: ecs::Key<GameInfo>,
+    light: ecs::Key<Light>,
+    state: ecs::Key<EntityState>,
+    entity_type: ecs::Key<EntityType>,
+}
+
+impl DefinedModelRenderer {
+    pub fn new(m: &mut ecs::Manager, definition: ModelDefinition) -> Self {
+        let animated: Vec<_> = definition
+            .parts
+            .iter()
+            .enumerate()
+            .filter(|(_, part)| !part.animations.is_empty())
+            .map(|(i, _)| i)
+            .zip(1..)
+            .collect();
+        DefinedModelRenderer {
+            name_tag: animated.len() + 1,
+            definition,
+            animated,
+            defined_model: m.get_key(),
+            position: m.get_key(),
+            rotation: m.get_key(),
+            game_info: m.get_key(),
+            light: m.get_key(),
+            state: m.get_key(),
+            entity_type: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for DefinedModelRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        world: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        let world_entity = m.get_world();
+        let delta = m.get_component(world_entity, self.game_info).unwrap().delta;
+        let defined_model = m.get_component_mut(e, self.defined_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let rotation = m.get_component(e, self.rotation).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+        let entity_type = *m.get_component(e, self.entity_type).unwrap();
+
+        let mut scale = self.definition.scale;
+        let mut invisible = false;
+        if let Some(state) = m.get_component_mut(e, self.state) {
+            if state.dirty {
+                state.dirty = false;
+                self.entity_removed(m, e, world, renderer);
+                self.entity_added(m, e, world, renderer);
+            }
+            if state.baby {
+                scale *= 0.5;
+            }
+            if entity_type.is_slime() {
+                scale *= state.slime_size.max(1) as f32;
+            }
+            invisible = state.invisible;
+        }
+
+        // Swing faster the further the entity moved since the last tick
+        let ticks = (delta / 3.0) as f32;
+        let moved = position.position - position.last_position;
+        let moved = (moved.x * moved.x + moved.z * moved.z).sqrt() as f32;
+        let target = (moved * 4.0).min(1.0);
+        defined_model.walk_amount += (target - defined_model.walk_amount) * (0.4 * ticks).min(1.0);
+        defined_model.walk_time += defined_model.walk_amount * ticks;
+
+        let mdl = match defined_model.model {
+            Some(key) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+
+        let offset = Vector3::new(
+            position.position.x as f32,
+            -position.position.y as f32,
+            position.position.z as f32,
+        );
+        let offset_matrix = Matrix4::from(Decomposed {
+            scale,
+            rot: Quaternion::from_angle_y(Rad(PI + rotation.yaw as f32)),
+            disp: offset,
+        });
+
+        if invisible {
+            for part in &mut mdl.matrix[..self.name_tag] {
+                *part = Matrix4::from_scale(0.0);
+            }
+        } else {
+            mdl.matrix[0] = offset_matrix;
+            for &(part, index) in &self.animated {
+                let part = &self.definition.parts[part];
+                let mut extra = [0.0; 3];
+                for anim in &part.animations {
+                    let value = match anim.channel {
+                        Channel::Walk => {
+                            (defined_model.walk_time * 0.6662 + anim.phase).cos()
+                                * defined_model.walk_amount
+                        }
+                        Channel::HeadPitch => -rotation.pitch as f32,
+                    };
+                    extra[anim.axis as usize] += value * anim.amplitude;
+                }
+                mdl.matrix[index] = offset_matrix * part.matrix(extra);
+            }
+        }
+
+        let ang = (position.position.x - renderer.camera.pos.x)
+            .atan2(position.position.z - renderer.camera.pos.z) as f32;
+        mdl.matrix[self.name_tag] = Matrix4::from(Decomposed {
+            scale: 1.0,
+            rot: Quaternion::from_angle_y(Rad(ang)),
+            disp: offset + Vector3::new(0.0, -self.definition.height * scale - 0.3, 0.0),
+        });
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let defined_model = m.get_component_mut(e, self.defined_model).unwrap();
+        let tex = Renderer::get_texture(renderer.get_textures_ref(), &self.definition.texture);
+        let texture_size = self.definition.texture_size;
+
+        let mut parts = vec![vec![]; self.name_tag + 1];
+        for (i, part) in self.definition.parts.iter().enumerate() {
+            match self.animated.iter().find(|(p, _)| *p == i) {
+                Some(&(_, index)) => {
+                    part.append_verts(&mut parts[index], renderer, &tex, texture_size)
+                }
+                None => {
+                    // Static parts are moved into place once and share
+                    // the first model part
+                    let mut verts = vec![];
+                    part.append_verts(&mut verts, renderer, &tex, texture_size);
+                    let matrix = part.matrix([0.0; 3]);
+                    for vert in &mut verts {
+                        let pos = matrix * Vector4::new(vert.x, -vert.y, vert.z, 1.0);
+                        vert.x = pos.x;
+                        vert.y = -pos.y;
+                        vert.z = pos.z;
+                    }
+                    parts[0].extend(verts);
+                }
+            }
+        }
+
+        // Custom names are only shown all the time when the server asks for it
+        let name = m
+            .get_component(e, self.state)
+            .filter(|state| state.custom_name_visible)
+            .and_then(|state| state.custom_name.as_ref());
+        if let Some(name) = name {
+            let mut state = FormatState {
+                width: 0.0,
+                offset: 0.0,
+                text: Vec::new(),
+                renderer,
+                y_scale: 0.16,
+                x_scale: 0.01,
+            };
+            state.build(name, format::Color::Black);
+            let width = state.width;
+            // Center align text
+            for vert in &mut state.text {
+                vert.x += width * 0.5;
+                vert.r = 64;
+                vert.g = 64;
+                vert.b = 64;
+            }
+            parts[self.name_tag].extend_from_slice(&state.text);
+            for vert in &mut state.text {
+                vert.x -= 0.01;
+                vert.y -= 0.01;
+                vert.z -= 0.05;
+                vert.r = 255;
+                vert.g = 255;
+                vert.b = 255;
+            }
+            parts[self.name_tag].extend_from_slice(&state.text);
+        }
+
+        defined_model.model = Some(renderer.model.create_model(model::DEFAULT, parts));
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let defined_model = m.get_component_mut(e, self.defined_model).unwrap();
+        if let Some(model) = defined_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_definition() {
+        let mdl = serde_json::json!({
+            "texture": "minecraft:entity/pig/pig",
+            "parts": [{
+                "pivot": [0, 12, -6],
+                "animations": [{"channel": "walk", "axis": "y", "phase": 180}],
+                "cubes": [{"origin": [-4, -4, -8], "size": [8, 8, 8]}]
+            }]
+        });
+        let def = ModelDefinition::parse_with_parent("test", &mdl, None).unwrap();
+        assert_eq!(def.texture, "minecraft:entity/pig/pig");
+        assert_eq!(def.texture_size, [64.0, 32.0]);
+        assert_eq!(def.scale, 1.0);
+        assert_eq!(def.height, 1.0);
+        let part = &def.parts[0];
+        assert_eq!(part.pivot, [0.0, 12.0, -6.0]);
+        assert_eq!(part.rotation, [0.0; 3]);
+        assert_eq!(part.cubes[0].uv, [0.0; 2]);
+        let anim = &part.animations[0];
+        assert!(matches!(anim.channel, Channel::Walk));
+        assert!(matches!(anim.axis, Axis::Y));
+        assert_eq!(anim.amplitude, 1.0);
+        assert!((anim.phase - PI).abs() < 1.0e-6);
+
+        // A child only replacing some fields keeps the rest
+        let child = serde_json::json!({"texture": "minecraft:entity/pig/saddle", "scale": 0.5});
+        let child = ModelDefinition::parse_with_parent("child", &child, Some(&def)).unwrap();
+        assert_eq!(child.texture, "minecraft:entity/pig/saddle");
+        assert_eq!(child.scale, 0.5);
+        assert_eq!(child.parts.len(), 1);
+    }
+
+    #[test]
+    fn invalid_definitions() {
+        let unknown_channel = serde_json::json!({
+            "texture": "minecraft:entity/pig/pig",
+            "parts": [{"animations": [{"channel": "dance"}], "cubes": []}]
+        });
+        assert!(ModelDefinition::parse_with_parent("test", &unknown_channel, None).is_none());
+        let no_size = serde_json::json!({
+            "texture": "minecraft:entity/pig/pig",
+            "parts": [{"cubes": [{"origin": [0, 0, 0]}]}]
+        });
+        assert!(ModelDefinition::parse_with_parent("test", &no_size, None).is_none());
+        let no_parts = serde_json::json!({"texture": "minecraft:entity/pig/pig"});
+        assert!(ModelDefinition::parse_with_parent("test", &no_parts, None).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use super::{CustomEntityRenderer, GameInfo, Light, Position};
+use crate::ecs;
+use crate::render::model;
+use crate::render::{self, Renderer};
+use crate::world;
+use cgmath::{Decomposed, Matrix4, Quaternion, Rad, Rotation3, Vector3};
+
+/// The smallest amount of experience each of the orb icons is used for
+const ICON_THRESHOLDS: [i32; 10] = [3, 7, 17, 37, 73, 149, 307, 617, 1237, 2477];
+
+#[derive(Default)]
+pub struct ExperienceOrbModel {
+    model: Option<model::ModelKey>,
+    /// The amount of experience the orb is worth, picks the icon
+    pub value: i32,
+    /// Ticks since the orb appeared, drives the colour cycling
+    age: f64,
+}
+
+impl ExperienceOrbModel {
+    pub fn new() -> ExperienceOrbModel {
+        Default::default()
+    }
+}
+
+pub struct ExperienceOrbRenderer {
+    orb_model: ecs::Key<ExperienceOrbModel>,
+    position: ecs::Key<Position>,
+    light: ecs::Key<Light>,
+    game_info: ecs::Key<GameInfo>,
+}
+
+impl ExperienceOrbRenderer {
+    pub fn new(m: &mut ecs::Manager) -> Self {
+        ExperienceOrbRenderer {
+            orb_model: m.get_key(),
+            position: m.get_key(),
+            light: m.get_key(),
+            game_info: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for ExperienceOrbRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        let world_entity = m.get_world();
+        let delta = m.get_component(world_entity, self.game_info).unwrap().delta;
+        let orb_model = m.get_component_mut(e, self.orb_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+
+        orb_model.age += delta / 3.0;
+        let mdl = match orb_model.model {
+            Some(key) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+
+        // Cycles between green and yellow like vanilla
+        let phase = orb_model.age as f32 / 2.0;
+        mdl.colors[0] = [
+            (phase.sin() + 1.0) * 0.5,
+            1.0,
+            ((phase + 4.188_790_3).sin() + 1.0) * 0.1,
+            0.5,
+        ];
+
+        // Always face the camera
+        let ang = (position.position.x - renderer.camera.pos.x)
+            .atan2(position.position.z - renderer.camera.pos.z) as f32;
+        mdl.matrix[0] = Matrix4::from(Decomposed {
+            scale: 0.3,
+            rot: Quaternion::from_angle_y(Rad(ang)),
+            disp: Vector3::new(
+                position.position.x as f32,
+                -position.position.y as f32 - 0.1,
+                position.position.z as f32,
+            ),
+        });
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let orb_model = m.get_component_mut(e, self.orb_model).unwrap();
+        let icon = ICON_THRESHOLDS
+            .iter()
+            .filter(|&&threshold| orb_model.value >= threshold)
+            .count();
+        let tex = Renderer::get_texture(
+            renderer.get_textures_ref(),
+            "minecraft:entity/experience_orb",
+        );
+        let tex = tex.relative(
+            (icon % 4) as f32 * 0.25,
+            (icon / 4) as f32 * 0.25,
+            0.25,
+            0.25,
+        );
+        let mut verts = vec![];
+        model::append_box(
+            &mut verts,
+            -0.5,
+            -0.25,
+            0.0,
+            1.0,
+            1.0,
+            0.0,
+            [None, None, Some(tex), None, None, None],
+        );
+        orb_model.model = Some(renderer.model.create_model(model::DEFAULT, vec![verts]));
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let orb_model = m.get_component_mut(e, self.orb_model).unwrap();
+        if let Some(model) = orb_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::metadata::EntityState;
+use super::{CustomEntityRenderer, EntityType, GameInfo, Light, Position};
+use crate::ecs;
+use crate::model::Factory;
+use crate::render::model;
+use crate::render::{self, Renderer};
+use crate::world;
+use crate::world::block::Block;
+use cgmath::{Matrix4, Vector3};
+
+/// The number of ticks primed TNT burns for unless the server says
+/// otherwise, which it can't before 1.9
+const TNT_FUSE: f64 = 80.0;
+
+/// The model of an entity that looks like a block, falling blocks and
+/// primed TNT.
+pub struct BlockModel {
+    /// The model and the block it was built from, falling blocks only get
+    /// theirs after they're spawned.
+    model: Option<(model::ModelKey, Block)>,
+    pub block: Block,
+    /// Ticks left on the fuse of primed TNT
+    fuse: f64,
+}
+
+impl BlockModel {
+    pub fn new(block: Block) -> BlockModel {
+        BlockModel {
+            model: None,
+            block,
+            fuse: TNT_FUSE,
+        }
+    }
+}
+
+pub struct BlockModelRenderer {
+    block_model: ecs::Key<BlockModel>,
+    position: ecs::Key<Position>,
+    light: ecs::Key<Light>,
+    entity_type: ecs::Key<EntityType>,
+    game_info: ecs::Key<GameInfo>,
+    state: ecs::Key<EntityState>,
+}
+
+impl BlockModelRenderer {
+    pub fn new(m: &mut ecs::Manager) -> Self {
+        BlockModelRenderer {
+            block_model: m.get_key(),
+            position: m.get_key(),
+            light: m.get_key(),
+            entity_type: m.get_key(),
+            game_info: m.get_key(),
+            state: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for BlockModelRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        let world_entity = m.get_world();
+        let delta = m.get_component(world_entity, self.game_info).unwrap().delta;
+        let fuse = m
+            .get_component_mut(e, self.state)
+            .and_then(|state| state.fuse.take());
+        let block_model = m.get_component_mut(e, self.block_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+        let entity_type = *m.get_component(e, self.entity_type).unwrap();
+
+        if let Some(fuse) = fuse {
+            block_model.fuse = fuse as f64;
+        }
+        block_model.fuse -= delta / 3.0;
+        if let Some((model, block)) = block_model.model {
+            if block != block_model.block {
+                renderer.model.remove_model(model);
+                build_model(renderer, entity_type, block_model);
+            }
+        }
+        let mdl = match block_model.model {
+            Some((key, _)) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+
+        // Swells just before it explodes and flashes white every few
+        // ticks, like vanilla
+        let (scale, flash) = if entity_type == EntityType::PrimedTnt {
+            let fuse = block_model.fuse;
+            let swell = (1.0 - (fuse + 1.0) / 10.0).clamp(0.0, 1.0).powi(4);
+            let flash = if (fuse as i32 / 5) % 2 == 0 {
+                ((1.0 - (fuse + 1.0) / 100.0) * 0.8).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (1.0 + swell as f32 * 0.3, flash as f32)
+        } else {
+            (1.0, 0.0)
+        };
+
+        // The model spans a block from the origin, center it on the
+        // bottom of the entity
+        let matrix = Matrix4::from_translation(Vector3::new(
+            position.position.x as f32,
+            -position.position.y as f32 - 0.5,
+            position.position.z as f32,
+        )) * Matrix4::from_scale(scale)
+            * Matrix4::from_translation(Vector3::new(-0.5, 0.5, -0.5));
+        mdl.matrix[0] = matrix;
+        if mdl.matrix.len() > 1 {
+            mdl.matrix[1] = matrix;
+            mdl.colors[1] = [1.0, 1.0, 1.0, flash];
+        }
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let entity_type = *m.get_component(e, self.entity_type).unwrap();
+        let block_model = m.get_component_mut(e, self.block_model).unwrap();
+        build_model(renderer, entity_type, block_model);
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let block_model = m.get_component_mut(e, self.block_model).unwrap();
+        if let Some((model, _)) = block_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
+
+/// Builds the model of the block the entity looks like.
+fn build_model(renderer: &mut Renderer, entity_type: EntityType, block_model: &mut BlockModel) {
+    let verts = Factory::get_state_vertices(
+        &renderer.block_models,
+        block_model.block,
+        &mut rand::thread_rng(),
+    );
+    let mut parts = vec![verts];
+    if entity_type == EntityType::PrimedTnt {
+        // A white copy of the block drawn over it for the flashes
+        let solid = Renderer::get_texture(renderer.get_textures_ref(), "leafish:solid");
+        let overlay = parts[0]
+            .iter()
+            .map(|vert| {
+                let mut vert = vert.clone();
+                vert.texture = solid.clone();
+                vert.texture_x = 0.0;
+                vert.texture_y = 0.0;
+                vert.r = 255;
+                vert.g = 255;
+                vert.b = 255;
+                vert
+            })
+            .collect();
+        parts.push(overlay);
+    }
+    let model = renderer.model.create_model(model::DEFAULT, parts);
+    block_model.model = Some((model, block_model.block));
+}
@@ -0,0 +1,532 @@
+use super::item;
+use super::metadata::EntityState;
+use super::{CustomEntityRenderer, Light, Position};
+use crate::ecs;
+use crate::inventory::{Item, Material};
+use crate::render::model::{self, Vertex};
+use crate::render::{self, Renderer, Texture};
+use crate::shared::Direction;
+use crate::world;
+use cgmath::{Matrix4, Rad, Vector3};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// The texture all paintings are packed into
+const PAINTINGS: &str = "minecraft:painting/paintings_kristoffer_zetterstrand";
+const PAINTINGS_SIZE: f32 = 256.0;
+/// Where the back of the paintings is in their texture
+const BACK: (f32, f32) = (192.0, 0.0);
+
+/// One pixel of a block
+const PIXEL: f32 = 1.0 / 16.0;
+/// Item frames are drawn at half the size of dropped items
+const FRAME_ITEM_SCALE: f32 = 0.5;
+
+/// A painting, with its size and where it is in the paintings texture in
+/// pixels.
+#[derive(Debug)]
+pub struct Motive {
+    pub name: &'static str,
+    pub width: i32,
+    pub height: i32,
+    u: i32,
+    v: i32,
+}
+
+/// The paintings in the order of the registry the server sends their ids
+/// from since 1.13.
+pub const MOTIVES: [Motive; 26] = [
+    motive("kebab", 16, 16, 0, 0),
+    motive("aztec", 16, 16, 16, 0),
+    motive("alban", 16, 16, 32, 0),
+    motive("aztec2", 16, 16, 48, 0),
+    motive("bomb", 16, 16, 64, 0),
+    motive("plant", 16, 16, 80, 0),
+    motive("wasteland", 16, 16, 96, 0),
+    motive("pool", 32, 16, 0, 32),
+    motive("courbet", 32, 16, 32, 32),
+    motive("sea", 32, 16, 64, 32),
+    motive("sunset", 32, 16, 96, 32),
+    motive("creebet", 32, 16, 128, 32),
+    motive("wanderer", 16, 32, 0, 64),
+    motive("graham", 16, 32, 16, 64),
+    motive("match", 32, 32, 0, 128),
+    motive("bust", 32, 32, 32, 128),
+    motive("stage", 32, 32, 64, 128),
+    motive("void", 32, 32, 96, 128),
+    motive("skull_and_roses", 32, 32, 128, 128),
+    motive("wither", 32, 32, 160, 128),
+    motive("fighters", 64, 32, 0, 96),
+    motive("pointer", 64, 64, 0, 192),
+    motive("pigscene", 64, 64, 64, 192),
+    motive("burning_skull", 64, 64, 128, 192),
+    motive("skeleton", 64, 48, 192, 64),
+    motive("donkey_kong", 64, 48, 192, 112),
+];
+
+const fn motive(name: &'static str, width: i32, height: i32, u: i32, v: i32) -> Motive {
+    Motive {
+        name,
+        width,
+        height,
+        u,
+        v,
+    }
+}
+
+impl Motive {
+    /// Looks up a painting by the id the server sends since 1.13.
+    pub fn by_id(id: i32) -> Option<&'static Motive> {
+        MOTIVES.get(id as usize)
+    }
+
+    /// Looks up a painting by the title the server sends before 1.13,
+    /// e.g. `SkullAndRoses`. Namespaced registry names work as well.
+    pub fn by_name(name: &str) -> Option<&'static Motive> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        let normalize = |name: &str| name.replace('_', "").to_lowercase();
+        let name = normalize(name);
+        MOTIVES.iter().find(|motive| normalize(motive.name) == name)
+    }
+}
+
+/// Returns the center of a hanging entity in the given block, flush
+/// against the block it hangs on. Sizes are in pixels and only matter
+/// for paintings, vanilla moves the ones with an even size in blocks by
+/// half a block as they can't be centered on one.
+pub fn hanging_position(
+    x: i32,
+    y: i32,
+    z: i32,
+    facing: Direction,
+    width: i32,
+    height: i32,
+) -> Vector3<f64> {
+    let (fx, fy, fz) = facing.get_offset();
+    let wall = 0.5 - 1.0 / 32.0;
+    let mut pos = Vector3::new(
+        x as f64 + 0.5 - fx as f64 * wall,
+        y as f64 + 0.5 - fy as f64 * wall,
+        z as f64 + 0.5 - fz as f64 * wall,
+    );
+    if fy == 0 {
+        let offset = |size: i32| if size % 32 == 0 { 0.5 } else { 0.0 };
+        let (sx, _, sz) = facing.counter_clockwise().get_offset();
+        pos.x += sx as f64 * offset(width);
+        pos.z += sz as f64 * offset(width);
+        pos.y += offset(height);
+    }
+    pos
+}
+
+/// Rotates a model facing south to the direction.
+fn facing_rotation(facing: Direction) -> Matrix4<f32> {
+    match facing {
+        Direction::West => Matrix4::from_angle_y(Rad(-FRAC_PI_2)),
+        Direction::North => Matrix4::from_angle_y(Rad(PI)),
+        Direction::East => Matrix4::from_angle_y(Rad(FRAC_PI_2)),
+        Direction::Up => Matrix4::from_angle_x(Rad(FRAC_PI_2)),
+        Direction::Down => Matrix4::from_angle_x(Rad(-FRAC_PI_2)),
+        _ => Matrix4::from_scale(1.0),
+    }
+}
+
+fn hanging_matrix(position: &Position, facing: Direction) -> Matrix4<f32> {
+    Matrix4::from_translation(Vector3::new(
+        position.position.x as f32,
+        -position.position.y as f32,
+        position.position.z as f32,
+    )) * facing_rotation(facing)
+}
+
+pub struct PaintingModel {
+    model: Option<model::ModelKey>,
+    pub motive: Option<&'static Motive>,
+    pub facing: Direction,
+}
+
+impl Default for PaintingModel {
+    fn default() -> Self {
+        PaintingModel {
+            model: None,
+            motive: None,
+            facing: Direction::South,
+        }
+    }
+}
+
+impl PaintingModel {
+    pub fn new() -> PaintingModel {
+        Default::default()
+    }
+}
+
+pub struct PaintingRenderer {
+    painting_model: ecs::Key<PaintingModel>,
+    position: ecs::Key<Position>,
+    light: ecs::Key<Light>,
+}
+
+impl PaintingRenderer {
+    pub fn new(m: &mut ecs::Manager) -> Self {
+        PaintingRenderer {
+            painting_model: m.get_key(),
+            position: m.get_key(),
+            light: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for PaintingRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        let painting_model = m.get_component(e, self.painting_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+
+        let mdl = match painting_model.model {
+            Some(key) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+        mdl.matrix[0] = hanging_matrix(position, painting_model.facing);
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let painting_model = m.get_component_mut(e, self.painting_model).unwrap();
+        let motive = match painting_model.motive {
+            Some(motive) => motive,
+            None => return,
+        };
+        let tex = Renderer::get_texture(renderer.get_textures_ref(), PAINTINGS);
+        let rel = |u: f32, v: f32, w: f32, h: f32| {
+            tex.relative(
+                u / PAINTINGS_SIZE,
+                v / PAINTINGS_SIZE,
+                w / PAINTINGS_SIZE,
+                h / PAINTINGS_SIZE,
+            )
+        };
+        let back = rel(BACK.0, BACK.1, 16.0, 16.0);
+        let edge_x = rel(BACK.0, BACK.1, 16.0, 1.0);
+        let edge_y = rel(BACK.0, BACK.1, 1.0, 16.0);
+
+        // Built a block at a time like vanilla so the back and the edges
+        // repeat rather than stretch
+        let (cols, rows) = (motive.width / 16, motive.height / 16);
+        let (width, height) = (cols as f32, rows as f32);
+        let mut verts = vec![];
+        for col in 0..cols {
+            for row in 0..rows {
+                let front = rel(
+                    (motive.u + col * 16) as f32,
+                    (motive.v + row * 16) as f32,
+                    16.0,
+                    16.0,
+                );
+                let edge = |show: bool, tex: &Texture| if show { Some(tex.clone()) } else { None };
+                model::append_box(
+                    &mut verts,
+                    -width / 2.0 + col as f32,
+                    height / 2.0 - (row + 1) as f32,
+                    -PIXEL / 2.0,
+                    1.0,
+                    1.0,
+                    PIXEL,
+                    [
+                        edge(row == rows - 1, &edge_x),
+                        edge(row == 0, &edge_x),
+                        Some(back.clone()),
+                        Some(front),
+                        edge(col == 0, &edge_y),
+                        edge(col == cols - 1, &edge_y),
+                    ],
+                );
+            }
+        }
+        painting_model.model = Some(renderer.model.create_model(model::DEFAULT, vec![verts]));
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let painting_model = m.get_component_mut(e, self.painting_model).unwrap();
+        if let Some(model) = painting_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
+
+/// Returns the name of the dynamic texture holding the contents of the
+/// map with the id.
+pub fn map_texture(id: i32) -> String {
+    format!("map-{}", id)
+}
+
+/// Returns the id of the map a filled map item shows. It is kept in the
+/// tag of the item since 1.13 and in its damage before.
+fn map_id(item: &Item) -> Option<i32> {
+    if !matches!(item.material, Material::Map) {
+        return None;
+    }
+    let from_tag = item
+        .stack
+        .tag
+        .as_ref()
+        .and_then(|tag| tag.1.get("map"))
+        .and_then(|id| id.as_int());
+    from_tag.or_else(|| item.stack.damage.map(|damage| damage as i32))
+}
+
+/// Appends a piece of the frame, a box between the given pixels of the
+/// block facing south.
+fn append_frame_box(
+    verts: &mut Vec<Vertex>,
+    tex: &Texture,
+    (x1, y1): (f32, f32),
+    (x2, y2): (f32, f32),
+    depth: f32,
+) {
+    let (w, h) = (x2 - x1, y2 - y1);
+    let face = tex.relative(x1 / 16.0, (16.0 - y2) / 16.0, w / 16.0, h / 16.0);
+    model::append_box(
+        verts,
+        x1 * PIXEL - 0.5,
+        y1 * PIXEL - 0.5,
+        -PIXEL / 2.0,
+        w * PIXEL,
+        h * PIXEL,
+        depth * PIXEL,
+        [
+            Some(tex.relative(x1 / 16.0, 0.0, w / 16.0, depth / 16.0)),
+            Some(tex.relative(x1 / 16.0, 0.0, w / 16.0, depth / 16.0)),
+            Some(face.clone()),
+            Some(face),
+            Some(tex.relative(0.0, (16.0 - y2) / 16.0, depth / 16.0, h / 16.0)),
+            Some(tex.relative(0.0, (16.0 - y2) / 16.0, depth / 16.0, h / 16.0)),
+        ],
+    );
+}
+
+/// Builds the frame, maps get a bigger one that fills the block.
+fn build_frame(renderer: &Renderer, map: bool) -> Vec<Vertex> {
+    let background = Renderer::get_texture(
+        renderer.get_textures_ref(),
+        "minecraft:blocks/itemframe_background",
+    );
+    let wood = Renderer::get_texture(renderer.get_textures_ref(), "minecraft:blocks/planks_birch");
+    let (outer, inner) = if map { (0.0, 1.0) } else { (2.0, 3.0) };
+    let (outer2, inner2) = (16.0 - outer, 16.0 - inner);
+
+    let mut verts = vec![];
+    append_frame_box(
+        &mut verts,
+        &background,
+        (inner, inner),
+        (inner2, inner2),
+        0.5,
+    );
+    append_frame_box(&mut verts, &wood, (outer, outer), (outer2, inner), 1.0);
+    append_frame_box(&mut verts, &wood, (outer, inner2), (outer2, outer2), 1.0);
+    append_frame_box(&mut verts, &wood, (outer, inner), (inner, inner2), 1.0);
+    append_frame_box(&mut verts, &wood, (inner2, inner), (outer2, inner2), 1.0);
+    verts
+}
+
+/// Builds the item held by a frame, centered on the frame.
+fn build_item(renderer: &Renderer, material: &Material) -> Vec<Vertex> {
+    let mut verts = vec![];
+    let block = item::append_item(&mut verts, renderer, material);
+    // Blocks rest on the background, sprites sit inside the frame
+    let z = if block {
+        FRAME_ITEM_SCALE / 2.0
+    } else {
+        PIXEL / 2.0
+    };
+    for vert in &mut verts {
+        vert.x *= FRAME_ITEM_SCALE;
+        vert.y = (vert.y - 0.5) * FRAME_ITEM_SCALE;
+        vert.z = vert.z * FRAME_ITEM_SCALE + z;
+    }
+    verts
+}
+
+pub struct ItemFrameModel {
+    model: Option<model::ModelKey>,
+    pub facing: Direction,
+    /// The map the frame holds, drawn once its contents arrived
+    map: Option<i32>,
+    map_drawn: bool,
+}
+
+impl Default for ItemFrameModel {
+    fn default() -> Self {
+        ItemFrameModel {
+            model: None,
+            facing: Direction::South,
+            map: None,
+            map_drawn: false,
+        }
+    }
+}
+
+impl ItemFrameModel {
+    pub fn new() -> ItemFrameModel {
+        Default::default()
+    }
+}
+
+pub struct ItemFrameRenderer {
+    frame_model: ecs::Key<ItemFrameModel>,
+    position: ecs::Key<Position>,
+    light: ecs::Key<Light>,
+    state: ecs::Key<EntityState>,
+}
+
+impl ItemFrameRenderer {
+    pub fn new(m: &mut ecs::Manager) -> Self {
+        ItemFrameRenderer {
+            frame_model: m.get_key(),
+            position: m.get_key(),
+            light: m.get_key(),
+            state: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for ItemFrameRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        world: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        let map_arrived = {
+            let frame_model = m.get_component(e, self.frame_model).unwrap();
+            match frame_model.map {
+                Some(id) if !frame_model.map_drawn => renderer
+                    .get_textures_ref()
+                    .read()
+                    .get_dynamic(&map_texture(id))
+                    .is_some(),
+                _ => false,
+            }
+        };
+        if let Some(state) = m.get_component_mut(e, self.state) {
+            if state.dirty || map_arrived {
+                state.dirty = false;
+                self.entity_removed(m, e, world, renderer);
+                self.entity_added(m, e, world, renderer);
+            }
+        }
+
+        let frame_model = m.get_component(e, self.frame_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+        let state = m.get_component(e, self.state).unwrap();
+
+        let mdl = match frame_model.model {
+            Some(key) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+
+        let matrix = hanging_matrix(position, frame_model.facing);
+        // Frames can be made invisible since 1.16, the item still shows
+        mdl.matrix[0] = if state.invisible {
+            Matrix4::from_scale(0.0)
+        } else {
+            matrix
+        };
+        if mdl.matrix.len() > 1 {
+            // Maps only turn by quarters
+            let rotation = if frame_model.map.is_some() {
+                state.item_rotation % 4 * 2
+            } else {
+                state.item_rotation
+            };
+            mdl.matrix[1] = matrix * Matrix4::from_angle_z(Rad(rotation as f32 * FRAC_PI_4));
+        }
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let (map, item) = match m
+            .get_component(e, self.state)
+            .and_then(|state| state.item.as_ref())
+        {
+            Some(item) => match map_id(item) {
+                Some(id) => (Some(id), None),
+                None => (None, Some(build_item(renderer, &item.material))),
+            },
+            None => (None, None),
+        };
+        let frame_model = m.get_component_mut(e, self.frame_model).unwrap();
+        frame_model.map = map;
+        frame_model.map_drawn = false;
+
+        let mut parts = vec![build_frame(renderer, map.is_some())];
+        if let Some(id) = map {
+            let tex = renderer
+                .get_textures_ref()
+                .read()
+                .get_dynamic(&map_texture(id));
+            if let Some(tex) = tex {
+                let mut verts = vec![];
+                model::append_box(
+                    &mut verts,
+                    -0.5,
+                    -0.5,
+                    PIXEL / 2.0 + 0.001,
+                    1.0,
+                    1.0,
+                    0.0,
+                    [None, None, None, Some(tex), None, None],
+                );
+                parts.push(verts);
+                frame_model.map_drawn = true;
+            }
+        }
+        parts.extend(item);
+        frame_model.model = Some(renderer.model.create_model(model::DEFAULT, parts));
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let frame_model = m.get_component_mut(e, self.frame_model).unwrap();
+        if let Some(model) = frame_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
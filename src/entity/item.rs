@@ -0,0 +1,322 @@
+use super::metadata::EntityState;
+use super::{CustomEntityRenderer, Light, Position};
+use crate::ecs;
+use crate::inventory::{Item, Material};
+use crate::model::{Factory as ModelFactory, ModelDisplay};
+use crate::render::model::{self, Vertex};
+use crate::render::{self, Renderer, Texture};
+use crate::world;
+use cgmath::{Decomposed, Matrix4, Quaternion, Rad, Rotation3, Vector3, Vector4};
+use rand::rngs::StdRng;
+use rand::{self, Rng, SeedableRng};
+use std::f32::consts::PI;
+
+/// Vanilla draws dropped blocks at a quarter of their size and sprites at
+/// half of theirs.
+const BLOCK_SCALE: f32 = 0.25;
+const SPRITE_SCALE: f32 = 0.5;
+/// The thickness of extruded sprites, one pixel of a 16x16 texture
+const SPRITE_DEPTH: f32 = 1.0 / 16.0;
+
+/// Returns the texture of an item and whether it is the texture of a
+/// block rather than an item sprite.
+pub fn item_texture(renderer: &Renderer, material: &Material) -> (Texture, bool) {
+    let textures = material.texture_locations();
+    match Renderer::get_texture_optional(renderer.get_textures_ref(), &textures.0) {
+        Some(tex) => (tex, false),
+        None => (
+            Renderer::get_texture(renderer.get_textures_ref(), &textures.1),
+            true,
+        ),
+    }
+}
+
+/// Appends a unit sized model of the item, standing on y = 0 and centered
+/// on the other axes. Blocks use their block model and all other items
+/// become their sprite extruded by a pixel, without a model blocks become
+/// a cube of their texture. Returns whether the item is a block.
+pub fn append_item(verts: &mut Vec<Vertex>, renderer: &Renderer, material: &Material) -> bool {
+    if let Some(item_model) =
+        ModelFactory::get_item_model(&renderer.block_models, &material.model_name())
+    {
+        verts.extend(item_model.vertices.iter().map(|vert| {
+            let mut vert = vert.clone();
+            vert.x -= 0.5;
+            vert.z -= 0.5;
+            vert
+        }));
+        return !item_model.generated;
+    }
+
+    let (tex, block) = item_texture(renderer, material);
+    if block {
+        model::append_box(
+            verts,
+            -0.5,
+            0.0,
+            -0.5,
+            1.0,
+            1.0,
+            1.0,
+            [
+                Some(tex.clone()),
+                Some(tex.clone()),
+                Some(tex.clone()),
+                Some(tex.clone()),
+                Some(tex.clone()),
+                Some(tex),
+            ],
+        );
+        return true;
+    }
+    model::append_sprite(
+        verts,
+        -0.5,
+        0.0,
+        -SPRITE_DEPTH / 2.0,
+        1.0,
+        1.0,
+        SPRITE_DEPTH,
+        &tex,
+    );
+    false
+}
+
+/// Appends the model of an item held by another player, centred on the
+/// origin and placed with the item's display for the hand.
+pub fn append_held(verts: &mut Vec<Vertex>, renderer: &Renderer, material: &Material, left: bool) {
+    let mut item_verts = vec![];
+    let block = append_item(&mut item_verts, renderer, material);
+    let context = if left {
+        "thirdperson_lefthand"
+    } else {
+        "thirdperson_righthand"
+    };
+    let display = ModelFactory::get_item_model(&renderer.block_models, &material.model_name())
+        .map_or_else(
+            || third_person_display(block),
+            |model| model.display(context),
+        );
+    let matrix = if left { display.mirrored() } else { display }.matrix();
+    verts.extend(item_verts.into_iter().map(|mut vert| {
+        let pos = matrix * Vector4::new(vert.x, vert.y - 0.5, vert.z, 1.0);
+        vert.x = pos.x;
+        vert.y = pos.y;
+        vert.z = pos.z;
+        vert
+    }));
+}
+
+/// Vanilla's defaults for holding blocks and items without a model in
+/// third person.
+fn third_person_display(block: bool) -> ModelDisplay {
+    if block {
+        ModelDisplay {
+            rotation: [75.0, 45.0, 0.0],
+            translation: [0.0, 2.5, 0.0],
+            scale: [0.375; 3],
+        }
+    } else {
+        ModelDisplay {
+            rotation: [0.0, 0.0, 0.0],
+            translation: [0.0, 3.0, 1.0],
+            scale: [0.55; 3],
+        }
+    }
+}
+
+/// How many copies of an item are drawn for a stack, like vanilla.
+fn copies(count: isize) -> usize {
+    match count {
+        c if c > 48 => 5,
+        c if c > 32 => 4,
+        c if c > 16 => 3,
+        c if c > 1 => 2,
+        _ => 1,
+    }
+}
+
+/// Builds the model of a dropped stack, placed with the "ground" display
+/// of its item model.
+fn build_stack(renderer: &Renderer, item: &Item) -> Vec<Vertex> {
+    let (mut verts, block, scale) =
+        match ModelFactory::get_item_model(&renderer.block_models, &item.material.model_name()) {
+            Some(model) => {
+                let display = model.display("ground");
+                // Vanilla lifts the model by a quarter of its size so that it
+                // doesn't sink into the ground
+                let lift = 0.25 * display.scale[1] as f32;
+                let verts = model
+                    .transformed("ground")
+                    .into_iter()
+                    .map(|mut vert| {
+                        vert.y += lift;
+                        vert
+                    })
+                    .collect();
+                (verts, !model.generated, display.scale[0] as f32)
+            }
+            None => {
+                let mut verts = vec![];
+                let block = append_item(&mut verts, renderer, &item.material);
+                let scale = if block { BLOCK_SCALE } else { SPRITE_SCALE };
+                for vert in &mut verts {
+                    vert.x *= scale;
+                    vert.y *= scale;
+                    vert.z *= scale;
+                }
+                (verts, block, scale)
+            }
+        };
+    let single = verts.len();
+
+    // Spread out the extra copies, the same way every time for a kind
+    // of item
+    let mut rng = StdRng::seed_from_u64(item.stack.id as u64);
+    for i in 1..copies(item.stack.count) {
+        let offset = if block {
+            Vector3::new(
+                rng.gen_range(-0.15..0.15) * scale * 2.0,
+                rng.gen_range(-0.15..0.15) * scale * 2.0,
+                rng.gen_range(-0.15..0.15) * scale * 2.0,
+            )
+        } else {
+            Vector3::new(
+                rng.gen_range(-0.075..0.075) * scale,
+                rng.gen_range(-0.075..0.075) * scale,
+                -(i as f32) * 0.09375 * scale,
+            )
+        };
+        for j in 0..single {
+            let mut vert = verts[j].clone();
+            vert.x += offset.x;
+            vert.y += offset.y;
+            vert.z += offset.z;
+            verts.push(vert);
+        }
+    }
+    verts
+}
+
+pub struct ItemModel {
+    model: Option<model::ModelKey>,
+    /// Ticks since the item appeared, drives the spinning and bobbing
+    age: f64,
+    /// Keeps items dropped together from moving in step
+    hover_start: f32,
+}
+
+impl Default for ItemModel {
+    fn default() -> Self {
+        ItemModel {
+            model: None,
+            age: 0.0,
+            hover_start: rand::thread_rng().gen_range(0.0..PI * 2.0),
+        }
+    }
+}
+
+impl ItemModel {
+    pub fn new() -> ItemModel {
+        Default::default()
+    }
+}
+
+pub struct ItemRenderer {
+    item_model: ecs::Key<ItemModel>,
+    position: ecs::Key<Position>,
+    light: ecs::Key<Light>,
+    state: ecs::Key<EntityState>,
+    game_info: ecs::Key<super::GameInfo>,
+}
+
+impl ItemRenderer {
+    pub fn new(m: &mut ecs::Manager) -> Self {
+        ItemRenderer {
+            item_model: m.get_key(),
+            position: m.get_key(),
+            light: m.get_key(),
+            state: m.get_key(),
+            game_info: m.get_key(),
+        }
+    }
+}
+
+impl CustomEntityRenderer for ItemRenderer {
+    fn update(
+        &self,
+        m: &mut ecs::Manager,
+        world: &world::World,
+        renderer: &mut render::Renderer,
+        _: bool,
+        _: bool,
+        e: ecs::Entity,
+    ) {
+        // The item is only known once the metadata of the entity arrived
+        if let Some(state) = m.get_component_mut(e, self.state) {
+            if state.dirty {
+                state.dirty = false;
+                self.entity_removed(m, e, world, renderer);
+                self.entity_added(m, e, world, renderer);
+            }
+        }
+
+        let world_entity = m.get_world();
+        let delta = m.get_component(world_entity, self.game_info).unwrap().delta;
+        let item_model = m.get_component_mut(e, self.item_model).unwrap();
+        let position = m.get_component(e, self.position).unwrap();
+        let light = m.get_component(e, self.light).unwrap();
+
+        item_model.age += delta / 3.0;
+        let mdl = match item_model.model {
+            Some(key) => renderer.model.get_model(key).unwrap(),
+            None => return,
+        };
+        mdl.block_light = light.block_light;
+        mdl.sky_light = light.sky_light;
+
+        let age = item_model.age as f32;
+        let bob = (age / 10.0 + item_model.hover_start).sin() * 0.1 + 0.1;
+        mdl.matrix[0] = Matrix4::from(Decomposed {
+            scale: 1.0,
+            rot: Quaternion::from_angle_y(Rad(age / 20.0 + item_model.hover_start)),
+            disp: Vector3::new(
+                position.position.x as f32,
+                -(position.position.y as f32 + bob),
+                position.position.z as f32,
+            ),
+        });
+    }
+
+    fn entity_added(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let item = match m
+            .get_component(e, self.state)
+            .and_then(|state| state.item.as_ref())
+        {
+            Some(item) => item,
+            None => return,
+        };
+        let verts = build_stack(renderer, item);
+        let item_model = m.get_component_mut(e, self.item_model).unwrap();
+        item_model.model = Some(renderer.model.create_model(model::DEFAULT, vec![verts]));
+    }
+
+    fn entity_removed(
+        &self,
+        m: &mut ecs::Manager,
+        e: ecs::Entity,
+        _: &world::World,
+        renderer: &mut render::Renderer,
+    ) {
+        let item_model = m.get_component_mut(e, self.item_model).unwrap();
+        if let Some(model) = item_model.model.take() {
+            renderer.model.remove_model(model);
+        }
+    }
+}
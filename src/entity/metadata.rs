@@ -0,0 +1,206 @@
+use crate::entity::versions::{self, MetadataField};
+use crate::entity::EntityType;
+use crate::format;
+use crate::inventory::material::versions::to_material;
+use crate::inventory::Item;
+use crate::protocol::Version;
+use crate::types::{Metadata, PoseData, Value};
+
+/// The pose of an entity. Sent by the server since 1.14, derived from
+/// the entity's flags before that.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Pose {
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Sneaking,
+    Dying,
+}
+
+impl From<PoseData> for Pose {
+    fn from(pose: PoseData) -> Pose {
+        match pose {
+            PoseData::Standing => Pose::Standing,
+            PoseData::FallFlying => Pose::FallFlying,
+            PoseData::Sleeping => Pose::Sleeping,
+            PoseData::Swimming => Pose::Swimming,
+            PoseData::SpinAttack => Pose::SpinAttack,
+            PoseData::Sneaking => Pose::Sneaking,
+            PoseData::Dying => Pose::Dying,
+        }
+    }
+}
+
+/// The state of an entity as described by the metadata the server sends
+/// for it.
+pub struct EntityState {
+    pub on_fire: bool,
+    pub sneaking: bool,
+    pub sprinting: bool,
+    pub swimming: bool,
+    pub invisible: bool,
+    pub glowing: bool,
+    pub fall_flying: bool,
+    pub pose: Pose,
+    pub custom_name: Option<format::Component>,
+    pub custom_name_visible: bool,
+    pub baby: bool,
+    pub slime_size: i32,
+    /// The stack of a dropped item or the one held by an item frame
+    pub item: Option<Item>,
+    /// How far the item in an item frame is turned, in eighths of a turn
+    pub item_rotation: i32,
+    /// The ticks left on the fuse of primed TNT when the server last sent
+    /// them, taken by the renderer to restart its countdown
+    pub fuse: Option<i32>,
+    /// The items held in the main and the off hand, from the equipment
+    /// the server sends rather than the metadata
+    pub main_hand: Option<Item>,
+    pub off_hand: Option<Item>,
+    /// Set when something the model of the entity is built from changed,
+    /// renderers clear it once they rebuilt the model.
+    pub dirty: bool,
+}
+
+impl Default for EntityState {
+    fn default() -> Self {
+        EntityState {
+            on_fire: false,
+            sneaking: false,
+            sprinting: false,
+            swimming: false,
+            invisible: false,
+            glowing: false,
+            fall_flying: false,
+            pose: Pose::Standing,
+            custom_name: None,
+            custom_name_visible: false,
+            baby: false,
+            slime_size: 1,
+            item: None,
+            item_rotation: 0,
+            fuse: None,
+            main_hand: None,
+            off_hand: None,
+            dirty: false,
+        }
+    }
+}
+
+impl EntityState {
+    pub fn new() -> EntityState {
+        Default::default()
+    }
+
+    pub fn crouching(&self) -> bool {
+        self.pose == Pose::Sneaking
+    }
+
+    /// Updates the state from the fields of the metadata that are present,
+    /// looking them up by the indices used by the entity type in the
+    /// given version.
+    pub fn apply(&mut self, metadata: &Metadata, entity_type: EntityType, version: Version) {
+        let field = |field| {
+            versions::metadata_index(field, entity_type, version)
+                .and_then(|index| metadata.get_raw(index))
+        };
+
+        if let Some(flags) = field(MetadataField::Flags).and_then(as_int) {
+            self.on_fire = flags & 0x01 != 0;
+            self.sneaking = flags & 0x02 != 0;
+            self.sprinting = flags & 0x08 != 0;
+            // Eating and blocking used this bit before 1.13
+            self.swimming = version >= Version::V1_13 && flags & 0x10 != 0;
+            self.invisible = flags & 0x20 != 0;
+            self.glowing = flags & 0x40 != 0;
+            self.fall_flying = flags & 0x80 != 0;
+            if version < Version::V1_14 {
+                self.pose = if self.fall_flying {
+                    Pose::FallFlying
+                } else if self.swimming {
+                    Pose::Swimming
+                } else if self.sneaking {
+                    Pose::Sneaking
+                } else {
+                    Pose::Standing
+                };
+            }
+        }
+
+        if let Some(Value::Pose(pose)) = field(MetadataField::Pose) {
+            self.pose = (*pose).into();
+        }
+
+        if let Some(name) = field(MetadataField::CustomName) {
+            let name = match name {
+                Value::String(name) if !name.is_empty() => {
+                    let mut name = format::Component::Text(format::TextComponent::new(name));
+                    format::convert_legacy(&mut name);
+                    Some(name)
+                }
+                Value::FormatComponent(name) => Some(name.clone()),
+                Value::OptionalFormatComponent(name) => name.data.first().cloned(),
+                _ => None,
+            };
+            self.custom_name = name;
+            self.dirty = true;
+        }
+
+        if let Some(visible) = field(MetadataField::CustomNameVisible).and_then(as_int) {
+            let visible = visible != 0;
+            self.dirty |= self.custom_name_visible != visible;
+            self.custom_name_visible = visible;
+        }
+
+        if let Some(baby) = field(MetadataField::Baby) {
+            let baby = match baby {
+                Value::Bool(baby) => *baby,
+                // Older versions send the age of animals, which is
+                // negative while they grow up
+                value if entity_type.is_ageable() => as_int(value).unwrap_or(0) < 0,
+                value => as_int(value).unwrap_or(0) != 0,
+            };
+            self.dirty |= self.baby != baby;
+            self.baby = baby;
+        }
+
+        if let Some(size) = field(MetadataField::SlimeSize).and_then(as_int) {
+            self.dirty |= self.slime_size != size;
+            self.slime_size = size;
+        }
+
+        if let Some(Value::OptionalItemStack(stack)) = field(MetadataField::Item) {
+            self.item = stack.clone().map(|stack| Item {
+                material: to_material(stack.id as u16, version),
+                stack,
+            });
+            self.dirty = true;
+        }
+
+        if let Some(rotation) = field(MetadataField::ItemRotation).and_then(as_int) {
+            // Items only turned by quarters before 1.8
+            self.item_rotation = if version < Version::V1_8 {
+                rotation * 2
+            } else {
+                rotation
+            };
+        }
+
+        if let Some(fuse) = field(MetadataField::Fuse).and_then(as_int) {
+            self.fuse = Some(fuse);
+        }
+    }
+}
+
+/// Reads the numeric metadata types, whose width changed between versions.
+fn as_int(value: &Value) -> Option<i32> {
+    match *value {
+        Value::Byte(val) => Some(val as i32),
+        Value::Short(val) => Some(val as i32),
+        Value::Int(val) => Some(val),
+        Value::Bool(val) => Some(val as i32),
+        _ => None,
+    }
+}
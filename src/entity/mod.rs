@@ -1,12 +1,25 @@
 pub mod block_entity;
+pub mod definition;
+pub mod experience_orb;
+pub mod falling_block;
+pub mod hanging;
+pub mod item;
+pub mod metadata;
+pub mod physics;
 pub mod player;
 
 use crate::ecs;
 use crate::ecs::{Entity, Filter, Manager, System};
+use crate::entity::definition::{DefinedModel, DefinedModelRenderer, ModelDefinition};
+use crate::entity::experience_orb::{ExperienceOrbModel, ExperienceOrbRenderer};
+use crate::entity::falling_block::{BlockModel, BlockModelRenderer};
+use crate::entity::hanging::{ItemFrameModel, ItemFrameRenderer, PaintingModel, PaintingRenderer};
+use crate::entity::item::{ItemModel, ItemRenderer};
+use crate::entity::metadata::EntityState;
 use crate::entity::player::PlayerRenderer;
-use crate::entity::slime::{SlimeModel, SlimeRenderer};
-use crate::entity::zombie::{ZombieModel, ZombieRenderer};
 use crate::render::{Renderer, Texture};
+use crate::resources;
+use crate::world::block::Block;
 use crate::world::World;
 use cgmath::{Point3, Vector3};
 use collision::Aabb3;
@@ -14,11 +27,8 @@ use dashmap::DashMap;
 use lazy_static::lazy_static;
 use std::sync::Arc;
 
-pub mod player_like;
-pub mod slime;
 mod systems;
 pub mod versions;
-pub mod zombie;
 
 // TODO: There may be wrong entries in this!
 // 1.0, 1.0, 0.0 | 0.0, 0.0, 0.0
@@ -71,6 +81,8 @@ pub fn add_systems(m: &mut ecs::Manager) {
     m.add_render_system(sys);
     let sys = systems::LerpRotation::new(m);
     m.add_render_system(sys);
+    let sys = systems::CollectEntity::new(m);
+    m.add_render_system(sys);
     let sys = systems::LightEntity::new(m);
     m.add_render_system(sys);
 
@@ -118,6 +130,22 @@ impl TargetPosition {
     }
 }
 
+/// The last position the server sent for an entity. Relative moves are
+/// applied to it rather than to the predicted position, which replaces
+/// the prediction with the server's view each update.
+#[derive(Debug)]
+pub struct ServerPosition {
+    pub position: Vector3<f64>,
+}
+
+impl ServerPosition {
+    pub fn new(x: f64, y: f64, z: f64) -> ServerPosition {
+        ServerPosition {
+            position: Vector3::new(x, y, z),
+        }
+    }
+}
+
 /// Velocity of an entity in the world.
 #[derive(Debug)]
 pub struct Velocity {
@@ -168,6 +196,28 @@ impl TargetRotation {
     }
 }
 
+/// How an entity moves on its own between the updates of the server, in
+/// blocks per tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Physics {
+    /// Taken from the vertical velocity every tick
+    pub gravity: f64,
+    /// Multiplies the velocity every tick
+    pub drag: f64,
+    /// Multiplies the horizontal velocity every tick on the ground
+    pub friction: f64,
+}
+
+impl Physics {
+    const fn new(gravity: f64, drag: f64, friction: f64) -> Physics {
+        Physics {
+            gravity,
+            drag,
+            friction,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Gravity {
     pub on_ground: bool,
@@ -189,6 +239,25 @@ impl Bounds {
     }
 }
 
+/// Marks an entity that was picked up and is flying towards the entity
+/// that collected it. It is removed once it arrives.
+pub struct Collected {
+    pub collector: ecs::Entity,
+    pub start: Vector3<f64>,
+    /// Ticks since the entity was picked up
+    pub ticks: f64,
+}
+
+impl Collected {
+    pub fn new(collector: ecs::Entity, start: Vector3<f64>) -> Collected {
+        Collected {
+            collector,
+            start,
+            ticks: 0.0,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct GameInfo {
     pub delta: f64,
@@ -441,10 +510,34 @@ lazy_static! {
 }
 
 impl EntityType {
-    pub fn init(manager: &mut Manager) {
-        ENTITY_RENDERERS.insert(EntityType::Slime, Arc::new(SlimeRenderer::new(manager)));
+    pub fn init(manager: &mut Manager, resources: &resources::Manager) {
         ENTITY_RENDERERS.insert(EntityType::Player, Arc::new(PlayerRenderer::new(manager)));
-        ENTITY_RENDERERS.insert(EntityType::Zombie, Arc::new(ZombieRenderer::new(manager)));
+        ENTITY_RENDERERS.insert(
+            EntityType::DroppedItem,
+            Arc::new(ItemRenderer::new(manager)),
+        );
+        ENTITY_RENDERERS.insert(
+            EntityType::ExperienceOrb,
+            Arc::new(ExperienceOrbRenderer::new(manager)),
+        );
+        let block_renderer = Arc::new(BlockModelRenderer::new(manager));
+        ENTITY_RENDERERS.insert(EntityType::FallingBlock, block_renderer.clone());
+        ENTITY_RENDERERS.insert(EntityType::PrimedTnt, block_renderer);
+        ENTITY_RENDERERS.insert(
+            EntityType::Painting,
+            Arc::new(PaintingRenderer::new(manager)),
+        );
+        ENTITY_RENDERERS.insert(
+            EntityType::ItemFrame,
+            Arc::new(ItemFrameRenderer::new(manager)),
+        );
+        for &(ty, name) in definition::DEFINITIONS {
+            // Broken definitions are logged and leave the entity unrendered
+            if let Some(definition) = ModelDefinition::load(resources, name) {
+                ENTITY_RENDERERS
+                    .insert(ty, Arc::new(DefinedModelRenderer::new(manager, definition)));
+            }
+        }
     }
 
     pub fn deinit() {
@@ -503,6 +596,7 @@ impl EntityType {
         m.add_component_direct(entity, Rotation::new(yaw, pitch));
         m.add_component_direct(entity, Velocity::new(0.0, 0.0, 0.0));
         m.add_component_direct(entity, TargetPosition::new(x, y, z));
+        m.add_component_direct(entity, ServerPosition::new(x, y, z));
         m.add_component_direct(entity, TargetRotation::new(yaw, pitch));
         m.add_component_direct(
             entity,
@@ -511,6 +605,7 @@ impl EntityType {
                 Point3::new(0.3, 1.8, 0.3),
             )),
         );
+        m.add_component_direct(entity, EntityState::new());
         m.add_component_direct(entity, Light::new());
         m.add_component_direct(entity, *self);
         entity
@@ -518,16 +613,140 @@ impl EntityType {
 
     fn create_model(&self, m: &mut ecs::Manager, entity: ecs::Entity) {
         match self {
-            EntityType::Zombie => {
-                m.add_component_direct(entity, ZombieModel::new(Some(String::from("test"))))
+            EntityType::DroppedItem => m.add_component_direct(entity, ItemModel::new()),
+            EntityType::ExperienceOrb => m.add_component_direct(entity, ExperienceOrbModel::new()),
+            // The server sends the block along with the spawn
+            EntityType::FallingBlock => {
+                m.add_component_direct(entity, BlockModel::new(Block::Missing {}))
             }
-            EntityType::Slime => m.add_component_direct(entity, SlimeModel::new("test")),
-            _ => {}
+            EntityType::PrimedTnt => {
+                m.add_component_direct(entity, BlockModel::new(Block::TNT { explode: false }))
+            }
+            // Hanging entities are placed by the server after they spawned
+            EntityType::Painting => m.add_component_direct(entity, PaintingModel::new()),
+            EntityType::ItemFrame => m.add_component_direct(entity, ItemFrameModel::new()),
+            _ => m.add_component_direct(entity, DefinedModel::new()),
         };
     }
 
     fn supported(&self) -> bool {
-        matches!(self, EntityType::Zombie)
+        // Players are created by their own spawn packet
+        *self != EntityType::Player && ENTITY_RENDERERS.contains_key(self)
+    }
+
+    /// Whether the entity grows up from a baby, which the server sends
+    /// as an age rather than a flag before 1.9.
+    pub fn is_ageable(&self) -> bool {
+        matches!(
+            self,
+            EntityType::Pig
+                | EntityType::Sheep
+                | EntityType::Cow
+                | EntityType::Chicken
+                | EntityType::Wolf
+                | EntityType::MushroomCow
+                | EntityType::Ocelot
+                | EntityType::Horse
+                | EntityType::Rabbit
+                | EntityType::PolarBear
+                | EntityType::Villager
+                | EntityType::SkeletonHorse
+                | EntityType::ZombieHorse
+                | EntityType::Donkey
+                | EntityType::Mule
+                | EntityType::Llama
+                | EntityType::TraderLlama
+                | EntityType::Parrot
+                | EntityType::Turtle
+                | EntityType::Cat
+                | EntityType::Panda
+                | EntityType::Fox
+                | EntityType::Bee
+                | EntityType::Hoglin
+                | EntityType::Strider
+                | EntityType::WanderingTrader
+        )
+    }
+
+    pub fn can_be_baby(&self) -> bool {
+        self.is_ageable()
+            || matches!(
+                self,
+                EntityType::Zombie
+                    | EntityType::Husk
+                    | EntityType::Drowned
+                    | EntityType::ZombieVillager
+                    | EntityType::PigZombie
+                    | EntityType::ZombifiedPiglin
+                    | EntityType::Zoglin
+            )
+    }
+
+    pub fn is_slime(&self) -> bool {
+        matches!(self, EntityType::Slime | EntityType::MagmaCube)
+    }
+
+    /// Whether the player can aim at the entity to attack or use it,
+    /// which isn't the case for most projectiles and drops.
+    pub fn can_be_targeted(&self) -> bool {
+        use EntityType::*;
+        !matches!(
+            self,
+            DroppedItem
+                | ExperienceOrb
+                | Arrow
+                | TippedArrow
+                | SpectralArrow
+                | Trident
+                | Snowball
+                | Egg
+                | EnderPearl
+                | EnderSignal
+                | ThrownExpBottle
+                | SplashPotion
+                | LingeringPotion
+                | SmallFireball
+                | WitherSkull
+                | DragonFireball
+                | LlamaSpit
+                | Firework
+                | FishingHook
+                | AreaEffectCloud
+                | EvokerFangs
+                | Lightning
+                | Weather
+                | Unknown
+        )
+    }
+
+    /// How the client moves the entity between the updates of the server,
+    /// like vanilla does. `None` for entities that don't move on their own.
+    pub fn physics(&self) -> Option<Physics> {
+        use EntityType::*;
+        Some(match self {
+            Painting | ItemFrame | LeashHitch | EnderCrystal | AreaEffectCloud | EvokerFangs
+            | Lightning | Weather | Unknown => return None,
+            DroppedItem | PrimedTnt | FallingBlock => Physics::new(0.04, 0.98, 0.6),
+            ExperienceOrb => Physics::new(0.03, 0.98, 0.6),
+            Arrow | TippedArrow | SpectralArrow | Trident => Physics::new(0.05, 0.99, 0.0),
+            Snowball | Egg | EnderPearl => Physics::new(0.03, 0.99, 0.0),
+            SplashPotion | LingeringPotion => Physics::new(0.05, 0.99, 0.0),
+            ThrownExpBottle => Physics::new(0.07, 0.99, 0.0),
+            LlamaSpit => Physics::new(0.06, 0.99, 0.0),
+            FishingHook => Physics::new(0.03, 0.92, 0.5),
+            // Fireballs fly straight on, pushed by their own acceleration
+            Fireball | SmallFireball | WitherSkull | DragonFireball | ShulkerBullet
+            | EnderSignal | Firework => Physics::new(0.0, 0.95, 1.0),
+            Boat | Minecart | MinecartChest | MinecartFurnace | MinecartTnt | MinecartHopper
+            | MinecartMobSpawner | MinecartCommand => Physics::new(0.04, 0.95, 0.5),
+            // Ghasts, bats and the like fly without gravity
+            Ghast | Bat | Blaze | EnderDragon | Wither | Vex | Parrot | Phantom | Bee => {
+                Physics::new(0.0, 0.91, 1.0)
+            }
+            Squid | Guardian | ElderGuardian | Cod | Salmon | Pufferfish | TropicalFish
+            | Dolphin | Turtle => Physics::new(0.005, 0.9, 0.6),
+            _ => Physics::new(0.08, 0.98, 0.6 * 0.91),
+        })
     }
 }
 
@@ -0,0 +1,1035 @@
+//! Movement of the local player, following what the vanilla client does
+//! every tick so the server agrees with where the player ends up.
+
+use crate::shared::Position as BPos;
+use crate::world::block::{Block, WorldAccess};
+use cgmath::{Point3, Vector3};
+use collision::Aabb3;
+
+/// The highest ledge the player walks up without jumping
+pub const STEP_HEIGHT: f64 = 0.6;
+pub const WIDTH: f64 = 0.6;
+pub const HEIGHT: f64 = 1.8;
+
+const GRAVITY: f64 = 0.08;
+const AIR_DRAG: f64 = 0.98;
+const AIR_FRICTION: f64 = 0.91;
+const DEFAULT_SLIPPERINESS: f64 = 0.6;
+const WALK_SPEED: f64 = 0.1;
+const SPRINT_MULTIPLIER: f64 = 1.3;
+const AIR_ACCELERATION: f64 = 0.02;
+const SPRINT_AIR_ACCELERATION: f64 = 0.026;
+const FLY_SPEED: f64 = 0.05;
+const JUMP_VELOCITY: f64 = 0.42;
+const SPRINT_JUMP_BOOST: f64 = 0.2;
+/// Ticks before the player jumps again while holding jump
+const JUMP_DELAY: u32 = 10;
+const LADDER_SPEED: f64 = 0.15;
+const LADDER_CLIMB: f64 = 0.2;
+/// Velocities smaller than this are dropped every tick
+const MIN_VELOCITY: f64 = 0.003;
+/// How far sneaking players are moved back from an edge at a time
+const SNEAK_EDGE_STEP: f64 = 0.05;
+
+/// What the player asks for this tick.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Input {
+    /// From -1 to 1, positive is forwards
+    pub forward: f64,
+    /// From -1 to 1, positive is to the left
+    pub strafe: f64,
+    pub jump: bool,
+    pub sneak: bool,
+    pub sprint: bool,
+    /// The direction the player looks at in vanilla's convention, in
+    /// radians from south (+z) turning towards west (-x)
+    pub yaw: f64,
+}
+
+/// The physical state of the player, kept between ticks.
+#[derive(Clone, Debug)]
+pub struct Body {
+    /// The center of the bottom of the player
+    pub position: Vector3<f64>,
+    /// In blocks per tick
+    pub velocity: Vector3<f64>,
+    pub on_ground: bool,
+    pub collided_horizontally: bool,
+    pub flying: bool,
+    /// Moves through blocks, like spectators
+    pub noclip: bool,
+    in_web: bool,
+    jump_ticks: u32,
+}
+
+impl Body {
+    pub fn new(position: Vector3<f64>) -> Body {
+        Body {
+            position,
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            on_ground: false,
+            collided_horizontally: false,
+            flying: false,
+            noclip: false,
+            in_web: false,
+            jump_ticks: 0,
+        }
+    }
+
+    pub fn bounds(&self) -> Aabb3<f64> {
+        Aabb3::new(
+            Point3::new(
+                self.position.x - WIDTH / 2.0,
+                self.position.y,
+                self.position.z - WIDTH / 2.0,
+            ),
+            Point3::new(
+                self.position.x + WIDTH / 2.0,
+                self.position.y + HEIGHT,
+                self.position.z + WIDTH / 2.0,
+            ),
+        )
+    }
+}
+
+/// Moves the player by a tick.
+pub fn tick<W: WorldAccess>(world: &W, body: &mut Body, input: &Input) {
+    if body.jump_ticks > 0 {
+        body.jump_ticks -= 1;
+    }
+    for axis in 0..3 {
+        if body.velocity[axis].abs() < MIN_VELOCITY {
+            body.velocity[axis] = 0.0;
+        }
+    }
+
+    let in_water = !body.flying && is_in_water(world, &body.bounds());
+    let in_lava = !body.flying && is_in_lava(world, &body.bounds());
+    if body.flying {
+        if input.sneak {
+            body.velocity.y -= FLY_SPEED * 3.0;
+        }
+        if input.jump {
+            body.velocity.y += FLY_SPEED * 3.0;
+        }
+    } else if input.jump {
+        if in_water || in_lava {
+            // Swimming up
+            body.velocity.y += 0.04;
+        } else if body.on_ground && body.jump_ticks == 0 {
+            body.velocity.y = JUMP_VELOCITY;
+            if input.sprint {
+                body.velocity.x -= input.yaw.sin() * SPRINT_JUMP_BOOST;
+                body.velocity.z += input.yaw.cos() * SPRINT_JUMP_BOOST;
+            }
+            body.jump_ticks = JUMP_DELAY;
+        }
+    } else {
+        body.jump_ticks = 0;
+    }
+
+    let mut strafe = input.strafe * 0.98;
+    let mut forward = input.forward * 0.98;
+    if input.sneak && !body.flying {
+        strafe *= 0.3;
+        forward *= 0.3;
+    }
+
+    if in_water || in_lava {
+        travel_in_liquid(world, body, input, strafe, forward, in_water);
+    } else {
+        travel(world, body, input, strafe, forward);
+    }
+}
+
+/// Moves the player on the ground, in the air or flying.
+fn travel<W: WorldAccess>(world: &W, body: &mut Body, input: &Input, strafe: f64, forward: f64) {
+    let fly_velocity = body.velocity.y;
+    let friction = if body.on_ground {
+        slipperiness(block_below(world, body)) * AIR_FRICTION
+    } else {
+        AIR_FRICTION
+    };
+    let acceleration = if body.on_ground {
+        let speed = if input.sprint {
+            WALK_SPEED * SPRINT_MULTIPLIER
+        } else {
+            WALK_SPEED
+        };
+        // Slippery blocks take longer to get up to speed
+        speed * (0.6 * 0.91f64).powi(3) / friction.powi(3)
+    } else if body.flying {
+        FLY_SPEED * if input.sprint { 2.0 } else { 1.0 }
+    } else if input.sprint {
+        SPRINT_AIR_ACCELERATION
+    } else {
+        AIR_ACCELERATION
+    };
+    accelerate(body, strafe, forward, acceleration, input.yaw);
+
+    let on_ladder = !body.flying && is_on_ladder(world, body);
+    if on_ladder {
+        body.velocity.x = body.velocity.x.clamp(-LADDER_SPEED, LADDER_SPEED);
+        body.velocity.z = body.velocity.z.clamp(-LADDER_SPEED, LADDER_SPEED);
+        body.velocity.y = body.velocity.y.max(-LADDER_SPEED);
+        // Sneaking holds on to the ladder
+        if input.sneak && body.velocity.y < 0.0 {
+            body.velocity.y = 0.0;
+        }
+    }
+
+    move_body(world, body, input.sneak);
+    if body.collided_horizontally && on_ladder {
+        body.velocity.y = LADDER_CLIMB;
+    }
+
+    body.velocity.y -= GRAVITY;
+    body.velocity.y *= AIR_DRAG;
+    body.velocity.x *= friction;
+    body.velocity.z *= friction;
+    if body.flying {
+        body.velocity.y = fly_velocity * 0.6;
+    }
+}
+
+/// Moves the player through water or lava, which pull the player down
+/// slower and let it climb out at the edges.
+fn travel_in_liquid<W: WorldAccess>(
+    world: &W,
+    body: &mut Body,
+    input: &Input,
+    strafe: f64,
+    forward: f64,
+    water: bool,
+) {
+    let start_y = body.position.y;
+    accelerate(body, strafe, forward, 0.02, input.yaw);
+    move_body(world, body, input.sneak);
+    let drag = if water { 0.8 } else { 0.5 };
+    body.velocity *= drag;
+    body.velocity.y -= 0.02;
+
+    if body.collided_horizontally {
+        let offset = Vector3::new(
+            body.velocity.x,
+            body.velocity.y + 0.6 - body.position.y + start_y,
+            body.velocity.z,
+        );
+        if is_free(world, &offset_box(&body.bounds(), offset)) {
+            body.velocity.y = 0.3;
+        }
+    }
+}
+
+/// Speeds the player up towards where it wants to go, relative to where
+/// it looks.
+fn accelerate(body: &mut Body, strafe: f64, forward: f64, acceleration: f64, yaw: f64) {
+    let length = strafe * strafe + forward * forward;
+    if length < 1.0e-4 {
+        return;
+    }
+    let scale = acceleration / length.sqrt().max(1.0);
+    let (strafe, forward) = (strafe * scale, forward * scale);
+    let (sin, cos) = yaw.sin_cos();
+    body.velocity.x += strafe * cos - forward * sin;
+    body.velocity.z += forward * cos + strafe * sin;
+}
+
+/// Moves the player by its velocity, stopping it at blocks and stepping
+/// up ledges.
+fn move_body<W: WorldAccess>(world: &W, body: &mut Body, sneak: bool) {
+    if body.noclip {
+        body.position += body.velocity;
+        return;
+    }
+
+    let mut motion = body.velocity;
+    if body.in_web {
+        body.in_web = false;
+        motion.x *= 0.25;
+        motion.y *= 0.05;
+        motion.z *= 0.25;
+        body.velocity = Vector3::new(0.0, 0.0, 0.0);
+    }
+
+    let bounds = body.bounds();
+    if body.on_ground && sneak {
+        motion = keep_on_edge(world, &bounds, motion);
+    }
+    let wanted = motion;
+
+    let boxes = collision_boxes(world, &stretch(&bounds, wanted));
+    let (mut moved, mut motion) = slide(&boxes, bounds, wanted);
+
+    let could_step = body.on_ground || (wanted.y != motion.y && wanted.y < 0.0);
+    if could_step && (wanted.x != motion.x || wanted.z != motion.z) {
+        if let Some((stepped, step_motion)) = step_up(world, &bounds, wanted) {
+            let flat = motion.x * motion.x + motion.z * motion.z;
+            let step = step_motion.x * step_motion.x + step_motion.z * step_motion.z;
+            if step > flat {
+                moved = stepped;
+                motion = step_motion;
+            }
+        }
+    }
+
+    body.position = Vector3::new(
+        (moved.min.x + moved.max.x) / 2.0,
+        moved.min.y,
+        (moved.min.z + moved.max.z) / 2.0,
+    );
+    body.collided_horizontally = wanted.x != motion.x || wanted.z != motion.z;
+    let collided_vertically = wanted.y != motion.y;
+    body.on_ground = collided_vertically && wanted.y < 0.0;
+    if wanted.x != motion.x {
+        body.velocity.x = 0.0;
+    }
+    if wanted.z != motion.z {
+        body.velocity.z = 0.0;
+    }
+
+    let below = world.get_block(BPos::new(
+        body.position.x.floor() as i32,
+        (body.position.y - 0.2).floor() as i32,
+        body.position.z.floor() as i32,
+    ));
+    if collided_vertically {
+        // Slime bounces the player back up unless it sneaks
+        if matches!(below, Block::Slime {}) && !sneak && body.velocity.y < 0.0 {
+            body.velocity.y = -body.velocity.y;
+        } else {
+            body.velocity.y = 0.0;
+        }
+    }
+    if body.on_ground && !sneak && matches!(below, Block::Slime {}) && body.velocity.y.abs() < 0.1 {
+        let factor = 0.4 + body.velocity.y.abs() * 0.2;
+        body.velocity.x *= factor;
+        body.velocity.z *= factor;
+    }
+
+    touch_blocks(world, body, &moved);
+}
+
+/// Moves the bounds by the motion an axis at a time, vertically first,
+/// stopping at the boxes. Returns the moved bounds and how far they moved.
+fn slide(
+    boxes: &[Aabb3<f64>],
+    mut bounds: Aabb3<f64>,
+    motion: Vector3<f64>,
+) -> (Aabb3<f64>, Vector3<f64>) {
+    let mut moved = Vector3::new(0.0, 0.0, 0.0);
+    for &axis in &[1, 0, 2] {
+        let offset = boxes.iter().fold(motion[axis], |offset, bb| {
+            clip_offset(bb, &bounds, axis, offset)
+        });
+        let mut delta = Vector3::new(0.0, 0.0, 0.0);
+        delta[axis] = offset;
+        bounds = offset_box(&bounds, delta);
+        moved[axis] = offset;
+    }
+    (bounds, moved)
+}
+
+/// Tries walking over a ledge by moving up by the step height first and
+/// back down after. Like vanilla it tries both rising before and during
+/// the horizontal move and keeps whichever gets further.
+fn step_up<W: WorldAccess>(
+    world: &W,
+    bounds: &Aabb3<f64>,
+    wanted: Vector3<f64>,
+) -> Option<(Aabb3<f64>, Vector3<f64>)> {
+    let up = Vector3::new(wanted.x, STEP_HEIGHT, wanted.z);
+    let boxes = collision_boxes(world, &stretch(bounds, up));
+
+    let mut best: Option<(Aabb3<f64>, f64, Vector3<f64>)> = None;
+    let sweep = stretch(bounds, Vector3::new(wanted.x, 0.0, wanted.z));
+    for rise_over in &[Some(sweep), None] {
+        let rise_bounds = rise_over.as_ref().unwrap_or(bounds);
+        let rise = boxes.iter().fold(STEP_HEIGHT, |offset, bb| {
+            clip_offset(bb, rise_bounds, 1, offset)
+        });
+        let raised = offset_box(bounds, Vector3::new(0.0, rise, 0.0));
+        let (moved, motion) = slide(&boxes, raised, Vector3::new(wanted.x, 0.0, wanted.z));
+        let distance = motion.x * motion.x + motion.z * motion.z;
+        if best.as_ref().is_none_or(|best| distance > best.1) {
+            best = Some((moved, distance, Vector3::new(motion.x, rise, motion.z)));
+        }
+    }
+    let (moved, _, motion) = best?;
+
+    // Back down onto whatever was stepped on
+    let fall = boxes
+        .iter()
+        .fold(-motion.y, |offset, bb| clip_offset(bb, &moved, 1, offset));
+    let moved = offset_box(&moved, Vector3::new(0.0, fall, 0.0));
+    Some((moved, Vector3::new(motion.x, fall, motion.z)))
+}
+
+/// Shortens the motion of a sneaking player so it doesn't walk off the
+/// edge of the blocks it stands on.
+fn keep_on_edge<W: WorldAccess>(
+    world: &W,
+    bounds: &Aabb3<f64>,
+    mut motion: Vector3<f64>,
+) -> Vector3<f64> {
+    let over_edge = |x: f64, z: f64| {
+        collision_boxes(world, &offset_box(bounds, Vector3::new(x, -STEP_HEIGHT, z))).is_empty()
+    };
+    let back_off = |value: f64| {
+        if value.abs() < SNEAK_EDGE_STEP {
+            0.0
+        } else if value > 0.0 {
+            value - SNEAK_EDGE_STEP
+        } else {
+            value + SNEAK_EDGE_STEP
+        }
+    };
+    while motion.x != 0.0 && over_edge(motion.x, 0.0) {
+        motion.x = back_off(motion.x);
+    }
+    while motion.z != 0.0 && over_edge(0.0, motion.z) {
+        motion.z = back_off(motion.z);
+    }
+    while motion.x != 0.0 && motion.z != 0.0 && over_edge(motion.x, motion.z) {
+        motion.x = back_off(motion.x);
+        motion.z = back_off(motion.z);
+    }
+    motion
+}
+
+/// Applies the blocks the player is inside of, soul sand slows it down
+/// and cobwebs catch it.
+fn touch_blocks<W: WorldAccess>(world: &W, body: &mut Body, bounds: &Aabb3<f64>) {
+    let inner = shrink(bounds, 0.001);
+    for pos in blocks_in(&inner) {
+        match world.get_block(pos) {
+            Block::SoulSand {} => {
+                body.velocity.x *= 0.4;
+                body.velocity.z *= 0.4;
+            }
+            Block::Web {} => body.in_web = true,
+            _ => {}
+        }
+    }
+}
+
+/// How slippery the top of a block is, the share of the speed kept on it
+/// every tick.
+pub fn slipperiness(block: Block) -> f64 {
+    match block {
+        Block::Ice {} | Block::PackedIce {} | Block::FrostedIce { .. } => 0.98,
+        Block::BlueIce {} => 0.989,
+        Block::Slime {} => 0.8,
+        _ => DEFAULT_SLIPPERINESS,
+    }
+}
+
+fn block_below<W: WorldAccess>(world: &W, body: &Body) -> Block {
+    world.get_block(BPos::new(
+        body.position.x.floor() as i32,
+        (body.position.y - 1.0).floor() as i32,
+        body.position.z.floor() as i32,
+    ))
+}
+
+fn is_on_ladder<W: WorldAccess>(world: &W, body: &Body) -> bool {
+    matches!(
+        world.get_block(BPos::new(
+            body.position.x.floor() as i32,
+            body.position.y.floor() as i32,
+            body.position.z.floor() as i32,
+        )),
+        Block::Ladder { .. } | Block::Vine { .. }
+    )
+}
+
+/// Whether the player is deep enough in water to swim, taking the height
+/// of flowing water into account.
+fn is_in_water<W: WorldAccess>(world: &W, bounds: &Aabb3<f64>) -> bool {
+    let check = shrink(&grow(bounds, 0.0, -0.4, 0.0), 0.001);
+    blocks_in(&check).any(|pos| {
+        let level = match world.get_block(pos) {
+            Block::Water { level } | Block::FlowingWater { level } => level,
+            _ => return false,
+        };
+        let height = if level >= 8 {
+            0.0
+        } else {
+            (level + 1) as f64 / 9.0
+        };
+        check.max.y >= pos.y as f64 + 1.0 - height
+    })
+}
+
+fn is_in_lava<W: WorldAccess>(world: &W, bounds: &Aabb3<f64>) -> bool {
+    blocks_in(&grow(bounds, -0.1, -0.4, -0.1)).any(|pos| {
+        matches!(
+            world.get_block(pos),
+            Block::Lava { .. } | Block::FlowingLava { .. }
+        )
+    })
+}
+
+fn is_liquid(block: Block) -> bool {
+    matches!(
+        block,
+        Block::Water { .. }
+            | Block::FlowingWater { .. }
+            | Block::Lava { .. }
+            | Block::FlowingLava { .. }
+    )
+}
+
+/// Whether nothing solid or liquid is in the bounds.
+fn is_free<W: WorldAccess>(world: &W, bounds: &Aabb3<f64>) -> bool {
+    collision_boxes(world, bounds).is_empty()
+        && !blocks_in(bounds).any(|pos| is_liquid(world.get_block(pos)))
+}
+
+/// Returns the collision boxes of the blocks that intersect the bounds, in
+/// world space.
+fn collision_boxes<W: WorldAccess>(world: &W, bounds: &Aabb3<f64>) -> Vec<Aabb3<f64>> {
+    // Fences and walls reach into the block above them
+    let search = grow(bounds, 1.0, 1.0, 1.0);
+    let mut boxes = vec![];
+    for pos in blocks_in(&search) {
+        let block = world.get_block(pos);
+        if !block.get_material().collidable {
+            continue;
+        }
+        for bb in block.get_collision_boxes() {
+            let bb = offset_box(&bb, Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64));
+            if intersects(&bb, bounds) {
+                boxes.push(bb);
+            }
+        }
+    }
+    boxes
+}
+
+/// Returns the positions of the blocks the bounds reach into.
+fn blocks_in(bounds: &Aabb3<f64>) -> impl Iterator<Item = BPos> {
+    let min = (
+        bounds.min.x.floor() as i32,
+        bounds.min.y.floor() as i32,
+        bounds.min.z.floor() as i32,
+    );
+    let max = (
+        bounds.max.x.ceil() as i32,
+        bounds.max.y.ceil() as i32,
+        bounds.max.z.ceil() as i32,
+    );
+    (min.1..max.1).flat_map(move |y| {
+        (min.2..max.2).flat_map(move |z| (min.0..max.0).map(move |x| BPos::new(x, y, z)))
+    })
+}
+
+/// Limits how far `other` can move along the axis before running into
+/// `bb`, if they overlap on the other two axes.
+fn clip_offset(bb: &Aabb3<f64>, other: &Aabb3<f64>, axis: usize, offset: f64) -> f64 {
+    let overlaps = (0..3)
+        .filter(|&a| a != axis)
+        .all(|a| other.max[a] > bb.min[a] && other.min[a] < bb.max[a]);
+    if !overlaps {
+        return offset;
+    }
+    if offset > 0.0 && other.max[axis] <= bb.min[axis] {
+        offset.min(bb.min[axis] - other.max[axis])
+    } else if offset < 0.0 && other.min[axis] >= bb.max[axis] {
+        offset.max(bb.max[axis] - other.min[axis])
+    } else {
+        offset
+    }
+}
+
+/// Whether the block would be in the way of something with the bounds,
+/// such as a player standing where it is placed.
+pub fn block_intersects(block: Block, pos: BPos, bounds: &Aabb3<f64>) -> bool {
+    block.get_material().collidable
+        && block.get_collision_boxes().iter().any(|bb| {
+            let bb = offset_box(bb, Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64));
+            intersects(&bb, bounds)
+        })
+}
+
+fn intersects(a: &Aabb3<f64>, b: &Aabb3<f64>) -> bool {
+    (0..3).all(|axis| a.min[axis] < b.max[axis] && a.max[axis] > b.min[axis])
+}
+
+fn offset_box(bb: &Aabb3<f64>, offset: Vector3<f64>) -> Aabb3<f64> {
+    Aabb3::new(bb.min + offset, bb.max + offset)
+}
+
+/// Extends the bounds in the direction of the motion.
+fn stretch(bb: &Aabb3<f64>, motion: Vector3<f64>) -> Aabb3<f64> {
+    let mut bb = *bb;
+    for axis in 0..3 {
+        if motion[axis] < 0.0 {
+            bb.min[axis] += motion[axis];
+        } else {
+            bb.max[axis] += motion[axis];
+        }
+    }
+    bb
+}
+
+fn grow(bb: &Aabb3<f64>, x: f64, y: f64, z: f64) -> Aabb3<f64> {
+    let amount = Vector3::new(x, y, z);
+    Aabb3::new(bb.min - amount, bb.max + amount)
+}
+
+fn shrink(bb: &Aabb3<f64>, amount: f64) -> Aabb3<f64> {
+    grow(bb, -amount, -amount, -amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Direction;
+    use crate::world::block::{BlockHalf, CobblestoneWallVariant, StairShape, StoneSlabVariant};
+    use crate::world::test_world::TestWorld;
+
+    impl TestWorld {
+        /// A floor of the block under y = 0, from -size to size.
+        fn floor(size: i32, block: Block) -> TestWorld {
+            let mut world = TestWorld::default();
+            for x in -size..=size {
+                for z in -size..=size {
+                    world.set(x, -1, z, block);
+                }
+            }
+            world
+        }
+    }
+
+    const STONE: Block = Block::Bedrock {};
+
+    /// Facing east, forwards is +x
+    const EAST: f64 = -std::f64::consts::FRAC_PI_2;
+
+    fn standing(x: f64, y: f64, z: f64) -> Body {
+        let mut body = Body::new(Vector3::new(x, y, z));
+        body.on_ground = true;
+        body
+    }
+
+    fn walk_east() -> Input {
+        Input {
+            forward: 1.0,
+            yaw: EAST,
+            ..Default::default()
+        }
+    }
+
+    fn run<W: WorldAccess>(world: &W, body: &mut Body, input: &Input, ticks: usize) {
+        for _ in 0..ticks {
+            tick(world, body, input);
+        }
+    }
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1.0e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn gravity() {
+        let world = TestWorld::default();
+        let mut body = Body::new(Vector3::new(0.5, 10.0, 0.5));
+        tick(&world, &mut body, &Input::default());
+        assert_near(body.position.y, 10.0);
+        assert_near(body.velocity.y, -0.0784);
+        tick(&world, &mut body, &Input::default());
+        assert_near(body.position.y, 10.0 - 0.0784);
+        assert_near(body.velocity.y, (-0.0784 - 0.08) * 0.98);
+    }
+
+    #[test]
+    fn lands_on_ground() {
+        let world = TestWorld::floor(2, STONE);
+        let mut body = Body::new(Vector3::new(0.5, 3.0, 0.5));
+        run(&world, &mut body, &Input::default(), 40);
+        assert!(body.on_ground);
+        assert_near(body.position.y, 0.0);
+        // Gravity keeps pulling the player into the ground
+        assert_near(body.velocity.y, -GRAVITY * AIR_DRAG);
+    }
+
+    #[test]
+    fn walking_speed() {
+        let world = TestWorld::floor(20, STONE);
+        let mut body = standing(-15.0, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 30);
+        let start = body.position.x;
+        tick(&world, &mut body, &walk_east());
+        // Vanilla's walking speed, 4.317 blocks per second
+        assert!((body.position.x - start - 0.21585).abs() < 1.0e-3);
+        assert_near(body.position.z, 0.5);
+    }
+
+    #[test]
+    fn sprinting_is_faster() {
+        let world = TestWorld::floor(30, STONE);
+        let mut walking = standing(-25.0, 0.0, 0.5);
+        let mut sprinting = walking.clone();
+        run(&world, &mut walking, &walk_east(), 20);
+        let sprint = Input {
+            sprint: true,
+            ..walk_east()
+        };
+        run(&world, &mut sprinting, &sprint, 20);
+        assert!(sprinting.position.x - walking.position.x > 0.5);
+    }
+
+    #[test]
+    fn jump_height() {
+        let world = TestWorld::floor(2, STONE);
+        let mut body = standing(0.5, 0.0, 0.5);
+        let jump = Input {
+            jump: true,
+            ..Default::default()
+        };
+        tick(&world, &mut body, &jump);
+        let mut highest: f64 = 0.0;
+        for _ in 0..20 {
+            tick(&world, &mut body, &Input::default());
+            highest = highest.max(body.position.y);
+        }
+        // Vanilla jumps a bit higher than a block
+        assert!(highest > 1.24 && highest < 1.26, "jumped {}", highest);
+        assert!(body.on_ground);
+    }
+
+    #[test]
+    fn walls_stop_the_player() {
+        let mut world = TestWorld::floor(4, STONE);
+        world.set(2, 0, 0, STONE);
+        world.set(2, 1, 0, STONE);
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 20);
+        assert_near(body.position.x, 2.0 - WIDTH / 2.0);
+        assert!(body.collided_horizontally);
+        assert_near(body.velocity.x, 0.0);
+        assert_near(body.position.y, 0.0);
+    }
+
+    #[test]
+    fn steps_up_slabs() {
+        let mut world = TestWorld::floor(4, STONE);
+        world.set(
+            2,
+            0,
+            0,
+            Block::StoneSlab {
+                half: BlockHalf::Bottom,
+                variant: StoneSlabVariant::Stone,
+            },
+        );
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 15);
+        assert!(body.position.x > 2.0);
+        assert_near(body.position.y, 0.5);
+        assert!(body.on_ground);
+    }
+
+    #[test]
+    fn does_not_step_up_full_blocks() {
+        let mut world = TestWorld::floor(4, STONE);
+        world.set(2, 0, 0, STONE);
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 15);
+        assert_near(body.position.x, 2.0 - WIDTH / 2.0);
+        assert_near(body.position.y, 0.0);
+    }
+
+    #[test]
+    fn walks_up_stairs() {
+        let mut world = TestWorld::floor(8, STONE);
+        world.set(
+            2,
+            0,
+            0,
+            Block::OakStairs {
+                facing: Direction::East,
+                half: BlockHalf::Bottom,
+                shape: StairShape::Straight,
+                waterlogged: false,
+            },
+        );
+        for x in 3..=6 {
+            world.set(x, 0, 0, STONE);
+        }
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 25);
+        // Up the slab half and then the step, half a block each
+        assert!(body.position.x > 4.0, "walked to {}", body.position.x);
+        assert_near(body.position.y, 1.0);
+        assert!(body.on_ground);
+    }
+
+    #[test]
+    fn cannot_jump_onto_fences_or_walls() {
+        let blocked = |block: Block| {
+            let mut world = TestWorld::floor(4, STONE);
+            world.set(2, 0, 0, block);
+            let mut body = standing(0.5, 0.0, 0.5);
+            let jump = Input {
+                jump: true,
+                ..walk_east()
+            };
+            run(&world, &mut body, &jump, 60);
+            run(&world, &mut body, &walk_east(), 20);
+            // Jumps only reach 1.25 but they're 1.5 high to collide with
+            assert!(body.position.x < 2.5, "got over to {}", body.position.x);
+            assert_near(body.position.y, 0.0);
+            assert!(body.collided_horizontally);
+        };
+        blocked(Block::Fence {
+            north: false,
+            south: false,
+            west: false,
+            east: false,
+            waterlogged: false,
+        });
+        blocked(Block::CobblestoneWall {
+            up: true,
+            north: false,
+            south: false,
+            west: false,
+            east: false,
+            variant: CobblestoneWallVariant::Normal,
+            waterlogged: false,
+        });
+    }
+
+    #[test]
+    fn ice_is_slippery() {
+        let slide = |block: Block| {
+            let world = TestWorld::floor(40, block);
+            let mut body = standing(-30.0, 0.0, 0.5);
+            run(&world, &mut body, &walk_east(), 30);
+            let start = body.position.x;
+            run(&world, &mut body, &Input::default(), 40);
+            body.position.x - start
+        };
+        let on_stone = slide(STONE);
+        let on_ice = slide(Block::Ice {});
+        // Friction takes the speed away within a few ticks on stone
+        assert!(on_stone < 0.3, "slid {}", on_stone);
+        assert!(on_ice > on_stone * 5.0, "slid {} on ice", on_ice);
+    }
+
+    #[test]
+    fn slime_is_sticky_and_bouncy() {
+        let world = TestWorld::floor(40, Block::Slime {});
+        let mut body = standing(-30.0, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 30);
+        let start = body.position.x;
+        tick(&world, &mut body, &walk_east());
+        assert!(body.position.x - start < 0.21585 / 2.0);
+
+        let mut body = Body::new(Vector3::new(0.5, 3.0, 0.5));
+        let mut bounced = false;
+        for _ in 0..30 {
+            tick(&world, &mut body, &Input::default());
+            bounced |= body.velocity.y > 0.0;
+        }
+        assert!(bounced);
+    }
+
+    #[test]
+    fn sneaking_slime_does_not_bounce() {
+        let world = TestWorld::floor(2, Block::Slime {});
+        let mut body = Body::new(Vector3::new(0.5, 3.0, 0.5));
+        let sneak = Input {
+            sneak: true,
+            ..Default::default()
+        };
+        for _ in 0..30 {
+            tick(&world, &mut body, &sneak);
+            assert!(body.velocity.y <= 0.0);
+        }
+        assert!(body.on_ground);
+    }
+
+    #[test]
+    fn soul_sand_slows_down() {
+        let world = TestWorld::floor(30, Block::SoulSand {});
+        let mut body = standing(-20.0, -0.125, 0.5);
+        run(&world, &mut body, &walk_east(), 30);
+        let start = body.position.x;
+        tick(&world, &mut body, &walk_east());
+        assert!(body.position.x - start < 0.21585 / 2.0);
+        assert_near(body.position.y, -0.125);
+    }
+
+    #[test]
+    fn cobwebs_slow_down() {
+        let mut world = TestWorld::floor(4, STONE);
+        world.set(0, 0, 0, Block::Web {});
+        world.set(0, 1, 0, Block::Web {});
+        let mut body = standing(0.5, 0.0, 0.5);
+        tick(&world, &mut body, &walk_east());
+        let start = body.position.x;
+        tick(&world, &mut body, &walk_east());
+        assert!(body.position.x - start < 0.03);
+        assert_near(body.velocity.y, -GRAVITY * AIR_DRAG);
+    }
+
+    #[test]
+    fn sneaking_stops_at_edges() {
+        let world = TestWorld::floor(0, STONE);
+        let mut body = standing(0.5, 0.0, 0.5);
+        let sneak = Input {
+            sneak: true,
+            ..walk_east()
+        };
+        run(&world, &mut body, &sneak, 40);
+        assert!(body.on_ground);
+        assert_near(body.position.y, 0.0);
+        // Stays with part of its feet on the block
+        assert!(body.position.x < 1.0 + WIDTH / 2.0);
+        assert!(body.position.x > 1.0);
+    }
+
+    #[test]
+    fn walking_off_edges_falls() {
+        let world = TestWorld::floor(0, STONE);
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 20);
+        assert!(body.position.y < -1.0);
+    }
+
+    #[test]
+    fn climbs_ladders() {
+        let mut world = TestWorld::floor(4, STONE);
+        for y in 0..5 {
+            world.set(
+                0,
+                y,
+                0,
+                Block::Ladder {
+                    facing: Direction::West,
+                    waterlogged: false,
+                },
+            );
+            world.set(1, y, 0, STONE);
+        }
+        let mut body = standing(0.5, 0.0, 0.5);
+        run(&world, &mut body, &walk_east(), 10);
+        assert!(body.position.y > 1.0);
+        assert_near(body.velocity.y, (LADDER_CLIMB - GRAVITY) * AIR_DRAG);
+    }
+
+    #[test]
+    fn ladders_limit_falling() {
+        let mut world = TestWorld::floor(4, STONE);
+        for y in 0..10 {
+            world.set(
+                0,
+                y,
+                0,
+                Block::Vine {
+                    up: false,
+                    south: false,
+                    west: false,
+                    north: false,
+                    east: true,
+                },
+            );
+        }
+        let mut body = Body::new(Vector3::new(0.5, 9.0, 0.5));
+        for _ in 0..20 {
+            tick(&world, &mut body, &Input::default());
+            assert!(body.velocity.y >= -(LADDER_SPEED + GRAVITY) * AIR_DRAG - 1.0e-9);
+        }
+
+        // Sneaking holds on
+        let sneak = Input {
+            sneak: true,
+            ..Default::default()
+        };
+        let mut body = Body::new(Vector3::new(0.5, 5.0, 0.5));
+        run(&world, &mut body, &sneak, 5);
+        tick(&world, &mut body, &sneak);
+        let y = body.position.y;
+        tick(&world, &mut body, &sneak);
+        assert_near(body.position.y, y);
+    }
+
+    #[test]
+    fn water_slows_falling_and_swims_up() {
+        let mut world = TestWorld::floor(4, STONE);
+        for y in 0..10 {
+            world.set(0, y, 0, Block::Water { level: 0 });
+        }
+        let mut body = Body::new(Vector3::new(0.5, 6.0, 0.5));
+        run(&world, &mut body, &Input::default(), 20);
+        // Sinks at a fraction of the speed it would fall at
+        assert!(body.velocity.y > -0.1);
+        assert!(body.velocity.y < 0.0);
+
+        let swim = Input {
+            jump: true,
+            ..Default::default()
+        };
+        let y = body.position.y;
+        run(&world, &mut body, &swim, 20);
+        assert!(body.position.y > y);
+    }
+
+    #[test]
+    fn lava_is_thicker_than_water() {
+        let sink = |block: Block| {
+            let mut world = TestWorld::floor(4, STONE);
+            for y in 0..10 {
+                world.set(0, y, 0, block);
+            }
+            let mut body = Body::new(Vector3::new(0.5, 6.0, 0.5));
+            run(&world, &mut body, &walk_east(), 10);
+            body.position.x - 0.5
+        };
+        let water = sink(Block::Water { level: 0 });
+        let lava = sink(Block::Lava { level: 0 });
+        assert!(lava > 0.0);
+        assert!(lava < water);
+    }
+
+    #[test]
+    fn climbs_out_of_water() {
+        let mut world = TestWorld::floor(4, STONE);
+        world.set(0, -1, 0, Block::Water { level: 0 });
+        world.set(0, 0, 0, Block::Water { level: 0 });
+        world.set(0, -2, 0, STONE);
+        for z in -1..=1 {
+            world.set(1, -1, z, STONE);
+        }
+        let mut body = Body::new(Vector3::new(0.5, -0.5, 0.5));
+        let swim_out = Input {
+            jump: true,
+            ..walk_east()
+        };
+        run(&world, &mut body, &swim_out, 40);
+        assert!(body.position.x > 1.0);
+        assert!(body.position.y >= 0.0);
+    }
+
+    #[test]
+    fn flying_ignores_gravity() {
+        let world = TestWorld::default();
+        let mut body = Body::new(Vector3::new(0.5, 10.0, 0.5));
+        body.flying = true;
+        run(&world, &mut body, &Input::default(), 10);
+        assert_near(body.position.y, 10.0);
+        let up = Input {
+            jump: true,
+            ..Default::default()
+        };
+        run(&world, &mut body, &up, 10);
+        assert!(body.position.y > 11.0);
+    }
+}
@@ -1,19 +1,21 @@
+use super::metadata::EntityState;
+use super::physics;
 use super::{
-    Bounds, GameInfo, Gravity, Light, Position, Rotation, TargetPosition, TargetRotation, Velocity,
+    Bounds, GameInfo, Gravity, Light, Position, Rotation, ServerPosition, TargetPosition,
+    TargetRotation, Velocity,
 };
 use crate::ecs;
 use crate::ecs::Entity;
-use crate::entity::{resolve_textures, CustomEntityRenderer, EntityRenderer, EntityType};
+use crate::entity::{item, resolve_textures, CustomEntityRenderer, EntityRenderer, EntityType};
 use crate::format;
 use crate::render;
 use crate::render::model::{self, FormatState};
 use crate::settings::Actionkey;
-use crate::shared::Position as BPosition;
 use crate::types::hash::FNVHash;
 use crate::types::GameMode;
 use crate::world;
 use cgmath::{self, Decomposed, Matrix4, Point3, Quaternion, Rad, Rotation3, Vector3};
-use collision::{Aabb, Aabb3};
+use collision::Aabb3;
 use instant::Instant;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
@@ -44,6 +46,7 @@ pub fn create_local(m: &mut ecs::Manager) -> ecs::Entity {
         )),
     );
     m.add_component_direct(entity, PlayerModel::new("", false, false, true));
+    m.add_component_direct(entity, EntityState::new());
     m.add_component_direct(entity, Light::new());
     m.add_component_direct(entity, EntityType::Player);
     entity
@@ -53,6 +56,7 @@ pub fn create_remote(m: &mut ecs::Manager, name: &str) -> ecs::Entity {
     let entity = m.create_entity();
     m.add_component_direct(entity, Position::new(0.0, 0.0, 0.0));
     m.add_component_direct(entity, TargetPosition::new(0.0, 0.0, 0.0));
+    m.add_component_direct(entity, ServerPosition::new(0.0, 0.0, 0.0));
     m.add_component_direct(entity, Rotation::new(0.0, 0.0));
     m.add_component_direct(entity, TargetRotation::new(0.0, 0.0));
     m.add_component_direct(entity, Velocity::new(0.0, 0.0, 0.0));
@@ -64,6 +68,7 @@ pub fn create_remote(m: &mut ecs::Manager, name: &str) -> ecs::Entity {
         )),
     );
     m.add_component_direct(entity, PlayerModel::new(name, true, true, false));
+    m.add_component_direct(entity, EntityState::new());
     m.add_component_direct(entity, Light::new());
     m.add_component_direct(entity, EntityType::Player);
     entity
@@ -118,6 +123,7 @@ pub struct PlayerRenderer {
     rotation: ecs::Key<Rotation>,
     game_info: ecs::Key<GameInfo>,
     light: ecs::Key<Light>,
+    state: ecs::Key<EntityState>,
 }
 
 impl PlayerRenderer {
@@ -132,10 +138,18 @@ impl PlayerRenderer {
             rotation,
             game_info: m.get_key(),
             light,
+            state: m.get_key(),
         }
     }
 }
 
+// How far the parts of the model move while crouching, as in vanilla
+const CROUCH_HEAD_DROP: f32 = 4.2 / 16.0;
+const CROUCH_BODY_DROP: f32 = 3.2 / 16.0;
+const CROUCH_LEG_OFFSET: f32 = 4.0 / 16.0;
+const CROUCH_BODY_ANGLE: f32 = 0.5;
+const CROUCH_ARM_ANGLE: f32 = 0.4;
+
 enum PlayerModelPart {
     Head = 0,
     Body = 1,
@@ -144,7 +158,25 @@ enum PlayerModelPart {
     ArmLeft = 4,
     ArmRight = 5,
     NameTag = 6,
-    // Cape = 7, // TODO
+    HeldRight = 7,
+    HeldLeft = 8,
+    // Cape = 9, // TODO
+}
+
+/// Moves an item held in the arm of the given side from the shoulder into
+/// the hand, the way vanilla places it in front of the hand before the
+/// display of the item.
+fn held_matrix(side: f32) -> Matrix4<f32> {
+    use std::f32::consts::PI;
+    // Vanilla turns the arm around a point a pixel further in and two
+    // further down, and has the model's x axis the other way around
+    Matrix4::from_translation(Vector3::new(-side / 16.0, 2.0 / 16.0, 0.0))
+        * Matrix4::from_nonuniform_scale(-1.0, 1.0, 1.0)
+        * Matrix4::from(Quaternion::from_angle_x(Rad(-PI / 2.0)))
+        * Matrix4::from(Quaternion::from_angle_y(Rad(PI)))
+        * Matrix4::from_translation(Vector3::new(side / 16.0, 0.125, -0.625))
+        // Items are built with y up, which the model shader would flip
+        * Matrix4::from_nonuniform_scale(1.0, -1.0, 1.0)
 }
 
 // TODO: Setup culling
@@ -169,6 +201,16 @@ impl CustomEntityRenderer for PlayerRenderer {
         let position = m.get_component_mut(e, self.position).unwrap();
         let rotation = m.get_component_mut(e, self.rotation).unwrap();
         let light = m.get_component(e, self.light).unwrap();
+        let (crouching, invisible) = m
+            .get_component(e, self.state)
+            .map_or((false, false), |state| (state.crouching(), state.invisible));
+        // The held items are part of the model
+        if let Some(state) = m.get_component_mut(e, self.state) {
+            if state.dirty && !player_model.first_person {
+                state.dirty = false;
+                player_model.dirty = true;
+            }
+        }
 
         if player_model.dirty {
             self.entity_removed(m, e, world, renderer);
@@ -202,6 +244,18 @@ impl CustomEntityRenderer for PlayerRenderer {
                 disp: offset,
             });
 
+            let (head_drop, body_drop, leg_offset, body_angle, arm_angle) = if crouching {
+                (
+                    CROUCH_HEAD_DROP,
+                    CROUCH_BODY_DROP,
+                    CROUCH_LEG_OFFSET,
+                    CROUCH_BODY_ANGLE,
+                    CROUCH_ARM_ANGLE,
+                )
+            } else {
+                (0.0, 0.0, 0.0, 0.0, 0.0)
+            };
+
             // TODO This sucks
             if player_model.has_name_tag {
                 let ang = (position.position.x - renderer.camera.pos.x)
@@ -210,7 +264,7 @@ impl CustomEntityRenderer for PlayerRenderer {
                 mdl.matrix[PlayerModelPart::NameTag as usize] = Matrix4::from(Decomposed {
                     scale: 1.0,
                     rot: Quaternion::from_angle_y(Rad(ang)),
-                    disp: offset + Vector3::new(0.0, (-24.0 / 16.0) - 0.6, 0.0),
+                    disp: offset + Vector3::new(0.0, (-24.0 / 16.0) - 0.6 + head_drop, 0.0),
                 });
             }
 
@@ -218,14 +272,16 @@ impl CustomEntityRenderer for PlayerRenderer {
                 * Matrix4::from(Decomposed {
                     scale: 1.0,
                     rot: Quaternion::from_angle_x(Rad(-rotation.pitch as f32)),
-                    disp: Vector3::new(0.0, -12.0 / 16.0 - 12.0 / 16.0, 0.0),
+                    disp: Vector3::new(0.0, -12.0 / 16.0 - 12.0 / 16.0 + head_drop, 0.0),
                 });
+            // Leans forward around the neck
             mdl.matrix[PlayerModelPart::Body as usize] = offset_matrix
                 * Matrix4::from(Decomposed {
                     scale: 1.0,
-                    rot: Quaternion::from_angle_x(Rad(0.0)),
-                    disp: Vector3::new(0.0, -12.0 / 16.0 - 6.0 / 16.0, 0.0),
-                });
+                    rot: Quaternion::from_angle_x(Rad(body_angle)),
+                    disp: Vector3::new(0.0, -12.0 / 16.0 - 12.0 / 16.0 + body_drop, 0.0),
+                })
+                * Matrix4::from_translation(Vector3::new(0.0, 6.0 / 16.0, 0.0));
 
             let mut time = player_model.time;
             let mut dir = player_model.dir;
@@ -239,13 +295,13 @@ impl CustomEntityRenderer for PlayerRenderer {
                 * Matrix4::from(Decomposed {
                     scale: 1.0,
                     rot: Quaternion::from_angle_x(Rad(ang as f32)),
-                    disp: Vector3::new(2.0 / 16.0, -12.0 / 16.0, 0.0),
+                    disp: Vector3::new(2.0 / 16.0, -12.0 / 16.0, leg_offset),
                 });
             mdl.matrix[PlayerModelPart::LegLeft as usize] = offset_matrix
                 * Matrix4::from(Decomposed {
                     scale: 1.0,
                     rot: Quaternion::from_angle_x(Rad(-ang as f32)),
-                    disp: Vector3::new(-2.0 / 16.0, -12.0 / 16.0, 0.0),
+                    disp: Vector3::new(-2.0 / 16.0, -12.0 / 16.0, leg_offset),
                 });
 
             let mut i_time = player_model.idle_time;
@@ -261,13 +317,15 @@ impl CustomEntityRenderer for PlayerRenderer {
                 player_model.arm_time -= delta;
             }
 
-            mdl.matrix[PlayerModelPart::ArmRight as usize] = offset_matrix
+            let arm_right = offset_matrix
                 * Matrix4::from_translation(Vector3::new(
                     6.0 / 16.0,
-                    -12.0 / 16.0 - 12.0 / 16.0,
+                    -12.0 / 16.0 - 12.0 / 16.0 + body_drop,
                     0.0,
                 ))
-                * Matrix4::from(Quaternion::from_angle_x(Rad(-(ang * 0.75) as f32)))
+                * Matrix4::from(Quaternion::from_angle_x(Rad(
+                    -(ang * 0.75) as f32 + arm_angle
+                )))
                 * Matrix4::from(Quaternion::from_angle_z(Rad(
                     (i_time.cos() * 0.06 - 0.06) as f32
                 )))
@@ -275,17 +333,31 @@ impl CustomEntityRenderer for PlayerRenderer {
                     - ((7.5 - (player_model.arm_time - 7.5).abs()) / 7.5))
                     as f32)));
 
-            mdl.matrix[PlayerModelPart::ArmLeft as usize] = offset_matrix
+            let arm_left = offset_matrix
                 * Matrix4::from_translation(Vector3::new(
                     -6.0 / 16.0,
-                    -12.0 / 16.0 - 12.0 / 16.0,
+                    -12.0 / 16.0 - 12.0 / 16.0 + body_drop,
                     0.0,
                 ))
-                * Matrix4::from(Quaternion::from_angle_x(Rad((ang * 0.75) as f32)))
+                * Matrix4::from(Quaternion::from_angle_x(Rad(
+                    (ang * 0.75) as f32 + arm_angle
+                )))
                 * Matrix4::from(Quaternion::from_angle_z(Rad(
                     -(i_time.cos() * 0.06 - 0.06) as f32
                 )))
                 * Matrix4::from(Quaternion::from_angle_x(Rad(-(i_time.sin() * 0.06) as f32)));
+            mdl.matrix[PlayerModelPart::ArmRight as usize] = arm_right;
+            mdl.matrix[PlayerModelPart::ArmLeft as usize] = arm_left;
+            // The main hand is taken to be the right one
+            mdl.matrix[PlayerModelPart::HeldRight as usize] = arm_right * held_matrix(1.0);
+            mdl.matrix[PlayerModelPart::HeldLeft as usize] = arm_left * held_matrix(-1.0);
+
+            if invisible {
+                // Only the name tag stays visible
+                for part in &mut mdl.matrix[..PlayerModelPart::NameTag as usize] {
+                    *part = Matrix4::from_scale(0.0);
+                }
+            }
 
             let mut update = true;
             if position.moved {
@@ -322,6 +394,11 @@ impl CustomEntityRenderer for PlayerRenderer {
         _: &world::World,
         renderer: &mut render::Renderer,
     ) {
+        let held = m
+            .get_component(e, self.state)
+            .map_or((None, None), |state| {
+                (state.main_hand.clone(), state.off_hand.clone())
+            });
         let player_model = m.get_component_mut(e, self.player_model).unwrap();
 
         player_model.dirty = false;
@@ -332,13 +409,6 @@ impl CustomEntityRenderer for PlayerRenderer {
             render::Renderer::get_texture(renderer.get_textures_ref(), "entity/steve")
         };
 
-        // TODO: Replace this shit entirely!
-        macro_rules! srel {
-            ($x:expr, $y:expr, $w:expr, $h:expr) => {
-                Some(skin.relative(($x) / 64.0, ($y) / 64.0, ($w) / 64.0, ($h) / 64.0))
-            };
-        }
-
         let mut head_verts = vec![];
         if player_model.has_head {
             model::append_box(
@@ -397,50 +467,7 @@ impl CustomEntityRenderer for PlayerRenderer {
         .iter()
         .enumerate()
         {
-            // TODO: Fix alex (slim) skins
-            let alex = i > 1;
-            let width = if alex {
-                // arms of alex (slim) skins have 3/4 of the width of normal skins!
-                3.0
-            } else {
-                4.0
-            };
-            let (ox, oy) = (offsets[0], offsets[1]);
-            model::append_box(
-                &mut part_verts[i],
-                -2.0 / 16.0,
-                -12.0 / 16.0,
-                -2.0 / 16.0,
-                4.0 / 16.0,
-                12.0 / 16.0,
-                4.0 / 16.0,
-                [
-                    srel!(ox + 8.0, oy + 0.0, 4.0, 4.0),     // Down
-                    srel!(ox + 4.0, oy + 0.0, 4.0, 4.0),     // Up
-                    srel!(ox + 4.0, oy + 4.0, width, 12.0),  // North
-                    srel!(ox + 12.0, oy + 4.0, width, 12.0), // South
-                    srel!(ox + 8.0, oy + 4.0, width, 12.0),  // West
-                    srel!(ox + 0.0, oy + 4.0, width, 12.0),  // East
-                ],
-            );
-            let (ox, oy) = (offsets[2], offsets[3]);
-            model::append_box(
-                &mut part_verts[i],
-                -2.2 / 16.0,
-                -12.2 / 16.0,
-                -2.2 / 16.0,
-                4.4 / 16.0,
-                12.4 / 16.0,
-                4.4 / 16.0,
-                [
-                    srel!(ox + 8.0, oy + 0.0, 4.0, 4.0),   // Down
-                    srel!(ox + 4.0, oy + 0.0, 4.0, 4.0),   // Up
-                    srel!(ox + 4.0, oy + 4.0, 4.0, 12.0),  // North
-                    srel!(ox + 12.0, oy + 4.0, 4.0, 12.0), // South
-                    srel!(ox + 8.0, oy + 4.0, 4.0, 12.0),  // West
-                    srel!(ox + 0.0, oy + 4.0, 4.0, 12.0),  // East
-                ],
-            );
+            append_limb(&mut part_verts[i], &skin, *offsets, i > 1);
         }
 
         let mut name_verts = vec![];
@@ -477,6 +504,16 @@ impl CustomEntityRenderer for PlayerRenderer {
             name_verts.extend_from_slice(&state.text);
         }
 
+        // The local player's items are drawn in its hands in first person
+        let mut held_verts = vec![vec![]; 2];
+        if !player_model.first_person {
+            for (i, held) in [held.0, held.1].iter().enumerate() {
+                if let Some(held) = held {
+                    item::append_held(&mut held_verts[i], renderer, &held.material, i == 1);
+                }
+            }
+        }
+
         player_model.model = Some(renderer.model.create_model(
             model::DEFAULT,
             vec![
@@ -487,6 +524,8 @@ impl CustomEntityRenderer for PlayerRenderer {
                 part_verts[2].clone(),
                 part_verts[3].clone(),
                 name_verts,
+                held_verts[0].clone(),
+                held_verts[1].clone(),
             ],
         ));
     }
@@ -508,6 +547,72 @@ impl CustomEntityRenderer for PlayerRenderer {
     }
 }
 
+/// The right arm of a player with the skin, hanging down from the
+/// shoulder at the origin.
+pub fn append_right_arm(verts: &mut Vec<model::Vertex>, skin: &render::Texture) {
+    append_limb(verts, skin, [40.0, 16.0, 40.0, 32.0], true);
+}
+
+/// Appends an arm or a leg, with the offsets of its texture and of the
+/// texture of its outer layer in the skin.
+fn append_limb(
+    verts: &mut Vec<model::Vertex>,
+    skin: &render::Texture,
+    offsets: [f32; 4],
+    arm: bool,
+) {
+    // TODO: Replace this shit entirely!
+    macro_rules! srel {
+        ($x:expr, $y:expr, $w:expr, $h:expr) => {
+            Some(skin.relative(($x) / 64.0, ($y) / 64.0, ($w) / 64.0, ($h) / 64.0))
+        };
+    }
+
+    // TODO: Fix alex (slim) skins
+    let width = if arm {
+        // arms of alex (slim) skins have 3/4 of the width of normal skins!
+        3.0
+    } else {
+        4.0
+    };
+    let (ox, oy) = (offsets[0], offsets[1]);
+    model::append_box(
+        verts,
+        -2.0 / 16.0,
+        -12.0 / 16.0,
+        -2.0 / 16.0,
+        4.0 / 16.0,
+        12.0 / 16.0,
+        4.0 / 16.0,
+        [
+            srel!(ox + 8.0, oy + 0.0, 4.0, 4.0),     // Down
+            srel!(ox + 4.0, oy + 0.0, 4.0, 4.0),     // Up
+            srel!(ox + 4.0, oy + 4.0, width, 12.0),  // North
+            srel!(ox + 12.0, oy + 4.0, width, 12.0), // South
+            srel!(ox + 8.0, oy + 4.0, width, 12.0),  // West
+            srel!(ox + 0.0, oy + 4.0, width, 12.0),  // East
+        ],
+    );
+    let (ox, oy) = (offsets[2], offsets[3]);
+    model::append_box(
+        verts,
+        -2.2 / 16.0,
+        -12.2 / 16.0,
+        -2.2 / 16.0,
+        4.4 / 16.0,
+        12.4 / 16.0,
+        4.4 / 16.0,
+        [
+            srel!(ox + 8.0, oy + 0.0, 4.0, 4.0),   // Down
+            srel!(ox + 4.0, oy + 0.0, 4.0, 4.0),   // Up
+            srel!(ox + 4.0, oy + 4.0, 4.0, 12.0),  // North
+            srel!(ox + 12.0, oy + 4.0, 4.0, 12.0), // South
+            srel!(ox + 8.0, oy + 4.0, 4.0, 12.0),  // West
+            srel!(ox + 0.0, oy + 4.0, 4.0, 12.0),  // East
+        ],
+    );
+}
+
 #[derive(Default)]
 pub struct PlayerMovement {
     pub flying: bool,
@@ -515,6 +620,7 @@ pub struct PlayerMovement {
     pub when_last_jump_pressed: Option<Instant>,
     pub when_last_jump_released: Option<Instant>,
     pub did_touch_ground: bool,
+    body: Option<physics::Body>,
     pub pressed_keys: HashMap<Actionkey, bool, BuildHasherDefault<FNVHash>>,
 }
 
@@ -523,41 +629,31 @@ impl PlayerMovement {
         Default::default()
     }
 
-    fn calculate_movement(&self, player_yaw: f64) -> (f64, f64, bool) {
-        use std::f64::consts::PI;
-        let mut forward = 0.0f64;
-        let mut yaw = player_yaw - (PI / 2.0);
-        if self.is_key_pressed(Actionkey::Forward) || self.is_key_pressed(Actionkey::Backward) {
-            // TODO: Make walking backwards slower!
-            forward = 1.0;
-            if self.is_key_pressed(Actionkey::Backward) {
-                yaw += PI;
+    /// Turns the pressed keys into what the player wants to do this tick.
+    fn input(&self, yaw: f64) -> physics::Input {
+        let axis = |positive: Actionkey, negative: Actionkey| {
+            let mut value = 0.0;
+            if self.is_key_pressed(positive) {
+                value += 1.0;
             }
-        }
-        let change = if self.is_key_pressed(Actionkey::Left) {
-            (PI / 2.0) / (forward.abs() + 1.0)
-        } else if self.is_key_pressed(Actionkey::Right) {
-            -(PI / 2.0) / (forward.abs() + 1.0)
-        } else {
-            0.0
+            if self.is_key_pressed(negative) {
+                value -= 1.0;
+            }
+            value
         };
-        if self.is_key_pressed(Actionkey::Left) || self.is_key_pressed(Actionkey::Right) {
-            forward = 1.0;
-        }
-        if self.is_key_pressed(Actionkey::Backward) {
-            yaw -= change;
-        } else {
-            yaw += change;
-        }
-
-        (
+        let forward = axis(Actionkey::Forward, Actionkey::Backward);
+        let sneak = self.is_key_pressed(Actionkey::Sneak);
+        physics::Input {
             forward,
-            yaw,
-            self.is_key_pressed(Actionkey::Forward) && !self.is_key_pressed(Actionkey::Backward),
-        )
+            strafe: axis(Actionkey::Left, Actionkey::Right),
+            jump: self.is_key_pressed(Actionkey::Jump),
+            sneak,
+            sprint: self.is_key_pressed(Actionkey::Sprint) && forward > 0.0 && !sneak,
+            yaw: -yaw,
+        }
     }
 
-    fn is_key_pressed(&self, key: Actionkey) -> bool {
+    pub fn is_key_pressed(&self, key: Actionkey) -> bool {
         self.pressed_keys.get(&key).map_or(false, |v| *v)
     }
 }
@@ -569,7 +665,6 @@ struct MovementHandler {
     gamemode: ecs::Key<GameMode>,
     position: ecs::Key<TargetPosition>,
     velocity: ecs::Key<Velocity>,
-    bounds: ecs::Key<Bounds>,
     rotation: ecs::Key<Rotation>,
 }
 
@@ -578,21 +673,18 @@ impl MovementHandler {
         let movement = m.get_key();
         let position = m.get_key();
         let velocity = m.get_key();
-        let bounds = m.get_key();
         let rotation = m.get_key();
         MovementHandler {
             filter: ecs::Filter::new()
                 .with(movement)
                 .with(position)
                 .with(velocity)
-                .with(bounds)
                 .with(rotation),
             movement,
             gravity: m.get_key(),
             gamemode: m.get_key(),
             position,
             velocity,
-            bounds,
             rotation,
         }
     }
@@ -663,233 +755,33 @@ impl ecs::System for MovementHandler {
             let velocity = m.get_component_mut(e, self.velocity).unwrap();
             let gravity = m.get_component_mut(e, self.gravity);
 
-            let player_bounds = m.get_component(e, self.bounds).unwrap().bounds;
-
-            let mut last_position = position.position;
-
-            if world.is_chunk_loaded(
+            if !world.is_chunk_loaded(
                 (position.position.x as i32) >> 4,
                 (position.position.z as i32) >> 4,
             ) {
-                let (forward, yaw, is_forward) = movement.calculate_movement(rotation.yaw);
-                let mut speed = 0.21585;
-                let mut additional_speed =
-                    if movement.is_key_pressed(Actionkey::Sprint) && is_forward {
-                        0.2806 - 0.21585
-                    } else {
-                        0.0
-                    };
-                let looking_vec = calculate_looking_vector(rotation.yaw, rotation.pitch);
-                if movement.flying {
-                    speed *= 2.5;
-                    additional_speed *= 2.5;
-
-                    if movement.is_key_pressed(Actionkey::Jump) {
-                        position.position.y += speed + additional_speed;
-                    }
-                    if movement.is_key_pressed(Actionkey::Sneak) {
-                        position.position.y -= speed + additional_speed;
-                    }
-                } else if gravity.as_ref().map_or(false, |v| v.on_ground) {
-                    if movement.is_key_pressed(Actionkey::Jump) && velocity.velocity.y.abs() < 0.001
-                    {
-                        velocity.velocity.y = 0.42;
-                    }
-                } else {
-                    velocity.velocity.y -= 0.08;
-                    if velocity.velocity.y < -3.92 {
-                        velocity.velocity.y = -3.92;
-                    }
-                }
-                velocity.velocity.y *= 0.98;
-                velocity.velocity.x *= 0.98;
-                velocity.velocity.z *= 0.98;
-                // position.position.x += look_vec.0 * speed;
-                // position.position.z -= look_vec.1 * speed;
-                position.position.x +=
-                    forward * yaw.cos() * (speed + looking_vec.0 * additional_speed); // TODO: Multiply with speed only for walking forwards
-                position.position.z -=
-                    forward * yaw.sin() * (speed + looking_vec.1 * additional_speed);
-                position.position.y += velocity.velocity.y;
-                if (velocity.velocity.x.abs() * 0.2) < 0.005 {
-                    velocity.velocity.x = 0.0;
-                }
-
-                if (velocity.velocity.y.abs() * 0.2) < 0.005 {
-                    velocity.velocity.y = 0.0;
-                }
-
-                if (velocity.velocity.z.abs() * 0.2) < 0.005 {
-                    velocity.velocity.z = 0.0;
-                }
-
-                if !gamemode.noclip() {
-                    let mut target = position.position;
-                    position.position.y = last_position.y;
-                    position.position.z = last_position.z;
-
-                    // We handle each axis separately to allow for a sliding
-                    // effect when pushing up against walls.
-
-                    let (bounds, xhit) =
-                        check_collisions(world, position, &last_position, player_bounds);
-                    position.position.x = bounds.min.x + 0.3;
-                    last_position.x = position.position.x;
-
-                    position.position.z = target.z;
-                    let (bounds, zhit) =
-                        check_collisions(world, position, &last_position, player_bounds);
-                    position.position.z = bounds.min.z + 0.3;
-                    last_position.z = position.position.z;
-
-                    // Half block jumps
-                    // Minecraft lets you 'jump' up 0.5 blocks
-                    // for slabs and stairs (or smaller blocks).
-                    // Currently we implement this as a teleport to the
-                    // top of the block if we could move there
-                    // but this isn't smooth.
-                    if (xhit || zhit) && gravity.as_ref().map_or(false, |v| v.on_ground) {
-                        let mut ox = position.position.x;
-                        let mut oz = position.position.z;
-                        position.position.x = target.x;
-                        position.position.z = target.z;
-                        for offset in 1..9 {
-                            let mini = player_bounds.add_v(cgmath::Vector3::new(
-                                0.0,
-                                offset as f64 / 16.0,
-                                0.0,
-                            ));
-                            let (_, hit) = check_collisions(world, position, &last_position, mini);
-                            if !hit {
-                                target.y += offset as f64 / 16.0;
-                                ox = target.x;
-                                oz = target.z;
-                                break;
-                            }
-                        }
-                        position.position.x = ox;
-                        position.position.z = oz;
-                    }
-
-                    position.position.y = target.y;
-                    let (bounds, yhit) =
-                        check_collisions(world, position, &last_position, player_bounds);
-                    position.position.y = bounds.min.y;
-                    last_position.y = position.position.y;
-                    if yhit {
-                        velocity.velocity.y = 0.0;
-                    }
-
-                    if let Some(gravity) = gravity {
-                        let ground =
-                            Aabb3::new(Point3::new(-0.3, -0.005, -0.3), Point3::new(0.3, 0.0, 0.3));
-                        let prev = gravity.on_ground;
-                        let (_, hit) = check_collisions(world, position, &last_position, ground);
-                        gravity.on_ground = hit;
-                        if !prev && gravity.on_ground {
-                            movement.did_touch_ground = true;
-                        }
-                    }
-                }
+                continue;
             }
-        }
-    }
-}
-
-fn calculate_looking_vector(yaw: f64, pitch: f64) -> (f64, f64) {
-    let xz = pitch.to_radians().cos();
-    let x = -xz * yaw.to_radians().sin();
-    let z = xz * yaw.to_radians().cos();
-    (x, z)
-}
-
-fn check_collisions(
-    world: &world::World,
-    position: &mut TargetPosition,
-    last_position: &Vector3<f64>,
-    bounds: Aabb3<f64>,
-) -> (Aabb3<f64>, bool) {
-    let mut bounds = bounds.add_v(position.position);
-
-    let dir = position.position - last_position;
-
-    let min_x = (bounds.min.x - 1.0) as i32;
-    let min_y = (bounds.min.y - 1.0) as i32;
-    let min_z = (bounds.min.z - 1.0) as i32;
-    let max_x = (bounds.max.x + 1.0) as i32;
-    let max_y = (bounds.max.y + 1.0) as i32;
-    let max_z = (bounds.max.z + 1.0) as i32;
-
-    let mut hit = false;
-    for y in min_y..max_y {
-        for z in min_z..max_z {
-            for x in min_x..max_x {
-                let block = world.get_block(BPosition::new(x, y, z));
-                if block.get_material().collidable {
-                    for bb in block.get_collision_boxes() {
-                        let bb = bb.add_v(cgmath::Vector3::new(x as f64, y as f64, z as f64));
-                        if bb.collides(&bounds) {
-                            bounds = bounds.move_out_of(bb, dir);
-                            hit = true;
-                        }
-                    }
+            let input = movement.input(rotation.yaw);
+            // The server may have moved the player or pushed it since the
+            // last tick
+            let body = movement
+                .body
+                .get_or_insert_with(|| physics::Body::new(position.position));
+            body.position = position.position;
+            body.velocity = velocity.velocity;
+            body.flying = movement.flying;
+            body.noclip = gamemode.noclip();
+            physics::tick(world, body, &input);
+            position.position = body.position;
+            velocity.velocity = body.velocity;
+
+            let on_ground = body.on_ground;
+            if let Some(gravity) = gravity {
+                if !gravity.on_ground && on_ground {
+                    movement.did_touch_ground = true;
                 }
+                gravity.on_ground = on_ground;
             }
         }
     }
-
-    (bounds, hit)
-}
-
-trait Collidable<T> {
-    fn collides(&self, t: &T) -> bool;
-    fn move_out_of(self, other: Self, dir: cgmath::Vector3<f64>) -> Self;
-}
-
-impl Collidable<Aabb3<f64>> for Aabb3<f64> {
-    fn collides(&self, t: &Aabb3<f64>) -> bool {
-        !(t.min.x >= self.max.x
-            || t.max.x <= self.min.x
-            || t.min.y >= self.max.y
-            || t.max.y <= self.min.y
-            || t.min.z >= self.max.z
-            || t.max.z <= self.min.z)
-    }
-
-    fn move_out_of(mut self, other: Self, dir: cgmath::Vector3<f64>) -> Self {
-        if dir.x != 0.0 {
-            if dir.x > 0.0 {
-                let ox = self.max.x;
-                self.max.x = other.min.x - 0.0001;
-                self.min.x += self.max.x - ox;
-            } else {
-                let ox = self.min.x;
-                self.min.x = other.max.x + 0.0001;
-                self.max.x += self.min.x - ox;
-            }
-        }
-        if dir.y != 0.0 {
-            if dir.y > 0.0 {
-                let oy = self.max.y;
-                self.max.y = other.min.y - 0.0001;
-                self.min.y += self.max.y - oy;
-            } else {
-                let oy = self.min.y;
-                self.min.y = other.max.y + 0.0001;
-                self.max.y += self.min.y - oy;
-            }
-        }
-        if dir.z != 0.0 {
-            if dir.z > 0.0 {
-                let oz = self.max.z;
-                self.max.z = other.min.z - 0.0001;
-                self.min.z += self.max.z - oz;
-            } else {
-                let oz = self.min.z;
-                self.min.z = other.max.z + 0.0001;
-                self.max.z += self.min.z - oz;
-            }
-        }
-        self
-    }
 }
@@ -5,26 +5,48 @@ use crate::shared::Position as BPos;
 use crate::world;
 use cgmath::InnerSpace;
 
+/// Moves entities by their velocity between the updates of the server,
+/// slowing them down and pulling them down like vanilla does for the type
+/// of entity. Relative moves from the server are applied to the server's
+/// position, so the prediction never drifts away for long.
 pub struct ApplyVelocity {
     filter: ecs::Filter,
-    position: ecs::Key<Position>,
+    target_position: ecs::Key<TargetPosition>,
     velocity: ecs::Key<Velocity>,
+    entity_type: ecs::Key<EntityType>,
     movement: ecs::Key<super::player::PlayerMovement>,
 }
 
 impl ApplyVelocity {
     pub fn new(m: &mut ecs::Manager) -> ApplyVelocity {
-        let position = m.get_key();
+        let target_position = m.get_key();
         let velocity = m.get_key();
+        let entity_type = m.get_key();
         ApplyVelocity {
-            filter: ecs::Filter::new().with(position).with(velocity),
-            position,
+            filter: ecs::Filter::new()
+                .with(target_position)
+                .with(velocity)
+                .with(entity_type),
+            target_position,
             velocity,
+            entity_type,
             movement: m.get_key(),
         }
     }
 }
 
+/// Whether a point is inside of a block entities can't move through.
+fn is_solid(world: &world::World, pos: Vector3<f64>) -> bool {
+    world
+        .get_block(BPos::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        ))
+        .get_material()
+        .collidable
+}
+
 impl ecs::System for ApplyVelocity {
     fn filter(&self) -> &ecs::Filter {
         &self.filter
@@ -33,7 +55,7 @@ impl ecs::System for ApplyVelocity {
     fn update(
         &mut self,
         m: &mut ecs::Manager,
-        _: &world::World,
+        world: &world::World,
         _: &mut render::Renderer,
         _: bool,
         _: bool,
@@ -43,9 +65,39 @@ impl ecs::System for ApplyVelocity {
                 // Players handle their own physics
                 continue;
             }
-            let pos = m.get_component_mut(e, self.position).unwrap();
-            let vel = m.get_component(e, self.velocity).unwrap();
-            pos.position += vel.velocity;
+            let physics = match m.get_component(e, self.entity_type).unwrap().physics() {
+                Some(physics) => physics,
+                None => continue,
+            };
+            let vel = m.get_component_mut(e, self.velocity).unwrap();
+            // Entities at rest wait for the server to move them
+            if vel.velocity == Vector3::new(0.0, 0.0, 0.0) {
+                continue;
+            }
+            let pos = m.get_component_mut(e, self.target_position).unwrap();
+
+            // Each axis on its own so entities slide along walls
+            let mut on_ground = false;
+            for axis in 0..3 {
+                let mut next = pos.position;
+                next[axis] += vel.velocity[axis];
+                if is_solid(world, next) {
+                    on_ground |= axis == 1 && vel.velocity.y < 0.0;
+                    vel.velocity[axis] = 0.0;
+                } else {
+                    pos.position = next;
+                }
+            }
+
+            vel.velocity.y -= physics.gravity;
+            vel.velocity *= physics.drag;
+            if on_ground {
+                vel.velocity.x *= physics.friction;
+                vel.velocity.z *= physics.friction;
+                if vel.velocity.x.abs() < 0.003 && vel.velocity.z.abs() < 0.003 {
+                    vel.velocity = Vector3::new(0.0, 0.0, 0.0);
+                }
+            }
         }
     }
 }
@@ -250,6 +302,73 @@ impl ecs::System for LerpRotation {
     }
 }
 
+/// How many ticks a collected entity takes to reach its collector
+const COLLECT_TICKS: f64 = 3.0;
+
+pub struct CollectEntity {
+    filter: ecs::Filter,
+    collected: ecs::Key<Collected>,
+    position: ecs::Key<Position>,
+    target_position: ecs::Key<TargetPosition>,
+    game_info: ecs::Key<GameInfo>,
+}
+
+impl CollectEntity {
+    pub fn new(m: &mut ecs::Manager) -> CollectEntity {
+        let collected = m.get_key();
+        let position = m.get_key();
+        CollectEntity {
+            filter: ecs::Filter::new().with(collected).with(position),
+            collected,
+            position,
+            target_position: m.get_key(),
+            game_info: m.get_key(),
+        }
+    }
+}
+
+impl ecs::System for CollectEntity {
+    fn filter(&self) -> &ecs::Filter {
+        &self.filter
+    }
+
+    fn update(
+        &mut self,
+        m: &mut ecs::Manager,
+        _: &world::World,
+        _: &mut render::Renderer,
+        _: bool,
+        _: bool,
+    ) {
+        let world_entity = m.get_world();
+        let delta = m.get_component(world_entity, self.game_info).unwrap().delta;
+        for e in m.find(&self.filter) {
+            let collected = m.get_component_mut(e, self.collected).unwrap();
+            collected.ticks += delta / 3.0;
+            let progress = collected.ticks / COLLECT_TICKS;
+            let target = if m.is_entity_valid(collected.collector) {
+                m.get_component(collected.collector, self.position)
+                    .map(|pos| pos.position + Vector3::new(0.0, 0.5, 0.0))
+            } else {
+                None
+            };
+            let target = match target {
+                Some(target) if progress < 1.0 => target,
+                _ => {
+                    m.remove_entity(e);
+                    continue;
+                }
+            };
+            // Speeds up as it gets closer, like vanilla
+            let position = collected.start + (target - collected.start) * (progress * progress);
+            m.get_component_mut(e, self.position).unwrap().position = position;
+            if let Some(target_position) = m.get_component_mut(e, self.target_position) {
+                target_position.position = position;
+            }
+        }
+    }
+}
+
 pub struct LightEntity {
     filter: ecs::Filter,
     position: ecs::Key<Position>,
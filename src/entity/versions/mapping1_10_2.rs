@@ -1,3 +1,4 @@
+use crate::entity::versions::MetadataField;
 use crate::entity::EntityType;
 
 pub fn to_id(entity_type: EntityType) -> i16 {
@@ -7,3 +8,7 @@ pub fn to_id(entity_type: EntityType) -> i16 {
 pub fn to_entity_type(type_id: i16) -> EntityType {
     crate::entity::versions::mapping1_12_2::to_entity_type(type_id)
 }
+
+pub fn metadata_index(field: MetadataField, entity_type: EntityType) -> Option<i32> {
+    crate::entity::versions::mapping1_12_2::metadata_index(field, entity_type)
+}
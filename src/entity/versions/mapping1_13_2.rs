@@ -1,5 +1,6 @@
 #![allow(unreachable_patterns)]
 
+use crate::entity::versions::MetadataField;
 use crate::entity::EntityType;
 
 pub fn to_id(entity_type: EntityType) -> i16 {
@@ -213,3 +214,7 @@ pub fn to_entity_type(type_id: i16) -> EntityType {
         _ => EntityType::Unknown,
     }
 }
+
+pub fn metadata_index(field: MetadataField, entity_type: EntityType) -> Option<i32> {
+    crate::entity::versions::mapping1_12_2::metadata_index(field, entity_type)
+}
@@ -1,3 +1,4 @@
+use crate::entity::versions::MetadataField;
 use crate::entity::EntityType;
 
 pub fn to_id(entity_type: EntityType) -> i16 {
@@ -219,3 +220,19 @@ pub fn to_entity_type(type_id: i16) -> EntityType {
         _ => EntityType::Unknown,
     }
 }
+
+pub fn metadata_index(field: MetadataField, entity_type: EntityType) -> Option<i32> {
+    match field {
+        MetadataField::Flags => Some(0),
+        MetadataField::CustomName => Some(2),
+        MetadataField::CustomNameVisible => Some(3),
+        MetadataField::Pose => Some(6),
+        MetadataField::Baby if entity_type.can_be_baby() => Some(15),
+        MetadataField::SlimeSize if entity_type.is_slime() => Some(15),
+        MetadataField::Item if entity_type == EntityType::DroppedItem => Some(7),
+        MetadataField::Item if entity_type == EntityType::ItemFrame => Some(7),
+        MetadataField::ItemRotation if entity_type == EntityType::ItemFrame => Some(8),
+        MetadataField::Fuse if entity_type == EntityType::PrimedTnt => Some(7),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+use crate::entity::versions::MetadataField;
 use crate::entity::EntityType;
 
 pub fn to_id(entity_type: EntityType) -> i16 {
@@ -7,3 +8,18 @@ pub fn to_id(entity_type: EntityType) -> i16 {
 pub fn to_entity_type(type_id: i16) -> EntityType {
     crate::entity::versions::mapping1_12_2::to_entity_type(type_id)
 }
+
+pub fn metadata_index(field: MetadataField, entity_type: EntityType) -> Option<i32> {
+    match field {
+        MetadataField::Flags => Some(0),
+        MetadataField::CustomName => Some(2),
+        MetadataField::CustomNameVisible => Some(3),
+        MetadataField::Baby if entity_type.can_be_baby() => Some(11),
+        MetadataField::SlimeSize if entity_type.is_slime() => Some(11),
+        MetadataField::Item if entity_type == EntityType::DroppedItem => Some(5),
+        MetadataField::Item if entity_type == EntityType::ItemFrame => Some(5),
+        MetadataField::ItemRotation if entity_type == EntityType::ItemFrame => Some(6),
+        MetadataField::Fuse if entity_type == EntityType::PrimedTnt => Some(5),
+        _ => None,
+    }
+}
@@ -43,3 +43,78 @@ pub fn to_entity_type(id: i16, version: Version) -> EntityType {
         _ => EntityType::Unknown,
     }
 }
+
+/// Returns the entity type of an object spawned by `SpawnObject`. Objects
+/// had their own ids until 1.14 merged them into the entity type ids.
+pub fn object_to_entity_type(id: i16, version: Version) -> EntityType {
+    if version >= Version::V1_14 {
+        return to_entity_type(id, version);
+    }
+    match id {
+        1 => EntityType::Boat,
+        2 => EntityType::DroppedItem,
+        3 => EntityType::AreaEffectCloud,
+        10 => EntityType::Minecart,
+        50 => EntityType::PrimedTnt,
+        51 => EntityType::EnderCrystal,
+        60 => EntityType::Arrow,
+        61 => EntityType::Snowball,
+        62 => EntityType::Egg,
+        63 => EntityType::Fireball,
+        64 => EntityType::SmallFireball,
+        65 => EntityType::EnderPearl,
+        66 => EntityType::WitherSkull,
+        67 => EntityType::ShulkerBullet,
+        68 => EntityType::LlamaSpit,
+        70 => EntityType::FallingBlock,
+        71 => EntityType::ItemFrame,
+        72 => EntityType::EnderSignal,
+        73 => EntityType::SplashPotion,
+        75 => EntityType::ThrownExpBottle,
+        76 => EntityType::Firework,
+        77 => EntityType::LeashHitch,
+        78 => EntityType::ArmorStand,
+        79 => EntityType::EvokerFangs,
+        90 => EntityType::FishingHook,
+        91 => EntityType::SpectralArrow,
+        93 => EntityType::DragonFireball,
+        94 => EntityType::Trident,
+        _ => EntityType::Unknown,
+    }
+}
+
+/// The entity metadata fields the client makes use of.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetadataField {
+    Flags,
+    CustomName,
+    CustomNameVisible,
+    Pose,
+    Baby,
+    SlimeSize,
+    Item,
+    ItemRotation,
+    Fuse,
+}
+
+/// Returns the index of the metadata field for the entity type, or `None`
+/// if the entity doesn't have the field in that version.
+pub fn metadata_index(
+    field: MetadataField,
+    entity_type: EntityType,
+    version: Version,
+) -> Option<i32> {
+    match version {
+        Version::V1_7 => mapping1_7_10::metadata_index(field, entity_type),
+        Version::V1_8 => mapping1_8_8::metadata_index(field, entity_type),
+        Version::V1_9 => mapping1_9_4::metadata_index(field, entity_type),
+        Version::V1_10 => mapping1_10_2::metadata_index(field, entity_type),
+        Version::V1_11 => mapping1_11_2::metadata_index(field, entity_type),
+        Version::V1_12 => mapping1_12_2::metadata_index(field, entity_type),
+        Version::V1_13 => mapping1_13_2::metadata_index(field, entity_type),
+        Version::V1_14 => mapping1_14_4::metadata_index(field, entity_type),
+        Version::V1_15 => mapping1_15_2::metadata_index(field, entity_type),
+        Version::V1_16 => mapping1_16_5::metadata_index(field, entity_type),
+        _ => None,
+    }
+}
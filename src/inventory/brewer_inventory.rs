@@ -0,0 +1,205 @@
+use crate::inventory::container::{ContainerBase, Layout};
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::{Inventory, InventoryType, Item, SlotKind};
+use crate::render::hud::HudContext;
+use crate::render::inventory::InventoryWindow;
+use crate::render::Renderer;
+use crate::ui::Container;
+use leafish_protocol::protocol::Version;
+use parking_lot::RwLock;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// How far the bubbles have risen, they loop every 14 ticks of brewing.
+const BUBBLES: [f64; 7] = [29.0, 24.0, 20.0, 16.0, 11.0, 6.0, 0.0];
+
+/// The brewing stand's window. Slots 0-2 are the bottles, 3 the ingredient
+/// and since 1.9 slot 4 is the blaze powder fuel.
+pub struct BrewerInventory {
+    base: ContainerBase,
+    version: Version,
+    brew_time: i16,
+    fuel: i16,
+    progress_dirty: bool,
+}
+
+impl BrewerInventory {
+    pub fn new(
+        version: Version,
+        id: i8,
+        name: String,
+        player_inventory: Arc<RwLock<PlayerInventory>>,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Self {
+        let slots = if version < Version::V1_9 {
+            vec![(56.0, 46.0), (79.0, 53.0), (102.0, 46.0), (79.0, 17.0)]
+        } else {
+            vec![
+                (56.0, 51.0),
+                (79.0, 58.0),
+                (102.0, 51.0),
+                (79.0, 17.0),
+                (17.0, 17.0),
+            ]
+        };
+        let layout = Layout::new(
+            "minecraft:gui/container/brewing_stand",
+            176.0,
+            166.0,
+            slots,
+            84.0,
+        );
+        BrewerInventory {
+            base: ContainerBase::new(id, name, layout, player_inventory, hud_context),
+            version,
+            brew_time: 0,
+            fuel: 0,
+            progress_dirty: true,
+        }
+    }
+
+    fn draw_progress(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        let modern = self.version >= Version::V1_9;
+        if modern {
+            let fuel = ((18 * self.fuel as i32 + 19) / 20).clamp(0, 18) as f64;
+            if fuel > 0.0 {
+                let image = self.base.draw_image(
+                    renderer,
+                    ui_container,
+                    (176.0, 29.0, fuel, 4.0),
+                    (60.0, 44.0),
+                );
+                inventory_window.elements[2].push(image);
+            }
+        }
+        if self.brew_time > 0 {
+            // The brew time counts down from 400 ticks
+            let arrow = (28.0 * (1.0 - self.brew_time as f64 / 400.0)).floor();
+            if arrow > 0.0 {
+                let image = self.base.draw_image(
+                    renderer,
+                    ui_container,
+                    (176.0, 0.0, 9.0, arrow),
+                    (97.0, 16.0),
+                );
+                inventory_window.elements[2].push(image);
+            }
+            let bubbles = BUBBLES[(self.brew_time as usize / 2) % BUBBLES.len()];
+            if bubbles > 0.0 {
+                let x = if modern { 63.0 } else { 65.0 };
+                let image = self.base.draw_image(
+                    renderer,
+                    ui_container,
+                    (185.0, 29.0 - bubbles, 12.0, bubbles),
+                    (x, 14.0 + 29.0 - bubbles),
+                );
+                inventory_window.elements[2].push(image);
+            }
+        }
+    }
+}
+
+impl Inventory for BrewerInventory {
+    fn size(&self) -> i16 {
+        self.base.size()
+    }
+
+    fn id(&self) -> i8 {
+        self.base.id
+    }
+
+    fn name(&self) -> Option<&String> {
+        Some(&self.base.name)
+    }
+
+    fn get_item(&self, slot: i16) -> &Option<Item> {
+        self.base.get_item(slot)
+    }
+
+    fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.base.get_item_mut(slot)
+    }
+
+    fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        self.base.set_item(slot, item);
+    }
+
+    fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.init(renderer, ui_container, inventory_window);
+        self.progress_dirty = true;
+    }
+
+    fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.tick(renderer, ui_container, inventory_window);
+        if self.progress_dirty {
+            self.progress_dirty = false;
+            inventory_window.elements.get_mut(2).unwrap().clear();
+            self.draw_progress(renderer, ui_container, inventory_window);
+        }
+    }
+
+    fn close(&mut self, _inventory_window: &mut InventoryWindow) {
+        self.base.close();
+    }
+
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.base.slot_at(x, y)
+    }
+
+    fn slot_kind(&self, slot: i16) -> SlotKind {
+        if slot < self.base.container_size() {
+            SlotKind::Restricted
+        } else {
+            SlotKind::Normal
+        }
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        if slot < self.base.container_size() {
+            Some(self.base.quick_move(slot))
+        } else {
+            // Whether it's a potion or an ingredient decides where it goes
+            None
+        }
+    }
+
+    fn resize(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.resize(renderer, ui_container, inventory_window);
+        self.progress_dirty = true;
+    }
+
+    fn ty(&self) -> InventoryType {
+        InventoryType::Brewer
+    }
+
+    fn handle_property(&mut self, property: i16, value: i16) {
+        match property {
+            0 => self.brew_time = value,
+            1 => self.fuel = value,
+            _ => return,
+        }
+        self.progress_dirty = true;
+    }
+}
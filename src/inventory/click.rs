@@ -0,0 +1,1008 @@
+//! Clicks in inventory windows. They're carried out right away the way the
+//! server is going to do them, and undone again should it refuse one.
+
+use crate::inventory::{Inventory, InventoryContext, Item, SlotKind};
+use leafish_protocol::item::Stack;
+
+/// The slot vanilla uses for clicks beside the window.
+pub const OUTSIDE: i16 = -999;
+
+/// Clicks that are still waiting for the server to confirm them are
+/// forgotten past this many, it won't answer them anymore.
+const MAX_PENDING: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DragButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl DragButton {
+    fn ordinal(self) -> u8 {
+        match self {
+            DragButton::Left => 0,
+            DragButton::Right => 1,
+            DragButton::Middle => 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Click {
+    /// Picks up the slot's items, or puts down, adds or swaps the cursor's.
+    Left(i16),
+    /// Picks up half of the slot's items or puts down one of the cursor's.
+    Right(i16),
+    /// Moves the slot's items over to the other part of the window.
+    Shift(i16),
+    /// Swaps the slot's items with the ones in a slot of the hotbar.
+    Hotbar(i16, u8),
+    /// Puts a full stack of the slot's item on the cursor, in creative only.
+    Middle(i16),
+    /// Drops one of the slot's items, or all of them.
+    Drop(i16, bool),
+    /// Gathers the items like the cursor's from the whole window.
+    PickupAll(i16),
+    /// Drops all of the cursor's items beside the window, or one of them.
+    Outside(bool),
+    /// Spreads the cursor's items evenly over the slots, puts one in each
+    /// or, in creative, fills each with a full stack.
+    Drag(DragButton, Vec<i16>),
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Pickup = 0,
+    QuickMove = 1,
+    Swap = 2,
+    Clone = 3,
+    Throw = 4,
+    QuickCraft = 5,
+    PickupAll = 6,
+}
+
+/// One `ClickWindow` packet.
+pub struct WindowClick {
+    pub id: u8,
+    pub slot: i16,
+    pub button: u8,
+    pub mode: u8,
+    pub action_number: i16,
+    /// What the slot held before the click for the modes where the server
+    /// checks it. When it disagrees, the server refuses the click and sends
+    /// the whole window again, which is also how clicks whose outcome only
+    /// the server knows get the window back in sync.
+    pub clicked_item: Option<Stack>,
+}
+
+/// How things were before a click, to go back to if it's refused.
+pub struct Transaction {
+    window: i8,
+    actions: Vec<i16>,
+    items: Vec<Option<Item>>,
+    cursor: Option<Item>,
+}
+
+struct Packet {
+    slot: i16,
+    button: u8,
+    mode: Mode,
+    clicked_item: Option<Stack>,
+}
+
+impl Packet {
+    fn new(slot: i16, button: u8, mode: Mode, clicked_item: Option<Stack>) -> Self {
+        Packet {
+            slot,
+            button,
+            mode,
+            clicked_item,
+        }
+    }
+}
+
+impl InventoryContext {
+    /// Carries out the click on the window and returns the packets telling
+    /// the server about it, none if it does nothing.
+    pub fn click(
+        &mut self,
+        inventory: &mut dyn Inventory,
+        click: Click,
+        creative: bool,
+    ) -> Vec<WindowClick> {
+        let items = (0..inventory.size())
+            .map(|slot| inventory.get_item(slot).clone())
+            .collect();
+        let cursor = self.cursor.clone();
+        let packets = self.predict(inventory, click, creative);
+        if packets.is_empty() {
+            return vec![];
+        }
+        let id = inventory.id();
+        let clicks: Vec<WindowClick> = packets
+            .into_iter()
+            .map(|packet| {
+                self.action_number = self.action_number.wrapping_add(1);
+                WindowClick {
+                    id: id as u8,
+                    slot: packet.slot,
+                    button: packet.button,
+                    mode: packet.mode as u8,
+                    action_number: self.action_number,
+                    clicked_item: packet.clicked_item,
+                }
+            })
+            .collect();
+        if self.transactions.len() >= MAX_PENDING {
+            self.transactions.remove(0);
+        }
+        self.transactions.push(Transaction {
+            window: id,
+            actions: clicks.iter().map(|click| click.action_number).collect(),
+            items,
+            cursor,
+        });
+        clicks
+    }
+
+    /// Carries out a click in the creative inventory. The server doesn't
+    /// keep track of the cursor there and is only told which of the
+    /// player's slots changed, returned as the slot and its new item, with
+    /// items to drop going to slot -1.
+    pub fn creative_click(
+        &mut self,
+        inventory: &mut dyn Inventory,
+        click: Click,
+    ) -> Vec<(i16, Option<Stack>)> {
+        let items: Vec<Option<Item>> = (0..inventory.size())
+            .map(|slot| inventory.get_item(slot).clone())
+            .collect();
+        let mut dropped = vec![];
+        let slot = match click {
+            Click::Left(slot)
+            | Click::Right(slot)
+            | Click::Shift(slot)
+            | Click::Hotbar(slot, _)
+            | Click::Middle(slot)
+            | Click::Drop(slot, _)
+            | Click::PickupAll(slot) => Some(slot),
+            Click::Outside(_) | Click::Drag(_, _) => None,
+        };
+        match (slot.map(|slot| inventory.slot_kind(slot)), click) {
+            (Some(SlotKind::Infinite), click) => {
+                self.copy(inventory, click, &mut dropped);
+            }
+            (Some(SlotKind::Destroy), Click::Shift(_)) => {
+                for slot in 0..inventory.size() {
+                    if !is_creative(&inventory.slot_kind(slot)) {
+                        inventory.set_item(slot, None);
+                    }
+                }
+            }
+            (Some(SlotKind::Destroy), Click::Left(_) | Click::Right(_)) => {
+                self.cursor = None;
+            }
+            (Some(SlotKind::Destroy), _) => {}
+            (_, Click::Outside(all)) => {
+                if let Some(held) = self.cursor.take() {
+                    let count = if all { held.stack.count } else { 1 };
+                    dropped.extend(with_count(&held, count));
+                    self.cursor = with_count(&held, held.stack.count - count);
+                }
+            }
+            (_, Click::Drop(slot, all)) => {
+                let item = inventory.get_item(slot).clone();
+                if let Some(item) = item.filter(|_| self.cursor.is_none()) {
+                    let count = if all { item.stack.count } else { 1 };
+                    dropped.extend(with_count(&item, count));
+                    take(inventory, slot, count);
+                }
+            }
+            (_, click) => {
+                self.predict(inventory, click, true);
+            }
+        }
+        let mut changes: Vec<(i16, Option<Stack>)> = items
+            .iter()
+            .enumerate()
+            .map(|(slot, item)| (slot as i16, item))
+            .filter(|&(slot, item)| {
+                !is_creative(&inventory.slot_kind(slot))
+                    && !same_item(item, inventory.get_item(slot))
+            })
+            .map(|(slot, _)| {
+                (
+                    slot,
+                    inventory
+                        .get_item(slot)
+                        .as_ref()
+                        .map(|item| item.stack.clone()),
+                )
+            })
+            .collect();
+        changes.extend(dropped.into_iter().map(|item| (-1, Some(item.stack))));
+        changes
+    }
+
+    /// Handles the server's answer to one of our clicks. A refused click is
+    /// undone along with the ones made after it, the server sends the whole
+    /// window again anyway. The window's items from before the refused
+    /// click are handed back to be put in place once the context is
+    /// unlocked, as the inventory is locked after it.
+    pub fn confirm_transaction(
+        &mut self,
+        window: i8,
+        action_number: i16,
+        accepted: bool,
+    ) -> Option<Vec<Option<Item>>> {
+        let index = self.transactions.iter().position(|transaction| {
+            transaction.window == window && transaction.actions.contains(&action_number)
+        })?;
+        if accepted {
+            if self.transactions[index].actions.last() == Some(&action_number) {
+                self.transactions.drain(..=index);
+            }
+            return None;
+        }
+        let transaction = self.transactions.remove(index);
+        self.transactions.truncate(index);
+        self.cursor = transaction.cursor;
+        self.cursor_dirty = true;
+        Some(transaction.items)
+    }
+
+    /// Forgets about unconfirmed clicks when their window goes away.
+    pub fn clear_transactions(&mut self) {
+        self.transactions.clear();
+    }
+
+    fn predict(
+        &mut self,
+        inventory: &mut dyn Inventory,
+        click: Click,
+        creative: bool,
+    ) -> Vec<Packet> {
+        match click {
+            Click::Left(slot) => vec![self.pickup(inventory, slot, false)],
+            Click::Right(slot) => vec![self.pickup(inventory, slot, true)],
+            Click::Shift(slot) => vec![quick_move(inventory, slot)],
+            Click::Hotbar(slot, index) => vec![swap(inventory, slot, index)],
+            Click::Middle(slot) => self.clone_stack(inventory, slot, creative),
+            Click::Drop(slot, all) => self.throw(inventory, slot, all),
+            Click::PickupAll(slot) => vec![self.collect(inventory, slot)],
+            Click::Outside(all) => self.drop_cursor(all),
+            Click::Drag(button, slots) => self.drag(inventory, button, slots, creative),
+        }
+    }
+
+    /// Clicks on the creative inventory's item list hand out copies of the
+    /// item, clicking it with anything else deletes what's on the cursor.
+    fn copy(&mut self, inventory: &mut dyn Inventory, click: Click, dropped: &mut Vec<Item>) {
+        let (slot, item) = match click {
+            Click::Left(slot)
+            | Click::Right(slot)
+            | Click::Shift(slot)
+            | Click::Hotbar(slot, _)
+            | Click::Middle(slot)
+            | Click::Drop(slot, _)
+            | Click::PickupAll(slot) => (slot, inventory.get_item(slot).clone()),
+            Click::Outside(_) | Click::Drag(_, _) => return,
+        };
+        let item = match item {
+            Some(item) => item,
+            None => {
+                if !matches!(
+                    click,
+                    Click::Middle(_) | Click::Hotbar(_, _) | Click::Drop(_, _)
+                ) {
+                    self.cursor = None;
+                }
+                return;
+            }
+        };
+        let max = item.material.max_stack_size();
+        match click {
+            Click::Hotbar(_, index) => {
+                let hotbar = inventory.hotbar_slot(index);
+                if hotbar != slot {
+                    inventory.set_item(hotbar, with_count(&item, max));
+                }
+            }
+            Click::Middle(_) => {
+                if self.cursor.is_none() {
+                    self.cursor = with_count(&item, max);
+                }
+            }
+            Click::Drop(_, all) => dropped.extend(with_count(&item, if all { max } else { 1 })),
+            click => {
+                self.cursor = match self.cursor.take() {
+                    Some(held) if stackable(&held, &item) => match click {
+                        Click::Right(_) => with_count(&held, held.stack.count - 1),
+                        Click::Shift(_) => with_count(&held, max),
+                        _ => with_count(&held, (held.stack.count + 1).min(max)),
+                    },
+                    Some(_) => None,
+                    None => with_count(
+                        &item,
+                        if let Click::Shift(_) = click {
+                            max
+                        } else {
+                            item.stack.count
+                        },
+                    ),
+                };
+            }
+        }
+    }
+
+    fn pickup(&mut self, inventory: &mut dyn Inventory, slot: i16, right: bool) -> Packet {
+        let kind = inventory.slot_kind(slot);
+        let current = inventory.get_item(slot).clone();
+        let clicked_item = current.as_ref().map(|item| item.stack.clone());
+        match (current, self.cursor.take()) {
+            (None, None) => {}
+            (Some(item), None) => {
+                let count = if right && !is_output(&kind) {
+                    (item.stack.count + 1) / 2
+                } else {
+                    item.stack.count
+                };
+                self.cursor = with_count(&item, count);
+                take(inventory, slot, count);
+            }
+            (current, Some(held)) => {
+                let same = current.as_ref().is_some_and(|item| stackable(item, &held));
+                match accepts(&kind, &held) {
+                    None if !same => {
+                        let clicked_item = Some(held.stack.clone());
+                        self.cursor = Some(held);
+                        return Packet::new(slot, right as u8, Mode::Pickup, clicked_item);
+                    }
+                    Some(false) => {
+                        // Items the slot won't take can still be added to
+                        // from it, like stacking up crafting results
+                        let count = current.as_ref().map_or(0, |item| item.stack.count);
+                        if same && held.stack.count + count <= held.material.max_stack_size() {
+                            self.cursor = with_count(&held, held.stack.count + count);
+                            take(inventory, slot, count);
+                        } else {
+                            self.cursor = Some(held);
+                        }
+                    }
+                    _ => {
+                        let limit = limit(&kind, &held);
+                        match current {
+                            Some(item) if !same => {
+                                if held.stack.count <= limit {
+                                    inventory.set_item(slot, Some(held));
+                                    self.cursor = Some(item);
+                                } else {
+                                    self.cursor = Some(held);
+                                }
+                            }
+                            current => {
+                                let count = current.as_ref().map_or(0, |item| item.stack.count);
+                                let moved = (if right { 1 } else { held.stack.count })
+                                    .min(limit - count)
+                                    .max(0);
+                                inventory.set_item(slot, with_count(&held, count + moved));
+                                self.cursor = with_count(&held, held.stack.count - moved);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Packet::new(slot, right as u8, Mode::Pickup, clicked_item)
+    }
+
+    fn clone_stack(&mut self, inventory: &dyn Inventory, slot: i16, creative: bool) -> Vec<Packet> {
+        if !creative {
+            return vec![];
+        }
+        if self.cursor.is_none() {
+            if let Some(item) = inventory.get_item(slot) {
+                self.cursor = with_count(item, item.material.max_stack_size());
+            }
+        }
+        vec![Packet::new(slot, 2, Mode::Clone, None)]
+    }
+
+    fn throw(&mut self, inventory: &mut dyn Inventory, slot: i16, all: bool) -> Vec<Packet> {
+        if self.cursor.is_some() {
+            return vec![];
+        }
+        let count = match inventory.get_item(slot) {
+            Some(item) if all => item.stack.count,
+            Some(_) => 1,
+            None => return vec![],
+        };
+        take(inventory, slot, count);
+        vec![Packet::new(slot, all as u8, Mode::Throw, None)]
+    }
+
+    fn collect(&mut self, inventory: &mut dyn Inventory, slot: i16) -> Packet {
+        let packet = Packet::new(slot, 0, Mode::PickupAll, None);
+        let mut held = match self.cursor.clone() {
+            Some(held) => held,
+            None => return packet,
+        };
+        let max = held.material.max_stack_size();
+        // Full stacks are only taken once the others are used up
+        for full in [false, true] {
+            for other in 0..inventory.size() {
+                if held.stack.count >= max {
+                    break;
+                }
+                if let SlotKind::CraftingResult(_) = inventory.slot_kind(other) {
+                    continue;
+                }
+                let item = match inventory.get_item(other) {
+                    Some(item) if stackable(item, &held) => item.clone(),
+                    _ => continue,
+                };
+                if !full && item.stack.count >= item.material.max_stack_size() {
+                    continue;
+                }
+                let moved = item.stack.count.min(max - held.stack.count);
+                held.stack.count += moved;
+                inventory.set_item(other, with_count(&item, item.stack.count - moved));
+            }
+        }
+        self.cursor = Some(held);
+        packet
+    }
+
+    fn drop_cursor(&mut self, all: bool) -> Vec<Packet> {
+        let held = match self.cursor.take() {
+            Some(held) => held,
+            None => return vec![],
+        };
+        if !all {
+            self.cursor = with_count(&held, held.stack.count - 1);
+        }
+        vec![Packet::new(OUTSIDE, !all as u8, Mode::Pickup, None)]
+    }
+
+    fn drag(
+        &mut self,
+        inventory: &mut dyn Inventory,
+        button: DragButton,
+        slots: Vec<i16>,
+        creative: bool,
+    ) -> Vec<Packet> {
+        let held = match self.cursor.clone() {
+            Some(held) => held,
+            None => return vec![],
+        };
+        if button == DragButton::Middle && !creative {
+            return vec![];
+        }
+        let slots: Vec<i16> = slots
+            .into_iter()
+            .filter(|&slot| can_drag_into(inventory, slot, &held))
+            .collect();
+        // Dragging over a single slot is a plain click, as in vanilla
+        match (slots.len(), button) {
+            (0, _) => return vec![],
+            (1, DragButton::Left) => return vec![self.pickup(inventory, slots[0], false)],
+            (1, DragButton::Right) => return vec![self.pickup(inventory, slots[0], true)],
+            _ => {}
+        }
+        let button_base = button.ordinal() * 4;
+        let mut packets = vec![Packet::new(OUTSIDE, button_base, Mode::QuickCraft, None)];
+        packets.extend(
+            slots
+                .iter()
+                .map(|&slot| Packet::new(slot, button_base + 1, Mode::QuickCraft, None)),
+        );
+        packets.push(Packet::new(
+            OUTSIDE,
+            button_base + 2,
+            Mode::QuickCraft,
+            None,
+        ));
+        let per_slot = match button {
+            DragButton::Left => held.stack.count / slots.len() as isize,
+            DragButton::Right => 1,
+            DragButton::Middle => held.material.max_stack_size(),
+        };
+        let mut left = held.stack.count;
+        for &slot in &slots {
+            let limit = limit(&inventory.slot_kind(slot), &held);
+            let existing = inventory
+                .get_item(slot)
+                .as_ref()
+                .map_or(0, |item| item.stack.count);
+            let count = (existing + per_slot).min(limit).max(existing);
+            left -= count - existing;
+            inventory.set_item(slot, with_count(&held, count));
+        }
+        if button != DragButton::Middle {
+            self.cursor = with_count(&held, left);
+        }
+        packets
+    }
+}
+
+/// Whether a drag with `item` on the cursor may put some of it into the
+/// slot.
+pub fn can_drag_into(inventory: &dyn Inventory, slot: i16, item: &Item) -> bool {
+    accepts(&inventory.slot_kind(slot), item) == Some(true)
+        && inventory
+            .get_item(slot)
+            .as_ref()
+            .is_none_or(|existing| stackable(existing, item))
+}
+
+fn quick_move(inventory: &mut dyn Inventory, slot: i16) -> Packet {
+    let nothing = Packet::new(slot, 0, Mode::QuickMove, None);
+    let item = match inventory.get_item(slot).clone() {
+        Some(item) => item,
+        None => return nothing,
+    };
+    let (targets, reverse) = match inventory.quick_move(slot) {
+        Some(targets) => targets,
+        // Claiming nothing moved gets the window sent again if it did
+        None => return nothing,
+    };
+    let targets: Vec<i16> = if reverse {
+        targets.rev().collect()
+    } else {
+        targets.collect()
+    };
+    let mut count = item.stack.count;
+    // Stacks of the same item are topped up before empty slots are used
+    for &target in &targets {
+        if let Some(existing) = inventory.get_item(target).clone() {
+            if stackable(&existing, &item) {
+                let limit = limit(&inventory.slot_kind(target), &item);
+                let moved = count.min(limit - existing.stack.count).max(0);
+                inventory.set_item(target, with_count(&existing, existing.stack.count + moved));
+                count -= moved;
+            }
+        }
+    }
+    for &target in &targets {
+        if count > 0
+            && inventory.get_item(target).is_none()
+            && accepts(&inventory.slot_kind(target), &item) == Some(true)
+        {
+            let moved = count.min(limit(&inventory.slot_kind(target), &item));
+            inventory.set_item(target, with_count(&item, moved));
+            count -= moved;
+        }
+    }
+    let moved = item.stack.count - count;
+    if moved == 0 {
+        return nothing;
+    }
+    take(inventory, slot, moved);
+    Packet::new(slot, 0, Mode::QuickMove, Some(item.stack))
+}
+
+fn swap(inventory: &mut dyn Inventory, slot: i16, index: u8) -> Packet {
+    let packet = Packet::new(slot, index, Mode::Swap, None);
+    let hotbar = inventory.hotbar_slot(index);
+    if hotbar == slot {
+        return packet;
+    }
+    let kind = inventory.slot_kind(slot);
+    let current = inventory.get_item(slot).clone();
+    match inventory.get_item(hotbar).clone() {
+        None => {
+            if let Some(item) = current {
+                inventory.set_item(hotbar, Some(item.clone()));
+                take(inventory, slot, item.stack.count);
+            }
+        }
+        Some(held) => match accepts(&kind, &held) {
+            Some(true) if held.stack.count <= limit(&kind, &held) => {
+                inventory.set_item(slot, Some(held));
+                inventory.set_item(hotbar, current);
+            }
+            None => return Packet::new(slot, index, Mode::Swap, Some(held.stack)),
+            _ => {}
+        },
+    }
+    packet
+}
+
+/// Takes items out of the slot, taking a crafting result uses up the grid.
+fn take(inventory: &mut dyn Inventory, slot: i16, count: isize) {
+    if let Some(item) = inventory.get_item(slot).clone() {
+        inventory.set_item(slot, with_count(&item, item.stack.count - count));
+    }
+    if let SlotKind::CraftingResult(grid) = inventory.slot_kind(slot) {
+        for slot in grid {
+            if let Some(item) = inventory.get_item(slot).clone() {
+                inventory.set_item(slot, with_count(&item, item.stack.count - 1));
+            }
+        }
+    }
+}
+
+/// Whether the item may be put into the slot, None if only the server
+/// knows.
+fn accepts(kind: &SlotKind, item: &Item) -> Option<bool> {
+    match kind {
+        SlotKind::Normal => Some(true),
+        SlotKind::Restricted => None,
+        SlotKind::Armor(slot) => Some(item.material.armor_slot() == Some(*slot)),
+        SlotKind::Output | SlotKind::CraftingResult(_) | SlotKind::Infinite | SlotKind::Destroy => {
+            Some(false)
+        }
+    }
+}
+
+/// Whether the slot belongs to the creative inventory itself rather than
+/// the player.
+fn is_creative(kind: &SlotKind) -> bool {
+    matches!(kind, SlotKind::Infinite | SlotKind::Destroy)
+}
+
+fn is_output(kind: &SlotKind) -> bool {
+    matches!(kind, SlotKind::Output | SlotKind::CraftingResult(_))
+}
+
+fn limit(kind: &SlotKind, item: &Item) -> isize {
+    match kind {
+        SlotKind::Armor(_) => 1,
+        _ => item.material.max_stack_size(),
+    }
+}
+
+fn stackable(a: &Item, b: &Item) -> bool {
+    a.stack.id == b.stack.id && a.stack.damage == b.stack.damage && a.stack.tag == b.stack.tag
+}
+
+fn same_item(a: &Option<Item>, b: &Option<Item>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => stackable(a, b) && a.stack.count == b.stack.count,
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+fn with_count(item: &Item, count: isize) -> Option<Item> {
+    if count <= 0 {
+        return None;
+    }
+    let mut item = item.clone();
+    item.stack.count = count;
+    Some(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::player_inventory::PlayerInventory;
+    use crate::inventory::Material;
+    use crate::render::hud::HudContext;
+    use leafish_protocol::protocol::Version;
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+
+    /// A fresh context and an empty 1.16 player inventory: the crafting
+    /// result and grid, armor from 5, the main inventory from 9 and the
+    /// hotbar from 36.
+    fn setup() -> (InventoryContext, PlayerInventory) {
+        let hud_context = Arc::new(RwLock::new(HudContext::new()));
+        (
+            InventoryContext::new(Version::V1_16, hud_context.clone()),
+            PlayerInventory::new(Version::V1_16, hud_context),
+        )
+    }
+
+    fn item(material: Material, count: isize) -> Option<Item> {
+        let id = match material {
+            Material::Stone => 1,
+            Material::Dirt => 3,
+            Material::Pumpkin => 86,
+            Material::DiamondHelmet => 310,
+            _ => 0,
+        };
+        Some(Item {
+            stack: Stack {
+                id,
+                count,
+                damage: None,
+                tag: None,
+            },
+            material,
+        })
+    }
+
+    fn count(item: &Option<Item>) -> isize {
+        item.as_ref().map_or(0, |item| item.stack.count)
+    }
+
+    /// The slot, button and mode of each packet.
+    fn sent(clicks: &[WindowClick]) -> Vec<(i16, u8, u8)> {
+        clicks
+            .iter()
+            .map(|click| (click.slot, click.button, click.mode))
+            .collect()
+    }
+
+    #[test]
+    fn left_click_picks_up_and_puts_down() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 10));
+        let clicks = context.click(&mut inventory, Click::Left(9), false);
+        assert_eq!(sent(&clicks), vec![(9, 0, 0)]);
+        assert_eq!(
+            clicks[0].clicked_item.as_ref().map(|stack| stack.count),
+            Some(10)
+        );
+        assert_eq!(count(&context.cursor), 10);
+        assert!(inventory.get_item(9).is_none());
+
+        context.click(&mut inventory, Click::Left(10), false);
+        assert!(context.cursor.is_none());
+        assert_eq!(count(inventory.get_item(10)), 10);
+    }
+
+    #[test]
+    fn right_click_splits_and_puts_down_one() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 5));
+        context.click(&mut inventory, Click::Right(9), false);
+        assert_eq!(count(&context.cursor), 3);
+        assert_eq!(count(inventory.get_item(9)), 2);
+
+        context.click(&mut inventory, Click::Right(10), false);
+        assert_eq!(count(&context.cursor), 2);
+        assert_eq!(count(inventory.get_item(10)), 1);
+    }
+
+    #[test]
+    fn left_click_adds_up_to_the_stack_size() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 60));
+        context.cursor = item(Material::Stone, 10);
+        context.click(&mut inventory, Click::Left(9), false);
+        assert_eq!(count(inventory.get_item(9)), 64);
+        assert_eq!(count(&context.cursor), 6);
+    }
+
+    #[test]
+    fn left_click_swaps_other_items() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 5));
+        context.cursor = item(Material::Dirt, 3);
+        context.click(&mut inventory, Click::Left(9), false);
+        assert!(matches!(
+            inventory.get_item(9).as_ref().map(|item| &item.material),
+            Some(Material::Dirt)
+        ));
+        assert!(matches!(
+            context.cursor.as_ref().map(|item| &item.material),
+            Some(Material::Stone)
+        ));
+    }
+
+    #[test]
+    fn armor_slots_take_one_matching_piece() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::DiamondHelmet, 1);
+        // The chestplate's slot
+        context.click(&mut inventory, Click::Left(6), false);
+        assert!(inventory.get_item(6).is_none());
+        assert_eq!(count(&context.cursor), 1);
+        context.click(&mut inventory, Click::Left(5), false);
+        assert_eq!(count(inventory.get_item(5)), 1);
+        assert!(context.cursor.is_none());
+
+        // Pumpkins stack to 64 but are worn one at a time
+        inventory.set_item(5, None);
+        context.cursor = item(Material::Pumpkin, 64);
+        context.click(&mut inventory, Click::Left(5), false);
+        assert_eq!(count(inventory.get_item(5)), 1);
+        assert_eq!(count(&context.cursor), 63);
+    }
+
+    #[test]
+    fn crafting_results_are_only_taken() {
+        let (mut context, mut inventory) = setup();
+        for slot in 1..5 {
+            inventory.set_item(slot, item(Material::Dirt, 2));
+        }
+        inventory.set_item(0, item(Material::Stone, 4));
+        context.click(&mut inventory, Click::Left(0), false);
+        assert_eq!(count(&context.cursor), 4);
+        for slot in 1..5 {
+            assert_eq!(count(inventory.get_item(slot)), 1);
+        }
+
+        // Clicking the result again stacks it onto the cursor
+        inventory.set_item(0, item(Material::Stone, 4));
+        context.click(&mut inventory, Click::Left(0), false);
+        assert_eq!(count(&context.cursor), 8);
+
+        // Anything else is kept on the cursor
+        inventory.set_item(0, item(Material::Dirt, 1));
+        context.click(&mut inventory, Click::Left(0), false);
+        assert_eq!(count(&context.cursor), 8);
+        assert_eq!(count(inventory.get_item(0)), 1);
+    }
+
+    #[test]
+    fn shift_click_tops_up_stacks_first() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 10));
+        inventory.set_item(40, item(Material::Stone, 60));
+        let clicks = context.click(&mut inventory, Click::Shift(9), false);
+        assert_eq!(sent(&clicks), vec![(9, 0, 1)]);
+        assert_eq!(count(inventory.get_item(40)), 64);
+        assert_eq!(count(inventory.get_item(36)), 6);
+        assert!(inventory.get_item(9).is_none());
+    }
+
+    #[test]
+    fn shift_click_puts_on_armor() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::DiamondHelmet, 1));
+        context.click(&mut inventory, Click::Shift(9), false);
+        assert_eq!(count(inventory.get_item(5)), 1);
+        assert!(inventory.get_item(9).is_none());
+    }
+
+    #[test]
+    fn hotbar_key_swaps() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 5));
+        inventory.set_item(38, item(Material::Dirt, 3));
+        let clicks = context.click(&mut inventory, Click::Hotbar(9, 2), false);
+        assert_eq!(sent(&clicks), vec![(9, 2, 2)]);
+        assert_eq!(count(inventory.get_item(9)), 3);
+        assert_eq!(count(inventory.get_item(38)), 5);
+    }
+
+    #[test]
+    fn left_drag_splits_evenly() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::Stone, 10);
+        let clicks = context.click(
+            &mut inventory,
+            Click::Drag(DragButton::Left, vec![9, 10, 11]),
+            false,
+        );
+        assert_eq!(
+            sent(&clicks),
+            vec![
+                (OUTSIDE, 0, 5),
+                (9, 1, 5),
+                (10, 1, 5),
+                (11, 1, 5),
+                (OUTSIDE, 2, 5),
+            ]
+        );
+        for slot in 9..12 {
+            assert_eq!(count(inventory.get_item(slot)), 3);
+        }
+        // What doesn't divide evenly stays on the cursor
+        assert_eq!(count(&context.cursor), 1);
+    }
+
+    #[test]
+    fn right_drag_puts_one_in_each() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::Stone, 10);
+        let clicks = context.click(
+            &mut inventory,
+            Click::Drag(DragButton::Right, vec![9, 10]),
+            false,
+        );
+        assert_eq!(
+            sent(&clicks),
+            vec![(OUTSIDE, 4, 5), (9, 5, 5), (10, 5, 5), (OUTSIDE, 6, 5)]
+        );
+        assert_eq!(count(inventory.get_item(9)), 1);
+        assert_eq!(count(inventory.get_item(10)), 1);
+        assert_eq!(count(&context.cursor), 8);
+    }
+
+    #[test]
+    fn middle_drag_fills_in_creative_only() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::Stone, 1);
+        let drag = || Click::Drag(DragButton::Middle, vec![9, 10]);
+        assert!(context.click(&mut inventory, drag(), false).is_empty());
+        assert!(inventory.get_item(9).is_none());
+
+        let clicks = context.click(&mut inventory, drag(), true);
+        assert_eq!(
+            sent(&clicks),
+            vec![(OUTSIDE, 8, 5), (9, 9, 5), (10, 9, 5), (OUTSIDE, 10, 5)]
+        );
+        assert_eq!(count(inventory.get_item(9)), 64);
+        assert_eq!(count(inventory.get_item(10)), 64);
+        assert_eq!(count(&context.cursor), 1);
+    }
+
+    #[test]
+    fn double_click_takes_full_stacks_last() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::Stone, 10);
+        inventory.set_item(9, item(Material::Stone, 64));
+        inventory.set_item(10, item(Material::Stone, 30));
+        inventory.set_item(11, item(Material::Stone, 20));
+        inventory.set_item(12, item(Material::Dirt, 20));
+        let clicks = context.click(&mut inventory, Click::PickupAll(10), false);
+        assert_eq!(sent(&clicks), vec![(10, 0, 6)]);
+        assert_eq!(count(&context.cursor), 64);
+        assert_eq!(count(inventory.get_item(9)), 60);
+        assert!(inventory.get_item(10).is_none());
+        assert!(inventory.get_item(11).is_none());
+        assert_eq!(count(inventory.get_item(12)), 20);
+    }
+
+    #[test]
+    fn refused_click_is_undone_with_later_ones() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 10));
+        let first = context.click(&mut inventory, Click::Left(9), false);
+        let second = context.click(&mut inventory, Click::Left(10), false);
+
+        let items = context
+            .confirm_transaction(0, first[0].action_number, false)
+            .unwrap();
+        assert_eq!(count(&items[9]), 10);
+        assert!(items[10].is_none());
+        assert!(context.cursor.is_none());
+        assert!(context.cursor_dirty);
+        // The later click went along with it
+        assert!(context
+            .confirm_transaction(0, second[0].action_number, false)
+            .is_none());
+    }
+
+    #[test]
+    fn accepted_click_is_forgotten() {
+        let (mut context, mut inventory) = setup();
+        inventory.set_item(9, item(Material::Stone, 10));
+        let first = context.click(&mut inventory, Click::Left(9), false);
+        let second = context.click(&mut inventory, Click::Left(10), false);
+
+        assert!(context
+            .confirm_transaction(0, first[0].action_number, true)
+            .is_none());
+        assert!(context
+            .confirm_transaction(0, first[0].action_number, false)
+            .is_none());
+        // Refusing the second goes back to how things were after the first
+        let items = context
+            .confirm_transaction(0, second[0].action_number, false)
+            .unwrap();
+        assert!(items[9].is_none());
+        assert!(items[10].is_none());
+        assert_eq!(count(&context.cursor), 10);
+    }
+
+    #[test]
+    fn drag_is_kept_until_its_last_packet_is_accepted() {
+        let (mut context, mut inventory) = setup();
+        context.cursor = item(Material::Stone, 10);
+        let clicks = context.click(
+            &mut inventory,
+            Click::Drag(DragButton::Left, vec![9, 10]),
+            false,
+        );
+        context.confirm_transaction(0, clicks[0].action_number, true);
+        assert!(context
+            .confirm_transaction(0, clicks[2].action_number, false)
+            .is_some());
+        assert_eq!(count(&context.cursor), 10);
+
+        let clicks = context.click(
+            &mut inventory,
+            Click::Drag(DragButton::Left, vec![9, 10]),
+            false,
+        );
+        context.confirm_transaction(0, clicks[3].action_number, true);
+        assert!(context
+            .confirm_transaction(0, clicks[0].action_number, false)
+            .is_none());
+    }
+}
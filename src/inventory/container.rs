@@ -0,0 +1,458 @@
+//! The parts every block container window has in common: its own slots with
+//! the player's inventory below them, drawn from a vanilla GUI texture.
+
+use crate::inventory::click::OUTSIDE;
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::{Inventory, InventoryType, Item, Slot};
+use crate::render::hud::{Hud, HudContext};
+use crate::render::inventory::InventoryWindow;
+use crate::render::Renderer;
+use crate::ui;
+use crate::ui::{Container, HAttach, ImageRef, VAttach};
+use leafish_protocol::protocol::packet;
+use parking_lot::RwLock;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Where things are on a container's background texture, in pixels of the
+/// texture like the vanilla GUI code uses them.
+pub struct Layout {
+    pub texture: &'static str,
+    pub width: f64,
+    pub height: f64,
+    /// The top-left corners of the container's own slots.
+    pub slots: Vec<(f64, f64)>,
+    /// The top of the player's main inventory, the hotbar is 58 below it.
+    pub player_y: f64,
+    /// None centres the title like furnaces and dispensers do.
+    pub title_x: Option<f64>,
+    /// Texture y, height and GUI y of each strip of the background. Chests
+    /// stitch theirs together from two parts of the texture.
+    pub background: Vec<(f64, f64, f64)>,
+}
+
+impl Layout {
+    pub fn new(
+        texture: &'static str,
+        width: f64,
+        height: f64,
+        slots: Vec<(f64, f64)>,
+        player_y: f64,
+    ) -> Self {
+        Layout {
+            texture,
+            width,
+            height,
+            slots,
+            player_y,
+            title_x: None,
+            background: vec![(0.0, height, 0.0)],
+        }
+    }
+
+    /// Chests, large chests, ender chests, shulker boxes and minecarts with
+    /// chests, which all only differ in their number of rows.
+    pub fn chest(rows: u8) -> Self {
+        let rows = rows.clamp(1, 6) as f64;
+        let mut slots = vec![];
+        for y in 0..rows as usize {
+            for x in 0..9 {
+                slots.push((8.0 + x as f64 * 18.0, 18.0 + y as f64 * 18.0));
+            }
+        }
+        let top = rows * 18.0 + 17.0;
+        let mut layout = Layout::new(
+            "minecraft:gui/container/generic_54",
+            176.0,
+            top + 97.0,
+            slots,
+            top + 14.0,
+        );
+        layout.title_x = Some(8.0);
+        // The texture has room for six rows, the player's inventory is
+        // moved up under however many this one has.
+        layout.background = vec![(0.0, top, 0.0), (126.0, 96.0, top)];
+        layout
+    }
+
+    /// Dispensers and droppers, which share the 3x3 window.
+    pub fn dropper() -> Self {
+        let mut slots = vec![];
+        for y in 0..3 {
+            for x in 0..3 {
+                slots.push((62.0 + x as f64 * 18.0, 17.0 + y as f64 * 18.0));
+            }
+        }
+        Layout::new(
+            "minecraft:gui/container/dispenser",
+            176.0,
+            166.0,
+            slots,
+            84.0,
+        )
+    }
+
+    pub fn hopper() -> Self {
+        let slots = (0..5).map(|x| (44.0 + x as f64 * 18.0, 20.0)).collect();
+        let mut layout = Layout::new("minecraft:gui/container/hopper", 176.0, 133.0, slots, 51.0);
+        layout.title_x = Some(8.0);
+        layout
+    }
+
+    /// Turns a top-left corner in texture pixels into the offset of the
+    /// element's centre from the centre of the screen.
+    pub fn center(
+        &self,
+        icon_scale: f64,
+        (x, y): (f64, f64),
+        (width, height): (f64, f64),
+    ) -> (f64, f64) {
+        (
+            (x + width / 2.0 - self.width / 2.0) * icon_scale,
+            (y + height / 2.0 - self.height / 2.0) * icon_scale,
+        )
+    }
+
+    /// The slot at a point given like `center` returns them, or `OUTSIDE`
+    /// if the point is beside the window.
+    pub fn slot_at(&self, slots: &[Slot], icon_scale: f64, x: f64, y: f64) -> Option<i16> {
+        if let Some(slot) = slots.iter().position(|slot| slot.contains(x, y)) {
+            return Some(slot as i16);
+        }
+        if x.abs() > self.width / 2.0 * icon_scale || y.abs() > self.height / 2.0 * icon_scale {
+            Some(OUTSIDE)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct ContainerBase {
+    pub id: i8,
+    pub name: String,
+    pub slots: Vec<Slot>,
+    pub dirty: bool,
+    layout: Layout,
+    icon_scale: f64,
+    container_size: usize,
+    player_inventory: Arc<RwLock<PlayerInventory>>,
+    hud_context: Arc<RwLock<HudContext>>,
+}
+
+impl ContainerBase {
+    pub fn new(
+        id: i8,
+        name: String,
+        mut layout: Layout,
+        player_inventory: Arc<RwLock<PlayerInventory>>,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Self {
+        let container_size = layout.slots.len();
+        for y in 0..3 {
+            for x in 0..9 {
+                layout
+                    .slots
+                    .push((8.0 + x as f64 * 18.0, layout.player_y + y as f64 * 18.0));
+            }
+        }
+        for x in 0..9 {
+            layout
+                .slots
+                .push((8.0 + x as f64 * 18.0, layout.player_y + 58.0));
+        }
+        let mut slots: Vec<Slot> = layout
+            .slots
+            .iter()
+            .map(|_| Slot::new(0.0, 0.0, 0.0))
+            .collect();
+        // Show what the player is carrying until the server sends the
+        // window's contents.
+        {
+            let player_inventory = player_inventory.read();
+            for (i, slot) in slots.iter_mut().skip(container_size).enumerate() {
+                slot.item = player_inventory.get_item(9 + i as i16).clone();
+            }
+        }
+        ContainerBase {
+            id,
+            name,
+            slots,
+            dirty: true,
+            layout,
+            icon_scale: 0.0,
+            container_size,
+            player_inventory,
+            hud_context,
+        }
+    }
+
+    pub fn size(&self) -> i16 {
+        self.slots.len() as i16
+    }
+
+    pub fn container_size(&self) -> i16 {
+        self.container_size as i16
+    }
+
+    pub fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.layout.slot_at(&self.slots, self.icon_scale, x, y)
+    }
+
+    /// Shift-clicks in most containers move items between the container,
+    /// which fills up front to back, and the player's inventory, which
+    /// fills up from the end of the hotbar.
+    pub fn quick_move(&self, slot: i16) -> (Range<i16>, bool) {
+        let container = self.container_size();
+        if slot < container {
+            (container..self.size(), true)
+        } else {
+            (0..container, false)
+        }
+    }
+
+    pub fn get_item(&self, slot: i16) -> &Option<Item> {
+        &self.slots[slot as usize].item
+    }
+
+    pub fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.dirty = true;
+        &mut self.slots[slot as usize].item
+    }
+
+    /// Slots past the container's own are the player's main inventory and
+    /// hotbar, so they're kept in sync with the player inventory (and so
+    /// with the HUD's hotbar).
+    pub fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        if let Some(player_slot) = (slot as usize).checked_sub(self.container_size) {
+            self.player_inventory
+                .write()
+                .set_item(9 + player_slot as i16, item.clone());
+        }
+        self.slots[slot as usize].item = item;
+        self.dirty = true;
+    }
+
+    /// Draws the background and the labels into the first element list and
+    /// makes room for the items in the second and any progress bars in the
+    /// third.
+    pub fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        inventory_window.elements.push(vec![]);
+        for &(v, height, y) in &self.layout.background {
+            let image = self.draw_image(
+                renderer,
+                ui_container,
+                (0.0, v, self.layout.width, height),
+                (0.0, y),
+            );
+            inventory_window.elements[0].push(image);
+        }
+        inventory_window.text_elements.push(vec![]);
+        let title_x = self.layout.title_x.unwrap_or_else(|| {
+            (self.layout.width - renderer.ui.size_of_string(&self.name) / 2.0) / 2.0
+        });
+        let title = self.name.clone();
+        self.draw_text(
+            renderer,
+            ui_container,
+            inventory_window,
+            &title,
+            (title_x, 6.0),
+        );
+        self.draw_text(
+            renderer,
+            ui_container,
+            inventory_window,
+            "Inventory",
+            (8.0, self.layout.height - 96.0 + 2.0),
+        );
+        inventory_window.elements.push(vec![]);
+        inventory_window.elements.push(vec![]);
+        self.update_icons(renderer);
+    }
+
+    pub fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        if self.dirty {
+            self.dirty = false;
+            inventory_window.elements.get_mut(1).unwrap().clear();
+            for slot in self.slots.iter() {
+                if let Some(item) = slot.item.as_ref() {
+                    inventory_window.draw_item(item, slot.x, slot.y, 1, ui_container, renderer);
+                }
+            }
+        }
+    }
+
+    /// Tells the server we closed the window.
+    pub fn close(&mut self) {
+        if let Some(server) = self.hud_context.read().server.as_ref() {
+            server.write_packet(packet::play::serverbound::CloseWindow { id: self.id as u8 });
+        }
+    }
+
+    pub fn resize(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        inventory_window.clear_elements();
+        self.init(renderer, ui_container, inventory_window);
+    }
+
+    /// Draws the `(u, v, width, height)` part of the background texture with
+    /// its top-left corner at `(x, y)`.
+    pub fn draw_image(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        (u, v, width, height): (f64, f64, f64, f64),
+        (x, y): (f64, f64),
+    ) -> ImageRef {
+        let icon_scale = Hud::icon_scale(renderer);
+        let (x, y) = self.layout.center(icon_scale, (x, y), (width, height));
+        ui::ImageBuilder::new()
+            .texture_coords((u / 256.0, v / 256.0, width / 256.0, height / 256.0))
+            .position(x, y)
+            .alignment(VAttach::Middle, HAttach::Center)
+            .size(icon_scale * width, icon_scale * height)
+            .texture(self.layout.texture)
+            .create(ui_container)
+    }
+
+    fn draw_text(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+        text: &str,
+        (x, y): (f64, f64),
+    ) {
+        let icon_scale = Hud::icon_scale(renderer);
+        let scale = icon_scale / 2.0;
+        let width = renderer.ui.size_of_string(text) / 2.0;
+        let (x, y) = self.layout.center(icon_scale, (x, y), (width, 9.0));
+        let text = ui::TextBuilder::new()
+            .alignment(VAttach::Middle, HAttach::Center)
+            .scale_x(scale)
+            .scale_y(scale)
+            .position(x, y)
+            .text(text)
+            .colour((64, 64, 64, 255))
+            .shadow(false)
+            .create(ui_container);
+        inventory_window.text_elements[0].push(text);
+    }
+
+    fn update_icons(&mut self, renderer: &Renderer) {
+        self.icon_scale = Hud::icon_scale(renderer);
+        for (slot, &position) in self.slots.iter_mut().zip(self.layout.slots.iter()) {
+            let (x, y) = self.layout.center(self.icon_scale, position, (16.0, 16.0));
+            slot.update_position(x, y, self.icon_scale * 16.0);
+        }
+        self.dirty = true;
+    }
+}
+
+/// A container that does nothing but hold items, like chests, dispensers
+/// and hoppers, so it's only told apart by its layout.
+pub struct SimpleContainer {
+    base: ContainerBase,
+    ty: InventoryType,
+}
+
+impl SimpleContainer {
+    pub fn new(
+        id: i8,
+        name: String,
+        ty: InventoryType,
+        layout: Layout,
+        player_inventory: Arc<RwLock<PlayerInventory>>,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Self {
+        SimpleContainer {
+            base: ContainerBase::new(id, name, layout, player_inventory, hud_context),
+            ty,
+        }
+    }
+}
+
+impl Inventory for SimpleContainer {
+    fn size(&self) -> i16 {
+        self.base.size()
+    }
+
+    fn id(&self) -> i8 {
+        self.base.id
+    }
+
+    fn name(&self) -> Option<&String> {
+        Some(&self.base.name)
+    }
+
+    fn get_item(&self, slot: i16) -> &Option<Item> {
+        self.base.get_item(slot)
+    }
+
+    fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.base.get_item_mut(slot)
+    }
+
+    fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        self.base.set_item(slot, item);
+    }
+
+    fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.init(renderer, ui_container, inventory_window);
+    }
+
+    fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.tick(renderer, ui_container, inventory_window);
+    }
+
+    fn close(&mut self, _inventory_window: &mut InventoryWindow) {
+        self.base.close();
+    }
+
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.base.slot_at(x, y)
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        Some(self.base.quick_move(slot))
+    }
+
+    fn resize(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.resize(renderer, ui_container, inventory_window);
+    }
+
+    fn ty(&self) -> InventoryType {
+        self.ty
+    }
+}
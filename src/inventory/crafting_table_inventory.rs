@@ -0,0 +1,133 @@
+use crate::inventory::container::{ContainerBase, Layout};
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::{Inventory, InventoryType, Item, SlotKind};
+use crate::render::hud::HudContext;
+use crate::render::inventory::InventoryWindow;
+use crate::render::Renderer;
+use crate::ui::Container;
+use leafish_protocol::protocol::Version;
+use parking_lot::RwLock;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// The crafting table's window, slot 0 is the result and 1-9 the grid.
+pub struct CraftingTableInventory {
+    base: ContainerBase,
+    version: Version,
+}
+
+impl CraftingTableInventory {
+    pub fn new(
+        version: Version,
+        id: i8,
+        name: String,
+        player_inventory: Arc<RwLock<PlayerInventory>>,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Self {
+        let mut slots = vec![(124.0, 35.0)];
+        for y in 0..3 {
+            for x in 0..3 {
+                slots.push((30.0 + x as f64 * 18.0, 17.0 + y as f64 * 18.0));
+            }
+        }
+        let mut layout = Layout::new(
+            "minecraft:gui/container/crafting_table",
+            176.0,
+            166.0,
+            slots,
+            84.0,
+        );
+        layout.title_x = Some(28.0);
+        CraftingTableInventory {
+            base: ContainerBase::new(id, name, layout, player_inventory, hud_context),
+            version,
+        }
+    }
+}
+
+impl Inventory for CraftingTableInventory {
+    fn size(&self) -> i16 {
+        self.base.size()
+    }
+
+    fn id(&self) -> i8 {
+        self.base.id
+    }
+
+    fn name(&self) -> Option<&String> {
+        Some(&self.base.name)
+    }
+
+    fn get_item(&self, slot: i16) -> &Option<Item> {
+        self.base.get_item(slot)
+    }
+
+    fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.base.get_item_mut(slot)
+    }
+
+    fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        self.base.set_item(slot, item);
+    }
+
+    fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.init(renderer, ui_container, inventory_window);
+    }
+
+    fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.tick(renderer, ui_container, inventory_window);
+    }
+
+    fn close(&mut self, _inventory_window: &mut InventoryWindow) {
+        self.base.close();
+    }
+
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.base.slot_at(x, y)
+    }
+
+    fn slot_kind(&self, slot: i16) -> SlotKind {
+        match slot {
+            0 => SlotKind::CraftingResult(1..10),
+            // Before 1.12 the client worked out the recipe itself, so only
+            // the server can tell what changing the grid crafts
+            1..=9 if self.version < Version::V1_12 => SlotKind::Restricted,
+            _ => SlotKind::Normal,
+        }
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        Some(match slot {
+            // Crafts as many as the grid allows
+            0 => return None,
+            1..=9 => (10..46, false),
+            10..=36 => (37..46, false),
+            _ => (10..37, false),
+        })
+    }
+
+    fn resize(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.resize(renderer, ui_container, inventory_window);
+    }
+
+    fn ty(&self) -> InventoryType {
+        InventoryType::CraftingTable
+    }
+}
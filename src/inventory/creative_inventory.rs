@@ -0,0 +1,1112 @@
+//! The inventory creative players get instead of their own: every item of
+//! the version in tabs, a search and their own slots. It numbers the
+//! player's slots like window 0 so the server's updates to it fit as is.
+
+use crate::inventory::click::OUTSIDE;
+use crate::inventory::container::Layout;
+use crate::inventory::material::versions::{to_id, to_material};
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::tooltip;
+use crate::inventory::{Inventory, InventoryType, Item, Material, Slot, SlotKind};
+use crate::nbt;
+use crate::render::hud::Hud;
+use crate::render::inventory::InventoryWindow;
+use crate::render::Renderer;
+use crate::ui;
+use crate::ui::{Container, HAttach, ImageRef, VAttach};
+use lazy_static::lazy_static;
+use leafish_protocol::item::Stack;
+use leafish_protocol::protocol::Version;
+use parking_lot::{Mutex, RwLock};
+use std::ops::{Range, RangeInclusive};
+use std::sync::Arc;
+
+/// Slots of the item list, 9 wide and 5 high.
+const LIST_SIZE: usize = 45;
+const TABS_TEXTURE: &str = "minecraft:gui/container/creative_inventory/tabs";
+
+/// The potions since 1.9 in vanilla's order, with the version each was
+/// added in.
+const POTIONS: &[(&str, Version)] = &[
+    ("water", Version::V1_9),
+    ("mundane", Version::V1_9),
+    ("thick", Version::V1_9),
+    ("awkward", Version::V1_9),
+    ("night_vision", Version::V1_9),
+    ("long_night_vision", Version::V1_9),
+    ("invisibility", Version::V1_9),
+    ("long_invisibility", Version::V1_9),
+    ("leaping", Version::V1_9),
+    ("long_leaping", Version::V1_9),
+    ("strong_leaping", Version::V1_9),
+    ("fire_resistance", Version::V1_9),
+    ("long_fire_resistance", Version::V1_9),
+    ("swiftness", Version::V1_9),
+    ("long_swiftness", Version::V1_9),
+    ("strong_swiftness", Version::V1_9),
+    ("slowness", Version::V1_9),
+    ("long_slowness", Version::V1_9),
+    ("strong_slowness", Version::V1_13),
+    ("turtle_master", Version::V1_13),
+    ("long_turtle_master", Version::V1_13),
+    ("strong_turtle_master", Version::V1_13),
+    ("water_breathing", Version::V1_9),
+    ("long_water_breathing", Version::V1_9),
+    ("healing", Version::V1_9),
+    ("strong_healing", Version::V1_9),
+    ("harming", Version::V1_9),
+    ("strong_harming", Version::V1_9),
+    ("poison", Version::V1_9),
+    ("long_poison", Version::V1_9),
+    ("strong_poison", Version::V1_9),
+    ("regeneration", Version::V1_9),
+    ("long_regeneration", Version::V1_9),
+    ("strong_regeneration", Version::V1_9),
+    ("strength", Version::V1_9),
+    ("long_strength", Version::V1_9),
+    ("strong_strength", Version::V1_9),
+    ("weakness", Version::V1_9),
+    ("long_weakness", Version::V1_9),
+    ("luck", Version::V1_9),
+    ("slow_falling", Version::V1_13),
+    ("long_slow_falling", Version::V1_13),
+];
+
+/// The damage of the drinkable potions before 1.9, the effect is in the
+/// low bits and the level and duration above them.
+const LEGACY_POTIONS: &[(isize, Version)] = &[
+    (8193, Version::V1_7),
+    (8225, Version::V1_7),
+    (8257, Version::V1_7),
+    (8194, Version::V1_7),
+    (8226, Version::V1_7),
+    (8258, Version::V1_7),
+    (8195, Version::V1_7),
+    (8259, Version::V1_7),
+    (8196, Version::V1_7),
+    (8228, Version::V1_7),
+    (8260, Version::V1_7),
+    (8197, Version::V1_7),
+    (8229, Version::V1_7),
+    (8198, Version::V1_7),
+    (8262, Version::V1_7),
+    (8200, Version::V1_7),
+    (8264, Version::V1_7),
+    (8201, Version::V1_7),
+    (8233, Version::V1_7),
+    (8265, Version::V1_7),
+    (8202, Version::V1_7),
+    (8266, Version::V1_7),
+    (8203, Version::V1_8),
+    (8235, Version::V1_8),
+    (8267, Version::V1_8),
+    (8204, Version::V1_7),
+    (8236, Version::V1_7),
+    (8205, Version::V1_7),
+    (8269, Version::V1_7),
+    (8206, Version::V1_7),
+    (8270, Version::V1_7),
+];
+
+/// The items of a version with the tab each is on.
+type Items = Arc<Vec<(Tab, Item)>>;
+
+lazy_static! {
+    /// The item list of the version it was last built for, looking up
+    /// every id is too slow to do each time the inventory opens.
+    static ref ITEMS: Mutex<Option<(Version, Items)>> = Mutex::new(None);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tab {
+    BuildingBlocks,
+    Decorations,
+    Redstone,
+    Transportation,
+    Miscellaneous,
+    Search,
+    Foodstuffs,
+    Tools,
+    Combat,
+    Brewing,
+    Materials,
+    Inventory,
+}
+
+/// The tabs in the order vanilla shows them, the first six along the top.
+const TABS: [Tab; 12] = [
+    Tab::BuildingBlocks,
+    Tab::Decorations,
+    Tab::Redstone,
+    Tab::Transportation,
+    Tab::Miscellaneous,
+    Tab::Search,
+    Tab::Foodstuffs,
+    Tab::Tools,
+    Tab::Combat,
+    Tab::Brewing,
+    Tab::Materials,
+    Tab::Inventory,
+];
+
+impl Tab {
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::BuildingBlocks => "Building Blocks",
+            Tab::Decorations => "Decoration Blocks",
+            Tab::Redstone => "Redstone",
+            Tab::Transportation => "Transportation",
+            Tab::Miscellaneous => "Miscellaneous",
+            Tab::Search => "Search Items",
+            Tab::Foodstuffs => "Foodstuffs",
+            Tab::Tools => "Tools",
+            Tab::Combat => "Combat",
+            Tab::Brewing => "Brewing",
+            Tab::Materials => "Materials",
+            Tab::Inventory => "Survival Inventory",
+        }
+    }
+
+    fn icon(self) -> Material {
+        match self {
+            Tab::BuildingBlocks => Material::Brick,
+            Tab::Decorations => Material::DoublePlant,
+            Tab::Redstone => Material::Redstone,
+            Tab::Transportation => Material::PoweredRail,
+            Tab::Miscellaneous => Material::LavaBucket,
+            Tab::Search => Material::Compass,
+            Tab::Foodstuffs => Material::Apple,
+            Tab::Tools => Material::IronAxe,
+            Tab::Combat => Material::GoldSword,
+            Tab::Brewing => Material::Potion,
+            Tab::Materials => Material::Stick,
+            Tab::Inventory => Material::Chest,
+        }
+    }
+
+    fn texture(self) -> &'static str {
+        match self {
+            Tab::Search => "minecraft:gui/container/creative_inventory/tab_item_search",
+            Tab::Inventory => "minecraft:gui/container/creative_inventory/tab_inventory",
+            _ => "minecraft:gui/container/creative_inventory/tab_items",
+        }
+    }
+
+    /// The tab's top-left corner, with the top row sticking out above the
+    /// window and the bottom row below it.
+    fn position(index: usize) -> (f64, f64) {
+        let column = index % 6;
+        let x = if column == 5 {
+            195.0 - 28.0
+        } else {
+            column as f64 * 29.0
+        };
+        if index < 6 {
+            (x, -28.0)
+        } else {
+            (x, 136.0 - 4.0)
+        }
+    }
+}
+
+/// The tab an item belongs in, going by its name, or None for blocks that
+/// only exist placed in the world.
+fn group(material: &Material, id: u16) -> Option<Tab> {
+    use self::Material::*;
+    if matches!(
+        material,
+        Air | Water
+            | StationaryWater
+            | Lava
+            | StationaryLava
+            | BedBlock
+            | PistonExtension
+            | PistonMovingPiece
+            | DoubleStep
+            | Fire
+            | RedstoneWire
+            | Crops
+            | Soil
+            | BurningFurnace
+            | SignPost
+            | WoodenDoor
+            | WallSign
+            | IronDoorBlock
+            | GlowingRedstoneOre
+            | RedstoneTorchOff
+            | SugarCaneBlock
+            | Portal
+            | CakeBlock
+            | DiodeBlockOff
+            | DiodeBlockOn
+            | LockedChest
+            | PumpkinStem
+            | MelonStem
+            | NetherWarts
+            | BrewingStand
+            | Cauldron
+            | EnderPortal
+            | RedstoneLampOn
+            | WoodDoubleStep
+            | Cocoa
+            | Tripwire
+            | FlowerPot
+            | Carrot
+            | Potato
+            | Skull
+            | RedstoneComparatorOff
+            | RedstoneComparatorOn
+            | StandingBanner
+            | WallBanner
+            | DaylightDetectorInverted
+            | DoubleStoneSlab2
+            | SpruceDoor
+            | BirchDoor
+            | JungleDoor
+            | AcaciaDoor
+            | DarkOakDoor
+            | PurpurDoubleSlab
+            | BeetrootBlock
+            | EndGateway
+            | FrostedIce
+            | PistonHead
+    ) {
+        return None;
+    }
+    let name = material.name();
+    let has = |parts: &[&str]| parts.iter().any(|part| name.contains(part));
+    Some(
+        if has(&["Rail", "Minecart", "Boat"]) || matches!(material, Saddle | CarrotStick | Elytra) {
+            Tab::Transportation
+        } else if has(&["Potion", "Bottle"])
+            || matches!(
+                material,
+                BlazePowder
+                    | MagmaCream
+                    | FermentedSpiderEye
+                    | SpeckledMelon
+                    | GhastTear
+                    | NetherStalk
+                    | BrewingStandItem
+                    | CauldronItem
+                    | DragonsBreath
+                    | RabbitFoot
+            )
+        {
+            Tab::Brewing
+        } else if has(&["Sword", "Arrow", "Barding", "HorseArmor"])
+            || material.armor_slot().is_some() && !has(&["Pumpkin", "Skull", "Head"])
+            || matches!(material, Bow | Shield)
+        {
+            Tab::Combat
+        } else if material.tool().is_some()
+            || has(&["Bucket"])
+            || matches!(
+                material,
+                FlintAndSteel | FishingRod | Compass | Watch | Leash | NameTag | EmptyMap
+            )
+        {
+            Tab::Tools
+        } else if has(&[
+            "Cooked", "Raw", "Stew", "Soup", "Mutton", "Beef", "Porkchop",
+        ]) || matches!(
+            material,
+            Apple
+                | GoldenApple
+                | Bread
+                | Pork
+                | GrilledPork
+                | Cake
+                | Cookie
+                | Melon
+                | RottenFlesh
+                | SpiderEye
+                | CarrotItem
+                | PotatoItem
+                | BakedPotato
+                | PoisonousPotato
+                | GoldenCarrot
+                | PumpkinPie
+                | Rabbit
+                | ChorusFruit
+                | Beetroot
+        ) {
+            Tab::Foodstuffs
+        } else if has(&[
+            "Redstone",
+            "Piston",
+            "Plate",
+            "Button",
+            "Diode",
+            "Repeater",
+            "Comparator",
+            "Lever",
+            "TripwireHook",
+            "Observer",
+            "DaylightDetector",
+            "Hopper",
+            "Dispenser",
+            "Dropper",
+            "NoteBlock",
+            "Door",
+            "FenceGate",
+            "TrappedChest",
+        ]) && !matches!(material, RedstoneOre)
+            || matches!(material, Tnt | Diode)
+        {
+            Tab::Redstone
+        } else if has(&[
+            "Sapling",
+            "Leaves",
+            "Flower",
+            "Fence",
+            "Wall",
+            "Pane",
+            "Carpet",
+            "Banner",
+            "ShulkerBox",
+            "Sign",
+            "Skull",
+            "Head",
+            "Torch",
+            "Chest",
+            "Painting",
+            "Frame",
+        ]) || name.ends_with("Bed")
+            || matches!(
+                material,
+                Web | LongGrass
+                    | DeadBush
+                    | RedRose
+                    | BrownMushroom
+                    | RedMushroom
+                    | Ladder
+                    | Snow
+                    | Cactus
+                    | Jukebox
+                    | IronFence
+                    | ThinGlass
+                    | Vine
+                    | WaterLily
+                    | EnchantmentTable
+                    | Anvil
+                    | DoublePlant
+                    | Workbench
+                    | Furnace
+                    | MobSpawner
+                    | EndRod
+                    | ChorusPlant
+                    | ChorusFlower
+                    | ArmorStand
+                    | EnderPortalFrame
+                    | DragonEgg
+            )
+        {
+            Tab::Decorations
+        } else if id < 256 {
+            // Blocks kept their ids below 256 as items before 1.13
+            Tab::BuildingBlocks
+        } else if has(&[
+            "Ingot", "Nugget", "Seeds", "Dye", "Shard", "Crystals", "Dust", "Ball", "Hide",
+        ]) || matches!(
+            material,
+            Coal | Diamond
+                | Stick
+                | Bowl
+                | String
+                | Feather
+                | Sulphur
+                | Wheat
+                | Flint
+                | Leather
+                | ClayBrick
+                | Paper
+                | Book
+                | Egg
+                | InkSack
+                | Bone
+                | Sugar
+                | BlazeRod
+                | Emerald
+                | NetherStar
+                | Quartz
+                | NetherBrickItem
+                | ChorusFruitPopped
+        ) {
+            Tab::Materials
+        } else {
+            Tab::Miscellaneous
+        },
+    )
+}
+
+/// The kinds of an item vanilla lists one by one, as the damage and tag of
+/// their stacks. Before 1.13 the damage picks things like the colour of
+/// wool or the wood of planks.
+fn variants(material: &Material, version: Version) -> Vec<(isize, Option<nbt::NamedTag>)> {
+    use self::Material::*;
+    if matches!(
+        material,
+        Potion | SplashPotion | LingeringPotion | TippedArrow
+    ) && version >= Version::V1_9
+    {
+        return POTIONS
+            .iter()
+            .filter(|(name, since)| {
+                version >= *since
+                    // Arrows are only tipped with potions that do something
+                    && !(matches!(material, TippedArrow)
+                        && matches!(*name, "water" | "mundane" | "thick" | "awkward"))
+            })
+            .map(|(name, _)| {
+                let mut tag = nbt::Tag::new_compound();
+                tag.put("Potion", nbt::Tag::String(format!("minecraft:{}", name)));
+                (0, Some(nbt::NamedTag("".to_owned(), tag)))
+            })
+            .collect();
+    }
+    if version >= Version::V1_13 {
+        return vec![(0, None)];
+    }
+    let since = |added: Version, damages: RangeInclusive<isize>| {
+        if version >= added {
+            damages.collect()
+        } else {
+            vec![0]
+        }
+    };
+    let damages: Vec<isize> = match material {
+        Stone | Sponge => since(
+            Version::V1_8,
+            0..=if matches!(material, Stone) { 6 } else { 1 },
+        ),
+        Dirt if version >= Version::V1_8 => vec![0, 1, 2],
+        // Coarse dirt came in 1.8
+        Dirt => vec![0, 2],
+        Wood | Sapling | WoodStep | DoublePlant | MonsterEggs => (0..=5).collect(),
+        Sand | Log2 | Leaves2 | CobbleWall | GoldenApple | Coal | CookedFish => vec![0, 1],
+        Log | Leaves | SmoothBrick | RawFish => (0..=3).collect(),
+        Sandstone | RedSandstone | Prismarine | QuartzBlock | Anvil => (0..=2).collect(),
+        // Dead bushes as tall grass aren't listed
+        LongGrass => vec![1, 2],
+        RedRose => (0..=8).collect(),
+        // Neither is the petrified oak slab
+        Step => vec![0, 1, 3, 4, 5, 6, 7],
+        Wool | StainedClay | StainedGlass | StainedGlassPane | Carpet | InkSack | Banner
+        | Concrete | ConcretePowder => (0..=15).collect(),
+        Bed => since(Version::V1_12, 0..=15),
+        // The dragon head came in 1.9
+        SkullItem if version >= Version::V1_9 => (0..=5).collect(),
+        SkullItem => (0..=4).collect(),
+        Potion => {
+            let drinkable: Vec<isize> = LEGACY_POTIONS
+                .iter()
+                .filter(|(_, since)| version >= *since)
+                .map(|(damage, _)| *damage)
+                .collect();
+            // The water bottle, then the drinkable and the splash potions
+            let splash = drinkable.iter().map(|damage| damage & !0x2000 | 0x4000);
+            std::iter::once(0)
+                .chain(drinkable.iter().copied())
+                .chain(splash)
+                .collect()
+        }
+        _ => vec![0],
+    };
+    damages.into_iter().map(|damage| (damage, None)).collect()
+}
+
+pub struct CreativeInventory {
+    version: Version,
+    /// The player's slots like in window 0, the item list and the slot
+    /// that destroys items.
+    slots: Vec<Slot>,
+    /// Where each slot is on the current tab, if it's on it.
+    positions: Vec<Option<(f64, f64)>>,
+    player_size: i16,
+    items: Items,
+    /// The items of the tab, or those matching the search.
+    shown: Vec<Item>,
+    tab: Tab,
+    search: String,
+    /// The first row of `shown` in the item list.
+    row: usize,
+    dirty: bool,
+    /// Everything has to be drawn again, after changing tabs for example.
+    redraw: bool,
+    layout: Layout,
+    icon_scale: f64,
+    player_inventory: Arc<RwLock<PlayerInventory>>,
+}
+
+impl CreativeInventory {
+    pub fn new(version: Version, player_inventory: Arc<RwLock<PlayerInventory>>) -> Self {
+        let items = Self::items(version);
+        let player_size = player_inventory.read().size();
+        let mut slots: Vec<Slot> = (0..player_size as usize + LIST_SIZE + 1)
+            .map(|_| Slot::new(0.0, 0.0, 0.0))
+            .collect();
+        {
+            let player_inventory = player_inventory.read();
+            for (slot, item) in slots.iter_mut().take(player_size as usize).enumerate() {
+                item.item = player_inventory.get_item(slot as i16).clone();
+            }
+        }
+        let mut inventory = CreativeInventory {
+            version,
+            positions: vec![],
+            slots,
+            player_size,
+            items,
+            shown: vec![],
+            tab: Tab::BuildingBlocks,
+            search: String::new(),
+            row: 0,
+            dirty: true,
+            redraw: false,
+            layout: Layout::new(Tab::BuildingBlocks.texture(), 195.0, 136.0, vec![], 0.0),
+            icon_scale: 0.0,
+            player_inventory,
+        };
+        inventory.select(Tab::BuildingBlocks);
+        inventory
+    }
+
+    /// Every item of the version with the tab it's on, built once for
+    /// each version.
+    fn items(version: Version) -> Items {
+        let mut cache = ITEMS.lock();
+        if let Some((cached, items)) = cache.as_ref() {
+            if *cached == version {
+                return items.clone();
+            }
+        }
+        let items: Items = Arc::new(
+            (1..=u16::MAX)
+                .filter_map(|id| {
+                    let material = to_material(id, version);
+                    // Ids the mappings don't know end up as air, and some
+                    // items have more than one id
+                    if to_id(material.clone(), version) != id {
+                        return None;
+                    }
+                    let tab = group(&material, id)?;
+                    Some((tab, id, material))
+                })
+                .flat_map(|(tab, id, material)| {
+                    variants(&material, version)
+                        .into_iter()
+                        .map(move |(damage, tag)| {
+                            let mut item = Self::item(version, id, material.clone());
+                            if item.stack.damage.is_some() {
+                                item.stack.damage = Some(damage);
+                            }
+                            item.stack.tag = tag;
+                            (tab, item)
+                        })
+                })
+                .collect(),
+        );
+        *cache = Some((version, items.clone()));
+        items
+    }
+
+    fn item(version: Version, id: u16, material: Material) -> Item {
+        Item {
+            stack: Stack {
+                id: id as isize,
+                count: 1,
+                damage: if version < Version::V1_13 {
+                    Some(0)
+                } else {
+                    None
+                },
+                tag: None,
+            },
+            material,
+        }
+    }
+
+    fn list(&self) -> Range<i16> {
+        self.player_size..self.player_size + LIST_SIZE as i16
+    }
+
+    fn destroy_slot(&self) -> i16 {
+        self.player_size + LIST_SIZE as i16
+    }
+
+    fn select(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.layout.texture = tab.texture();
+        self.row = 0;
+        self.update_shown();
+        self.positions = (0..self.slots.len() as i16)
+            .map(|slot| self.position(slot))
+            .collect();
+        self.redraw = true;
+    }
+
+    fn update_shown(&mut self) {
+        let search = self.search.trim().to_lowercase();
+        self.shown = self
+            .items
+            .iter()
+            .filter(|(tab, item)| match self.tab {
+                Tab::Search => tooltip::item_name(&item.material)
+                    .to_lowercase()
+                    .contains(&search),
+                Tab::Inventory => false,
+                _ => *tab == self.tab,
+            })
+            .map(|(_, item)| item.clone())
+            .collect();
+        self.row = self.row.min(self.max_row());
+        self.fill_list();
+    }
+
+    fn max_row(&self) -> usize {
+        self.shown.len().div_ceil(9).saturating_sub(LIST_SIZE / 9)
+    }
+
+    fn fill_list(&mut self) {
+        let first = self.row * 9;
+        for (i, slot) in self.list().enumerate() {
+            self.slots[slot as usize].item = self.shown.get(first + i).cloned();
+        }
+        self.dirty = true;
+    }
+
+    /// Where the slot's top-left corner is on the current tab. The
+    /// survival inventory leaves out the crafting grid like vanilla does.
+    fn position(&self, slot: i16) -> Option<(f64, f64)> {
+        let inventory = self.tab == Tab::Inventory;
+        if self.list().contains(&slot) {
+            let i = (slot - self.player_size) as f64;
+            return (!inventory).then(|| (9.0 + (i % 9.0) * 18.0, 18.0 + (i / 9.0).floor() * 18.0));
+        }
+        if slot == self.destroy_slot() {
+            return inventory.then_some((173.0, 112.0));
+        }
+        match slot {
+            36..=44 => Some((9.0 + (slot - 36) as f64 * 18.0, 112.0)),
+            _ if !inventory => None,
+            5..=8 => {
+                let i = (slot - 5) as f64;
+                Some((54.0 + (i / 2.0).floor() * 54.0, 6.0 + (i % 2.0) * 27.0))
+            }
+            9..=35 => {
+                let i = (slot - 9) as f64;
+                Some((9.0 + (i % 9.0) * 18.0, 54.0 + (i / 9.0).floor() * 18.0))
+            }
+            45 => Some((35.0, 20.0)),
+            _ => None,
+        }
+    }
+
+    /// Turns a point relative to the centre of the screen into pixels of
+    /// the window's texture.
+    fn to_texture(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            x / self.icon_scale + self.layout.width / 2.0,
+            y / self.icon_scale + self.layout.height / 2.0,
+        )
+    }
+
+    fn draw_image(
+        &self,
+        ui_container: &mut Container,
+        texture: &str,
+        (u, v, width, height): (f64, f64, f64, f64),
+        (x, y): (f64, f64),
+    ) -> ImageRef {
+        let (x, y) = self.layout.center(self.icon_scale, (x, y), (width, height));
+        ui::ImageBuilder::new()
+            .texture_coords((u / 256.0, v / 256.0, width / 256.0, height / 256.0))
+            .position(x, y)
+            .alignment(VAttach::Middle, HAttach::Center)
+            .size(self.icon_scale * width, self.icon_scale * height)
+            .texture(texture)
+            .create(ui_container)
+    }
+
+    fn draw_tab(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+        index: usize,
+    ) {
+        let tab = TABS[index];
+        let (x, y) = Tab::position(index);
+        let v = match (index < 6, tab == self.tab) {
+            (true, false) => 0.0,
+            (true, true) => 32.0,
+            (false, false) => 64.0,
+            (false, true) => 96.0,
+        };
+        let image = self.draw_image(
+            ui_container,
+            TABS_TEXTURE,
+            ((index % 6) as f64 * 28.0, v, 28.0, 32.0),
+            (x, y),
+        );
+        inventory_window.elements[0].push(image);
+        let icon_y = if index < 6 { y + 9.0 } else { y + 7.0 };
+        let (x, y) = self
+            .layout
+            .center(self.icon_scale, (x + 6.0, icon_y), (16.0, 16.0));
+        let icon = tab.icon();
+        let id = to_id(icon.clone(), self.version);
+        inventory_window.draw_item(
+            &Self::item(self.version, id, icon),
+            x,
+            y,
+            0,
+            ui_container,
+            renderer,
+        );
+    }
+
+    fn draw_text(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+        text: &str,
+        (x, y): (f64, f64),
+        colour: (u8, u8, u8, u8),
+    ) {
+        let width = renderer.ui.size_of_string(text) / 2.0;
+        let (x, y) = self.layout.center(self.icon_scale, (x, y), (width, 9.0));
+        let text = ui::TextBuilder::new()
+            .alignment(VAttach::Middle, HAttach::Center)
+            .scale_x(self.icon_scale / 2.0)
+            .scale_y(self.icon_scale / 2.0)
+            .position(x, y)
+            .text(text)
+            .colour(colour)
+            .shadow(false)
+            .create(ui_container);
+        inventory_window.text_elements[0].push(text);
+    }
+
+    fn update_icons(&mut self) {
+        for (slot, position) in self.slots.iter_mut().zip(self.positions.iter()) {
+            if let Some(position) = *position {
+                let (x, y) = self.layout.center(self.icon_scale, position, (16.0, 16.0));
+                slot.update_position(x, y, self.icon_scale * 16.0);
+            }
+        }
+        self.dirty = true;
+    }
+}
+
+impl Inventory for CreativeInventory {
+    fn size(&self) -> i16 {
+        self.slots.len() as i16
+    }
+
+    fn id(&self) -> i8 {
+        0
+    }
+
+    fn name(&self) -> Option<&String> {
+        None
+    }
+
+    fn get_item(&self, slot: i16) -> &Option<Item> {
+        &self.slots[slot as usize].item
+    }
+
+    fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.dirty = true;
+        &mut self.slots[slot as usize].item
+    }
+
+    /// The player's slots are kept in sync with their inventory, the item
+    /// list can't be changed.
+    fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        if slot >= self.player_size {
+            return;
+        }
+        self.player_inventory.write().set_item(slot, item.clone());
+        self.slots[slot as usize].item = item;
+        self.dirty = true;
+    }
+
+    fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.icon_scale = Hud::icon_scale(renderer);
+        self.redraw = false;
+        inventory_window.elements.push(vec![]);
+        inventory_window.text_elements.push(vec![]);
+        let selected = TABS.iter().position(|&tab| tab == self.tab).unwrap();
+        for index in 0..TABS.len() {
+            if index != selected {
+                self.draw_tab(renderer, ui_container, inventory_window, index);
+            }
+        }
+        let background = self.draw_image(
+            ui_container,
+            self.layout.texture,
+            (0.0, 0.0, self.layout.width, self.layout.height),
+            (0.0, 0.0),
+        );
+        inventory_window.elements[0].push(background);
+        self.draw_tab(renderer, ui_container, inventory_window, selected);
+        if self.tab != Tab::Inventory {
+            let max_row = self.max_row();
+            let (u, offset) = if max_row == 0 {
+                (244.0, 0.0)
+            } else {
+                (232.0, (112.0 - 15.0) * self.row as f64 / max_row as f64)
+            };
+            let scroller = self.draw_image(
+                ui_container,
+                TABS_TEXTURE,
+                (u, 0.0, 12.0, 15.0),
+                (175.0, 18.0 + offset),
+            );
+            inventory_window.elements[0].push(scroller);
+        }
+        self.draw_text(
+            renderer,
+            ui_container,
+            inventory_window,
+            self.tab.title(),
+            (8.0, 6.0),
+            (64, 64, 64, 255),
+        );
+        if self.tab == Tab::Search {
+            let search = format!("{}_", self.search);
+            self.draw_text(
+                renderer,
+                ui_container,
+                inventory_window,
+                &search,
+                (82.0, 6.0),
+                (255, 255, 255, 255),
+            );
+        }
+        inventory_window.elements.push(vec![]);
+        self.update_icons();
+    }
+
+    fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        if self.redraw {
+            inventory_window.clear_elements();
+            self.init(renderer, ui_container, inventory_window);
+        }
+        if self.dirty {
+            self.dirty = false;
+            inventory_window.elements.get_mut(1).unwrap().clear();
+            for (slot, position) in self.slots.iter().zip(self.positions.iter()) {
+                if let (Some(item), Some(_)) = (slot.item.as_ref(), position) {
+                    inventory_window.draw_item(item, slot.x, slot.y, 1, ui_container, renderer);
+                }
+            }
+        }
+    }
+
+    fn close(&mut self, _inventory_window: &mut InventoryWindow) {}
+
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        let slot = self
+            .slots
+            .iter()
+            .zip(self.positions.iter())
+            .position(|(slot, position)| position.is_some() && slot.contains(x, y));
+        if let Some(slot) = slot {
+            return Some(slot as i16);
+        }
+        let (x, y) = self.to_texture(x, y);
+        if x < 0.0 || y < 0.0 || x > self.layout.width || y > self.layout.height {
+            Some(OUTSIDE)
+        } else {
+            None
+        }
+    }
+
+    fn slot_kind(&self, slot: i16) -> SlotKind {
+        if self.list().contains(&slot) {
+            SlotKind::Infinite
+        } else if slot == self.destroy_slot() {
+            SlotKind::Destroy
+        } else if (5..=8).contains(&slot) {
+            SlotKind::Armor(slot as u8 - 5)
+        } else {
+            SlotKind::Normal
+        }
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        if self.tab == Tab::Inventory && slot < self.player_size {
+            self.player_inventory.read().quick_move(slot)
+        } else {
+            None
+        }
+    }
+
+    fn hotbar_slot(&self, index: u8) -> i16 {
+        36 + index as i16
+    }
+
+    fn resize(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        inventory_window.clear_elements();
+        self.init(renderer, ui_container, inventory_window);
+    }
+
+    fn ty(&self) -> InventoryType {
+        InventoryType::Creative
+    }
+
+    fn on_click(&mut self, x: f64, y: f64) -> bool {
+        let (x, y) = self.to_texture(x, y);
+        for (index, &tab) in TABS.iter().enumerate() {
+            let (tab_x, tab_y) = Tab::position(index);
+            // Only the part of the tab sticking out of the window counts
+            let (top, bottom) = if index < 6 {
+                (tab_y, 0.0)
+            } else {
+                (self.layout.height, tab_y + 32.0)
+            };
+            if x >= tab_x && x < tab_x + 28.0 && y >= top && y < bottom {
+                if tab != self.tab {
+                    self.select(tab);
+                }
+                return true;
+            }
+        }
+        if self.tab != Tab::Inventory && (174.0..188.0).contains(&x) && (18.0..130.0).contains(&y) {
+            let fraction = ((y - 18.0 - 7.5) / (112.0 - 15.0)).clamp(0.0, 1.0);
+            self.row = (fraction * self.max_row() as f64).round() as usize;
+            self.fill_list();
+            self.redraw = true;
+            return true;
+        }
+        false
+    }
+
+    fn on_scroll(&mut self, y: f64) {
+        if self.tab == Tab::Inventory {
+            return;
+        }
+        let row = if y > 0.0 {
+            self.row.saturating_sub(1)
+        } else if y < 0.0 {
+            (self.row + 1).min(self.max_row())
+        } else {
+            self.row
+        };
+        if row != self.row {
+            self.row = row;
+            self.fill_list();
+            self.redraw = true;
+        }
+    }
+
+    fn takes_text(&self) -> bool {
+        self.tab == Tab::Search
+    }
+
+    fn on_char(&mut self, received: char) {
+        if self.tab != Tab::Search {
+            return;
+        }
+        if received == 8 as char {
+            self.search.pop();
+        } else if !received.is_control() && self.search.len() < 50 {
+            self.search.push(received);
+        } else {
+            return;
+        }
+        self.row = 0;
+        self.update_shown();
+        self.redraw = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs() {
+        assert!(group(&Material::Water, 9).is_none());
+        assert_eq!(group(&Material::Bedrock, 7), Some(Tab::BuildingBlocks));
+        assert_eq!(group(&Material::RedstoneOre, 73), Some(Tab::BuildingBlocks));
+        assert_eq!(group(&Material::Pumpkin, 86), Some(Tab::BuildingBlocks));
+        assert_eq!(group(&Material::Bed, 355), Some(Tab::Decorations));
+        assert_eq!(group(&Material::RedBed, 598), Some(Tab::Decorations));
+        assert_eq!(group(&Material::RedstoneBlock, 152), Some(Tab::Redstone));
+        assert_eq!(group(&Material::Diode, 356), Some(Tab::Redstone));
+        assert_eq!(group(&Material::DiamondSword, 276), Some(Tab::Combat));
+        assert_eq!(group(&Material::Apple, 260), Some(Tab::Foodstuffs));
+    }
+
+    #[test]
+    fn legacy_variants() {
+        let damages = |material, version| {
+            variants(&material, version)
+                .into_iter()
+                .map(|(damage, _)| damage)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            damages(Material::Wool, Version::V1_12),
+            (0..=15).collect::<Vec<_>>()
+        );
+        assert_eq!(damages(Material::Wool, Version::V1_13), vec![0]);
+        assert_eq!(damages(Material::Dirt, Version::V1_7), vec![0, 2]);
+        assert_eq!(damages(Material::Bed, Version::V1_11), vec![0]);
+        assert_eq!(damages(Material::Stone, Version::V1_7), vec![0]);
+
+        let potions = damages(Material::Potion, Version::V1_8);
+        assert_eq!(potions[0], 0);
+        assert!(potions.contains(&8193));
+        // The splash potion of the same effect
+        assert!(potions.contains(&16385));
+        assert!(!damages(Material::Potion, Version::V1_7).contains(&8203));
+    }
+
+    #[test]
+    fn potion_tags() {
+        let potions = |material, version| {
+            variants(&material, version)
+                .into_iter()
+                .map(|(_, tag)| {
+                    tag.unwrap()
+                        .1
+                        .get("Potion")
+                        .and_then(|potion| potion.as_str())
+                        .unwrap()
+                        .to_owned()
+                })
+                .collect::<Vec<_>>()
+        };
+        let potions_1_9 = potions(Material::Potion, Version::V1_9);
+        assert_eq!(potions_1_9[0], "minecraft:water");
+        assert!(!potions_1_9.contains(&"minecraft:turtle_master".to_owned()));
+        assert!(potions(Material::Potion, Version::V1_13)
+            .contains(&"minecraft:turtle_master".to_owned()));
+        assert!(
+            !potions(Material::TippedArrow, Version::V1_13).contains(&"minecraft:water".to_owned())
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::inventory::container::{ContainerBase, Layout};
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::{Inventory, InventoryType, Item, SlotKind};
+use crate::render::hud::HudContext;
+use crate::render::inventory::InventoryWindow;
+use crate::render::Renderer;
+use crate::ui::Container;
+use leafish_protocol::protocol::Version;
+use parking_lot::RwLock;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Furnaces, and blast furnaces and smokers which use the same window. Slot 0
+/// is the input, 1 the fuel and 2 the result.
+pub struct FurnaceInventory {
+    base: ContainerBase,
+    version: Version,
+    burn_time: i16,
+    item_burn_time: i16,
+    cook_time: i16,
+    total_cook_time: i16,
+    progress_dirty: bool,
+}
+
+impl FurnaceInventory {
+    pub fn new(
+        version: Version,
+        id: i8,
+        name: String,
+        player_inventory: Arc<RwLock<PlayerInventory>>,
+        hud_context: Arc<RwLock<HudContext>>,
+    ) -> Self {
+        let slots = vec![(56.0, 17.0), (56.0, 53.0), (116.0, 35.0)];
+        let layout = Layout::new("minecraft:gui/container/furnace", 176.0, 166.0, slots, 84.0);
+        FurnaceInventory {
+            base: ContainerBase::new(id, name, layout, player_inventory, hud_context),
+            version,
+            burn_time: 0,
+            item_burn_time: 0,
+            cook_time: 0,
+            // 1.7 doesn't send it, every item took 200 ticks back then
+            total_cook_time: 200,
+            progress_dirty: true,
+        }
+    }
+
+    fn draw_progress(
+        &self,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        if self.burn_time > 0 {
+            let item_burn_time = if self.item_burn_time > 0 {
+                self.item_burn_time
+            } else {
+                200
+            };
+            let flame = (self.burn_time as f64 * 13.0 / item_burn_time as f64)
+                .floor()
+                .min(13.0);
+            let image = self.base.draw_image(
+                renderer,
+                ui_container,
+                (176.0, 12.0 - flame, 14.0, flame + 1.0),
+                (56.0, 36.0 + 12.0 - flame),
+            );
+            inventory_window.elements[2].push(image);
+        }
+        if self.cook_time > 0 && self.total_cook_time > 0 {
+            let arrow = (self.cook_time as f64 * 24.0 / self.total_cook_time as f64)
+                .floor()
+                .min(24.0);
+            let image = self.base.draw_image(
+                renderer,
+                ui_container,
+                (176.0, 14.0, arrow + 1.0, 16.0),
+                (79.0, 34.0),
+            );
+            inventory_window.elements[2].push(image);
+        }
+    }
+}
+
+impl Inventory for FurnaceInventory {
+    fn size(&self) -> i16 {
+        self.base.size()
+    }
+
+    fn id(&self) -> i8 {
+        self.base.id
+    }
+
+    fn name(&self) -> Option<&String> {
+        Some(&self.base.name)
+    }
+
+    fn get_item(&self, slot: i16) -> &Option<Item> {
+        self.base.get_item(slot)
+    }
+
+    fn get_item_mut(&mut self, slot: i16) -> &mut Option<Item> {
+        self.base.get_item_mut(slot)
+    }
+
+    fn set_item(&mut self, slot: i16, item: Option<Item>) {
+        self.base.set_item(slot, item);
+    }
+
+    fn init(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.init(renderer, ui_container, inventory_window);
+        self.progress_dirty = true;
+    }
+
+    fn tick(
+        &mut self,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.tick(renderer, ui_container, inventory_window);
+        if self.progress_dirty {
+            self.progress_dirty = false;
+            inventory_window.elements.get_mut(2).unwrap().clear();
+            self.draw_progress(renderer, ui_container, inventory_window);
+        }
+    }
+
+    fn close(&mut self, _inventory_window: &mut InventoryWindow) {
+        self.base.close();
+    }
+
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.base.slot_at(x, y)
+    }
+
+    fn slot_kind(&self, slot: i16) -> SlotKind {
+        match slot {
+            1 => SlotKind::Restricted,
+            2 => SlotKind::Output,
+            _ => SlotKind::Normal,
+        }
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        match slot {
+            0 | 1 => Some((3..39, false)),
+            2 => Some((3..39, true)),
+            // Whether it can be smelted or burned decides where it goes
+            _ => None,
+        }
+    }
+
+    fn resize(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        renderer: &mut Renderer,
+        ui_container: &mut Container,
+        inventory_window: &mut InventoryWindow,
+    ) {
+        self.base.resize(renderer, ui_container, inventory_window);
+        self.progress_dirty = true;
+    }
+
+    fn ty(&self) -> InventoryType {
+        InventoryType::Furnace
+    }
+
+    fn handle_property(&mut self, property: i16, value: i16) {
+        let old = self.version < Version::V1_8;
+        match property {
+            0 if old => self.cook_time = value,
+            1 if old => self.burn_time = value,
+            2 if old => self.item_burn_time = value,
+            0 => self.burn_time = value,
+            1 => self.item_burn_time = value,
+            2 => self.cook_time = value,
+            3 => self.total_cook_time = value,
+            _ => return,
+        }
+        self.progress_dirty = true;
+    }
+}
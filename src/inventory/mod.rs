@@ -1,14 +1,24 @@
+pub mod brewer_inventory;
+pub mod click;
+pub mod container;
+pub mod crafting_table_inventory;
+pub mod creative_inventory;
+pub mod furnace_inventory;
 pub(crate) mod material;
 pub mod player_inventory;
+pub mod tooltip;
 
 use crate::inventory::player_inventory::PlayerInventory;
 use crate::render::hud::HudContext;
 use crate::render::inventory::InventoryWindow;
 use crate::render::Renderer;
 use crate::ui::Container;
+use crate::world::block::{Tool, ToolTier};
 use leafish_protocol::item::Stack;
+use leafish_protocol::nbt::Tag;
 use leafish_protocol::protocol::Version;
 use parking_lot::RwLock;
+use std::ops::Range;
 use std::sync::Arc;
 
 pub trait Inventory {
@@ -40,7 +50,23 @@ pub trait Inventory {
 
     fn close(&mut self, inventory_window: &mut InventoryWindow);
 
-    fn click_at(&self, cursor: (u32, u32)); // TODO: Pass mouse data (buttons, wheel etc and shift button state)
+    /// The slot under the cursor, given relative to the centre of the
+    /// screen. Clicks beside the window are on `click::OUTSIDE`.
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16>;
+
+    /// What may be put into the slot.
+    fn slot_kind(&self, _slot: i16) -> SlotKind {
+        SlotKind::Normal
+    }
+
+    /// The slots a shift-click on `slot` moves its item into and whether
+    /// they're filled back to front, or None if only the server knows.
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)>;
+
+    /// The slot of the window that holds the given hotbar slot.
+    fn hotbar_slot(&self, index: u8) -> i16 {
+        self.size() - 9 + index as i16
+    }
 
     fn resize(
         &mut self,
@@ -52,6 +78,24 @@ pub trait Inventory {
     );
 
     fn ty(&self) -> InventoryType;
+
+    /// Updates one of the window's properties, like a furnace's burn time.
+    fn handle_property(&mut self, _property: i16, _value: i16) {}
+
+    /// Handles a click on something other than a slot, like a tab, and
+    /// returns whether there was anything there.
+    fn on_click(&mut self, _x: f64, _y: f64) -> bool {
+        false
+    }
+
+    fn on_scroll(&mut self, _y: f64) {}
+
+    /// Whether typing goes into a text field rather than being hotkeys.
+    fn takes_text(&self) -> bool {
+        false
+    }
+
+    fn on_char(&mut self, _received: char) {}
 }
 
 pub struct Slot {
@@ -59,7 +103,6 @@ pub struct Slot {
     pub y: f64,
     pub size: f64,
     pub item: Option<Item>,
-    // TODO: Is valid fn for Anvil, crafting, armor etc.
 }
 
 impl Slot {
@@ -77,34 +120,58 @@ impl Slot {
         self.y = y;
         self.size = size;
     }
+
+    /// Whether the point is over the slot or its border.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let half = self.size * 9.0 / 16.0;
+        (x - self.x).abs() <= half && (y - self.y).abs() <= half
+    }
+}
+
+pub enum SlotKind {
+    Normal,
+    /// Only the server knows what fits, like a furnace's fuel.
+    Restricted,
+    /// Only takes armor worn in the given slot, 0 being the head.
+    Armor(u8),
+    /// Can only be taken from, like a furnace's result.
+    Output,
+    /// The result of the crafting grid in the given slots, taking it uses
+    /// up one of each of their items.
+    CraftingResult(Range<i16>),
+    /// Hands out copies of its item without ever running out, like the
+    /// creative inventory's item list.
+    Infinite,
+    /// Deletes what's put into it.
+    Destroy,
 }
 
 pub struct InventoryContext {
     pub cursor: Option<Item>,
+    /// Set when the cursor changes behind the open window's back.
+    pub cursor_dirty: bool,
     pub hotbar_index: u8,
     pub inventory: Option<Arc<RwLock<dyn Inventory + Send + Sync>>>,
     pub player_inventory: Arc<RwLock<PlayerInventory>>,
+    action_number: i16,
+    transactions: Vec<click::Transaction>,
 }
 
 impl InventoryContext {
-    pub fn new(
-        version: Version,
-        renderer: &Renderer,
-        hud_context: Arc<RwLock<HudContext>>,
-    ) -> Self {
+    pub fn new(version: Version, hud_context: Arc<RwLock<HudContext>>) -> Self {
         InventoryContext {
             cursor: None,
+            cursor_dirty: false,
             hotbar_index: 0,
             inventory: None,
-            player_inventory: Arc::new(RwLock::new(PlayerInventory::new(
-                version,
-                renderer,
-                hud_context,
-            ))),
+            player_inventory: Arc::new(RwLock::new(PlayerInventory::new(version, hud_context))),
+            action_number: 0,
+            transactions: vec![],
         }
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum InventoryType {
     Main,
     Chest,
@@ -115,18 +182,122 @@ pub enum InventoryType {
     Brewer,
     CraftingTable,
     Dropper,
+    Furnace,
+    Creative,
     Horse,
     Merchant,
     EntityEquipment,
 }
 
-#[derive(Debug)]
+impl InventoryType {
+    /// Works out the window the server opened and how many slots it has on
+    /// top of the player's inventory. The type was a number in 1.7, a name
+    /// from 1.8 until 1.13 and a number again from 1.14 on.
+    pub fn from_window(
+        version: Version,
+        ty: Option<i32>,
+        ty_name: Option<&str>,
+        slot_count: Option<u8>,
+    ) -> Option<(InventoryType, u8)> {
+        if let Some(name) = ty_name {
+            let ty = match name.strip_prefix("minecraft:").unwrap_or(name) {
+                "chest" | "container" | "shulker_box" => InventoryType::Chest,
+                "crafting_table" => InventoryType::CraftingTable,
+                "furnace" => InventoryType::Furnace,
+                "dispenser" | "dropper" => InventoryType::Dropper,
+                "enchanting_table" => InventoryType::Enchanter,
+                "brewing_stand" => InventoryType::Brewer,
+                "villager" => InventoryType::Merchant,
+                "beacon" => InventoryType::Beacon,
+                "anvil" => InventoryType::Anvil,
+                "hopper" => InventoryType::Hopper,
+                "EntityHorse" => InventoryType::Horse,
+                _ => return None,
+            };
+            return Some((ty, slot_count.unwrap_or(0)));
+        }
+        let ty = ty?;
+        if version < Version::V1_14 {
+            let ty = match ty {
+                0 => InventoryType::Chest,
+                1 => InventoryType::CraftingTable,
+                2 => InventoryType::Furnace,
+                3 | 10 => InventoryType::Dropper,
+                4 => InventoryType::Enchanter,
+                5 => InventoryType::Brewer,
+                6 => InventoryType::Merchant,
+                7 => InventoryType::Beacon,
+                8 => InventoryType::Anvil,
+                9 => InventoryType::Hopper,
+                11 => InventoryType::Horse,
+                _ => return None,
+            };
+            return Some((ty, slot_count.unwrap_or(0)));
+        }
+        // 1.16 put the smithing table in front of the smoker
+        let ty = match ty {
+            20 if version >= Version::V1_16 => return None,
+            ty if ty > 20 && version >= Version::V1_16 => ty - 1,
+            ty => ty,
+        };
+        Some(match ty {
+            0..=5 => (InventoryType::Chest, (ty as u8 + 1) * 9),
+            6 => (InventoryType::Dropper, 9),
+            7 => (InventoryType::Anvil, 3),
+            8 => (InventoryType::Beacon, 1),
+            // blast furnace, furnace and smoker
+            9 | 13 | 20 => (InventoryType::Furnace, 3),
+            10 => (InventoryType::Brewer, 5),
+            11 => (InventoryType::CraftingTable, 10),
+            12 => (InventoryType::Enchanter, 2),
+            15 => (InventoryType::Hopper, 5),
+            18 => (InventoryType::Merchant, 3),
+            19 => (InventoryType::Chest, 27),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Item {
     pub stack: Stack,
     pub material: Material,
 }
 
-#[derive(Debug)]
+impl Item {
+    /// The level of an enchantment on the item, looked up by name in the
+    /// NBT of 1.13+ and by its numeric id before that.
+    pub fn enchantment_level(&self, name: &str, legacy_id: i16) -> u8 {
+        let tag = match &self.stack.tag {
+            Some(tag) if tag.1.is_compound() => &tag.1,
+            _ => return 0,
+        };
+        let enchantments = tag
+            .get("Enchantments")
+            .or_else(|| tag.get("ench"))
+            .and_then(|list| list.as_list())
+            .unwrap_or(&[]);
+        enchantments
+            .iter()
+            .filter(|enchantment| enchantment.is_compound())
+            .find(|enchantment| match enchantment.get("id") {
+                Some(Tag::String(id)) => id.strip_prefix("minecraft:").unwrap_or(id) == name,
+                Some(id) => id
+                    .as_short()
+                    .or_else(|| id.as_int().map(|id| id as i16))
+                    .is_some_and(|id| id == legacy_id),
+                None => false,
+            })
+            .and_then(|enchantment| enchantment.get("lvl"))
+            .and_then(|lvl| {
+                lvl.as_short()
+                    .or_else(|| lvl.as_int().map(|lvl| lvl as i16))
+            })
+            .map_or(0, |lvl| lvl.clamp(0, 255) as u8)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Material {
     Air,                             // 1.7.10 (id: 0, stack: 0)| 1.13 (id: 9648)
     Stone,                           // 1.7.10 (id: 1)| 1.13 (id: 22948)
@@ -1205,7 +1376,7 @@ pub enum Material {
     Composter, // 1.14 (id: 31247, stack: 0)
     Cornflower, // 1.14 (id: 15405)
     CreeperBannerPattern, // 1.14 (id: 15774, stack: 1)
-    Crossbow, // 1.14(id; 4340, stack: 1, durability: 326 )
+    Crossbow, // 1.14(id; 4340, stack: 1, durability: 465 )
     CutRedSandstoneSlab, // 1.14 (id: 7220, stack: 0)
     CutSandstoneSlab, // 1.14 (id: 30944, stack: 0)
     DarkOakSign, // 1.14(id; 15127, stack: 16, durability: 0 )
@@ -1554,7 +1725,189 @@ impl Material {
         format!("{:?}", self)
     }
 
+    /// The kind of tool the item is and what it's made of, if it's one.
+    pub fn tool(&self) -> Option<(Tool, ToolTier)> {
+        use self::Material::*;
+        Some(match self {
+            WoodPickaxe | WoodenPickaxe => (Tool::Pickaxe, ToolTier::Wood),
+            StonePickaxe => (Tool::Pickaxe, ToolTier::Stone),
+            IronPickaxe => (Tool::Pickaxe, ToolTier::Iron),
+            GoldPickaxe | GoldenPickaxe => (Tool::Pickaxe, ToolTier::Gold),
+            DiamondPickaxe => (Tool::Pickaxe, ToolTier::Diamond),
+            NetheritePickaxe => (Tool::Pickaxe, ToolTier::Netherite),
+            WoodAxe | WoodenAxe => (Tool::Axe, ToolTier::Wood),
+            StoneAxe => (Tool::Axe, ToolTier::Stone),
+            IronAxe => (Tool::Axe, ToolTier::Iron),
+            GoldAxe | GoldenAxe => (Tool::Axe, ToolTier::Gold),
+            DiamondAxe => (Tool::Axe, ToolTier::Diamond),
+            NetheriteAxe => (Tool::Axe, ToolTier::Netherite),
+            WoodSpade | WoodenShovel => (Tool::Shovel, ToolTier::Wood),
+            StoneSpade | StoneShovel => (Tool::Shovel, ToolTier::Stone),
+            IronSpade | IronShovel => (Tool::Shovel, ToolTier::Iron),
+            GoldSpade | GoldenShovel => (Tool::Shovel, ToolTier::Gold),
+            DiamondSpade | DiamondShovel => (Tool::Shovel, ToolTier::Diamond),
+            NetheriteShovel => (Tool::Shovel, ToolTier::Netherite),
+            WoodHoe | WoodenHoe => (Tool::Hoe, ToolTier::Wood),
+            StoneHoe => (Tool::Hoe, ToolTier::Stone),
+            IronHoe => (Tool::Hoe, ToolTier::Iron),
+            GoldHoe | GoldenHoe => (Tool::Hoe, ToolTier::Gold),
+            DiamondHoe => (Tool::Hoe, ToolTier::Diamond),
+            NetheriteHoe => (Tool::Hoe, ToolTier::Netherite),
+            WoodSword | WoodenSword => (Tool::Sword, ToolTier::Wood),
+            StoneSword => (Tool::Sword, ToolTier::Stone),
+            IronSword => (Tool::Sword, ToolTier::Iron),
+            GoldSword | GoldenSword => (Tool::Sword, ToolTier::Gold),
+            DiamondSword => (Tool::Sword, ToolTier::Diamond),
+            NetheriteSword => (Tool::Sword, ToolTier::Netherite),
+            // Shears have no tier, only whether they suit the block matters
+            Shears => (Tool::Shears, ToolTier::Iron),
+            _ => return None,
+        })
+    }
+
+    /// How many of the item fit into one slot, as listed next to the
+    /// materials above.
+    pub fn max_stack_size(&self) -> isize {
+        use self::Material::*;
+        match self {
+            Sign | Bucket | SnowBall | Egg | EnderPearl | WrittenBook | ArmorStand | Banner
+            | BlackBanner | BlueBanner | BrownBanner | CyanBanner | GrayBanner | GreenBanner
+            | LightBlueBanner | LightGrayBanner | LimeBanner | MagentaBanner | OrangeBanner
+            | PinkBanner | PurpleBanner | RedBanner | Snowball | WhiteBanner | YellowBanner
+            | AcaciaSign | AcaciaWallSign | BirchSign | BirchWallSign | DarkOakSign
+            | DarkOakWallSign | JungleSign | JungleWallSign | OakSign | OakWallSign
+            | SpruceSign | SpruceWallSign | HoneyBottle | CrimsonSign | CrimsonWallSign
+            | WarpedSign | WarpedWallSign => 16,
+            // Doors are left out, they only stacked to one before 1.8
+            IronSpade | IronPickaxe | IronAxe | FlintAndSteel | Bow | IronSword | WoodSword
+            | WoodSpade | WoodPickaxe | WoodAxe | StoneSword | StoneSpade | StonePickaxe
+            | StoneAxe | DiamondSword | DiamondSpade | DiamondPickaxe | DiamondAxe
+            | MushroomSoup | GoldSword | GoldSpade | GoldPickaxe | GoldAxe | WoodHoe | StoneHoe
+            | IronHoe | DiamondHoe | GoldHoe | LeatherHelmet | LeatherChestplate
+            | LeatherLeggings | LeatherBoots | ChainmailHelmet | ChainmailChestplate
+            | ChainmailLeggings | ChainmailBoots | IronHelmet | IronChestplate | IronLeggings
+            | IronBoots | DiamondHelmet | DiamondChestplate | DiamondLeggings | DiamondBoots
+            | GoldHelmet | GoldChestplate | GoldLeggings | GoldBoots | WaterBucket | LavaBucket
+            | Minecart | Saddle | Boat | MilkBucket | StorageMinecart | PoweredMinecart
+            | FishingRod | Cake | Bed | Shears | Potion | BookAndQuill | CarrotStick
+            | EnchantedBook | ExplosiveMinecart | HopperMinecart | IronBarding | GoldBarding
+            | DiamondBarding | CommandMinecart | GoldRecord | GreenRecord | Record3 | Record4
+            | Record5 | Record6 | Record7 | Record8 | Record9 | Record10 | Record11 | Record12
+            | RabbitStew | BeetrootSoup | SplashPotion | LingeringPotion | Shield | Elytra
+            | BoatSpruce | BoatBirch | BoatJungle | BoatAcacia | BoatDarkOak | WhiteShulkerBox
+            | OrangeShulkerBox | MagentaShulkerBox | LightBlueShulkerBox | YellowShulkerBox
+            | LimeShulkerBox | PinkShulkerBox | GrayShulkerBox | SilverShulkerBox
+            | CyanShulkerBox | PurpleShulkerBox | BlueShulkerBox | BrownShulkerBox
+            | GreenShulkerBox | RedShulkerBox | BlackShulkerBox | Totem | KnowledgeBook
+            | AcaciaBoat | BirchBoat | BlackBed | BlueBed | BrownBed | CarrotOnAStick
+            | ChestMinecart | CodBucket | CommandBlockMinecart | CyanBed | DarkOakBoat
+            | DebugStick | DiamondHorseArmor | DiamondShovel | FurnaceMinecart | GoldenAxe
+            | GoldenBoots | GoldenChestplate | GoldenHelmet | GoldenHoe | GoldenHorseArmor
+            | GoldenLeggings | GoldenPickaxe | GoldenShovel | GoldenSword | GrayBed | GreenBed
+            | IronHorseArmor | IronShovel | JungleBoat | LightBlueBed | LightGrayBed
+            | LightGrayShulkerBox | LimeBed | MagentaBed | MushroomStew | MusicDisc11
+            | MusicDisc13 | MusicDiscBlocks | MusicDiscCat | MusicDiscChirp | MusicDiscFar
+            | MusicDiscMall | MusicDiscMellohi | MusicDiscStal | MusicDiscStrad | MusicDiscWait
+            | MusicDiscWard | OakBoat | OrangeBed | PinkBed | PufferfishBucket | PurpleBed
+            | RedBed | SalmonBucket | ShulkerBox | SpruceBoat | StoneShovel | TntMinecart
+            | TotemOfUndying | Trident | TropicalFishBucket | TurtleHelmet | WhiteBed
+            | WoodenAxe | WoodenHoe | WoodenPickaxe | WoodenShovel | WoodenSword | WritableBook
+            | YellowBed | CreeperBannerPattern | Crossbow | FlowerBannerPattern
+            | GlobeBannerPattern | LeatherHorseArmor | MojangBannerPattern | SkullBannerPattern
+            | SuspiciousStew | MusicDiscPigstep | NetheriteAxe | NetheriteBoots
+            | NetheriteChestplate | NetheriteHelmet | NetheriteHoe | NetheriteLeggings
+            | NetheritePickaxe | NetheriteShovel | NetheriteSword | PiglinBannerPattern
+            | WarpedFungusOnAStick | PowderSnowBucket | AxolotlBucket | Bundle | Spyglass => 1,
+            _ => 64,
+        }
+    }
+
+    /// How much damage the item takes before it breaks, None if it can't
+    /// be damaged.
+    pub fn max_durability(&self) -> Option<isize> {
+        use self::Material::*;
+        match self {
+            Shears => return Some(238),
+            Bow => return Some(384),
+            FishingRod | FlintAndSteel => return Some(64),
+            Shield => return Some(336),
+            Elytra => return Some(432),
+            CarrotStick | CarrotOnAStick => return Some(25),
+            WarpedFungusOnAStick => return Some(100),
+            Trident => return Some(250),
+            Crossbow => return Some(465),
+            _ => {}
+        }
+        if let Some((_, tier)) = self.tool() {
+            return Some(match tier {
+                ToolTier::Wood => 59,
+                ToolTier::Stone => 131,
+                ToolTier::Iron => 250,
+                ToolTier::Gold => 32,
+                ToolTier::Diamond => 1561,
+                ToolTier::Netherite => 2031,
+            });
+        }
+        let name = self.name();
+        // Armor lasts for a multiple of these depending on its material
+        let base = match self.armor_slot()? {
+            0 => 11,
+            1 => 16,
+            2 => 15,
+            _ => 13,
+        };
+        let factor = if name.starts_with("Leather") {
+            5
+        } else if name.starts_with("Chainmail") || name.starts_with("Iron") {
+            15
+        } else if name.starts_with("Gold") {
+            7
+        } else if name.starts_with("Diamond") {
+            33
+        } else if name.starts_with("Netherite") {
+            37
+        } else if name.starts_with("Turtle") {
+            25
+        } else {
+            return None;
+        };
+        Some(base * factor)
+    }
+
+    /// The armor slot the item can be worn in, 0 being the head and 3 the
+    /// feet.
+    pub fn armor_slot(&self) -> Option<u8> {
+        use self::Material::*;
+        match self {
+            Pumpkin | CarvedPumpkin | Skull | SkullItem => return Some(0),
+            Elytra => return Some(1),
+            PistonHead => return None,
+            _ => {}
+        }
+        let name = self.name();
+        if name.ends_with("HorseArmor") {
+            None
+        } else if name.ends_with("Helmet") || name.ends_with("Head") || name.ends_with("Skull") {
+            Some(0)
+        } else if name.ends_with("Chestplate") {
+            Some(1)
+        } else if name.ends_with("Leggings") {
+            Some(2)
+        } else if name.ends_with("Boots") {
+            Some(3)
+        } else {
+            None
+        }
+    }
+
     pub fn texture_locations(&self) -> (String, String) {
+        let name = self.model_name();
+        (format!("items/{}", name), format!("blocks/{}", name))
+    }
+
+    /// The name of the item's model and textures, "DiamondSword" is
+    /// "diamond_sword".
+    pub fn model_name(&self) -> String {
         // TODO: Compute this at compile time and only lookup at runtime in (O(1))
         let mut result = String::new();
         for (i, c) in self.name().chars().enumerate() {
@@ -1567,6 +1920,6 @@ impl Material {
                 result.push(c);
             }
         }
-        (format!("items/{}", result), format!("blocks/{}", result))
+        result
     }
 }
@@ -1,9 +1,11 @@
-use crate::inventory::{Inventory, InventoryType, Item, Material, Slot};
+use crate::inventory::container::Layout;
+use crate::inventory::{Inventory, InventoryType, Item, Material, Slot, SlotKind};
 use crate::render::hud::{Hud, HudContext};
 use crate::render::inventory::InventoryWindow;
 use crate::render::Renderer;
 use crate::ui;
 use crate::ui::{Container, HAttach, VAttach};
+use std::ops::Range;
 use std::sync::Arc;
 
 use leafish_protocol::protocol::Version;
@@ -15,223 +17,79 @@ pub struct PlayerInventory {
     dirty: bool,
     version: Version,
     hud_context: Arc<RwLock<HudContext>>,
+    layout: Layout,
+    icon_scale: f64,
 }
 
 impl PlayerInventory {
-    pub fn new(
-        version: Version,
-        renderer: &Renderer,
-        hud_context: Arc<RwLock<HudContext>>,
-    ) -> Self {
-        let scale = Hud::icon_scale(renderer);
-        let size = scale * 16.0;
-        let x_offset = -(size * 4.5);
-        let y_offset = size * 4.25;
-        let hot_bar_offset = scale * 4.0;
-        let mut slots = vec![];
-        let mut slot_craft_0 = Slot::new(
-            (size + size * 1.0 / 8.0) * 2.5,
-            scale * 5.0
-                - (scale * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0)
-                    - (size + size * 1.0 / 9.0) * 2.0),
-            size,
+    pub fn new(version: Version, hud_context: Arc<RwLock<HudContext>>) -> Self {
+        let layout = Layout::new(
+            "minecraft:gui/container/inventory",
+            176.0,
+            166.0,
+            Self::slot_positions(version),
+            84.0,
         );
-        slot_craft_0.item = Some(Item {
-            // TODO: The 0th slot isn't rendered, no matter what! FIX THIS!
-            stack: Default::default(),
-            material: Material::Apple,
-        });
-        slots.push(slot_craft_0);
-        let mut slot_craft_1 = Slot::new(
-            size + size * 1.0 / 8.0,
-            scale * 5.0 - (scale * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0)),
-            size,
-        );
-        slot_craft_1.item = Some(Item {
-            stack: Default::default(),
-            material: Material::Apple,
-        });
-        slots.push(slot_craft_1);
-        let mut slot_craft_2 = Slot::new(
-            (size + size * 1.0 / 8.0) * 2.0,
-            scale * 5.0 - (scale * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0)),
-            size,
-        );
-        slot_craft_2.item = Some(Item {
-            stack: Default::default(),
-            material: Material::Apple,
-        });
-        slots.push(slot_craft_2);
-        let mut slot_craft_3 = Slot::new(
-            size + size * 1.0 / 8.0,
-            scale * 5.0
-                - (scale * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0)
-                    - (size + size * 1.0 / 9.0)),
-            size,
-        );
-        slot_craft_3.item = Some(Item {
-            stack: Default::default(),
-            material: Material::Apple,
-        });
-        slots.push(slot_craft_3);
-        let mut slot_craft_4 = Slot::new(
-            (size + size * 1.0 / 8.0) * 2.0,
-            scale * 5.0
-                - (scale * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0)
-                    - (size + size * 1.0 / 9.0)),
-            size,
-        );
-        slot_craft_4.item = Some(Item {
-            stack: Default::default(),
-            material: Material::Apple,
-        });
-        slots.push(slot_craft_4);
-        let slot_head = Slot::new(
-            x_offset,
-            y_offset
-                + -((6_f64 + 1.0 / 8.0) * (size + size * 1.0 / 8.0) + size + hot_bar_offset * 2.0),
-            size,
-        ); // 6th slot!
-        let slot_chestplate = Slot::new(
-            x_offset,
-            y_offset
-                + -((5_f64 + 1.0 / 8.0) * (size + size * 1.0 / 8.0) + size + hot_bar_offset * 2.0),
-            size,
-        ); // 7th slot!
-        let slot_leggings = Slot::new(
-            x_offset,
-            y_offset
-                + -((4_f64 + 1.0 / 8.0) * (size + size * 1.0 / 8.0) + size + hot_bar_offset * 2.0),
-            size,
-        ); // 8th slot!
-        let slot_boots = Slot::new(
-            x_offset,
-            y_offset
-                + -((3_f64 + 1.0 / 8.0) * (size + size * 1.0 / 8.0) + size + hot_bar_offset * 2.0),
-            size,
-        ); // 9th slot!
-        slots.push(slot_head);
-        slots.push(slot_chestplate);
-        slots.push(slot_leggings);
-        slots.push(slot_boots);
-        for y in (0..3).rev() {
-            for x in 0..9 {
-                slots.push(Slot::new(
-                    x_offset + (x as f64) * (size + size * 1.0 / 8.0),
-                    y_offset
-                        + -((y as f64 + 1.0 / 8.0) * (size + size * 1.0 / 8.0)
-                            + size
-                            + size * 1.0 / 8.0
-                            + hot_bar_offset),
-                    size,
-                ));
-            }
-        }
-        for i in 0..9 {
-            slots.push(Slot::new(
-                x_offset + (i as f64) * (size + size * 1.0 / 8.0),
-                y_offset,
-                size,
-            ));
-        }
-        if version > Version::V1_8 {
-            let mut slot = Slot::new(-(scale * 3.0), scale * 5.0 - scale * 18.0, size);
-            slot.item = Some(Item {
-                stack: Default::default(),
-                material: Material::Apple,
-            });
-            slots.push(slot);
-        }
+        let slots = layout
+            .slots
+            .iter()
+            .map(|_| Slot::new(0.0, 0.0, 0.0))
+            .collect();
         PlayerInventory {
             slots,
             dirty: false,
             version,
             hud_context,
+            layout,
+            icon_scale: 0.0,
         }
     }
 
-    fn update_icons(&mut self, renderer: &Renderer) {
-        let scale = Hud::icon_scale(renderer);
-        let size = scale * 16.0;
-        let x_offset = -(size * 4.5);
-        let y_offset = size * 4.18;
-        let hot_bar_offset = scale * 4.0;
-        let slot_offset = size + size * 1.0 / 8.0;
-        let slot_craft_0 = self.slots.get_mut(0).unwrap();
-        // slot_craft_0.update_position((size + size * 1.0 / 8.0) * (4.0 + 2.0), scale / 9.0 * 5.0 - (scale / 9.0 * 24.5 + size * 1.0 / 9.0 + size * (2.0 + 1.0 / 5.0) - (size + size * 1.0 / 9.0)), size);
-        slot_craft_0.update_position(0.0/*(size + size * 1.0 / 8.0) * 2.5*/, 0.0/*scale / 9.0 * 5.0 - (scale / 9.0 * 24.5 + size * 1.0 / 8.0 + size * (2.0 + 1.0 / 5.0) * 2.0)*/, size); // TODO: The 0th slot isn't rendered, no matter what! FIX THIS!
-        let slot_craft_1 = self.slots.get_mut(1).unwrap();
-        slot_craft_1.update_position(
-            size + size * 1.0 / 8.0,
-            scale * 5.0 - (scale * 24.5 + size * 1.0 / 8.0 + size * (2.0 + 1.0 / 5.0)),
-            size,
-        );
-        let slot_craft_2 = self.slots.get_mut(2).unwrap();
-        slot_craft_2.update_position(
-            (size + size * 1.0 / 8.0) * 2.0,
-            scale * 5.0 - (scale * 24.5 + size * 1.0 / 8.0 + size * (2.0 + 1.0 / 5.0)),
-            size,
-        );
-        let slot_craft_3 = self.slots.get_mut(3).unwrap();
-        slot_craft_3.update_position(
-            size + size * 1.0 / 8.0,
-            scale * 5.0
-                - (scale * 24.5 + size * 1.0 / 8.0 + size * (2.0 + 1.0 / 5.0) - slot_offset),
-            size,
-        );
-        let slot_craft_4 = self.slots.get_mut(4).unwrap();
-        slot_craft_4.update_position(
-            (size + size * 1.0 / 8.0) * 2.0,
-            scale * 5.0
-                - (scale * 24.5 + size * 1.0 / 8.0 + size * (2.0 + 1.0 / 5.0) - slot_offset),
-            size,
-        );
-        let slot_head = self.slots.get_mut(5).unwrap(); // 6th slot!
-        slot_head.update_position(
-            x_offset,
-            y_offset + -((6_f64 + 1.0 / 8.0) * slot_offset + size + hot_bar_offset * 2.0),
-            size,
-        );
-        let slot_chestplate = self.slots.get_mut(6).unwrap(); // 7th slot!
-        slot_chestplate.update_position(
-            x_offset,
-            y_offset + -((5_f64 + 1.0 / 8.0) * slot_offset + size + hot_bar_offset * 2.0),
-            size,
-        );
-        let slot_leggings = self.slots.get_mut(7).unwrap(); // 8th slot!
-        slot_leggings.update_position(
-            x_offset,
-            y_offset + -((4_f64 + 1.0 / 8.0) * slot_offset + size + hot_bar_offset * 2.0),
-            size,
-        );
-        let slot_boots = self.slots.get_mut(8).unwrap(); // 9th slot!
-        slot_boots.update_position(
-            x_offset,
-            y_offset + -((3_f64 + 1.0 / 8.0) * slot_offset + size + hot_bar_offset * 2.0),
-            size,
-        );
-        for y in (0..3).rev() {
+    /// The top-left corners of the slots on the inventory texture: the
+    /// crafting result and grid, armor, main inventory, hotbar and, since
+    /// 1.9, the off hand.
+    fn slot_positions(version: Version) -> Vec<(f64, f64)> {
+        let mut positions = vec![(154.0, 28.0)];
+        for y in 0..2 {
+            for x in 0..2 {
+                positions.push((98.0 + x as f64 * 18.0, 18.0 + y as f64 * 18.0));
+            }
+        }
+        for y in 0..4 {
+            positions.push((8.0, 8.0 + y as f64 * 18.0));
+        }
+        for y in 0..3 {
             for x in 0..9 {
-                self.slots
-                    .get_mut(9 + x + 9 * (2 - y))
-                    .unwrap()
-                    .update_position(
-                        x_offset + x as f64 * slot_offset,
-                        y_offset + -(y as f64 * slot_offset * 2.0 + hot_bar_offset),
-                        size,
-                    );
+                positions.push((8.0 + x as f64 * 18.0, 84.0 + y as f64 * 18.0));
             }
         }
-        for i in 0..9 {
-            self.slots.get_mut(36 + i).unwrap().update_position(
-                x_offset + i as f64 * (size + size * 1.0 / 8.0),
-                y_offset,
-                size,
-            );
+        for x in 0..9 {
+            positions.push((8.0 + x as f64 * 18.0, 142.0));
         }
-        if self.version > Version::V1_8 {
-            let slot = self.slots.get_mut(45).unwrap();
-            slot.update_position(-(scale * 3.0), scale * 5.0 - scale * 18.0, size);
+        if version > Version::V1_8 {
+            positions.push((77.0, 62.0));
+        }
+        positions
+    }
+
+    /// The window slot showing slot `index` of the player's inventory as
+    /// the server numbers it: the hotbar, the main inventory, the armor from
+    /// the feet up and, since 1.9, the off hand.
+    pub fn window_slot(version: Version, index: i16) -> Option<i16> {
+        match index {
+            0..=8 => Some(36 + index),
+            9..=35 => Some(index),
+            36..=39 => Some(8 - (index - 36)),
+            40 if version > Version::V1_8 => Some(45),
+            _ => None,
+        }
+    }
+
+    fn update_icons(&mut self, renderer: &Renderer) {
+        self.icon_scale = Hud::icon_scale(renderer);
+        for (slot, &position) in self.slots.iter_mut().zip(self.layout.slots.iter()) {
+            let (x, y) = self.layout.center(self.icon_scale, position, (16.0, 16.0));
+            slot.update_position(x, y, self.icon_scale * 16.0);
         }
         self.dirty = true;
     }
@@ -243,7 +101,7 @@ impl Inventory for PlayerInventory {
     }
 
     fn id(&self) -> i8 {
-        -1
+        0
     }
 
     fn name(&self) -> Option<&String> {
@@ -357,8 +215,52 @@ impl Inventory for PlayerInventory {
         // TODO
     }
 
-    fn click_at(&self, _cursor: (u32, u32)) {
-        // TODO
+    fn slot_at(&self, x: f64, y: f64) -> Option<i16> {
+        self.layout.slot_at(&self.slots, self.icon_scale, x, y)
+    }
+
+    fn slot_kind(&self, slot: i16) -> SlotKind {
+        match slot {
+            0 => SlotKind::CraftingResult(1..5),
+            // Before 1.12 the client worked out the recipe itself, so only
+            // the server can tell what changing the grid crafts
+            1..=4 if self.version < Version::V1_12 => SlotKind::Restricted,
+            5..=8 => SlotKind::Armor(slot as u8 - 5),
+            _ => SlotKind::Normal,
+        }
+    }
+
+    fn quick_move(&self, slot: i16) -> Option<(Range<i16>, bool)> {
+        let armor = self.slots[slot as usize]
+            .item
+            .as_ref()
+            .and_then(|item| item.material.armor_slot())
+            .map(|armor| 5 + armor as i16);
+        Some(match slot {
+            0 => return None,
+            1..=8 => (9..45, false),
+            _ if armor.is_some_and(|armor| self.slots[armor as usize].item.is_none()) => {
+                let armor = armor.unwrap();
+                (armor..armor + 1, false)
+            }
+            _ if self.version > Version::V1_8
+                && slot != 45
+                && self.slots[45].item.is_none()
+                && self.slots[slot as usize]
+                    .item
+                    .as_ref()
+                    .is_some_and(|item| matches!(item.material, Material::Shield)) =>
+            {
+                (45..46, false)
+            }
+            9..=35 => (36..45, false),
+            36..=44 => (9..36, false),
+            _ => (9..45, false),
+        })
+    }
+
+    fn hotbar_slot(&self, index: u8) -> i16 {
+        36 + index as i16
     }
 
     fn resize(
@@ -377,3 +279,37 @@ impl Inventory for PlayerInventory {
         InventoryType::Main
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn off_hand_since_1_9() {
+        assert_eq!(PlayerInventory::slot_positions(Version::V1_7).len(), 45);
+        assert_eq!(PlayerInventory::slot_positions(Version::V1_8).len(), 45);
+        assert_eq!(PlayerInventory::slot_positions(Version::V1_9).len(), 46);
+        assert_eq!(PlayerInventory::slot_positions(Version::V1_16).len(), 46);
+    }
+
+    #[test]
+    fn window_slots() {
+        let version = Version::V1_12;
+        assert_eq!(PlayerInventory::window_slot(version, 0), Some(36));
+        assert_eq!(PlayerInventory::window_slot(version, 8), Some(44));
+        assert_eq!(PlayerInventory::window_slot(version, 9), Some(9));
+        assert_eq!(PlayerInventory::window_slot(version, 35), Some(35));
+        // Boots are the first armor slot but the last in the window
+        assert_eq!(PlayerInventory::window_slot(version, 36), Some(8));
+        assert_eq!(PlayerInventory::window_slot(version, 39), Some(5));
+        assert_eq!(PlayerInventory::window_slot(version, 40), Some(45));
+        assert_eq!(PlayerInventory::window_slot(version, 41), None);
+        assert_eq!(PlayerInventory::window_slot(version, -1), None);
+    }
+
+    #[test]
+    fn no_off_hand_before_1_9() {
+        assert_eq!(PlayerInventory::window_slot(Version::V1_8, 40), None);
+        assert_eq!(PlayerInventory::window_slot(Version::V1_8, 39), Some(5));
+    }
+}
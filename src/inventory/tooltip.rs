@@ -0,0 +1,729 @@
+//! The lines shown when hovering an item, worked out from its NBT the way
+//! vanilla does.
+
+use crate::inventory::{Item, Material};
+use leafish_protocol::format::{convert_legacy, Color, Component, Modifier, TextComponent};
+use leafish_protocol::nbt::Tag;
+use leafish_protocol::protocol::Version;
+
+/// Bits of `HideFlags`.
+const HIDE_ENCHANTMENTS: i32 = 1;
+const HIDE_ATTRIBUTES: i32 = 2;
+const HIDE_UNBREAKABLE: i32 = 4;
+
+/// Enchantments by their id before 1.13, their name since and how they're
+/// shown, along with their highest level.
+const ENCHANTMENTS: &[(i16, &str, &str, u8)] = &[
+    (0, "protection", "Protection", 4),
+    (1, "fire_protection", "Fire Protection", 4),
+    (2, "feather_falling", "Feather Falling", 4),
+    (3, "blast_protection", "Blast Protection", 4),
+    (4, "projectile_protection", "Projectile Protection", 4),
+    (5, "respiration", "Respiration", 3),
+    (6, "aqua_affinity", "Aqua Affinity", 1),
+    (7, "thorns", "Thorns", 3),
+    (8, "depth_strider", "Depth Strider", 3),
+    (9, "frost_walker", "Frost Walker", 2),
+    (10, "binding_curse", "Curse of Binding", 1),
+    (16, "sharpness", "Sharpness", 5),
+    (17, "smite", "Smite", 5),
+    (18, "bane_of_arthropods", "Bane of Arthropods", 5),
+    (19, "knockback", "Knockback", 2),
+    (20, "fire_aspect", "Fire Aspect", 2),
+    (21, "looting", "Looting", 3),
+    (22, "sweeping", "Sweeping Edge", 3),
+    (32, "efficiency", "Efficiency", 5),
+    (33, "silk_touch", "Silk Touch", 1),
+    (34, "unbreaking", "Unbreaking", 3),
+    (35, "fortune", "Fortune", 3),
+    (48, "power", "Power", 5),
+    (49, "punch", "Punch", 2),
+    (50, "flame", "Flame", 1),
+    (51, "infinity", "Infinity", 1),
+    (61, "luck_of_the_sea", "Luck of the Sea", 3),
+    (62, "lure", "Lure", 3),
+    (70, "mending", "Mending", 1),
+    (71, "vanishing_curse", "Curse of Vanishing", 1),
+    (-1, "loyalty", "Loyalty", 3),
+    (-1, "impaling", "Impaling", 5),
+    (-1, "riptide", "Riptide", 3),
+    (-1, "channeling", "Channeling", 1),
+    (-1, "multishot", "Multishot", 1),
+    (-1, "quick_charge", "Quick Charge", 3),
+    (-1, "piercing", "Piercing", 4),
+    (-1, "soul_speed", "Soul Speed", 3),
+];
+
+/// Attributes by their name with the namespace, dots and underscores
+/// taken out and lowercased, as they're spelt differently across versions.
+const ATTRIBUTES: &[(&str, &str)] = &[
+    ("genericmaxhealth", "Max Health"),
+    ("genericfollowrange", "Mob Follow Range"),
+    ("genericknockbackresistance", "Knockback Resistance"),
+    ("genericmovementspeed", "Speed"),
+    ("genericflyingspeed", "Flying Speed"),
+    ("genericattackdamage", "Attack Damage"),
+    ("genericattackknockback", "Attack Knockback"),
+    ("genericattackspeed", "Attack Speed"),
+    ("genericarmor", "Armor"),
+    ("genericarmortoughness", "Armor Toughness"),
+    ("genericluck", "Luck"),
+    ("horsejumpstrength", "Horse Jump Strength"),
+    ("zombiespawnreinforcements", "Zombie Reinforcements"),
+];
+
+/// The item's tooltip, one component per line. Advanced tooltips add the
+/// item's id and durability.
+pub fn tooltip(item: &Item, version: Version, advanced: bool) -> Vec<Component> {
+    let tag = item
+        .stack
+        .tag
+        .as_ref()
+        .map(|tag| &tag.1)
+        .filter(|tag| tag.is_compound());
+    let get = |name: &str| tag.and_then(|tag| tag.get(name));
+    let display = get("display").filter(|display| display.is_compound());
+    let hide_flags = get("HideFlags")
+        .and_then(|flags| flags.as_int())
+        .unwrap_or(0);
+    let enchantments = get("Enchantments")
+        .or_else(|| get("ench"))
+        .or_else(|| get("StoredEnchantments"))
+        .and_then(|list| list.as_list())
+        .unwrap_or(&[]);
+
+    let mut name = match display.and_then(|display| display.get("Name")?.as_str()) {
+        Some(name) if version >= Version::V1_13 => Component::from_string(name),
+        Some(name) => legacy(name),
+        None => {
+            // Enchanted items are rare, which shows in their name
+            let color = if enchantments.is_empty() {
+                Color::White
+            } else {
+                Color::Aqua
+            };
+            text(&item_name(&item.material), color)
+        }
+    };
+    if advanced {
+        let damage = item.stack.damage.unwrap_or(0);
+        let id = if damage != 0 && item.material.max_durability().is_none() {
+            format!(" (#{:04}/{})", item.stack.id, damage)
+        } else {
+            format!(" (#{:04})", item.stack.id)
+        };
+        name = join(vec![name, text(&id, Color::White)]);
+    }
+    let mut lines = vec![name];
+
+    if hide_flags & HIDE_ENCHANTMENTS == 0 {
+        lines.extend(enchantments.iter().filter_map(enchantment_line));
+    }
+
+    if let Some(color) = display.and_then(|display| display.get("color")?.as_int()) {
+        lines.push(if advanced {
+            text(&format!("Color: #{:06X}", color & 0xFF_FFFF), Color::Gray)
+        } else {
+            text("Dyed", Color::Gray)
+        });
+    }
+
+    if let Some(lore) = display.and_then(|display| display.get("Lore")?.as_list()) {
+        for line in lore.iter().filter_map(|line| line.as_str()) {
+            let line = if version >= Version::V1_14 {
+                Component::from_string(line)
+            } else {
+                legacy(line)
+            };
+            lines.push(Component::Text(TextComponent {
+                text: String::new(),
+                modifier: Modifier {
+                    extra: Some(vec![line]),
+                    italic: Some(true),
+                    color: Some(Color::DarkPurple),
+                    ..Default::default()
+                },
+            }));
+        }
+    }
+
+    if hide_flags & HIDE_ATTRIBUTES == 0 {
+        if let Some(modifiers) = get("AttributeModifiers").and_then(|list| list.as_list()) {
+            lines.extend(attribute_lines(modifiers, version));
+        }
+    }
+
+    let unbreakable = get("Unbreakable")
+        .and_then(|unbreakable| unbreakable.as_byte())
+        .unwrap_or(0)
+        != 0;
+    if unbreakable && hide_flags & HIDE_UNBREAKABLE == 0 {
+        lines.push(text("Unbreakable", Color::Blue));
+    }
+
+    if advanced {
+        // The damage moved into the NBT in 1.13
+        let damage = if version >= Version::V1_13 {
+            get("Damage")
+                .and_then(|damage| damage.as_int())
+                .unwrap_or(0) as isize
+        } else {
+            item.stack.damage.unwrap_or(0)
+        };
+        if let Some(max) = item.material.max_durability().filter(|_| damage > 0) {
+            lines.push(text(
+                &format!("Durability: {} / {}", max - damage, max),
+                Color::White,
+            ));
+        }
+    }
+    lines
+}
+
+fn enchantment_line(enchantment: &Tag) -> Option<Component> {
+    if !enchantment.is_compound() {
+        return None;
+    }
+    let id = enchantment.get("id")?;
+    let (name, max_level) = match id.as_str() {
+        Some(id) => {
+            let id = id.strip_prefix("minecraft:").unwrap_or(id);
+            ENCHANTMENTS
+                .iter()
+                .find(|enchantment| enchantment.1 == id)
+                .map_or((id.to_owned(), 0), |enchantment| {
+                    (enchantment.2.to_owned(), enchantment.3)
+                })
+        }
+        None => {
+            let id = id.as_short().or_else(|| id.as_int().map(|id| id as i16))?;
+            ENCHANTMENTS
+                .iter()
+                .find(|enchantment| enchantment.0 == id)
+                .map_or((format!("#{}", id), 0), |enchantment| {
+                    (enchantment.2.to_owned(), enchantment.3)
+                })
+        }
+    };
+    let level = enchantment.get("lvl").and_then(|level| {
+        level
+            .as_short()
+            .map(|level| level as i32)
+            .or_else(|| level.as_int())
+    })?;
+    let color = if name.starts_with("Curse") {
+        Color::Red
+    } else {
+        Color::Gray
+    };
+    // Enchantments that only have the one level don't show it
+    if level == 1 && max_level == 1 {
+        Some(text(&name, color))
+    } else {
+        Some(text(&format!("{} {}", name, roman(level)), color))
+    }
+}
+
+fn attribute_lines(modifiers: &[Tag], version: Version) -> Vec<Component> {
+    let mut slots: Vec<(&str, Vec<Component>)> = vec![];
+    for modifier in modifiers.iter().filter(|modifier| modifier.is_compound()) {
+        let name = match modifier.get("AttributeName").and_then(|name| name.as_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        let amount = modifier
+            .get("Amount")
+            .and_then(|amount| amount.as_double())
+            .unwrap_or(0.0);
+        let operation = modifier
+            .get("Operation")
+            .and_then(|operation| operation.as_int())
+            .unwrap_or(0);
+        if amount == 0.0 {
+            continue;
+        }
+        // The other operations multiply, so they're shown in percent
+        let shown = if operation == 0 {
+            number(amount.abs())
+        } else {
+            format!("{}%", number(amount.abs() * 100.0))
+        };
+        let line = if amount > 0.0 {
+            text(&format!("+{} {}", shown, attribute_name(name)), Color::Blue)
+        } else {
+            text(&format!("-{} {}", shown, attribute_name(name)), Color::Red)
+        };
+        let slot = modifier
+            .get("Slot")
+            .and_then(|slot| slot.as_str())
+            .unwrap_or("mainhand");
+        match slots.iter_mut().find(|(other, _)| *other == slot) {
+            Some((_, lines)) => lines.push(line),
+            None => slots.push((slot, vec![line])),
+        }
+    }
+    let mut lines = vec![];
+    for (slot, modifiers) in slots {
+        lines.push(text("", Color::White));
+        // Modifiers only apply in particular slots since 1.9
+        if version >= Version::V1_9 {
+            let header = match slot {
+                "offhand" => "When in off hand:",
+                "head" => "When on head:",
+                "chest" => "When on body:",
+                "legs" => "When on legs:",
+                "feet" => "When on feet:",
+                _ => "When in main hand:",
+            };
+            lines.push(text(header, Color::Gray));
+        }
+        lines.extend(modifiers);
+    }
+    lines
+}
+
+fn attribute_name(name: &str) -> String {
+    let key: String = name
+        .strip_prefix("minecraft:")
+        .unwrap_or(name)
+        .chars()
+        .filter(|c| *c != '.' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect();
+    ATTRIBUTES
+        .iter()
+        .find(|attribute| attribute.0 == key)
+        .map_or_else(|| name.to_owned(), |attribute| attribute.1.to_owned())
+}
+
+/// The item's name in English. Most are the material's name spaced out,
+/// the ones named after Bukkit's materials before 1.13 go by vanilla's.
+pub fn item_name(material: &Material) -> String {
+    use self::Material::*;
+    match material {
+        Sulphur => "Gunpowder",
+        Watch => "Clock",
+        Diode => "Redstone Repeater",
+        Step => "Stone Slab",
+        WoodStep => "Wooden Slab",
+        InkSack => "Dye",
+        Wood => "Wood Planks",
+        Web => "Cobweb",
+        LongGrass => "Grass",
+        RedRose => "Poppy",
+        YellowFlower => "Dandelion",
+        SmoothBrick => "Stone Bricks",
+        SmoothStairs => "Stone Brick Stairs",
+        CobbleWall => "Cobblestone Wall",
+        Workbench => "Crafting Table",
+        WoodPlate => "Wooden Pressure Plate",
+        StonePlate => "Stone Pressure Plate",
+        WoodButton => "Wooden Button",
+        WoodStairs => "Oak Wood Stairs",
+        WoodDoor => "Oak Door",
+        TrapDoor => "Wooden Trapdoor",
+        ThinGlass => "Glass Pane",
+        IronFence => "Iron Bars",
+        NetherFence => "Nether Brick Fence",
+        Mycel => "Mycelium",
+        EnderStone => "End Stone",
+        MonsterEggs => "Monster Egg",
+        SkullItem => "Head",
+        Redstone => "Redstone Dust",
+        SnowBall => "Snowball",
+        ExpBottle => "Bottle o' Enchanting",
+        Fireball => "Fire Charge",
+        Leash => "Lead",
+        CarrotStick => "Carrot on a Stick",
+        CarrotItem => "Carrot",
+        PotatoItem => "Potato",
+        SpeckledMelon => "Glistering Melon",
+        NetherStalk => "Nether Wart",
+        Pork => "Raw Porkchop",
+        GrilledPork => "Cooked Porkchop",
+        MushroomSoup => "Mushroom Stew",
+        ClayBrick => "Brick",
+        NetherBrickItem => "Nether Brick",
+        StorageMinecart => "Minecart with Chest",
+        PoweredMinecart => "Minecart with Furnace",
+        ExplosiveMinecart => "Minecart with TNT",
+        HopperMinecart => "Minecart with Hopper",
+        CommandMinecart => "Minecart with Command Block",
+        IronBarding => "Iron Horse Armor",
+        GoldBarding => "Gold Horse Armor",
+        DiamondBarding => "Diamond Horse Armor",
+        GoldRecord | GreenRecord => "Music Disc",
+        WoodSword => "Wooden Sword",
+        WoodSpade => "Wooden Shovel",
+        WoodPickaxe => "Wooden Pickaxe",
+        WoodAxe => "Wooden Axe",
+        WoodHoe => "Wooden Hoe",
+        StoneSpade => "Stone Shovel",
+        IronSpade => "Iron Shovel",
+        DiamondSpade => "Diamond Shovel",
+        GoldSword => "Golden Sword",
+        GoldSpade => "Golden Shovel",
+        GoldPickaxe => "Golden Pickaxe",
+        GoldAxe => "Golden Axe",
+        GoldHoe => "Golden Hoe",
+        GoldHelmet => "Golden Helmet",
+        GoldChestplate => "Golden Chestplate",
+        GoldLeggings => "Golden Leggings",
+        GoldBoots => "Golden Boots",
+        _ => return display_name(&material.name()),
+    }
+    .to_owned()
+}
+
+/// Spaces out the words of a material's name, "DiamondSword" becomes
+/// "Diamond Sword".
+fn display_name(name: &str) -> String {
+    let mut spaced = String::with_capacity(name.len() + 4);
+    for (i, c) in name.char_indices() {
+        if i > 0
+            && (c.is_uppercase()
+                || c.is_ascii_digit() && !name[..i].ends_with(|c: char| c.is_ascii_digit()))
+        {
+            spaced.push(' ');
+        }
+        spaced.push(c);
+    }
+    spaced
+}
+
+/// Levels in roman numerals like vanilla's translations have them.
+fn roman(mut number: i32) -> String {
+    if !(1..4000).contains(&number) {
+        return number.to_string();
+    }
+    let mut roman = String::new();
+    for &(value, numeral) in &[
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ] {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
+
+/// Numbers with at most two decimals and no trailing zeros.
+fn number(value: f64) -> String {
+    let value = format!("{:.2}", value);
+    value.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+/// Text with the § formatting codes used before components.
+fn legacy(text: &str) -> Component {
+    let mut component = Component::Text(TextComponent::new(text));
+    convert_legacy(&mut component);
+    component
+}
+
+fn text(text: &str, color: Color) -> Component {
+    Component::Text(TextComponent {
+        text: text.to_owned(),
+        modifier: Modifier {
+            color: Some(color),
+            ..Default::default()
+        },
+    })
+}
+
+fn join(parts: Vec<Component>) -> Component {
+    Component::Text(TextComponent {
+        text: String::new(),
+        modifier: Modifier {
+            extra: Some(parts),
+            ..Default::default()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::Material;
+    use leafish_protocol::item::Stack;
+    use leafish_protocol::nbt::NamedTag;
+
+    fn sword(damage: isize, tag: Tag) -> Item {
+        Item {
+            stack: Stack {
+                id: 276,
+                count: 1,
+                damage: Some(damage),
+                tag: Some(NamedTag("".to_owned(), tag)),
+            },
+            material: Material::DiamondSword,
+        }
+    }
+
+    fn compound(entries: Vec<(&str, Tag)>) -> Tag {
+        let mut tag = Tag::new_compound();
+        for (name, entry) in entries {
+            tag.put(name, entry);
+        }
+        tag
+    }
+
+    fn modifier(name: &str, amount: f64, operation: i32, slot: Option<&str>) -> Tag {
+        let mut modifier = compound(vec![
+            ("AttributeName", Tag::String(name.to_owned())),
+            ("Amount", Tag::Double(amount)),
+            ("Operation", Tag::Int(operation)),
+        ]);
+        if let Some(slot) = slot {
+            modifier.put("Slot", Tag::String(slot.to_owned()));
+        }
+        modifier
+    }
+
+    fn color(line: &Component) -> String {
+        let Component::Text(text) = line;
+        format!("{:?}", text.modifier.color)
+    }
+
+    fn text_of(lines: &[Component]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn modern_tooltip() {
+        let item = sword(
+            0,
+            compound(vec![
+                (
+                    "display",
+                    compound(vec![
+                        ("Name", Tag::String(r#"{"text":"Excalibur"}"#.to_owned())),
+                        (
+                            "Lore",
+                            Tag::List(vec![Tag::String(r#"{"text":"Forged in fire"}"#.to_owned())]),
+                        ),
+                    ]),
+                ),
+                (
+                    "Enchantments",
+                    Tag::List(vec![
+                        compound(vec![
+                            ("id", Tag::String("minecraft:sharpness".to_owned())),
+                            ("lvl", Tag::Short(5)),
+                        ]),
+                        compound(vec![
+                            ("id", Tag::String("minecraft:mending".to_owned())),
+                            ("lvl", Tag::Short(1)),
+                        ]),
+                        compound(vec![
+                            ("id", Tag::String("minecraft:binding_curse".to_owned())),
+                            ("lvl", Tag::Short(1)),
+                        ]),
+                    ]),
+                ),
+                (
+                    "AttributeModifiers",
+                    Tag::List(vec![
+                        modifier("minecraft:generic.attack_damage", 7.0, 0, Some("mainhand")),
+                        modifier("minecraft:generic.attack_speed", -2.4, 0, Some("mainhand")),
+                        modifier("minecraft:generic.movement_speed", 0.1, 1, Some("offhand")),
+                    ]),
+                ),
+            ]),
+        );
+        let lines = tooltip(&item, Version::V1_16, false);
+        assert_eq!(
+            text_of(&lines),
+            vec![
+                "Excalibur",
+                "Sharpness V",
+                "Mending",
+                "Curse of Binding",
+                "Forged in fire",
+                "",
+                "When in main hand:",
+                "+7 Attack Damage",
+                "-2.4 Attack Speed",
+                "",
+                "When in off hand:",
+                "+10% Speed",
+            ]
+        );
+        assert_eq!(color(&lines[1]), "Some(Gray)");
+        assert_eq!(color(&lines[3]), "Some(Red)");
+        assert_eq!(color(&lines[4]), "Some(DarkPurple)");
+        assert_eq!(color(&lines[7]), "Some(Blue)");
+        assert_eq!(color(&lines[8]), "Some(Red)");
+    }
+
+    #[test]
+    fn legacy_tooltip() {
+        let item = sword(
+            0,
+            compound(vec![
+                (
+                    "display",
+                    compound(vec![(
+                        "Lore",
+                        Tag::List(vec![Tag::String("\u{a7}6Old".to_owned())]),
+                    )]),
+                ),
+                (
+                    "ench",
+                    Tag::List(vec![
+                        compound(vec![("id", Tag::Short(16)), ("lvl", Tag::Short(2))]),
+                        compound(vec![("id", Tag::Short(99)), ("lvl", Tag::Short(1))]),
+                    ]),
+                ),
+                (
+                    "AttributeModifiers",
+                    Tag::List(vec![modifier("generic.attackDamage", 7.0, 0, None)]),
+                ),
+            ]),
+        );
+        let lines = tooltip(&item, Version::V1_8, false);
+        // Modifiers weren't tied to a slot yet, so there's no header
+        assert_eq!(
+            text_of(&lines),
+            vec![
+                "Diamond Sword",
+                "Sharpness II",
+                "#99 I",
+                "Old",
+                "",
+                "+7 Attack Damage",
+            ]
+        );
+        // Enchanted items without a name of their own are shown in aqua
+        assert_eq!(color(&lines[0]), "Some(Aqua)");
+    }
+
+    #[test]
+    fn advanced_tooltip_with_hidden_flags() {
+        let item = sword(
+            100,
+            compound(vec![
+                ("HideFlags", Tag::Int(HIDE_ENCHANTMENTS | HIDE_ATTRIBUTES)),
+                ("Unbreakable", Tag::Byte(1)),
+                (
+                    "ench",
+                    Tag::List(vec![compound(vec![
+                        ("id", Tag::Short(16)),
+                        ("lvl", Tag::Short(2)),
+                    ])]),
+                ),
+                (
+                    "AttributeModifiers",
+                    Tag::List(vec![modifier("generic.attackDamage", 7.0, 0, None)]),
+                ),
+            ]),
+        );
+        assert_eq!(
+            text_of(&tooltip(&item, Version::V1_12, true)),
+            vec![
+                "Diamond Sword (#0276)",
+                "Unbreakable",
+                "Durability: 1461 / 1561",
+            ]
+        );
+    }
+
+    #[test]
+    fn dyed_tooltip() {
+        let item = Item {
+            stack: Stack {
+                id: 298,
+                count: 1,
+                damage: Some(0),
+                tag: Some(NamedTag(
+                    "".to_owned(),
+                    compound(vec![
+                        (
+                            "display",
+                            compound(vec![
+                                ("color", Tag::Int(0x00A0_6540)),
+                                ("Lore", Tag::List(vec![Tag::String("Worn".to_owned())])),
+                            ]),
+                        ),
+                        (
+                            "ench",
+                            Tag::List(vec![compound(vec![
+                                ("id", Tag::Short(0)),
+                                ("lvl", Tag::Short(1)),
+                            ])]),
+                        ),
+                    ]),
+                )),
+            },
+            material: Material::LeatherHelmet,
+        };
+        // The colour goes between the enchantments and the lore
+        assert_eq!(
+            text_of(&tooltip(&item, Version::V1_12, false))[1..],
+            ["Protection I", "Dyed", "Worn"]
+        );
+        assert_eq!(
+            text_of(&tooltip(&item, Version::V1_12, true))[1..],
+            ["Protection I", "Color: #A06540", "Worn"]
+        );
+    }
+
+    #[test]
+    fn roman_numerals() {
+        assert_eq!(roman(1), "I");
+        assert_eq!(roman(4), "IV");
+        assert_eq!(roman(5), "V");
+        assert_eq!(roman(9), "IX");
+        assert_eq!(roman(10), "X");
+        assert_eq!(roman(14), "XIV");
+        assert_eq!(roman(255), "CCLV");
+        assert_eq!(roman(0), "0");
+        assert_eq!(roman(-3), "-3");
+    }
+
+    #[test]
+    fn display_names() {
+        assert_eq!(display_name("DiamondSword"), "Diamond Sword");
+        assert_eq!(display_name("Stone"), "Stone");
+        assert_eq!(display_name("Record11"), "Record 11");
+    }
+
+    #[test]
+    fn item_names() {
+        assert_eq!(item_name(&Material::DiamondSword), "Diamond Sword");
+        assert_eq!(item_name(&Material::Sulphur), "Gunpowder");
+        assert_eq!(item_name(&Material::GoldSpade), "Golden Shovel");
+        assert_eq!(item_name(&Material::OakSlab), "Oak Slab");
+    }
+
+    #[test]
+    fn attribute_names() {
+        assert_eq!(attribute_name("generic.attackDamage"), "Attack Damage");
+        assert_eq!(
+            attribute_name("minecraft:generic.attack_damage"),
+            "Attack Damage"
+        );
+        assert_eq!(attribute_name("generic.maxHealth"), "Max Health");
+        assert_eq!(attribute_name("mod.custom"), "mod.custom");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number(7.0), "7");
+        assert_eq!(number(0.5), "0.5");
+        assert_eq!(number(12.345), "12.35");
+        assert_eq!(number(150.0), "150");
+    }
+}
@@ -98,6 +98,7 @@ pub struct Game {
     last_mouse_yrel: f64,
     is_ctrl_pressed: bool,
     is_logo_pressed: bool,
+    is_shift_pressed: bool,
     is_fullscreen: bool,
     default_protocol_version: i32,
     current_account: Arc<Mutex<Option<Account>>>,
@@ -309,7 +310,7 @@ fn main() {
         vars.set(auth::CL_USERNAME, username);
     }
 
-    let textures = renderer.get_textures();
+    let block_models = renderer.block_models.clone();
     let default_protocol_version = protocol::versions::protocol_name_to_protocol_version(
         opt.default_protocol_version
             .unwrap_or_else(|| "".to_string()),
@@ -339,7 +340,7 @@ fn main() {
         console: con,
         vars,
         should_close: false,
-        chunk_builder: chunk_builder::ChunkBuilder::new(resource_manager, textures),
+        chunk_builder: chunk_builder::ChunkBuilder::new(block_models),
         connect_error: None,
         dpi_factor,
         last_mouse_x: 0.0,
@@ -348,6 +349,7 @@ fn main() {
         last_mouse_yrel: 0.0,
         is_ctrl_pressed: false,
         is_logo_pressed: false,
+        is_shift_pressed: false,
         is_fullscreen: false,
         default_protocol_version,
         clipboard_provider: Arc::new(RwLock::new(clipboard)),
@@ -516,6 +518,7 @@ fn tick_all(
             game.server.as_ref().unwrap().world.clone(),
             game.renderer.clone(),
             version,
+            &game.vars,
         );
     } else if game.renderer.clone().read().safe_width != physical_width
         || game.renderer.clone().read().safe_height != physical_height
@@ -653,6 +656,7 @@ fn handle_window_event<T>(
                 WindowEvent::ModifiersChanged(modifiers_state) => {
                     game.is_ctrl_pressed = modifiers_state.ctrl();
                     game.is_logo_pressed = modifiers_state.logo();
+                    game.is_shift_pressed = modifiers_state.shift();
                 }
                 WindowEvent::CloseRequested => game.should_close = true,
                 WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
@@ -671,43 +675,52 @@ fn handle_window_event<T>(
                     }
                 }
 
-                WindowEvent::MouseInput { state, button, .. } => match (state, button) {
-                    (ElementState::Released, MouseButton::Left) => {
-                        let physical_size = window.inner_size();
-                        let (width, height) =
-                            physical_size.to_logical::<f64>(game.dpi_factor).into();
-                        if !game.screen_sys.clone().is_current_ingame() && !game.focused {
-                            // TODO: after Pointer Lock https://github.com/rust-windowing/winit/issues/1674
-                            ui_container.click_at(
-                                game,
-                                game.last_mouse_x,
-                                game.last_mouse_y,
-                                width,
-                                height,
-                            );
-                        }
-                        if game.focused && game.server.is_some() {
-                            game.server.as_ref().unwrap().on_release_left_click();
-                        }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    if !game.focused {
+                        game.screen_sys.clone().on_mouse_input(
+                            button,
+                            state == ElementState::Pressed,
+                            game,
+                        );
                     }
-                    (ElementState::Pressed, MouseButton::Right) => {
-                        if game.focused && game.server.is_some() {
-                            game.server
-                                .as_ref()
-                                .unwrap()
-                                .on_right_click(game.renderer.clone());
+                    match (state, button) {
+                        (ElementState::Released, MouseButton::Left) => {
+                            let physical_size = window.inner_size();
+                            let (width, height) =
+                                physical_size.to_logical::<f64>(game.dpi_factor).into();
+                            if !game.screen_sys.clone().is_current_ingame() && !game.focused {
+                                // TODO: after Pointer Lock https://github.com/rust-windowing/winit/issues/1674
+                                ui_container.click_at(
+                                    game,
+                                    game.last_mouse_x,
+                                    game.last_mouse_y,
+                                    width,
+                                    height,
+                                );
+                            }
+                            if game.focused && game.server.is_some() {
+                                game.server.as_ref().unwrap().on_release_left_click();
+                            }
                         }
-                    }
-                    (ElementState::Pressed, MouseButton::Left) => {
-                        if game.focused && game.server.is_some() {
-                            game.server
-                                .as_ref()
-                                .unwrap()
-                                .on_left_click(game.renderer.clone());
+                        (ElementState::Pressed, MouseButton::Right) => {
+                            if game.focused && game.server.is_some() {
+                                game.server
+                                    .as_ref()
+                                    .unwrap()
+                                    .on_right_click(game.renderer.clone());
+                            }
                         }
+                        (ElementState::Pressed, MouseButton::Left) => {
+                            if game.focused && game.server.is_some() {
+                                game.server
+                                    .as_ref()
+                                    .unwrap()
+                                    .on_left_click(game.renderer.clone());
+                            }
+                        }
+                        (_, _) => (),
                     }
-                    (_, _) => (),
-                },
+                }
                 WindowEvent::CursorMoved { position, .. } => {
                     let (x, y) = position.to_logical::<f64>(game.dpi_factor).into();
                     game.last_mouse_x = x;
@@ -718,6 +731,7 @@ fn handle_window_event<T>(
                         let (width, height) =
                             physical_size.to_logical::<f64>(game.dpi_factor).into();
                         ui_container.hover_at(game, x, y, width, height);
+                        game.screen_sys.clone().on_cursor_move(x, y, game);
                     }
                 }
                 WindowEvent::MouseWheel { delta, .. } => {
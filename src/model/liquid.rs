@@ -9,6 +9,7 @@ use std::sync::Arc;
 pub fn render_liquid<W: Write>(
     textures: Arc<RwLock<render::TextureManager>>,
     lava: bool,
+    biome_blend: i32,
     snapshot: &world::ComposedSection,
     x: i32,
     y: i32,
@@ -49,6 +50,15 @@ pub fn render_liquid<W: Write>(
         }
         _ => unreachable!(),
     };
+    let (cr, cg, cb) = if lava {
+        (255, 255, 255)
+    } else {
+        let protocol_version = snapshot.protocol_version;
+        super::blend_biome(snapshot, x, y, z, biome_blend, |biome| {
+            biome.get_water_tint(protocol_version)
+        })
+    };
+
     let ux1 = 0i16;
     let ux2 = 16i16 * tex.get_width() as i16;
     let uy1 = 0i16;
@@ -71,9 +81,9 @@ pub fn render_liquid<W: Write>(
                 vert.tw = tex.get_width() as u16;
                 vert.th = tex.get_height() as u16;
                 vert.tatlas = tex.atlas as i16;
-                vert.r = 255;
-                vert.g = 255;
-                vert.b = 255;
+                vert.r = cr;
+                vert.g = cg;
+                vert.b = cb;
 
                 if vert.y == 0.0 {
                     vert.y = y as f32;
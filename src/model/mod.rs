@@ -4,8 +4,10 @@ use crate::render;
 use crate::resources;
 use crate::shared::Direction;
 use crate::world;
+use crate::world::biome::{self, Biome};
 use crate::world::block::{Block, TintType};
 use byteorder::{NativeEndian, WriteBytesExt};
+use cgmath::{Deg, Matrix4, Vector3, Vector4};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::Write;
@@ -15,7 +17,6 @@ use crate::types::hash::FNVHash;
 use log::error;
 use std::hash::BuildHasherDefault;
 
-use image::GenericImageView;
 use parking_lot::RwLock;
 use rand::seq::SliceRandom;
 use rand::Rng;
@@ -25,9 +26,13 @@ pub struct Factory {
     pub textures: Arc<RwLock<render::TextureManager>>,
 
     models: HashMap<Key, StateModel, BuildHasherDefault<FNVHash>>,
+    item_models: HashMap<String, Option<Arc<ItemModel>>, BuildHasherDefault<FNVHash>>,
 
     grass_colors: image::DynamicImage,
     foliage_colors: image::DynamicImage,
+
+    smooth_lighting: bool,
+    biome_blend: i32,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -70,6 +75,9 @@ impl Factory {
             textures,
 
             models: HashMap::with_hasher(BuildHasherDefault::default()),
+            item_models: HashMap::with_hasher(BuildHasherDefault::default()),
+            smooth_lighting: true,
+            biome_blend: 1,
         }
     }
 
@@ -86,30 +94,40 @@ impl Factory {
         image::load_from_memory(&data).unwrap()
     }
 
+    pub fn set_smooth_lighting(&mut self, smooth_lighting: bool) {
+        self.smooth_lighting = smooth_lighting;
+    }
+
+    /// Sets how many columns around a block are averaged for biome tints.
+    pub fn set_biome_blend(&mut self, radius: i32) {
+        self.biome_blend = radius;
+    }
+
+    pub fn biome_blend(&self) -> i32 {
+        self.biome_blend
+    }
+
     pub fn version_change(&mut self) {
         self.models.clear();
+        self.item_models.clear();
         self.grass_colors = Factory::load_biome_colors(self.resources.clone(), "grass");
         self.foliage_colors = Factory::load_biome_colors(self.resources.clone(), "foliage");
     }
 
-    fn get_model<R: Rng, W: Write>(
+    fn get_model<R: Rng, T, F: FnMut(&Factory, &Model) -> T>(
         &self,
         key: Key,
         block: Block,
         rng: &mut R,
-        snapshot: &world::ComposedSection,
-        x: i32,
-        y: i32,
-        z: i32,
-        buf: &mut W,
-    ) -> Result<usize, bool> {
+        f: &mut F,
+    ) -> Result<T, bool> {
         use std::collections::hash_map::Entry;
         if let Some(model) = self.models.get(&key) {
             if model.multipart.is_empty() {
                 let variant = block.get_model_variant();
                 if let Some(var) = model.get_variants(&variant) {
                     let model = var.choose_model(rng);
-                    return Ok(model.render(self, snapshot, x, y, z, buf));
+                    return Ok(f(self, model));
                 }
             } else {
                 return MULTIPART_CACHE.with(|cache| {
@@ -117,7 +135,7 @@ impl Factory {
                     let entry = cache.entry((key.clone(), block));
                     match entry {
                         Entry::Occupied(e) => {
-                            return Ok(e.get().render(self, snapshot, x, y, z, buf));
+                            return Ok(f(self, e.get()));
                         }
                         Entry::Vacant(e) => {
                             let mut res: Option<Model> = None;
@@ -132,7 +150,7 @@ impl Factory {
                                 }
                             }
                             if let Some(mdl) = res {
-                                return Ok(e.insert(mdl).render(self, snapshot, x, y, z, buf));
+                                return Ok(f(self, e.insert(mdl)));
                             }
                         }
                     };
@@ -179,12 +197,39 @@ impl Factory {
         z: i32,
         buf: &mut W,
     ) -> usize {
+        Factory::with_state_model(models, block, rng, &mut |factory, model| {
+            model.render(factory, snapshot, x, y, z, buf)
+        })
+    }
+
+    /// Returns the faces of the model of a block as vertices for
+    /// `render::model`, for drawing blocks outside of the world like
+    /// falling sand. Nothing is culled and the lighting is left to the
+    /// model.
+    pub fn get_state_vertices<R: Rng>(
+        models: &Arc<RwLock<Factory>>,
+        block: Block,
+        rng: &mut R,
+    ) -> Vec<render::model::Vertex> {
+        Factory::with_state_model(models, block, rng, &mut |factory, model| {
+            model.vertices(factory, block)
+        })
+    }
+
+    /// Calls `f` with the model for the block, loading it first if needed
+    /// and falling back to the missing block model.
+    fn with_state_model<R: Rng, T, F: FnMut(&Factory, &Model) -> T>(
+        models: &Arc<RwLock<Factory>>,
+        block: Block,
+        rng: &mut R,
+        f: &mut F,
+    ) -> T {
         let (plugin, name) = block.get_model();
         let key = Key(plugin.to_owned(), name.to_owned());
         let mut missing_variant;
         {
             let m = models.read();
-            match m.get_model(key.clone(), block, rng, snapshot, x, y, z, buf) {
+            match m.get_model(key.clone(), block, rng, f) {
                 Ok(val) => return val,
                 Err(val) => missing_variant = val,
             };
@@ -195,12 +240,12 @@ impl Factory {
             if !m.models.contains_key(&key) && !m.load_model(&plugin, &name) {
                 error!("Error loading model {}:{}", plugin, name);
             }
-            match m.get_model(key.clone(), block, rng, snapshot, x, y, z, buf) {
+            match m.get_model(key.clone(), block, rng, f) {
                 Ok(val) => return val,
                 Err(val) => missing_variant = val,
             };
         }
-        let ret = Factory::get_state_model(models, Block::Missing {}, rng, snapshot, x, y, z, buf);
+        let ret = Factory::with_state_model(models, Block::Missing {}, rng, f);
         if !missing_variant {
             // Still no model, replace with placeholder
             let mut m = models.write();
@@ -214,6 +259,71 @@ impl Factory {
         ret
     }
 
+    /// Returns the model of the item with the given name, loading it first
+    /// if needed. Items without a model, like most before 1.8, return None.
+    pub fn get_item_model(models: &Arc<RwLock<Factory>>, name: &str) -> Option<Arc<ItemModel>> {
+        if let Some(model) = models.read().item_models.get(name) {
+            return model.clone();
+        }
+        let mut m = models.write();
+        let model = m.load_item_model("minecraft", name).map(Arc::new);
+        m.item_models.insert(name.to_owned(), model.clone());
+        model
+    }
+
+    fn load_item_model(&self, plugin: &str, name: &str) -> Option<ItemModel> {
+        let file = self
+            .resources
+            .read()
+            .open(plugin, &format!("models/item/{}.json", name))?;
+        let item_model: serde_json::Value = try_log!(opt serde_json::from_reader(file));
+        let mut raw = self.parse_model(plugin, &item_model)?;
+        let display = std::mem::take(&mut raw.display);
+
+        let mut vertices = vec![];
+        let generated = matches!(raw.builtin, BuiltinType::Generated);
+        if generated {
+            // Sprites are a pixel thick in the middle of the block, with
+            // every layer on top of the last
+            for layer in 0.. {
+                let texture = match raw.texture_vars.get(&format!("layer{}", layer)) {
+                    Some(texture) => raw.lookup_texture(texture),
+                    None => break,
+                };
+                let texture = render::Renderer::get_texture(&self.textures, &texture);
+                render::model::append_sprite(
+                    &mut vertices,
+                    0.0,
+                    0.0,
+                    7.5 / 16.0,
+                    1.0,
+                    1.0,
+                    1.0 / 16.0,
+                    &texture,
+                );
+            }
+        } else {
+            // Items don't know where they are, vanilla tints them like
+            // they were in the plains
+            let model = self.process_model(raw);
+            vertices = model.vertices_tinted(|texture| {
+                if texture.contains("leaves") || texture.contains("vine") {
+                    biome::PLAINS.get_foliage_color(&self.foliage_colors)
+                } else {
+                    biome::PLAINS.get_grass_color(&self.grass_colors)
+                }
+            });
+        }
+        if vertices.is_empty() {
+            return None;
+        }
+        Some(ItemModel {
+            vertices,
+            display,
+            generated,
+        })
+    }
+
     fn load_model(&mut self, plugin: &str, name: &str) -> bool {
         let file = match self
             .resources
@@ -406,11 +516,36 @@ impl Factory {
             }
         }
 
-        // TODO: Display
+        if let Some(display) = v.get("display").and_then(|v| v.as_object()) {
+            for (k, v) in display {
+                model
+                    .display
+                    .insert(k.clone(), Self::parse_model_display(v));
+            }
+        }
 
         Some(model)
     }
 
+    fn parse_model_display(v: &serde_json::Value) -> ModelDisplay {
+        let vector = |name: &str, default: f64| {
+            v.get(name)
+                .and_then(|v| v.as_array())
+                .map_or([default, default, default], |v| {
+                    [
+                        v[0].as_f64().unwrap_or(default),
+                        v[1].as_f64().unwrap_or(default),
+                        v[2].as_f64().unwrap_or(default),
+                    ]
+                })
+        };
+        ModelDisplay {
+            rotation: vector("rotation", 0.0),
+            translation: vector("translation", 0.0),
+            scale: vector("scale", 1.0),
+        }
+    }
+
     fn parse_block_element(&self, v: &serde_json::Value) -> ModelElement {
         let mut element = ModelElement {
             from: v
@@ -895,11 +1030,93 @@ impl RawModel {
     }
 }
 
-#[derive(Debug)]
-struct ModelDisplay {
-    rotation: [f64; 3],
-    translation: [f64; 3],
-    scale: [f64; 3],
+/// How an item model is placed in a context like the GUI or the hand.
+/// Translations are in pixels of the block and rotations in degrees.
+#[derive(Clone, Copy, Debug)]
+pub struct ModelDisplay {
+    pub rotation: [f64; 3],
+    pub translation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+impl Default for ModelDisplay {
+    fn default() -> Self {
+        ModelDisplay {
+            rotation: [0.0; 3],
+            translation: [0.0; 3],
+            scale: [1.0; 3],
+        }
+    }
+}
+
+impl ModelDisplay {
+    /// The transform for vertices of a model centred on the origin, they
+    /// get scaled first, then rotated around z, y and x and then moved.
+    pub fn matrix(&self) -> Matrix4<f32> {
+        let [tx, ty, tz] = self.translation;
+        let [rx, ry, rz] = self.rotation;
+        let [sx, sy, sz] = self.scale;
+        Matrix4::from_translation(Vector3::new(tx as f32, ty as f32, tz as f32) / 16.0)
+            * Matrix4::from_angle_x(Deg(rx as f32))
+            * Matrix4::from_angle_y(Deg(ry as f32))
+            * Matrix4::from_angle_z(Deg(rz as f32))
+            * Matrix4::from_nonuniform_scale(sx as f32, sy as f32, sz as f32)
+    }
+
+    /// The display flipped to the other side, which vanilla applies for
+    /// the left hand.
+    pub fn mirrored(&self) -> ModelDisplay {
+        let mut display = *self;
+        display.translation[0] = -display.translation[0];
+        display.rotation[1] = -display.rotation[1];
+        display.rotation[2] = -display.rotation[2];
+        display
+    }
+}
+
+/// The model of an item, either the block model its item model inherits
+/// from or its sprite extruded by a pixel.
+pub struct ItemModel {
+    /// Quads of the model within the unit block
+    pub vertices: Vec<render::model::Vertex>,
+    display: HashMap<String, ModelDisplay, BuildHasherDefault<FNVHash>>,
+    /// Whether the model is a sprite rather than a block
+    pub generated: bool,
+}
+
+impl ItemModel {
+    /// How the model is shown in the context, like "gui" or
+    /// "firstperson_righthand". Models from before 1.9 only have one
+    /// display for both hands, like "firstperson".
+    pub fn display(&self, context: &str) -> ModelDisplay {
+        self.display
+            .get(context)
+            .or_else(|| {
+                let both_hands = context
+                    .strip_suffix("_righthand")
+                    .or_else(|| context.strip_suffix("_lefthand"))?;
+                self.display.get(both_hands)
+            })
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The vertices of the model centred on the origin and transformed for
+    /// the context.
+    pub fn transformed(&self, context: &str) -> Vec<render::model::Vertex> {
+        let matrix = self.display(context).matrix();
+        self.vertices
+            .iter()
+            .map(|vert| {
+                let pos = matrix * Vector4::new(vert.x - 0.5, vert.y - 0.5, vert.z - 0.5, 1.0);
+                let mut vert = vert.clone();
+                vert.x = pos.x;
+                vert.y = pos.y;
+                vert.z = pos.z;
+                vert
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -966,6 +1183,8 @@ impl Model {
 
         let tint = this.get_tint();
 
+        let smooth = factory.smooth_lighting && self.ambient_occlusion;
+
         for face in &self.faces {
             if face.cull_face != Direction::Invalid && !this_mat.never_cull {
                 let (ox, oy, oz) = face.cull_face.get_offset();
@@ -976,7 +1195,14 @@ impl Model {
             }
             indices += face.indices;
 
-            for vert in &face.vertices {
+            let lighting = if smooth {
+                Some(calculate_smooth_light(snapshot, x, y, z, face))
+            } else {
+                None
+            };
+
+            let mut verts = Vec::with_capacity(face.vertices.len());
+            for (i, vert) in face.vertices.iter().enumerate() {
                 let mut vert = vert.clone();
 
                 vert.x += x as f32;
@@ -987,17 +1213,21 @@ impl Model {
                     match tint {
                         TintType::Default => (255, 255, 255),
                         TintType::Color { r, g, b } => (r, g, b),
-                        TintType::Grass => calculate_biome(
+                        TintType::Grass => blend_biome(
                             snapshot,
                             vert.x as i32,
+                            vert.y as i32,
                             vert.z as i32,
-                            &factory.grass_colors,
+                            factory.biome_blend,
+                            |bi| bi.get_grass_color(&factory.grass_colors),
                         ),
-                        TintType::Foliage => calculate_biome(
+                        TintType::Foliage => blend_biome(
                             snapshot,
                             vert.x as i32,
+                            vert.y as i32,
                             vert.z as i32,
-                            &factory.foliage_colors,
+                            factory.biome_blend,
+                            |bi| bi.get_foliage_color(&factory.foliage_colors),
                         ),
                     }
                 } else {
@@ -1009,57 +1239,125 @@ impl Model {
                     cb = ((cb as f64) * 0.8) as u8;
                 }
 
+                let (bl, sl) = if let Some(lighting) = lighting.as_ref() {
+                    let ao = lighting.ao[i];
+                    cr = ((cr as f32) * ao) as u8;
+                    cg = ((cg as f32) * ao) as u8;
+                    cb = ((cb as f32) * ao) as u8;
+                    (lighting.block_light[i], lighting.sky_light[i])
+                } else {
+                    calculate_light(
+                        snapshot,
+                        x,
+                        y,
+                        z,
+                        vert.x as f64,
+                        vert.y as f64,
+                        vert.z as f64,
+                        face.facing,
+                        false,
+                        this_mat.force_shade,
+                    )
+                };
+
                 vert.r = cr;
                 vert.g = cg;
                 vert.b = cb;
-
-                let (bl, sl) = calculate_light(
-                    snapshot,
-                    x,
-                    y,
-                    z,
-                    vert.x as f64,
-                    vert.y as f64,
-                    vert.z as f64,
-                    face.facing,
-                    self.ambient_occlusion,
-                    this_mat.force_shade,
-                );
                 vert.block_light = bl;
                 vert.sky_light = sl;
-                vert.write(buf);
+                verts.push(vert);
+            }
+
+            // Quads are split along the 1-2 diagonal. When the other diagonal
+            // is brighter the interpolation shows a visible seam, so rotate
+            // the vertices to split along 0-3 instead.
+            match lighting {
+                Some(lighting) if verts.len() == 4 && lighting.should_flip() => {
+                    for i in &[1, 3, 0, 2] {
+                        verts[*i].write(buf);
+                    }
+                }
+                _ => {
+                    for vert in &verts {
+                        vert.write(buf);
+                    }
+                }
             }
         }
         indices
     }
+
+    fn vertices(&self, factory: &Factory, block: Block) -> Vec<render::model::Vertex> {
+        // Without a world around the block, biome tints use plains
+        let tint = match block.get_tint() {
+            TintType::Default => (255, 255, 255),
+            TintType::Color { r, g, b } => (r, g, b),
+            TintType::Grass => biome::PLAINS.get_grass_color(&factory.grass_colors),
+            TintType::Foliage => biome::PLAINS.get_foliage_color(&factory.foliage_colors),
+        };
+        self.vertices_tinted(|_| tint)
+    }
+
+    /// Returns the faces of the model as vertices, with the tinted faces
+    /// taking the colour `tint` gives for their texture.
+    fn vertices_tinted<F>(&self, tint: F) -> Vec<render::model::Vertex>
+    where
+        F: Fn(&str) -> (u8, u8, u8),
+    {
+        let mut verts = vec![];
+        for face in &self.faces {
+            let (mut cr, mut cg, mut cb) = match face.vertices_texture.first() {
+                Some(texture) if face.tint_index == 0 => tint(&texture.name),
+                _ => (255, 255, 255),
+            };
+            if face.facing == Direction::West || face.facing == Direction::East {
+                cr = ((cr as f64) * 0.8) as u8;
+                cg = ((cg as f64) * 0.8) as u8;
+                cb = ((cb as f64) * 0.8) as u8;
+            }
+            for (vert, texture) in face.vertices.iter().zip(&face.vertices_texture) {
+                verts.push(render::model::Vertex {
+                    x: vert.x,
+                    y: vert.y,
+                    z: vert.z,
+                    texture: texture.clone(),
+                    texture_x: vert.toffsetx as f64 / (texture.get_width() as f64 * 16.0),
+                    texture_y: vert.toffsety as f64 / (texture.get_height() as f64 * 16.0),
+                    r: cr,
+                    g: cg,
+                    b: cb,
+                    a: 255,
+                    id: 0,
+                });
+            }
+        }
+        verts
+    }
 }
 
-fn calculate_biome(
+/// Averages the colour returned by `color` over the biomes of the columns
+/// within `radius` blocks of the position.
+pub fn blend_biome<F>(
     snapshot: &world::ComposedSection,
     x: i32,
+    y: i32,
     z: i32,
-    img: &image::DynamicImage,
-) -> (u8, u8, u8) {
-    use std::cmp::{max, min};
+    radius: i32,
+    color: F,
+) -> (u8, u8, u8)
+where
+    F: Fn(Biome) -> (u8, u8, u8),
+{
     let mut count = 0;
     let mut r = 0;
     let mut g = 0;
     let mut b = 0;
-    for xx in -1..2 {
-        for zz in -1..2 {
-            let bi = snapshot.get_biome(x + xx, z + zz);
-            let color_index = bi.get_color_index();
-            let ix = color_index & 0xFF;
-            let iy = color_index >> 8;
-
-            let ix = min(max(ix, 0), 255);
-            let iy = min(max(iy, 0), 255);
-
-            let col = img.get_pixel(ix as u32, iy as u32);
-            let col = bi.process_color(col);
-            r += col.0[0] as u32;
-            g += col.0[1] as u32;
-            b += col.0[2] as u32;
+    for xx in -radius..=radius {
+        for zz in -radius..=radius {
+            let col = color(snapshot.get_biome(x + xx, y, z + zz));
+            r += col.0 as u32;
+            g += col.1 as u32;
+            b += col.2 as u32;
             count += 1;
         }
     }
@@ -1126,6 +1424,165 @@ fn calculate_light(
     )
 }
 
+/// Per-vertex light levels and ambient occlusion factors for a single face.
+struct FaceLighting {
+    block_light: [u16; 4],
+    sky_light: [u16; 4],
+    ao: [f32; 4],
+}
+
+impl FaceLighting {
+    fn brightness(&self, i: usize) -> f32 {
+        let light = self.block_light[i].max(self.sky_light[i]) as f32 / 4000.0;
+        self.ao[i] * (light + 1.0)
+    }
+
+    fn should_flip(&self) -> bool {
+        self.brightness(0) + self.brightness(3) > self.brightness(1) + self.brightness(2)
+    }
+}
+
+/// A single light sample taken next to a face.
+#[derive(Clone, Copy)]
+struct LightSample {
+    block_light: f32,
+    sky_light: f32,
+    occludes: bool,
+}
+
+impl LightSample {
+    fn take(snapshot: &world::ComposedSection, x: i32, y: i32, z: i32) -> LightSample {
+        LightSample {
+            block_light: snapshot.get_block_light(x, y, z) as f32,
+            sky_light: snapshot.get_sky_light(x, y, z) as f32,
+            occludes: snapshot
+                .get_block(x, y, z)
+                .get_material()
+                .should_cull_against,
+        }
+    }
+
+    fn ao(&self) -> f32 {
+        if self.occludes {
+            0.2
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Returns the two axes spanning the plane of a face.
+fn face_tangents(face: Direction) -> ((i32, i32, i32), (i32, i32, i32)) {
+    match face {
+        Direction::Up | Direction::Down => ((1, 0, 0), (0, 0, 1)),
+        Direction::North | Direction::South => ((1, 0, 0), (0, 1, 0)),
+        _ => ((0, 0, 1), (0, 1, 0)),
+    }
+}
+
+/// Vanilla style smooth lighting.
+///
+/// The light and occlusion values are computed for the four corners of the
+/// block face by averaging the block in front of the face with its two edge
+/// neighbours and the diagonal corner. Each vertex then interpolates between
+/// the corners, which keeps faces of partial blocks (slabs, stairs, etc)
+/// consistent with the full cubes around them.
+fn calculate_smooth_light(
+    snapshot: &world::ComposedSection,
+    x: i32,
+    y: i32,
+    z: i32,
+    face: &Face,
+) -> FaceLighting {
+    let (ox, oy, oz) = face.facing.get_offset();
+    let (u, v) = face_tangents(face.facing);
+
+    // Faces on the edge of the block sample the neighbour they point at,
+    // inset faces sample the block they belong to.
+    let on_edge = face.vertices.iter().all(|vert| {
+        let pos = match face.facing {
+            Direction::Up | Direction::Down => vert.y,
+            Direction::North | Direction::South => vert.z,
+            _ => vert.x,
+        };
+        let edge = if ox + oy + oz > 0 { 1.0 } else { 0.0 };
+        (pos - edge).abs() < 0.001
+    });
+    let (bx, by, bz) = if on_edge {
+        (x + ox, y + oy, z + oz)
+    } else {
+        (x, y, z)
+    };
+
+    let center = LightSample::take(snapshot, bx, by, bz);
+    let resolve = |sample: LightSample| {
+        if sample.occludes || (sample.block_light == 0.0 && sample.sky_light == 0.0) {
+            (center.block_light, center.sky_light)
+        } else {
+            (sample.block_light, sample.sky_light)
+        }
+    };
+
+    // Corners are indexed by (u, v) with 0 being the negative side
+    let mut corners = [(0.0f32, 0.0f32, 0.0f32); 4];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let su = if i & 1 == 0 { -1 } else { 1 };
+        let sv = if i & 2 == 0 { -1 } else { 1 };
+        let side1 = LightSample::take(snapshot, bx + u.0 * su, by + u.1 * su, bz + u.2 * su);
+        let side2 = LightSample::take(snapshot, bx + v.0 * sv, by + v.1 * sv, bz + v.2 * sv);
+        let diagonal = if side1.occludes && side2.occludes {
+            // Light can't reach the corner around two solid blocks
+            side1
+        } else {
+            LightSample::take(
+                snapshot,
+                bx + u.0 * su + v.0 * sv,
+                by + u.1 * su + v.1 * sv,
+                bz + u.2 * su + v.2 * sv,
+            )
+        };
+
+        let mut block_light = 0.0;
+        let mut sky_light = 0.0;
+        let mut ao = 0.0;
+        for sample in &[center, side1, side2, diagonal] {
+            let (bl, sl) = resolve(*sample);
+            block_light += bl;
+            sky_light += sl;
+            ao += sample.ao();
+        }
+        *corner = (block_light / 4.0, sky_light / 4.0, ao / 4.0);
+    }
+
+    let mut lighting = FaceLighting {
+        block_light: [0; 4],
+        sky_light: [0; 4],
+        ao: [1.0; 4],
+    };
+    for (i, vert) in face.vertices.iter().take(4).enumerate() {
+        let fu = (vert.x * u.0 as f32 + vert.y * u.1 as f32 + vert.z * u.2 as f32).clamp(0.0, 1.0);
+        let fv = (vert.x * v.0 as f32 + vert.y * v.1 as f32 + vert.z * v.2 as f32).clamp(0.0, 1.0);
+        let weights = [
+            (1.0 - fu) * (1.0 - fv),
+            fu * (1.0 - fv),
+            (1.0 - fu) * fv,
+            fu * fv,
+        ];
+        let mut block_light = 0.0;
+        let mut sky_light = 0.0;
+        let mut ao = 0.0;
+        for (corner, weight) in corners.iter().zip(weights.iter()) {
+            block_light += corner.0 * weight;
+            sky_light += corner.1 * weight;
+            ao += corner.2 * weight;
+        }
+        lighting.block_light[i] = (block_light * 4000.0) as u16;
+        lighting.sky_light[i] = (sky_light * 4000.0) as u16;
+        lighting.ao[i] = ao;
+    }
+    lighting
+}
+
 pub const PRECOMPUTED_VERTS: [&[BlockVertex; 4]; 6] = [
     &[
         // Up
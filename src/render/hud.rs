@@ -24,6 +24,7 @@ use rand::Rng;
 
 use crate::inventory::player_inventory::PlayerInventory;
 use crate::inventory::{Inventory, Item};
+use crate::model::{self, ItemModel};
 use crate::render;
 use crate::render::Renderer;
 use crate::screen::Screen;
@@ -47,6 +48,7 @@ pub struct HudContext {
     pub enabled: bool,
     pub debug: bool,
     fps: u32,
+    targeted_entity: Option<String>,
     dirty_debug: bool,
     hardcore: bool,  // TODO: Update this!
     wither: bool,    // TODO: Update this!
@@ -91,6 +93,7 @@ impl HudContext {
             enabled: true,
             debug: false,
             fps: 0,
+            targeted_entity: None,
             dirty_debug: false,
             hardcore: false,
             wither: false,
@@ -183,6 +186,15 @@ impl HudContext {
         }
     }
 
+    pub fn update_targeted_entity(&mut self, targeted_entity: Option<String>) {
+        if self.targeted_entity != targeted_entity {
+            self.targeted_entity = targeted_entity;
+            if self.debug {
+                self.dirty_debug = true;
+            }
+        }
+    }
+
     pub fn update_game_mode(&mut self, game_mode: GameMode) {
         self.game_mode = game_mode;
         self.dirty_game_mode = true;
@@ -192,6 +204,10 @@ impl HudContext {
         self.slot_index
     }
 
+    pub fn get_game_mode(&self) -> GameMode {
+        self.game_mode
+    }
+
     pub fn display_message_in_chat(&mut self, message: format::Component) {
         self.server
             .as_ref()
@@ -488,6 +504,13 @@ impl Hud {
         Hud::icon_scale_by_height(renderer.safe_height)
     }
 
+    /// The block model an item is drawn with in the GUI, items that are a
+    /// sprite are drawn with their texture instead.
+    pub fn item_model(item: &Item, renderer: &Renderer) -> Option<Arc<ItemModel>> {
+        model::Factory::get_item_model(&renderer.block_models, &item.material.model_name())
+            .filter(|model| !model.generated)
+    }
+
     pub fn icon_scale_by_height(height: u32) -> f64 {
         let icon_scale = if height > 500 {
             height as f64 / 36.50
@@ -1032,6 +1055,20 @@ impl Hud {
                 .shadow(false)
                 .create(ui_container),
         );
+        if let Some(targeted_entity) = &hud_context.targeted_entity {
+            self.debug_elements.push(
+                ui::TextBuilder::new()
+                    .draw_index(HUD_PRIORITY)
+                    .alignment(VAttach::Top, HAttach::Left)
+                    .scale_x(scale)
+                    .scale_y(scale)
+                    .position(icon_scale, icon_scale + 18.0 * scale)
+                    .text(format!("Targeted entity: {}", targeted_entity))
+                    .colour((0, 102, 204, 255))
+                    .shadow(false)
+                    .create(ui_container),
+            );
+        }
     }
 
     pub fn render_chat(&mut self, renderer: &mut Renderer, ui_container: &mut Container) {
@@ -1134,6 +1171,7 @@ impl Hud {
             .alignment(ui::VAttach::Bottom, ui::HAttach::Center)
             .size(icon_scale * 16.0, icon_scale * 16.0)
             .texture(format!("minecraft:{}", texture))
+            .model(Hud::item_model(item, renderer))
             .create(ui_container);
         image
     }
@@ -1,11 +1,22 @@
-use crate::inventory::{Inventory, InventoryContext, Item};
+use crate::inventory::click::{self, Click, DragButton};
+use crate::inventory::{tooltip, Inventory, InventoryContext, InventoryType, Item};
 use crate::render::hud::Hud;
 use crate::render::Renderer;
 use crate::screen::Screen;
+use crate::settings;
+use crate::types::GameMode;
 use crate::ui;
 use crate::ui::{Container, ImageRef, TextRef};
+use crate::Game;
+use instant::{Duration, Instant};
+use leafish_protocol::format::{Component, Modifier, TextComponent};
+use leafish_protocol::protocol::Version;
 use parking_lot::RwLock;
 use std::sync::Arc;
+use winit::event::{MouseButton, VirtualKeyCode};
+
+/// How quickly the second click of a double click has to follow the first.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(250);
 
 #[derive(Clone)]
 pub struct InventoryWindow {
@@ -13,6 +24,19 @@ pub struct InventoryWindow {
     pub text_elements: Vec<Vec<TextRef>>,
     pub inventory: Arc<RwLock<dyn Inventory + Sync + Send>>,
     inventory_context: Arc<RwLock<InventoryContext>>,
+    /// The mouse, relative to the centre of the screen.
+    mouse: (f64, f64),
+    /// Slots the mouse went over with a button held down and items on the
+    /// cursor, they get the items split between them when it's let go.
+    drag: Option<(DragButton, Vec<i16>)>,
+    last_click: Option<(Instant, i16)>,
+    held_item: Option<ImageRef>,
+    held_item_dirty: bool,
+    /// The protocol version and whether tooltips are advanced, as tooltips
+    /// are worked out where the game isn't around.
+    tooltip_style: (Version, bool),
+    tooltip: Option<ImageRef>,
+    tooltip_dirty: bool,
 }
 
 impl Screen for InventoryWindow {
@@ -29,8 +53,24 @@ impl Screen for InventoryWindow {
     }
 
     fn on_deactive(&mut self, _renderer: &mut Renderer, _ui_container: &mut Container) {
-        self.inventory_context.clone().write().inventory = None;
-        self.inventory.clone().write().close(self);
+        // The server may have closed the window already or opened another
+        // one in its place, it mustn't hear about either from us.
+        let current = {
+            let mut inventory_context = self.inventory_context.write();
+            let current = inventory_context
+                .inventory
+                .as_ref()
+                .is_some_and(|inventory| Arc::ptr_eq(inventory, &self.inventory));
+            if current {
+                inventory_context.inventory = None;
+                // Whatever was on the cursor gets dropped by the server
+                inventory_context.cursor = None;
+            }
+            current
+        };
+        if current {
+            self.inventory.clone().write().close(self);
+        }
         self.clear_elements();
     }
 
@@ -44,9 +84,168 @@ impl Screen for InventoryWindow {
             .clone()
             .write()
             .tick(renderer, ui_container, self);
+        let cursor_dirty =
+            std::mem::replace(&mut self.inventory_context.write().cursor_dirty, false);
+        if self.held_item_dirty || cursor_dirty {
+            self.held_item_dirty = false;
+            self.held_item = self.inventory_context.read().cursor.as_ref().map(|item| {
+                Self::item_image(item, self.mouse.0, self.mouse.1, renderer)
+                    .draw_index(1)
+                    .create(ui_container)
+            });
+        }
+        if self.tooltip_dirty || cursor_dirty {
+            self.tooltip_dirty = false;
+            self.tooltip = self
+                .hovered_tooltip()
+                .map(|lines| self.draw_tooltip(lines, renderer, ui_container));
+        }
         None
     }
 
+    fn on_key_press(&mut self, key: VirtualKeyCode, down: bool, game: &mut Game) -> bool {
+        if key == VirtualKeyCode::Escape && !down {
+            game.screen_sys.pop_screen();
+            return true;
+        }
+        if self.inventory.read().takes_text() {
+            return false;
+        }
+        let slot = match self.inventory.read().slot_at(self.mouse.0, self.mouse.1) {
+            Some(slot) if down && slot != click::OUTSIDE => slot,
+            _ => return false,
+        };
+        let hotbar_index = match key {
+            VirtualKeyCode::Key1 => 0,
+            VirtualKeyCode::Key2 => 1,
+            VirtualKeyCode::Key3 => 2,
+            VirtualKeyCode::Key4 => 3,
+            VirtualKeyCode::Key5 => 4,
+            VirtualKeyCode::Key6 => 5,
+            VirtualKeyCode::Key7 => 6,
+            VirtualKeyCode::Key8 => 7,
+            VirtualKeyCode::Key9 => 8,
+            VirtualKeyCode::Q => {
+                self.click(Click::Drop(slot, game.is_ctrl_pressed), game);
+                return true;
+            }
+            _ => return false,
+        };
+        self.click(Click::Hotbar(slot, hotbar_index), game);
+        true
+    }
+
+    fn on_mouse_input(&mut self, button: MouseButton, down: bool, game: &mut Game) {
+        let drag_button = match button {
+            MouseButton::Left => DragButton::Left,
+            MouseButton::Right => DragButton::Right,
+            MouseButton::Middle => DragButton::Middle,
+            MouseButton::Other(_) => return,
+        };
+        if !down {
+            if let Some((button, slots)) = self.drag.take() {
+                if button == drag_button {
+                    // Letting go where the drag started is a normal click
+                    let click = match (slots.as_slice(), button) {
+                        (&[slot], DragButton::Left) => Click::Left(slot),
+                        (&[slot], DragButton::Right) => Click::Right(slot),
+                        (&[slot], DragButton::Middle) => Click::Middle(slot),
+                        _ => Click::Drag(button, slots),
+                    };
+                    self.click(click, game);
+                } else {
+                    self.drag = Some((button, slots));
+                }
+            }
+            return;
+        }
+        if self.drag.is_some() {
+            return;
+        }
+        if self.inventory.write().on_click(self.mouse.0, self.mouse.1) {
+            return;
+        }
+        let slot = match self.inventory.read().slot_at(self.mouse.0, self.mouse.1) {
+            Some(slot) => slot,
+            None => return,
+        };
+        if slot == click::OUTSIDE {
+            match drag_button {
+                DragButton::Left => self.click(Click::Outside(true), game),
+                DragButton::Right => self.click(Click::Outside(false), game),
+                DragButton::Middle => {}
+            }
+            return;
+        }
+        let double_click = drag_button == DragButton::Left
+            && self
+                .last_click
+                .is_some_and(|(time, last)| last == slot && time.elapsed() < DOUBLE_CLICK_TIME);
+        self.last_click = Some((Instant::now(), slot));
+        let holding = self.inventory_context.read().cursor.is_some();
+        if game.is_shift_pressed && drag_button != DragButton::Middle {
+            self.click(Click::Shift(slot), game);
+        } else if double_click && holding {
+            self.click(Click::PickupAll(slot), game);
+        } else if holding {
+            self.drag = Some((drag_button, vec![slot]));
+        } else {
+            self.click(
+                match drag_button {
+                    DragButton::Left => Click::Left(slot),
+                    DragButton::Right => Click::Right(slot),
+                    DragButton::Middle => Click::Middle(slot),
+                },
+                game,
+            );
+        }
+    }
+
+    fn on_cursor_move(&mut self, x: f64, y: f64, game: &mut Game) {
+        {
+            let renderer = game.renderer.read();
+            self.mouse = (
+                x - renderer.safe_width as f64 / 2.0,
+                y - renderer.safe_height as f64 / 2.0,
+            );
+        }
+        if let Some(server) = game.server.as_ref() {
+            self.tooltip_style = (
+                server.mapped_protocol_version,
+                *game.vars.get(settings::CL_ADVANCED_TOOLTIPS),
+            );
+        }
+        self.held_item_dirty = true;
+        self.tooltip_dirty = true;
+        let (button, slots) = match self.drag.as_mut() {
+            Some(drag) => drag,
+            None => return,
+        };
+        let inventory_context = self.inventory_context.read();
+        let inventory = self.inventory.read();
+        let slot = match inventory.slot_at(self.mouse.0, self.mouse.1) {
+            Some(slot) if slot != click::OUTSIDE && !slots.contains(&slot) => slot,
+            _ => return,
+        };
+        let held = match inventory_context.cursor.as_ref() {
+            Some(held) => held,
+            None => return,
+        };
+        // Every slot has to get at least one item
+        let enough = *button == DragButton::Middle || held.stack.count > slots.len() as isize;
+        if enough && click::can_drag_into(&*inventory, slot, held) {
+            slots.push(slot);
+        }
+    }
+
+    fn on_scroll(&mut self, _x: f64, y: f64) {
+        self.inventory.write().on_scroll(y);
+    }
+
+    fn on_char_receive(&mut self, received: char, _game: &mut Game) {
+        self.inventory.write().on_char(received);
+    }
+
     fn on_resize(&mut self, renderer: &mut Renderer, ui_container: &mut Container) {
         self.inventory.clone().write().resize(
             renderer.safe_width,
@@ -76,8 +275,118 @@ impl InventoryWindow {
             text_elements: vec![],
             inventory,
             inventory_context,
+            mouse: (0.0, 0.0),
+            drag: None,
+            last_click: None,
+            held_item: None,
+            held_item_dirty: true,
+            tooltip_style: (Version::Other, false),
+            tooltip: None,
+            tooltip_dirty: true,
         }
     }
+
+    /// Carries out the click and tells the server about it.
+    fn click(&mut self, click: Click, game: &Game) {
+        let server = match game.server.as_ref() {
+            Some(server) => server,
+            None => return,
+        };
+        let creative = matches!(
+            server.hud_context.read().get_game_mode(),
+            GameMode::Creative
+        );
+        // The context is locked before the inventory, like everywhere else
+        let (changes, clicks) = {
+            let mut inventory_context = self.inventory_context.write();
+            let mut inventory = self.inventory.write();
+            if let InventoryType::Creative = inventory.ty() {
+                (
+                    inventory_context.creative_click(&mut *inventory, click),
+                    vec![],
+                )
+            } else {
+                (
+                    vec![],
+                    inventory_context.click(&mut *inventory, click, creative),
+                )
+            }
+        };
+        for (slot, item) in changes {
+            server.creative_inventory_action(slot, item);
+        }
+        for click in clicks {
+            server.click_window(click);
+        }
+        self.held_item_dirty = true;
+        self.tooltip_dirty = true;
+    }
+
+    /// The tooltip of the item under the mouse, which only shows while
+    /// nothing is held.
+    fn hovered_tooltip(&self) -> Option<Vec<Component>> {
+        if self.drag.is_some() || self.inventory_context.read().cursor.is_some() {
+            return None;
+        }
+        let inventory = self.inventory.read();
+        let slot = inventory
+            .slot_at(self.mouse.0, self.mouse.1)
+            .filter(|slot| *slot != click::OUTSIDE)?;
+        let item = inventory.get_item(slot).as_ref()?;
+        let (version, advanced) = self.tooltip_style;
+        Some(tooltip::tooltip(item, version, advanced))
+    }
+
+    /// A box beside the mouse with the tooltip's lines in it, kept on
+    /// screen.
+    fn draw_tooltip(
+        &self,
+        lines: Vec<Component>,
+        renderer: &Renderer,
+        ui_container: &mut Container,
+    ) -> ImageRef {
+        let mut extra = Vec::with_capacity(lines.len() * 2);
+        for line in lines {
+            if !extra.is_empty() {
+                extra.push(Component::Text(TextComponent::new("\n")));
+            }
+            extra.push(line);
+        }
+        let text = Component::Text(TextComponent {
+            text: String::new(),
+            modifier: Modifier {
+                extra: Some(extra),
+                ..Default::default()
+            },
+        });
+        let (width, height) = ui::Formatted::compute_size(renderer, &text, -1.0, 1.0);
+        let (width, height) = (width + 6.0, height + 6.0);
+        let screen_width = renderer.safe_width as f64;
+        let screen_height = renderer.safe_height as f64;
+        let mouse_x = self.mouse.0 + screen_width / 2.0;
+        let mouse_y = self.mouse.1 + screen_height / 2.0;
+        // Flips over to the left of the mouse when there's no room
+        let x = if mouse_x + 12.0 + width > screen_width {
+            mouse_x - 12.0 - width
+        } else {
+            mouse_x + 12.0
+        };
+        let y = (mouse_y - 12.0).min(screen_height - height);
+        let background = ui::ImageBuilder::new()
+            .texture("leafish:solid")
+            .position(x.max(0.0), y.max(0.0))
+            .size(width, height)
+            .colour((16, 0, 16, 240))
+            .alignment(ui::VAttach::Top, ui::HAttach::Left)
+            .draw_index(2)
+            .create(ui_container);
+        ui::FormattedBuilder::new()
+            .text(text)
+            .position(3.0, 3.0)
+            .alignment(ui::VAttach::Top, ui::HAttach::Left)
+            .attach(&mut *background.borrow_mut());
+        background
+    }
 }
 
 impl InventoryWindow {
@@ -90,6 +399,11 @@ impl InventoryWindow {
         ui_container: &mut Container,
         renderer: &Renderer,
     ) {
+        let image = Self::item_image(item, x, y, renderer).create(ui_container);
+        self.elements.get_mut(elements_idx).unwrap().push(image);
+    }
+
+    fn item_image(item: &Item, x: f64, y: f64, renderer: &Renderer) -> ui::ImageBuilder {
         let icon_scale = Hud::icon_scale(renderer);
         let textures = item.material.texture_locations();
         let texture = if Renderer::get_texture_optional(&renderer.textures, &*textures.0).is_some()
@@ -98,17 +412,20 @@ impl InventoryWindow {
         } else {
             textures.1
         };
-        let image = ui::ImageBuilder::new()
+        ui::ImageBuilder::new()
             .texture_coords((0.0, 0.0, 1.0, 1.0))
             .position(x, y)
             .alignment(ui::VAttach::Middle, ui::HAttach::Center)
             .size(icon_scale * 16.0, icon_scale * 16.0)
             .texture(format!("minecraft:{}", texture))
-            .create(ui_container);
-        self.elements.get_mut(elements_idx).unwrap().push(image);
+            .model(Hud::item_model(item, renderer))
     }
 
     pub fn clear_elements(&mut self) {
+        self.held_item = None;
+        self.held_item_dirty = true;
+        self.tooltip = None;
+        self.tooltip_dirty = true;
         for element in &mut self.elements {
             element.clear();
         }
@@ -34,6 +34,7 @@ use std::io::Write;
 use std::sync::Arc;
 
 use crate::types::hash::FNVHash;
+use crate::world::dimension;
 use crate::world::World;
 use crossbeam_channel::unbounded;
 use crossbeam_channel::{Receiver, Sender};
@@ -45,6 +46,9 @@ use std::thread;
 
 const ATLAS_SIZE: usize = 2048;
 
+/// The sky colour used when the biome doesn't define one
+pub const DEFAULT_SKY_COLOR: (f32, f32, f32) = (122.0 / 255.0, 165.0 / 255.0, 247.0 / 255.0);
+
 pub struct Camera {
     pub pos: cgmath::Point3<f64>,
     pub yaw: f64,
@@ -57,6 +61,8 @@ pub struct Renderer {
     textures: Arc<RwLock<TextureManager>>,
     pub ui: ui::UIState,
     pub model: model::Manager,
+    /// The baked block models, shared with the chunk builder
+    pub block_models: Arc<RwLock<crate::model::Factory>>,
     pub clouds: Option<clouds::Clouds>,
 
     gl_texture: gl::Texture,
@@ -72,6 +78,7 @@ pub struct Renderer {
 
     pub camera: Camera,
     perspective_matrix: cgmath::Matrix4<f32>,
+    hand_perspective_matrix: cgmath::Matrix4<f32>,
     camera_matrix: cgmath::Matrix4<f32>,
     pub frustum: collision::Frustum<f32>,
     pub view_vector: cgmath::Vector3<f32>,
@@ -88,6 +95,11 @@ pub struct Renderer {
     // Light renderering
     pub light_level: f32,
     pub sky_offset: f32,
+    /// The sky and fog colours of the biome the camera is in
+    pub sky_color: (f32, f32, f32),
+    pub fog_color: (f32, f32, f32),
+    pub ambient_light: f32,
+    pub sky_effects: dimension::Effects,
     skin_request: Sender<String>,
     skin_reply: Receiver<(String, Option<image::DynamicImage>)>,
 }
@@ -129,6 +141,7 @@ init_shader! {
             required texture => "textures",
             required light_level => "lightLevel",
             required sky_offset => "skyOffset",
+            required ambient_light => "ambientLight",
         },
     }
 }
@@ -151,6 +164,7 @@ init_shader! {
             required texture => "textures",
             required light_level => "lightLevel",
             required sky_offset => "skyOffset",
+            required ambient_light => "ambientLight",
         },
     }
 }
@@ -202,9 +216,15 @@ impl Renderer {
         let clouds = Some(clouds::Clouds::new(&greg, textures.clone()));
         // TODO: setting to disable clouds on native, too, if desired
 
+        let block_models = Arc::new(RwLock::new(crate::model::Factory::new(
+            res.clone(),
+            textures.clone(),
+        )));
+
         Renderer {
             resource_version: version,
             model: model::Manager::new(&greg),
+            block_models,
             clouds,
             textures,
             ui,
@@ -231,6 +251,7 @@ impl Renderer {
                 pitch: ::std::f64::consts::PI,
             },
             perspective_matrix: cgmath::Matrix4::identity(),
+            hand_perspective_matrix: cgmath::Matrix4::identity(),
             camera_matrix: cgmath::Matrix4::identity(),
             frustum: collision::Frustum::from_matrix4(cgmath::Matrix4::identity()).unwrap(),
             view_vector: cgmath::Vector3::zero(),
@@ -241,6 +262,10 @@ impl Renderer {
 
             light_level: 0.8,
             sky_offset: 1.0,
+            sky_color: DEFAULT_SKY_COLOR,
+            fog_color: DEFAULT_SKY_COLOR,
+            ambient_light: 0.0,
+            sky_effects: dimension::Effects::Overworld,
             skin_request: skin_req,
             skin_reply,
         }
@@ -280,6 +305,13 @@ impl Renderer {
                 near: 0.1f32,
                 far: 500.0f32,
             });
+            // The hand keeps the field of view of vanilla's default
+            self.hand_perspective_matrix = cgmath::Matrix4::from(cgmath::PerspectiveFov {
+                fovy: cgmath::Rad::from(cgmath::Deg(70.0_f32)),
+                aspect,
+                near: 0.05f32,
+                far: 10.0f32,
+            });
 
             self.init_trans(width, height);
         }
@@ -328,13 +360,25 @@ impl Renderer {
 
             gl::enable(gl::MULTISAMPLE);
 
-            let time_offset = self.sky_offset * 0.9;
-            gl::clear_color(
-                (122.0 / 255.0) * time_offset,
-                (165.0 / 255.0) * time_offset,
-                (247.0 / 255.0) * time_offset,
-                1.0,
-            );
+            match self.sky_effects {
+                dimension::Effects::Overworld => {
+                    // The horizon is mostly fog with a bit of the sky mixed in
+                    let time_offset = self.sky_offset * 0.9;
+                    let horizon = |fog: f32, sky: f32| (fog + (sky - fog) * 0.15) * time_offset;
+                    gl::clear_color(
+                        horizon(self.fog_color.0, self.sky_color.0),
+                        horizon(self.fog_color.1, self.sky_color.1),
+                        horizon(self.fog_color.2, self.sky_color.2),
+                        1.0,
+                    );
+                }
+                // No sky, only fog
+                dimension::Effects::Nether => {
+                    gl::clear_color(self.fog_color.0, self.fog_color.1, self.fog_color.2, 1.0)
+                }
+                // The end sky is drawn as a model on top of this
+                dimension::Effects::End => gl::clear_color(0.0, 0.0, 0.0, 1.0),
+            }
             gl::clear(gl::ClearFlags::Color | gl::ClearFlags::Depth);
             // Chunk rendering
             self.chunk_shader.program.use_program();
@@ -348,6 +392,9 @@ impl Renderer {
             self.chunk_shader.texture.set_int(0);
             self.chunk_shader.light_level.set_float(self.light_level);
             self.chunk_shader.sky_offset.set_float(self.sky_offset);
+            self.chunk_shader
+                .ambient_light
+                .set_float(self.ambient_light);
 
             let tmp_world = world.as_ref().unwrap().clone();
 
@@ -376,10 +423,13 @@ impl Renderer {
                 &self.camera_matrix,
                 self.light_level,
                 self.sky_offset,
+                self.ambient_light,
             );
             let tmp_world = world.as_ref().unwrap().clone();
 
-            if let Some(clouds) = &mut self.clouds {
+            // Only the overworld has clouds
+            let has_clouds = self.sky_effects == dimension::Effects::Overworld;
+            if let Some(clouds) = self.clouds.as_mut().filter(|_| has_clouds) {
                 if tmp_world.copy_cloud_heightmap(&mut clouds.heightmap_data) {
                     clouds.dirty = true;
                 }
@@ -409,6 +459,9 @@ impl Renderer {
                 self.chunk_shader_alpha
                     .sky_offset
                     .set_float(self.sky_offset);
+                self.chunk_shader_alpha
+                    .ambient_light
+                    .set_float(self.ambient_light);
 
                 // Copy the depth buffer
                 let trans = self.trans.as_ref().unwrap();
@@ -479,6 +532,18 @@ impl Renderer {
         gl::enable(gl::DEPTH_TEST);
         gl::depth_mask(true);
 
+        if world.is_some() {
+            // The hand is drawn over the world, never clipping into it
+            gl::clear(gl::ClearFlags::Depth);
+            gl::active_texture(0);
+            self.model.draw_hand(
+                &self.hand_perspective_matrix,
+                self.light_level,
+                self.sky_offset,
+                self.ambient_light,
+            );
+        }
+
         gl::disable(gl::MULTISAMPLE);
 
         self.ui.tick(width, height);
@@ -1551,6 +1616,38 @@ impl TextureManager {
         let desc = self.dynamic_textures.remove(name).unwrap();
         self.free_dynamics.push(desc.0);
     }
+
+    /// Returns the texture added by `put_dynamic` with the name, if any.
+    pub fn get_dynamic(&self, name: &str) -> Option<Texture> {
+        if !self.dynamic_textures.contains_key(name) {
+            return None;
+        }
+        self.get_texture(&format!("leafish-dynamic:{}", name))
+    }
+
+    /// Replaces the image of a dynamic texture in place, adding the
+    /// texture if it doesn't exist or the size changed.
+    pub fn update_dynamic(&mut self, name: &str, img: image::DynamicImage) -> Texture {
+        let tex = match self.dynamic_textures.get(name) {
+            Some((tex, old)) if old.dimensions() == img.dimensions() => tex.clone(),
+            Some(_) => {
+                self.remove_dynamic(name);
+                return self.put_dynamic(name, img);
+            }
+            None => return self.put_dynamic(name, img),
+        };
+        let (width, height) = img.dimensions();
+        let rect = atlas::Rect {
+            x: tex.x,
+            y: tex.y,
+            width: width as usize,
+            height: height as usize,
+        };
+        self.pending_uploads
+            .push((tex.atlas, rect, img.to_rgba8().into_vec()));
+        self.dynamic_textures.get_mut(name).unwrap().1 = img;
+        tex
+    }
 }
 
 #[allow(dead_code)]
@@ -23,6 +23,8 @@ pub struct Manager {
 
 pub const DEFAULT: CollectionKey = CollectionKey(0);
 pub const SUN: CollectionKey = CollectionKey(1);
+/// Drawn by `draw_hand` in view space on top of the world.
+pub const HAND: CollectionKey = CollectionKey(2);
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CollectionKey(usize);
@@ -51,6 +53,12 @@ impl Manager {
             gl::SRC_ALPHA,
             gl::ONE_FACTOR,
         );
+        m.add_collection(
+            &greg.get("model_vertex"),
+            &greg.get("model_frag"),
+            gl::SRC_ALPHA,
+            gl::ONE_MINUS_SRC_ALPHA,
+        );
         m
     }
 
@@ -150,14 +158,7 @@ impl Manager {
         };
 
         Self::rebuild_model(&mut model);
-        if self.max_index < model.count as usize {
-            let (data, ty) = super::generate_element_buffer(model.count as usize);
-            self.index_buffer.bind(gl::ELEMENT_ARRAY_BUFFER);
-            self.index_buffer
-                .set_data(gl::ELEMENT_ARRAY_BUFFER, &data, gl::DYNAMIC_DRAW);
-            self.max_index = model.count as usize;
-            self.index_type = ty;
-        }
+        self.grow_index_buffer(model.count as usize);
 
         let collection = &mut self.collections[ckey.0];
         let key = ModelKey(ckey, collection.next_id);
@@ -167,6 +168,33 @@ impl Manager {
         key
     }
 
+    /// Replaces the vertices of a single part model, for models that
+    /// change every frame.
+    pub fn set_model_verts(&mut self, key: ModelKey, verts: Vec<Vertex>) {
+        let count = {
+            let collection = &mut self.collections[(key.0).0];
+            let model = match collection.models.get_mut(&key) {
+                Some(model) => model,
+                None => return,
+            };
+            model.verts = verts;
+            Self::rebuild_model(model);
+            model.count as usize
+        };
+        self.grow_index_buffer(count);
+    }
+
+    fn grow_index_buffer(&mut self, count: usize) {
+        if self.max_index < count {
+            let (data, ty) = super::generate_element_buffer(count);
+            self.index_buffer.bind(gl::ELEMENT_ARRAY_BUFFER);
+            self.index_buffer
+                .set_data(gl::ELEMENT_ARRAY_BUFFER, &data, gl::DYNAMIC_DRAW);
+            self.max_index = count;
+            self.index_type = ty;
+        }
+    }
+
     pub fn remove_model(&mut self, key: ModelKey) {
         let collection = &mut self.collections[(key.0).0];
         collection.models.remove(&key);
@@ -246,28 +274,81 @@ impl Manager {
         camera_matrix: &Matrix4<f32>,
         light_level: f32,
         sky_offset: f32,
+        ambient_light: f32,
     ) {
         gl::enable(gl::BLEND);
-        for collection in &self.collections {
-            collection.shader.program.use_program();
-            if let Some(v) = &collection.shader.perspective_matrix {
-                v.set_matrix4(perspective_matrix)
-            }
-            if let Some(v) = &collection.shader.camera_matrix {
-                v.set_matrix4(camera_matrix)
-            }
-            if let Some(v) = &collection.shader.texture {
-                v.set_int(0)
-            }
-            if let Some(v) = &collection.shader.sky_offset {
-                v.set_float(sky_offset)
-            }
-            if let Some(v) = &collection.shader.light_level {
-                v.set_float(light_level)
+        for (i, collection) in self.collections.iter().enumerate() {
+            if i == HAND.0 {
+                continue;
             }
-            gl::blend_func(collection.blend_s, collection.blend_d);
+            self.draw_collection(
+                collection,
+                Some(&frustum),
+                perspective_matrix,
+                camera_matrix,
+                light_level,
+                sky_offset,
+                ambient_light,
+            );
+        }
+        gl::disable(gl::BLEND);
+    }
+
+    /// Draws the models of the `HAND` collection, whose matrices are
+    /// relative to the camera.
+    pub fn draw_hand(
+        &mut self,
+        perspective_matrix: &Matrix4<f32>,
+        light_level: f32,
+        sky_offset: f32,
+        ambient_light: f32,
+    ) {
+        gl::enable(gl::BLEND);
+        self.draw_collection(
+            &self.collections[HAND.0],
+            None,
+            perspective_matrix,
+            &Matrix4::identity(),
+            light_level,
+            sky_offset,
+            ambient_light,
+        );
+        gl::disable(gl::BLEND);
+    }
 
-            for model in collection.models.values() {
+    fn draw_collection(
+        &self,
+        collection: &Collection,
+        frustum: Option<&Frustum<f32>>,
+        perspective_matrix: &Matrix4<f32>,
+        camera_matrix: &Matrix4<f32>,
+        light_level: f32,
+        sky_offset: f32,
+        ambient_light: f32,
+    ) {
+        collection.shader.program.use_program();
+        if let Some(v) = &collection.shader.perspective_matrix {
+            v.set_matrix4(perspective_matrix)
+        }
+        if let Some(v) = &collection.shader.camera_matrix {
+            v.set_matrix4(camera_matrix)
+        }
+        if let Some(v) = &collection.shader.texture {
+            v.set_int(0)
+        }
+        if let Some(v) = &collection.shader.sky_offset {
+            v.set_float(sky_offset)
+        }
+        if let Some(v) = &collection.shader.light_level {
+            v.set_float(light_level)
+        }
+        if let Some(v) = &collection.shader.ambient_light {
+            v.set_float(ambient_light)
+        }
+        gl::blend_func(collection.blend_s, collection.blend_d);
+
+        for model in collection.models.values() {
+            if let Some(frustum) = frustum {
                 if model.radius > 0.0
                     && frustum.contains(&Sphere {
                         center: Point3::new(model.x, -model.y, model.z),
@@ -276,20 +357,19 @@ impl Manager {
                 {
                     continue;
                 }
-                model.array.bind();
-                if let Some(v) = &collection.shader.lighting {
-                    v.set_float2(model.block_light, model.sky_light)
-                }
-                if let Some(v) = &collection.shader.model_matrix {
-                    v.set_matrix4_multi(&model.matrix)
-                }
-                if let Some(v) = &collection.shader.color_mul {
-                    v.set_float_multi(&model.colors)
-                }
-                gl::draw_elements(gl::TRIANGLES, model.count, self.index_type, 0);
             }
+            model.array.bind();
+            if let Some(v) = &collection.shader.lighting {
+                v.set_float2(model.block_light, model.sky_light)
+            }
+            if let Some(v) = &collection.shader.model_matrix {
+                v.set_matrix4_multi(&model.matrix)
+            }
+            if let Some(v) = &collection.shader.color_mul {
+                v.set_float_multi(&model.colors)
+            }
+            gl::draw_elements(gl::TRIANGLES, model.count, self.index_type, 0);
         }
-        gl::disable(gl::BLEND);
     }
 }
 
@@ -356,6 +436,7 @@ init_shader! {
             optional texture => "textures",
             optional light_level => "lightLevel",
             optional sky_offset => "skyOffset",
+            optional ambient_light => "ambientLight",
             optional lighting => "lighting",
             optional color_mul => "colorMul",
         },
@@ -436,6 +517,59 @@ pub fn append_box_texture_scale(
     }
 }
 
+/// Appends a sprite extruded along z into a box of the given size. The
+/// sides are a slice per row and column of the sprite, transparent pixels
+/// are discarded by the shader which leaves the outline of the sprite
+/// behind.
+pub fn append_sprite(
+    verts: &mut Vec<Vertex>,
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    h: f32,
+    d: f32,
+    tex: &super::Texture,
+) {
+    append_box(
+        verts,
+        x,
+        y,
+        z,
+        w,
+        h,
+        d,
+        [None, None, Some(tex.clone()), Some(tex.clone()), None, None],
+    );
+    let (tw, th) = (tex.get_width().max(1), tex.get_height().max(1));
+    for i in 0..tw {
+        let col = tex.relative(i as f32 / tw as f32, 0.0, 1.0 / tw as f32, 1.0);
+        append_box(
+            verts,
+            x + w * i as f32 / tw as f32,
+            y,
+            z,
+            w / tw as f32,
+            h,
+            d,
+            [None, None, None, None, Some(col.clone()), Some(col)],
+        );
+    }
+    for i in 0..th {
+        let row = tex.relative(0.0, i as f32 / th as f32, 1.0, 1.0 / th as f32);
+        append_box(
+            verts,
+            x,
+            y + h * (1.0 - (i + 1) as f32 / th as f32),
+            z,
+            w,
+            h / th as f32,
+            d,
+            [Some(row.clone()), Some(row), None, None, None, None],
+        );
+    }
+}
+
 pub struct FormatState<'a> {
     pub offset: f32,
     pub width: f32,
@@ -2,6 +2,7 @@ in vec3 aPosition;
 
 uniform float lightLevel;
 uniform float skyOffset;
+uniform float ambientLight;
 
 out vec3 vLighting;
 
@@ -1,6 +1,8 @@
 
 vec3 getLight(vec2 light) {
     vec2 li = pow(vec2(lightLevel), 15.0 - light);
+    // Dimensions with ambient light are never fully dark
+    li += ambientLight * (1.0 - li);
     float skyTint = skyOffset * 0.95 + 0.05;
     float bl = li.x;
     float sk = li.y * skyTint;
@@ -1,5 +1,5 @@
 uniform sampler2DArray textures;
-uniform vec4 colorMul[10];
+uniform vec4 colorMul[12];
 
 in vec4 vColor;
 in vec4 vTextureInfo;
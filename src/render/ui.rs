@@ -551,6 +551,30 @@ impl UIElement {
         buf
     }
 
+    /// Like `bytes` but with each corner placed on its own, for drawing
+    /// the faces of models. The corners are positions with texture
+    /// coordinates within the texture, in the same order as `bytes`.
+    pub fn quad_bytes(
+        &self,
+        corners: &[(f64, f64, f64, f64); 4],
+        width: f64,
+        height: f64,
+    ) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(28 * 4);
+        for &(x, y, tx, ty) in corners {
+            self.append_vertex(
+                &mut buf,
+                x / UI_WIDTH,
+                y / UI_HEIGHT,
+                (tx * (self.t_w as f64) * 16.0) as i16,
+                (ty * (self.t_h as f64) * 16.0) as i16,
+                width,
+                height,
+            );
+        }
+        buf
+    }
+
     #[allow(unused_must_use)]
     pub fn append_vertex(
         &self,
@@ -37,7 +37,7 @@ use parking_lot::{Mutex, RwLock};
 use std::sync::atomic::{AtomicIsize, Ordering};
 use std::sync::Arc;
 use winit::dpi::{PhysicalPosition, Position};
-use winit::event::VirtualKeyCode;
+use winit::event::{MouseButton, VirtualKeyCode};
 use winit::window::Window;
 
 pub trait Screen {
@@ -60,6 +60,10 @@ pub trait Screen {
     // Events
     fn on_scroll(&mut self, _x: f64, _y: f64) {}
 
+    fn on_mouse_input(&mut self, _button: MouseButton, _down: bool, _game: &mut Game) {}
+
+    fn on_cursor_move(&mut self, _x: f64, _y: f64, _game: &mut Game) {}
+
     fn on_resize(&mut self, _renderer: &mut Renderer, _ui_container: &mut Container) {} // TODO: make non-optional!
 
     fn on_key_press(&mut self, key: VirtualKeyCode, down: bool, game: &mut Game) -> bool {
@@ -218,6 +222,36 @@ impl ScreenSystem {
         }
     }
 
+    pub fn on_mouse_input(&self, button: MouseButton, down: bool, game: &mut Game) {
+        if self.screens.clone().read().last().is_some() {
+            self.screens
+                .clone()
+                .read()
+                .last()
+                .as_ref()
+                .unwrap()
+                .screen
+                .clone()
+                .lock()
+                .on_mouse_input(button, down, game);
+        }
+    }
+
+    pub fn on_cursor_move(&self, x: f64, y: f64, game: &mut Game) {
+        if self.screens.clone().read().last().is_some() {
+            self.screens
+                .clone()
+                .read()
+                .last()
+                .as_ref()
+                .unwrap()
+                .screen
+                .clone()
+                .lock()
+                .on_cursor_move(x, y, game);
+        }
+    }
+
     #[allow(unused_must_use)]
     pub fn tick(
         &self,
@@ -270,6 +270,8 @@ impl super::Screen for VideoSettingsMenu {
         let r_max_fps = *self.vars.get(settings::R_MAX_FPS);
         let r_fov = *self.vars.get(settings::R_FOV);
         let r_vsync = *self.vars.get(settings::R_VSYNC);
+        let r_smooth_lighting = *self.vars.get(settings::R_SMOOTH_LIGHTING);
+        let r_biome_blend = *self.vars.get(settings::R_BIOME_BLEND);
 
         // Setting buttons
         // TODO: Slider
@@ -295,6 +297,35 @@ impl super::Screen for VideoSettingsMenu {
         }
         buttons.push(fov_setting);
 
+        let smooth_lighting_setting = ui::ButtonBuilder::new()
+            .position(-160.0, -50.0)
+            .size(300.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut smooth_lighting_setting = smooth_lighting_setting.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(format!(
+                    "Smooth lighting: {}",
+                    if r_smooth_lighting { "On" } else { "Off" }
+                ))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *smooth_lighting_setting);
+            let txt_smooth_lighting = txt.clone();
+            smooth_lighting_setting.add_text(txt);
+            smooth_lighting_setting.add_click_func(move |_, game| {
+                let r_smooth_lighting = !*game.vars.get(settings::R_SMOOTH_LIGHTING);
+                txt_smooth_lighting.borrow_mut().text = format!(
+                    "Smooth lighting: {}",
+                    if r_smooth_lighting { "On" } else { "Off" }
+                );
+                game.vars
+                    .set(settings::R_SMOOTH_LIGHTING, r_smooth_lighting);
+                true
+            });
+        }
+        buttons.push(smooth_lighting_setting);
+
         let vsync_setting = ui::ButtonBuilder::new()
             .position(-160.0, 0.0)
             .size(300.0, 40.0)
@@ -343,6 +374,28 @@ impl super::Screen for VideoSettingsMenu {
         }
         buttons.push(fps_setting);
 
+        let biome_blend_setting = ui::ButtonBuilder::new()
+            .position(-160.0, 50.0)
+            .size(300.0, 40.0)
+            .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+            .create(ui_container);
+        {
+            let mut biome_blend_setting = biome_blend_setting.borrow_mut();
+            let txt = ui::TextBuilder::new()
+                .text(biome_blend_text(r_biome_blend))
+                .alignment(ui::VAttach::Middle, ui::HAttach::Center)
+                .attach(&mut *biome_blend_setting);
+            let txt_biome_blend = txt.clone();
+            biome_blend_setting.add_text(txt);
+            biome_blend_setting.add_click_func(move |_, game| {
+                let r_biome_blend = (*game.vars.get(settings::R_BIOME_BLEND) + 1) % 8;
+                txt_biome_blend.borrow_mut().text = biome_blend_text(r_biome_blend);
+                game.vars.set(settings::R_BIOME_BLEND, r_biome_blend);
+                true
+            });
+        }
+        buttons.push(biome_blend_setting);
+
         let done_button = ui::ButtonBuilder::new()
             .position(0.0, 50.0)
             .size(300.0, 40.0)
@@ -405,6 +458,13 @@ impl super::Screen for VideoSettingsMenu {
     }
 }
 
+fn biome_blend_text(radius: i64) -> String {
+    match radius {
+        0 => "Biome blend: Off".into(),
+        val => format!("Biome blend: {0}x{0}", val * 2 + 1),
+    }
+}
+
 pub struct AudioSettingsMenu {
     _vars: Rc<console::Vars>,
     elements: Option<UIElements>,
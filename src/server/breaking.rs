@@ -0,0 +1,221 @@
+use crate::world::block::{Block, Tool, ToolTier};
+
+/// The status effect ids of Haste and Mining Fatigue.
+pub const HASTE: i8 = 3;
+pub const MINING_FATIGUE: i8 = 4;
+
+/// Everything about the player breaking a block that changes how long it
+/// takes, besides the block itself.
+#[derive(Default)]
+pub struct Miner {
+    pub tool: Option<(Tool, ToolTier)>,
+    pub efficiency: u8,
+    pub haste: Option<i8>,
+    pub mining_fatigue: Option<i8>,
+    /// Whether the player's eyes are in water without Aqua Affinity.
+    pub underwater: bool,
+    pub on_ground: bool,
+}
+
+/// How much of the block breaking it for a tick does, it breaks once
+/// that adds up to 1.0.
+pub fn progress_per_tick(block: Block, miner: &Miner) -> f32 {
+    let props = block.get_properties();
+    if props.is_unbreakable() {
+        return 0.0;
+    }
+    if props.hardness == 0.0 {
+        return 1.0;
+    }
+    let harvests = props.can_harvest(miner.tool)
+        || (matches!(block, Block::Web { .. }) && matches!(miner.tool, Some((Tool::Shears, _))));
+    dig_speed(block, miner) / props.hardness / if harvests { 30.0 } else { 100.0 }
+}
+
+/// The block's crack texture for the progress made on it, if it has
+/// started to show.
+pub fn destroy_stage(progress: f32) -> Option<u8> {
+    let stage = (progress * 10.0) as i32 - 1;
+    if (0..10).contains(&stage) {
+        Some(stage as u8)
+    } else {
+        None
+    }
+}
+
+fn dig_speed(block: Block, miner: &Miner) -> f32 {
+    let mut speed = tool_speed(block, miner.tool);
+    if speed > 1.0 && miner.efficiency > 0 {
+        let level = miner.efficiency as f32;
+        speed += level * level + 1.0;
+    }
+    if let Some(amplifier) = miner.haste {
+        speed *= 1.0 + (amplifier as f32 + 1.0) * 0.2;
+    }
+    if let Some(amplifier) = miner.mining_fatigue {
+        speed *= match amplifier {
+            0 => 0.3,
+            1 => 0.09,
+            2 => 0.0027,
+            _ => 0.00081,
+        };
+    }
+    if miner.underwater {
+        speed /= 5.0;
+    }
+    if !miner.on_ground {
+        speed /= 5.0;
+    }
+    speed
+}
+
+fn tool_speed(block: Block, tool: Option<(Tool, ToolTier)>) -> f32 {
+    let (tool, tier) = match tool {
+        Some(tool) => tool,
+        None => return 1.0,
+    };
+    match tool {
+        Tool::Shears => match block {
+            Block::Web { .. } | Block::Leaves { .. } | Block::Leaves2 { .. } => 15.0,
+            Block::Wool { .. } => 5.0,
+            _ => 1.0,
+        },
+        Tool::Sword => match block {
+            Block::Web { .. } => 15.0,
+            Block::Leaves { .. }
+            | Block::Leaves2 { .. }
+            | Block::Vine { .. }
+            | Block::Cocoa { .. }
+            | Block::Pumpkin { .. }
+            | Block::PumpkinFace { .. }
+            | Block::PumpkinCarved { .. }
+            | Block::PumpkinLit { .. }
+            | Block::MelonBlock { .. } => 1.5,
+            _ => 1.0,
+        },
+        _ if block.get_properties().tool == Some(tool) => tier.speed(),
+        _ => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ticks it takes to break the block as the wiki lists them,
+    /// without the rounding errors of adding up floats.
+    fn ticks(block: Block, miner: &Miner) -> u32 {
+        (1.0 / progress_per_tick(block, miner) - 0.001).ceil() as u32
+    }
+
+    fn holding(tool: Option<(Tool, ToolTier)>) -> Miner {
+        Miner {
+            tool,
+            on_ground: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stone_by_hand_and_pickaxe() {
+        let stone = Block::Cobblestone {};
+        assert_eq!(ticks(stone, &holding(None)), 200);
+        assert_eq!(
+            ticks(stone, &holding(Some((Tool::Pickaxe, ToolTier::Wood)))),
+            30
+        );
+        assert_eq!(
+            ticks(stone, &holding(Some((Tool::Pickaxe, ToolTier::Diamond)))),
+            8
+        );
+        // The wrong tool is no better than a hand
+        assert_eq!(
+            ticks(stone, &holding(Some((Tool::Shovel, ToolTier::Diamond)))),
+            200
+        );
+    }
+
+    #[test]
+    fn obsidian_needs_diamond() {
+        let obsidian = Block::Obsidian {};
+        assert_eq!(
+            ticks(obsidian, &holding(Some((Tool::Pickaxe, ToolTier::Diamond)))),
+            188
+        );
+        assert_eq!(
+            ticks(obsidian, &holding(Some((Tool::Pickaxe, ToolTier::Iron)))),
+            834
+        );
+    }
+
+    #[test]
+    fn instant_and_unbreakable() {
+        assert_eq!(
+            progress_per_tick(Block::TNT { explode: false }, &holding(None)),
+            1.0
+        );
+        assert_eq!(progress_per_tick(Block::Bedrock {}, &holding(None)), 0.0);
+    }
+
+    #[test]
+    fn efficiency_and_effects() {
+        let stone = Block::Stone {
+            variant: crate::world::block::StoneVariant::Normal,
+        };
+        let pickaxe = Some((Tool::Pickaxe, ToolTier::Diamond));
+        let efficient = Miner {
+            efficiency: 5,
+            ..holding(pickaxe)
+        };
+        assert_eq!(ticks(stone, &efficient), 2);
+        // Efficiency does nothing for a tool that doesn't suit the block
+        let wrong_tool = Miner {
+            tool: Some((Tool::Axe, ToolTier::Diamond)),
+            ..efficient
+        };
+        assert_eq!(ticks(stone, &wrong_tool), 150);
+
+        let haste = Miner {
+            haste: Some(1),
+            ..holding(pickaxe)
+        };
+        assert_eq!(ticks(stone, &haste), 5);
+        let fatigue = Miner {
+            mining_fatigue: Some(0),
+            ..holding(pickaxe)
+        };
+        assert_eq!(ticks(stone, &fatigue), 19);
+    }
+
+    #[test]
+    fn underwater_and_in_the_air() {
+        let gravel = Block::Gravel {};
+        assert_eq!(ticks(gravel, &holding(None)), 18);
+        let swimming = Miner {
+            underwater: true,
+            on_ground: false,
+            ..Default::default()
+        };
+        assert_eq!(ticks(gravel, &swimming), 450);
+    }
+
+    #[test]
+    fn shears_and_swords() {
+        let web = Block::Web {};
+        assert_eq!(ticks(web, &holding(None)), 400);
+        assert_eq!(ticks(web, &holding(Some((Tool::Sword, ToolTier::Wood)))), 8);
+        assert_eq!(
+            ticks(web, &holding(Some((Tool::Shears, ToolTier::Iron)))),
+            8
+        );
+    }
+
+    #[test]
+    fn crack_stages() {
+        assert_eq!(destroy_stage(0.05), None);
+        assert_eq!(destroy_stage(0.1), Some(0));
+        assert_eq!(destroy_stage(0.55), Some(4));
+        assert_eq!(destroy_stage(0.99), Some(8));
+        assert_eq!(destroy_stage(1.0), Some(9));
+    }
+}
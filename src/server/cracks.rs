@@ -0,0 +1,144 @@
+use crate::model::Factory;
+use crate::render;
+use crate::render::model;
+use crate::shared::{Direction, Position};
+use crate::world;
+use crate::world::block::{self, Block};
+use cgmath::{Matrix4, Vector3};
+use std::collections::HashMap;
+
+/// How many ticks a crack stays without being updated, the same as
+/// vanilla so a breaker that vanishes doesn't leave it forever.
+const TIMEOUT_TICKS: f64 = 400.0;
+/// How much bigger than the block the overlay is drawn to keep it from
+/// fighting with the block's faces.
+const GROWTH: f32 = 1.002;
+
+struct Crack {
+    pos: Position,
+    stage: u8,
+    ticks: f64,
+    /// The block and stage the model was built for
+    built: Option<(Block, u8)>,
+    model: Option<model::ModelKey>,
+}
+
+/// The cracks drawn over blocks being broken, by the entity id of the
+/// player breaking them. The local player's progress goes in here too.
+#[derive(Default)]
+pub struct Cracks {
+    cracks: HashMap<i32, Crack>,
+    /// Models of cleared cracks, removed on the next tick
+    removed: Vec<model::ModelKey>,
+}
+
+impl Cracks {
+    /// Sets the crack a breaker has made in a block, stages outside of
+    /// 0 to 9 clear it.
+    pub fn update(&mut self, breaker: i32, pos: Position, stage: i8) {
+        if !(0..10).contains(&stage) {
+            if let Some(crack) = self.cracks.remove(&breaker) {
+                self.removed.extend(crack.model);
+            }
+            return;
+        }
+        let crack = self.cracks.entry(breaker).or_insert_with(|| Crack {
+            pos,
+            stage: 0,
+            ticks: 0.0,
+            built: None,
+            model: None,
+        });
+        crack.pos = pos;
+        crack.stage = stage as u8;
+        crack.ticks = 0.0;
+    }
+
+    pub fn tick(&mut self, renderer: &mut render::Renderer, world: &world::World, delta: f64) {
+        for model in self.removed.drain(..) {
+            renderer.model.remove_model(model);
+        }
+        let ticks = delta / 3.0;
+        self.cracks.retain(|_, crack| {
+            crack.ticks += ticks;
+            if crack.ticks >= TIMEOUT_TICKS {
+                if let Some(model) = crack.model.take() {
+                    renderer.model.remove_model(model);
+                }
+                return false;
+            }
+            crack.update_model(renderer, world);
+            true
+        });
+    }
+
+    pub fn remove(&mut self, renderer: &mut render::Renderer) {
+        for model in self.removed.drain(..) {
+            renderer.model.remove_model(model);
+        }
+        for crack in self.cracks.values_mut() {
+            if let Some(model) = crack.model.take() {
+                renderer.model.remove_model(model);
+            }
+        }
+        self.cracks.clear();
+    }
+}
+
+impl Crack {
+    fn update_model(&mut self, renderer: &mut render::Renderer, world: &world::World) {
+        let bl = world.get_block(self.pos);
+        if self.built != Some((bl, self.stage)) {
+            if let Some(model) = self.model.take() {
+                renderer.model.remove_model(model);
+            }
+            self.built = Some((bl, self.stage));
+            if !matches!(bl, block::Air {} | block::CaveAir {} | block::VoidAir {}) {
+                self.model = Some(Crack::create_model(renderer, bl, self.stage));
+            }
+        }
+        let model = match self.model.and_then(|key| renderer.model.get_model(key)) {
+            Some(model) => model,
+            None => return,
+        };
+
+        // Solid blocks are dark inside, so light the crack like the
+        // brightest of the blocks around it
+        let (mut block_light, mut sky_light) = (0, 0);
+        for pos in Direction::all()
+            .into_iter()
+            .map(|dir| self.pos.shift(dir))
+            .chain(Some(self.pos))
+        {
+            block_light = block_light.max(world.get_block_light(pos));
+            sky_light = sky_light.max(world.get_sky_light(pos));
+        }
+        model.block_light = block_light as f32;
+        model.sky_light = sky_light as f32;
+
+        model.matrix[0] = Matrix4::from_translation(Vector3::new(
+            self.pos.x as f32 + 0.5,
+            -self.pos.y as f32 - 0.5,
+            self.pos.z as f32 + 0.5,
+        )) * Matrix4::from_scale(GROWTH)
+            * Matrix4::from_translation(Vector3::new(-0.5, 0.5, -0.5));
+    }
+
+    /// The block's model with every face showing the crack texture
+    /// instead of its own.
+    fn create_model(renderer: &mut render::Renderer, bl: Block, stage: u8) -> model::ModelKey {
+        let tex = render::Renderer::get_texture(
+            renderer.get_textures_ref(),
+            &format!("minecraft:blocks/destroy_stage_{}", stage),
+        );
+        let mut verts =
+            Factory::get_state_vertices(&renderer.block_models, bl, &mut rand::thread_rng());
+        for vert in &mut verts {
+            vert.texture = tex.clone();
+            vert.r = 255;
+            vert.g = 255;
+            vert.b = 255;
+        }
+        renderer.model.create_model(model::DEFAULT, vec![verts])
+    }
+}
@@ -0,0 +1,337 @@
+use crate::entity;
+use crate::inventory::Item;
+use crate::model::{Factory, ModelDisplay};
+use crate::render;
+use crate::render::model;
+use crate::shared::Position;
+use crate::world;
+use cgmath::{Deg, Matrix4, Point3, Vector3};
+use std::f32::consts::PI;
+
+/// How many ticks a swing of the arm takes, like vanilla without haste.
+const SWING_TICKS: f64 = 6.0;
+/// How much of the way up or down the hand moves in a tick when the held
+/// item changes.
+const EQUIP_SPEED: f64 = 0.4;
+
+/// What the local player holds, which the hands are drawn from.
+pub struct Held {
+    pub main_hand: Option<Item>,
+    pub off_hand: Option<Item>,
+    /// The hotbar slot, switching slots swaps the item even when the new
+    /// one is the same
+    pub slot: u8,
+    pub skin_url: Option<String>,
+}
+
+/// What a hand's model was built as.
+enum Built {
+    Item,
+    /// The bare arm, with the skin it was built with
+    Arm(Option<String>),
+    Nothing,
+}
+
+#[derive(Default)]
+struct Hand {
+    /// The item drawn, which lags behind the held one until the hand is
+    /// lowered to swap it
+    shown: Option<Item>,
+    slot: u8,
+    /// How far the hand is raised, from 0 to 1
+    equipped: f64,
+    built: Option<Built>,
+    model: Option<model::ModelKey>,
+    /// How the item model is placed in the hand, none for the bare arm
+    display: Option<ModelDisplay>,
+}
+
+/// The local player's hands in first person, drawn on top of the world
+/// with the item held in each or the bare main hand arm.
+#[derive(Default)]
+pub struct HandModel {
+    main_hand: Hand,
+    off_hand: Hand,
+    /// Ticks into the current swing of the main hand
+    swing: Option<f64>,
+    /// How far the player has walked, which the bobbing follows
+    walked: f64,
+    /// How strongly the hands bob, following the speed of the player
+    bob: f64,
+    last_pos: Option<Point3<f64>>,
+}
+
+impl HandModel {
+    /// Swings the main hand, restarting a swing that's over halfway
+    /// through like vanilla.
+    pub fn swing(&mut self) {
+        if self.swing.is_none_or(|ticks| ticks >= SWING_TICKS / 2.0) {
+            self.swing = Some(0.0);
+        }
+    }
+
+    /// Animates and places the hands, hiding them without anything held.
+    pub fn tick(
+        &mut self,
+        renderer: &mut render::Renderer,
+        world: &world::World,
+        delta: f64,
+        held: Option<Held>,
+    ) {
+        let held = match held {
+            Some(held) => held,
+            None => {
+                self.remove(renderer);
+                return;
+            }
+        };
+        let ticks = delta / 3.0;
+
+        if let Some(swing) = self.swing.as_mut() {
+            *swing += ticks;
+            if *swing >= SWING_TICKS {
+                self.swing = None;
+            }
+        }
+
+        let pos = renderer.camera.pos;
+        let moved = self
+            .last_pos
+            .map_or(0.0, |last| (pos.x - last.x).hypot(pos.z - last.z));
+        self.last_pos = Some(pos);
+        self.walked += moved * 0.6;
+        if ticks > 0.0 {
+            let speed = (moved / ticks).min(0.1);
+            self.bob += (speed - self.bob) * (0.4 * ticks).min(1.0);
+        }
+
+        self.main_hand.update(
+            renderer,
+            held.main_hand.as_ref(),
+            held.slot,
+            held.skin_url.as_ref(),
+            ticks,
+            false,
+        );
+        self.off_hand
+            .update(renderer, held.off_hand.as_ref(), 0, None, ticks, true);
+
+        let light_pos = Position::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        );
+        let light = (
+            world.get_block_light(light_pos) as f32,
+            world.get_sky_light(light_pos) as f32,
+        );
+        let bob = self.bob_matrix();
+        let swing = self.swing.map_or(0.0, |ticks| ticks / SWING_TICKS) as f32;
+        self.main_hand.place(renderer, bob, 1.0, swing, light);
+        self.off_hand.place(renderer, bob, -1.0, 0.0, light);
+    }
+
+    /// The sway of walking, vanilla's view bobbing.
+    fn bob_matrix(&self) -> Matrix4<f32> {
+        let phase = -self.walked as f32 * PI;
+        let bob = self.bob as f32;
+        Matrix4::from_translation(Vector3::new(
+            phase.sin() * bob * 0.5,
+            -(phase.cos() * bob).abs(),
+            0.0,
+        )) * Matrix4::from_angle_z(Deg(phase.sin() * bob * 3.0))
+            * Matrix4::from_angle_x(Deg((phase - 0.2).cos().abs() * bob * 5.0))
+    }
+
+    pub fn remove(&mut self, renderer: &mut render::Renderer) {
+        self.main_hand.remove(renderer);
+        self.off_hand.remove(renderer);
+        self.swing = None;
+        self.last_pos = None;
+        self.bob = 0.0;
+    }
+}
+
+impl Hand {
+    /// Lowers the hand when the held item changes and raises it again with
+    /// the new item once it's out of sight.
+    fn update(
+        &mut self,
+        renderer: &mut render::Renderer,
+        item: Option<&Item>,
+        slot: u8,
+        skin_url: Option<&String>,
+        ticks: f64,
+        off_hand: bool,
+    ) {
+        let changed = self.slot != slot || !same_item(self.shown.as_ref(), item);
+        let target = if changed { 0.0 } else { 1.0 };
+        let step = EQUIP_SPEED * ticks;
+        self.equipped += (target - self.equipped).clamp(-step, step);
+        if changed && self.equipped < 0.1 {
+            self.shown = item.cloned();
+            self.slot = slot;
+            self.clear(renderer);
+        }
+
+        let outdated = match &self.built {
+            None => true,
+            Some(Built::Arm(built_skin)) => built_skin.as_ref() != skin_url,
+            Some(_) => false,
+        };
+        if outdated {
+            self.build(renderer, skin_url, off_hand);
+        }
+    }
+
+    fn build(
+        &mut self,
+        renderer: &mut render::Renderer,
+        skin_url: Option<&String>,
+        off_hand: bool,
+    ) {
+        self.clear(renderer);
+        let mut verts = vec![];
+        if let Some(item) = &self.shown {
+            let block = entity::item::append_item(&mut verts, renderer, &item.material);
+            // Centre the item on the origin like the display expects
+            for vert in &mut verts {
+                vert.y -= 0.5;
+            }
+            let context = if off_hand {
+                "firstperson_lefthand"
+            } else {
+                "firstperson_righthand"
+            };
+            let display =
+                Factory::get_item_model(&renderer.block_models, &item.material.model_name())
+                    .map_or_else(|| default_display(block), |model| model.display(context));
+            self.display = Some(if off_hand {
+                display.mirrored()
+            } else {
+                display
+            });
+            self.built = Some(Built::Item);
+        } else if off_hand {
+            // Only the main hand is drawn bare
+            self.built = Some(Built::Nothing);
+            return;
+        } else {
+            let skin = if let Some(url) = skin_url {
+                renderer.get_skin(renderer.get_textures_ref(), url)
+            } else {
+                render::Renderer::get_texture(renderer.get_textures_ref(), "entity/steve")
+            };
+            entity::player::append_right_arm(&mut verts, &skin);
+            self.display = None;
+            self.built = Some(Built::Arm(skin_url.cloned()));
+        }
+        self.model = Some(renderer.model.create_model(model::HAND, vec![verts]));
+    }
+
+    /// Moves the hand into place in front of the camera, with `side` being
+    /// 1 for the right hand and -1 for the left.
+    fn place(
+        &mut self,
+        renderer: &mut render::Renderer,
+        bob: Matrix4<f32>,
+        side: f32,
+        swing: f32,
+        (block_light, sky_light): (f32, f32),
+    ) {
+        let model = match self.model.and_then(|key| renderer.model.get_model(key)) {
+            Some(model) => model,
+            None => return,
+        };
+        model.block_light = block_light;
+        model.sky_light = sky_light;
+
+        let lowered = (1.0 - self.equipped) as f32;
+        let matrix = match &self.display {
+            Some(display) => item_matrix(side, swing, lowered) * display.matrix(),
+            None => arm_matrix(side, swing, lowered),
+        };
+        // Undo the flip of y the model shader does for the world
+        model.matrix[0] = bob * matrix * Matrix4::from_nonuniform_scale(1.0, -1.0, 1.0);
+    }
+
+    fn clear(&mut self, renderer: &mut render::Renderer) {
+        if let Some(model) = self.model.take() {
+            renderer.model.remove_model(model);
+        }
+        if let Some(Built::Arm(Some(url))) = self.built.take() {
+            renderer.get_textures_ref().read().release_skin(&url);
+        }
+    }
+
+    fn remove(&mut self, renderer: &mut render::Renderer) {
+        self.clear(renderer);
+        *self = Default::default();
+    }
+}
+
+/// Whether the hand shows the same item, changes to just the count or
+/// the data of the item don't swap it.
+fn same_item(shown: Option<&Item>, held: Option<&Item>) -> bool {
+    match (shown, held) {
+        (None, None) => true,
+        (Some(shown), Some(held)) => {
+            std::mem::discriminant(&shown.material) == std::mem::discriminant(&held.material)
+        }
+        _ => false,
+    }
+}
+
+/// Vanilla's defaults for holding blocks and items without a model.
+fn default_display(block: bool) -> ModelDisplay {
+    if block {
+        ModelDisplay {
+            rotation: [0.0, 45.0, 0.0],
+            translation: [0.0, 0.0, 0.0],
+            scale: [0.4; 3],
+        }
+    } else {
+        ModelDisplay {
+            rotation: [0.0, -90.0, 25.0],
+            translation: [1.13, 3.2, 1.13],
+            scale: [0.68; 3],
+        }
+    }
+}
+
+/// Where a held item goes in front of the camera, before its display.
+fn item_matrix(side: f32, swing: f32, lowered: f32) -> Matrix4<f32> {
+    let swing_sqrt = swing.sqrt();
+    let arc = (swing_sqrt * PI).sin();
+    Matrix4::from_translation(Vector3::new(
+        side * -0.4 * arc,
+        0.2 * (swing_sqrt * PI * 2.0).sin(),
+        -0.2 * (swing * PI).sin(),
+    )) * Matrix4::from_translation(Vector3::new(side * 0.56, -0.52 - lowered * 0.6, -0.72))
+        * Matrix4::from_angle_y(Deg(side * (45.0 - (swing * swing * PI).sin() * 20.0)))
+        * Matrix4::from_angle_z(Deg(side * arc * -20.0))
+        * Matrix4::from_angle_x(Deg(arc * -80.0))
+        * Matrix4::from_angle_y(Deg(side * -45.0))
+}
+
+/// Where the bare arm goes in front of the camera, vanilla's arm with its
+/// shoulder off to the side of the screen.
+fn arm_matrix(side: f32, swing: f32, lowered: f32) -> Matrix4<f32> {
+    let swing_sqrt = swing.sqrt();
+    let arc = (swing_sqrt * PI).sin();
+    Matrix4::from_translation(Vector3::new(
+        side * (-0.3 * arc + 0.64),
+        0.4 * (swing_sqrt * PI * 2.0).sin() - 0.6 - lowered * 0.6,
+        -0.4 * (swing * PI).sin() - 0.72,
+    )) * Matrix4::from_angle_y(Deg(side * 45.0))
+        * Matrix4::from_angle_y(Deg(side * arc * 70.0))
+        * Matrix4::from_angle_z(Deg(side * (swing * swing * PI).sin() * -20.0))
+        * Matrix4::from_translation(Vector3::new(-side, 3.6, 3.5))
+        * Matrix4::from_angle_z(Deg(side * 120.0))
+        * Matrix4::from_angle_x(Deg(200.0))
+        * Matrix4::from_angle_y(Deg(side * -135.0))
+        * Matrix4::from_translation(Vector3::new(side * 5.6, 0.0, 0.0))
+        // The arm part, which points up out of the shoulder in vanilla's model
+        * Matrix4::from_translation(Vector3::new(-6.0 / 16.0, 0.0, 0.0))
+        * Matrix4::from_angle_z(Deg(180.0))
+}
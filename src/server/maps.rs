@@ -0,0 +1,96 @@
+use crate::entity::hanging::map_texture;
+use crate::render;
+use std::collections::HashMap;
+
+/// The width and height of a map in pixels
+const SIZE: usize = 128;
+
+/// The base colours of the map palette, each of them comes in four
+/// shades. The first one is transparent.
+const COLORS: [u32; 59] = [
+    0x000000, 0x7fb238, 0xf7e9a3, 0xc7c7c7, 0xff0000, 0xa0a0ff, 0xa7a7a7, 0x007c00, 0xffffff,
+    0xa4a8b8, 0x976d4d, 0x707070, 0x4040ff, 0x8f7748, 0xfffcf5, 0xd87f33, 0xb24cd8, 0x6699d8,
+    0xe5e533, 0x7fcc19, 0xf27fa5, 0x4c4c4c, 0x999999, 0x4c7f99, 0x7f3fb2, 0x334cb2, 0x664c33,
+    0x667f33, 0x993333, 0x191919, 0xfaee4d, 0x5cdbd5, 0x4a80ff, 0x00d93a, 0x815631, 0x700200,
+    0xd1b1a1, 0x9f5224, 0x95576c, 0x706c8a, 0xba8524, 0x677535, 0xa04d4e, 0x392923, 0x876b62,
+    0x575c5c, 0x7a4958, 0x4c3e5c, 0x4c3223, 0x4c522a, 0x8e3c2e, 0x251610, 0xbd3031, 0x943f61,
+    0x5c191d, 0x167e86, 0x3a8e8c, 0x562c3e, 0x14b485,
+];
+const SHADES: [u32; 4] = [180, 220, 255, 135];
+
+struct Map {
+    colors: Vec<u8>,
+    dirty: bool,
+}
+
+/// The contents of the maps the server sent, kept as textures for item
+/// frames to draw.
+#[derive(Default)]
+pub struct Maps {
+    maps: HashMap<i32, Map>,
+}
+
+impl Maps {
+    /// Replaces a rectangle of the map's pixels with the colours the
+    /// server sent, stored a row at a time.
+    pub fn update(
+        &mut self,
+        id: i32,
+        x: usize,
+        z: usize,
+        columns: usize,
+        rows: usize,
+        data: &[u8],
+    ) {
+        let map = self.maps.entry(id).or_insert_with(|| Map {
+            colors: vec![0; SIZE * SIZE],
+            dirty: true,
+        });
+        for row in 0..rows.min(SIZE.saturating_sub(z)) {
+            for col in 0..columns.min(SIZE.saturating_sub(x)) {
+                if let Some(&color) = data.get(row * columns + col) {
+                    map.colors[(z + row) * SIZE + x + col] = color;
+                }
+            }
+        }
+        map.dirty = true;
+    }
+
+    /// Uploads the maps that changed since the last tick.
+    pub fn tick(&mut self, renderer: &mut render::Renderer) {
+        for (id, map) in &mut self.maps {
+            if !map.dirty {
+                continue;
+            }
+            map.dirty = false;
+            let mut img = image::RgbaImage::new(SIZE as u32, SIZE as u32);
+            for (pixel, &color) in img.pixels_mut().zip(&map.colors) {
+                *pixel = to_rgba(color);
+            }
+            renderer
+                .get_textures_ref()
+                .write()
+                .update_dynamic(&map_texture(*id), image::DynamicImage::ImageRgba8(img));
+        }
+    }
+
+    pub fn remove(&mut self, renderer: &mut render::Renderer) {
+        let mut textures = renderer.get_textures_ref().write();
+        for id in self.maps.keys() {
+            if textures.get_dynamic(&map_texture(*id)).is_some() {
+                textures.remove_dynamic(&map_texture(*id));
+            }
+        }
+        self.maps.clear();
+    }
+}
+
+fn to_rgba(color: u8) -> image::Rgba<u8> {
+    let base = match COLORS.get(color as usize / 4) {
+        Some(&base) if color >= 4 => base,
+        _ => return image::Rgba([0, 0, 0, 0]),
+    };
+    let shade = SHADES[color as usize % 4];
+    let channel = |shift: u32| (((base >> shift) & 0xff) * shade / 255) as u8;
+    image::Rgba([channel(16), channel(8), channel(0), 255])
+}
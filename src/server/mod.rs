@@ -13,11 +13,19 @@
 // limitations under the License.
 
 use crate::entity;
+use crate::entity::metadata::EntityState;
 use crate::entity::player::create_local;
 use crate::entity::EntityType;
 use crate::format;
+use crate::inventory::brewer_inventory::BrewerInventory;
+use crate::inventory::click::WindowClick;
+use crate::inventory::container::{Layout, SimpleContainer};
+use crate::inventory::crafting_table_inventory::CraftingTableInventory;
+use crate::inventory::creative_inventory::CreativeInventory;
+use crate::inventory::furnace_inventory::FurnaceInventory;
 use crate::inventory::material::versions::to_material;
-use crate::inventory::{Inventory, InventoryContext, Item};
+use crate::inventory::player_inventory::PlayerInventory;
+use crate::inventory::{Inventory, InventoryContext, InventoryType, Item};
 use crate::protocol::{self, forge, mapped_packet, packet};
 use crate::render;
 use crate::render::hud::HudContext;
@@ -30,7 +38,9 @@ use crate::settings::Actionkey;
 use crate::shared::Position;
 use crate::types::hash::FNVHash;
 use crate::types::GameMode;
+use crate::types::Metadata;
 use crate::world;
+use crate::world::block;
 use crate::world::{CPos, LightData, LightUpdate};
 use crate::{ecs, Game};
 use cgmath::prelude::*;
@@ -38,6 +48,7 @@ use crossbeam_channel::unbounded;
 use crossbeam_channel::{Receiver, Sender};
 use instant::{Duration, Instant};
 use leafish_protocol::format::{Component, TextComponent};
+use leafish_protocol::item::Stack;
 use leafish_protocol::protocol::login::Account;
 use leafish_protocol::protocol::mapped_packet::MappablePacket;
 use leafish_protocol::protocol::mapped_packet::MappedPacket;
@@ -58,9 +69,16 @@ use std::sync::Arc;
 use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+mod breaking;
+mod cracks;
+mod hand;
+mod maps;
 pub mod plugin_messages;
+mod prediction;
+mod sky;
 mod sun;
 pub mod target;
+mod weather;
 
 #[derive(Default)]
 pub struct DisconnectData {
@@ -97,6 +115,7 @@ pub struct Server {
     pub world: Arc<world::World>,
     pub entities: Arc<RwLock<ecs::Manager>>,
     world_data: Arc<RwLock<WorldData>>,
+    dimension_types: RwLock<world::dimension::Registry>,
 
     resources: Arc<RwLock<resources::Manager>>,
     version: AtomicUsize,
@@ -114,6 +133,9 @@ pub struct Server {
     block_break_info: Mutex<BlockBreakInfo>,
     //
     pub player: Arc<RwLock<Option<ecs::Entity>>>,
+    player_id: RwLock<Option<i32>>,
+    /// The amplifiers of the status effects the local player has.
+    player_effects: RwLock<HashMap<i8, i8>>,
     entity_map: Arc<RwLock<HashMap<i32, ecs::Entity, BuildHasherDefault<FNVHash>>>>,
     players: Arc<RwLock<HashMap<protocol::UUID, PlayerInfo, BuildHasherDefault<FNVHash>>>>,
 
@@ -122,6 +144,12 @@ pub struct Server {
     pub received_chat_at: Arc<RwLock<Option<Instant>>>,
 
     sun_model: RwLock<Option<sun::SunModel>>,
+    end_sky_model: RwLock<Option<sky::EndSkyModel>>,
+    weather: RwLock<weather::Weather>,
+    maps: RwLock<maps::Maps>,
+    cracks: RwLock<cracks::Cracks>,
+    hand: RwLock<hand::HandModel>,
+    predictions: RwLock<prediction::Predictions>,
     target_info: Arc<RwLock<target::Info>>,
     pub render_list_computer: Sender<bool>,
     pub render_list_computer_notify: Receiver<bool>,
@@ -141,7 +169,6 @@ pub struct Server {
 pub struct BlockBreakInfo {
     break_position: Position,
     break_face: BlockDirection,
-    hardness: f32,
     progress: f32,
     delay: u8,
     active: bool,
@@ -410,7 +437,6 @@ impl Server {
             render_list_computer.0.clone(),
             render_list_computer.1,
             hud_context,
-            &renderer.read(),
             screen_sys,
         ));
         server.hud_context.clone().write().server = Some(server.clone());
@@ -509,6 +535,28 @@ impl Server {
                         MappedPacket::EntityMove(m) => {
                             server.on_entity_move(m);
                         }
+                        MappedPacket::EntityMetadata(entity_metadata) => {
+                            server.on_entity_metadata(
+                                entity_metadata.entity_id,
+                                &entity_metadata.metadata,
+                            );
+                        }
+                        MappedPacket::EntityEquipment_Array(equipment) => {
+                            for slot in equipment.equipments.equipments {
+                                server.on_entity_equipment(
+                                    equipment.entity_id,
+                                    slot.slot as i32,
+                                    slot.item,
+                                );
+                            }
+                        }
+                        MappedPacket::EntityEquipment_Single(equipment) => {
+                            server.on_entity_equipment(
+                                equipment.entity_id,
+                                equipment.slot,
+                                equipment.item,
+                            );
+                        }
                         MappedPacket::EntityLook(look) => {
                             server.on_entity_look(
                                 look.entity_id,
@@ -531,6 +579,14 @@ impl Server {
                             }
                         }
                         MappedPacket::JoinGame(join) => {
+                            if let Some(codec) = join.dimension_codec {
+                                server.on_dimension_codec(codec);
+                            }
+                            server.on_dimension_change(
+                                join.dimension.as_ref(),
+                                join.dimension_name.as_deref(),
+                                join.dimension_id,
+                            );
                             server.on_game_join(join.gamemode, join.entity_id);
                         }
                         MappedPacket::TeleportPlayer(teleport) => {
@@ -542,7 +598,10 @@ impl Server {
                         MappedPacket::SpawnMob(spawn) => {
                             use std::f64::consts::PI;
                             server.on_entity_spawn(
-                                spawn.ty as i16,
+                                entity::versions::to_entity_type(
+                                    spawn.ty as i16,
+                                    server.mapped_protocol_version,
+                                ),
                                 spawn.entity_id,
                                 spawn.x,
                                 spawn.y,
@@ -550,17 +609,27 @@ impl Server {
                                 -(spawn.yaw as f64 / 256.0) * PI * 2.0,
                                 -(spawn.pitch as f64 / 256.0) * PI * 2.0,
                             );
+                            // Sent along with the spawn before 1.15
+                            if let Some(metadata) = spawn.metadata.as_ref() {
+                                server.on_entity_metadata(spawn.entity_id, metadata);
+                            }
                         }
                         MappedPacket::SpawnObject(spawn) => {
-                            use std::f64::consts::PI;
-                            server.on_entity_spawn(
-                                spawn.ty as i16,
-                                spawn.entity_id,
-                                spawn.x,
-                                spawn.y,
-                                spawn.z,
-                                -(spawn.yaw as f64 / 256.0) * PI * 2.0,
-                                -(spawn.pitch as f64 / 256.0) * PI * 2.0,
+                            server.on_object_spawn(spawn);
+                        }
+                        MappedPacket::SpawnPainting(spawn) => {
+                            server.on_painting_spawn(spawn);
+                        }
+                        MappedPacket::Maps(maps) => {
+                            server.on_maps(maps);
+                        }
+                        MappedPacket::SpawnExperienceOrb(spawn) => {
+                            server.on_experience_orb_spawn(spawn);
+                        }
+                        MappedPacket::CollectItem(collect_item) => {
+                            server.on_collect_item(
+                                collect_item.collected_entity_id,
+                                collect_item.collector_entity_id,
                             );
                         }
                         MappedPacket::EntityTeleport(entity_teleport) => {
@@ -618,19 +687,15 @@ impl Server {
                                 spawn.pitch as f64,
                                 spawn.yaw as f64,
                             );
+                            if let Some(metadata) = spawn.metadata.as_ref() {
+                                server.on_entity_metadata(spawn.entity_id, metadata);
+                            }
                         }
                         MappedPacket::PlayerInfo(player_info) => {
                             server.on_player_info(player_info);
                         }
                         MappedPacket::ConfirmTransaction(transaction) => {
-                            read.write_packet(
-                                packet::play::serverbound::ConfirmTransactionServerbound {
-                                    id: 0, // TODO: Use current container id, if the id of the transaction is not 0.
-                                    action_number: transaction.action_number,
-                                    accepted: true,
-                                },
-                            )
-                            .unwrap();
+                            server.on_confirm_transaction(transaction);
                         }
                         MappedPacket::UpdateLight(update_light) => {
                             server.world.clone().lighting_cache.clone().write().insert(
@@ -645,6 +710,15 @@ impl Server {
                         MappedPacket::ChangeGameState(game_state) => {
                             server.on_game_state_change(game_state);
                         }
+                        MappedPacket::SpawnGlobalEntity(spawn) => {
+                            // Lightning is the only global entity
+                            if spawn.ty == 1 {
+                                server
+                                    .weather
+                                    .write()
+                                    .strike_lightning(spawn.x, spawn.y, spawn.z);
+                            }
+                        }
                         MappedPacket::UpdateHealth(update_health) => {
                             server.on_update_health(
                                 update_health.health,
@@ -687,72 +761,52 @@ impl Server {
                                 warn!("The server tried to set the hotbar slot to {}, although it has to be in a range of 0-8! Did it try to crash you?", set_slot.slot);
                             }
                         }
-                        MappedPacket::WindowItems(_window_items) => {
-                            debug!("items!");
+                        MappedPacket::WindowOpen(open) => {
+                            server.on_window_open(open);
                         }
-                        MappedPacket::WindowSetSlot(set_slot) => {
-                            let top_inventory = server.inventory_context.clone();
-                            let inventory = if let Some(inventory) =
-                                top_inventory.clone().read().inventory.as_ref()
-                            {
-                                inventory.clone()
-                            } else {
-                                top_inventory.clone().read().player_inventory.clone()
-                            };
-                            let curr_slots = inventory.clone().read().size();
-                            if set_slot.slot < 0 || set_slot.slot >= curr_slots {
-                                if set_slot.slot == -1 {
-                                    let item = set_slot.item.map(|stack| {
-                                        let id = stack.id;
-                                        Item {
-                                            stack,
-                                            material: to_material(
-                                                id as u16,
-                                                server.mapped_protocol_version,
-                                            ),
-                                        }
-                                    });
-                                    top_inventory.write().cursor = item; // TODO: Set to HUD and make it dirty!
-                                } else {
-                                    warn!("The server tried to set an item to slot {} but the current inventory only has {} slots. Did it try to crash you?", set_slot.id + 1, curr_slots);
-                                }
-                            } else {
-                                debug!(
-                                    "set item to {}, {}, {}",
-                                    set_slot.id,
-                                    set_slot.slot,
-                                    set_slot.item.as_ref().map_or(0, |s| s.id)
-                                );
-                                let item = set_slot.item.map(|stack| {
-                                    let id = stack.id;
-                                    Item {
-                                        stack,
-                                        material: to_material(
-                                            id as u16,
-                                            server.mapped_protocol_version,
-                                        ),
-                                    }
-                                });
-                                inventory.clone().write().set_item(set_slot.slot, item);
+                        MappedPacket::WindowClose(close) => {
+                            server.on_window_close(close);
+                        }
+                        MappedPacket::WindowItems(window_items) => {
+                            server.on_window_items(window_items);
+                        }
+                        MappedPacket::WindowProperty(property) => {
+                            if let Some(inventory) = server.window_inventory(property.id as i8) {
+                                inventory
+                                    .write()
+                                    .handle_property(property.property, property.value);
                             }
                         }
-                        MappedPacket::EntityVelocity(_velocity) => {
-                            // TODO: Only apply the velocity to the local player due to jittering of other players
-                            /* if let Some(entity) =
-                                server.entity_map.clone().read().get(&velocity.entity_id)
-                            {
-                                let entity_velocity = server
-                                    .entities
-                                    .clone()
+                        MappedPacket::WindowSetSlot(set_slot) => {
+                            server.on_window_set_slot(set_slot);
+                        }
+                        MappedPacket::EntityVelocity(velocity) => {
+                            server.on_entity_velocity(velocity);
+                        }
+                        MappedPacket::AcknowledgePlayerDigging(ack) => {
+                            // The block is the server's either way, whether
+                            // or not the digging went as we thought
+                            server.on_block_change_in_world(ack.location, ack.block);
+                        }
+                        MappedPacket::BlockBreakAnimation(animation) => {
+                            server.cracks.write().update(
+                                animation.entity_id,
+                                animation.location,
+                                animation.stage,
+                            );
+                        }
+                        MappedPacket::EntityEffect(effect) => {
+                            if *server.player_id.read() == Some(effect.entity_id) {
+                                server
+                                    .player_effects
                                     .write()
-                                    .get_component_mut(*entity, server.velocity)
-                                    .unwrap();
-                                entity_velocity.velocity = Vector3::new(
-                                    velocity.velocity_x as f64 / 8000.0,
-                                    velocity.velocity_y as f64 / 8000.0,
-                                    velocity.velocity_z as f64 / 8000.0,
-                                );
-                            }*/
+                                    .insert(effect.effect_id, effect.amplifier);
+                            }
+                        }
+                        MappedPacket::EntityRemoveEffect(effect) => {
+                            if *server.player_id.read() == Some(effect.entity_id) {
+                                server.player_effects.write().remove(&effect.effect_id);
+                            }
                         }
                         _ => {
                             // debug!("other packet!");
@@ -840,7 +894,6 @@ impl Server {
         render_list_computer: Sender<bool>,
         render_list_computer_notify: Receiver<bool>,
         hud_context: Arc<RwLock<HudContext>>,
-        renderer: &Renderer,
         screen_sys: Arc<ScreenSystem>,
     ) -> Server {
         let mut entities = ecs::Manager::new();
@@ -853,12 +906,11 @@ impl Server {
         let version = Version::from_id(protocol_version as u32);
         let inventory_context = Arc::new(RwLock::new(InventoryContext::new(
             version,
-            renderer,
             hud_context.clone(),
         )));
         hud_context.write().player_inventory =
             Some(inventory_context.read().player_inventory.clone());
-        EntityType::init(&mut entities);
+        EntityType::init(&mut entities, &resources.read());
 
         let version = resources.read().version();
         Server {
@@ -871,6 +923,7 @@ impl Server {
 
             world: Arc::new(world::World::new(protocol_version, light_updater)),
             world_data: Arc::new(RwLock::new(WorldData::default())),
+            dimension_types: RwLock::new(Default::default()),
             version: AtomicUsize::new(version),
             resources,
 
@@ -887,6 +940,8 @@ impl Server {
             //
             entities: Arc::new(RwLock::new(entities)),
             player: Arc::new(RwLock::new(None)),
+            player_id: RwLock::new(None),
+            player_effects: RwLock::new(HashMap::new()),
             entity_map: Arc::new(RwLock::new(HashMap::with_hasher(
                 BuildHasherDefault::default(),
             ))),
@@ -898,6 +953,12 @@ impl Server {
             entity_tick_timer: RwLock::from(0.0),
             received_chat_at: Arc::new(RwLock::new(None)),
             sun_model: RwLock::new(None),
+            end_sky_model: RwLock::new(None),
+            weather: RwLock::new(Default::default()),
+            maps: RwLock::new(Default::default()),
+            cracks: RwLock::new(Default::default()),
+            hand: RwLock::new(Default::default()),
+            predictions: RwLock::new(Default::default()),
 
             target_info: Arc::new(RwLock::new(target::Info::new())),
             render_list_computer,
@@ -911,7 +972,6 @@ impl Server {
             block_break_info: Mutex::new(BlockBreakInfo {
                 break_position: Default::default(),
                 break_face: BlockDirection::Invalid,
-                hardness: 0.0,
                 progress: 0.0,
                 delay: 0,
                 active: false,
@@ -966,10 +1026,7 @@ impl Server {
             self.world.clone().flag_dirty_all();
         }
         let renderer = &mut renderer.write();
-        // TODO: Check if the world type actually needs a sun
-        if self.sun_model.read().is_none() {
-            self.sun_model.write().replace(sun::SunModel::new(renderer));
-        }
+        self.update_sky(renderer);
 
         // Copy to camera
         if let Some(player) = *self.player.clone().read() {
@@ -999,6 +1056,7 @@ impl Server {
         }
 
         self.update_time(renderer, delta);
+        self.update_sky_color(renderer, delta);
         if let Some(sun_model) = self.sun_model.write().as_mut() {
             sun_model.tick(
                 renderer,
@@ -1006,6 +1064,15 @@ impl Server {
                 self.world_data.clone().read().world_age,
             );
         }
+        if let Some(end_sky_model) = self.end_sky_model.write().as_mut() {
+            end_sky_model.tick(renderer);
+        }
+        self.weather.write().tick(renderer, &self.world, delta);
+        self.maps.write().tick(renderer);
+        self.cracks.write().tick(renderer, &self.world, delta);
+        self.hand
+            .write()
+            .tick(renderer, &self.world, delta, self.held_items());
         let world = self.world.clone();
         world.tick(&mut self.entities.clone().write());
 
@@ -1029,6 +1096,20 @@ impl Server {
             } else {
                 self.target_info.clone().write().clear(renderer);
             }
+            let targeted = self.targeted_entity(renderer).map(|(id, _)| {
+                let ty = self.entity_map.clone().read().get(&id).and_then(|entity| {
+                    self.entities
+                        .clone()
+                        .read()
+                        .get_component_direct::<EntityType>(*entity)
+                        .copied()
+                });
+                format!("{:?} ({})", ty.unwrap_or(EntityType::Unknown), id)
+            });
+            self.hud_context
+                .clone()
+                .write()
+                .update_targeted_entity(targeted);
         } else {
             self.target_info.clone().write().clear(renderer);
         }
@@ -1073,12 +1154,49 @@ impl Server {
         if let Some(sun_model) = self.sun_model.write().as_mut() {
             sun_model.remove(renderer);
         }
+        if let Some(end_sky_model) = self.end_sky_model.write().as_mut() {
+            end_sky_model.remove(renderer);
+        }
+        self.weather.write().remove(renderer);
+        self.maps.write().remove(renderer);
+        self.cracks.write().remove(renderer);
+        self.hand.write().remove(renderer);
         self.target_info.clone().write().clear(renderer);
         EntityType::deinit();
     }
 
+    fn update_sky(&self, renderer: &mut render::Renderer) {
+        use crate::world::dimension::Effects;
+        let dimension = self.world.dimension();
+        renderer.sky_effects = dimension.effects;
+        renderer.ambient_light = dimension.ambient_light;
+
+        // Only the overworld's sky has a sun and moon
+        if dimension.effects == Effects::Overworld {
+            if self.sun_model.read().is_none() {
+                self.sun_model.write().replace(sun::SunModel::new(renderer));
+            }
+        } else if let Some(mut sun_model) = self.sun_model.write().take() {
+            sun_model.remove(renderer);
+        }
+        if dimension.effects == Effects::End {
+            if self.end_sky_model.read().is_none() {
+                self.end_sky_model
+                    .write()
+                    .replace(sky::EndSkyModel::new(renderer));
+            }
+        } else if let Some(mut end_sky_model) = self.end_sky_model.write().take() {
+            end_sky_model.remove(renderer);
+        }
+    }
+
     fn update_time(&self, renderer: &mut render::Renderer, delta: f64) {
-        if self.world_data.clone().read().tick_time {
+        if let Some(time) = self.world.dimension().fixed_time {
+            // The sun doesn't move in dimensions with a fixed time
+            let time = (time % 24000) as f64;
+            self.world_data.clone().write().world_time_target = time;
+            self.world_data.clone().write().world_time = time;
+        } else if self.world_data.clone().read().tick_time {
             self.world_data.clone().write().world_time_target += delta / 3.0;
             let time = self.world_data.clone().read().world_time_target;
             self.world_data.clone().write().world_time_target = (24000.0 + time) % 24000.0;
@@ -1096,7 +1214,40 @@ impl Server {
             let time = self.world_data.clone().read().world_time_target;
             self.world_data.clone().write().world_time = time;
         }
-        renderer.sky_offset = self.calculate_sky_offset();
+        renderer.sky_offset = self.calculate_sky_offset() * self.weather.read().sky_brightness();
+    }
+
+    fn update_sky_color(&self, renderer: &mut render::Renderer, delta: f64) {
+        let pos = renderer.camera.pos;
+        let biome = self.world.get_biome(Position::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        let (sky, fog) = match biome {
+            Some(biome) => (
+                biome
+                    .sky_color
+                    .map_or(render::DEFAULT_SKY_COLOR, color_to_rgb),
+                biome
+                    .fog_color
+                    .map_or(render::DEFAULT_SKY_COLOR, color_to_rgb),
+            ),
+            None => return,
+        };
+        let weather = self.weather.read();
+        let (sky, fog) = (weather.tint_sky(sky), weather.tint_sky(fog));
+        // Fade between biomes instead of switching instantly
+        let t = (delta / 60.0).min(1.0) as f32;
+        let fade = |from: (f32, f32, f32), to: (f32, f32, f32)| {
+            (
+                from.0 + (to.0 - from.0) * t,
+                from.1 + (to.1 - from.1) * t,
+                from.2 + (to.2 - from.2) * t,
+            )
+        };
+        renderer.sky_color = fade(renderer.sky_color, sky);
+        renderer.fog_color = fade(renderer.fog_color, fog);
     }
 
     fn calculate_sky_offset(&self) -> f32 {
@@ -1124,6 +1275,7 @@ impl Server {
     }
 
     pub fn minecraft_tick(&self) {
+        self.predictions.write().tick(&self.world);
         if let Some(player) = *self.player.clone().write() {
             let movement = self
                 .entities
@@ -1176,28 +1328,100 @@ impl Server {
         if break_delay > 0 {
             self.block_break_info.lock().delay -= 1;
         } else if self.block_break_info.lock().active {
+            let pos = self.block_break_info.lock().break_position;
+            let target = self.world.get_block(pos);
+            if matches!(
+                target,
+                block::Air {} | block::CaveAir {} | block::VoidAir {}
+            ) {
+                // Somebody else got to it first
+                self.block_break_info.lock().active = false;
+                self.show_crack(pos, None);
+                return;
+            }
+            self.block_break_info.lock().progress +=
+                breaking::progress_per_tick(target, &self.miner());
+            let progress = self.block_break_info.lock().progress;
+            self.show_crack(pos, Some(progress));
+            packet::send_arm_swing(
+                self.conn.clone().write().as_mut().unwrap(),
+                self.mapped_protocol_version,
+                Hand::MainHand,
+            )
+            .unwrap();
+            self.hand.write().swing();
             if self.block_break_info.lock().progress >= 1.0 {
                 let face_idx = self.block_break_info.lock().break_face.index() as u8;
                 packet::send_digging(
                     self.conn.clone().write().as_mut().unwrap(),
                     self.mapped_protocol_version,
                     DigType::StopDestroyBlock,
-                    self.block_break_info.lock().break_position,
+                    pos,
                     face_idx,
                 )
                 .unwrap();
                 self.block_break_info.lock().active = false;
                 self.block_break_info.lock().delay = 5;
-            } else {
-                packet::send_arm_swing(
-                    self.conn.clone().write().as_mut().unwrap(),
-                    self.mapped_protocol_version,
-                    Hand::MainHand,
-                )
-                .unwrap();
-                self.block_break_info.lock().progress += 0.1; // TODO: Make this value meaningful
+                self.show_crack(pos, None);
+                self.predictions
+                    .write()
+                    .predict(&self.world, pos, block::Air {});
+            }
+        }
+    }
+
+    /// Shows how far the local player got breaking the block, `None`
+    /// once they stop.
+    fn show_crack(&self, pos: Position, progress: Option<f32>) {
+        if let Some(id) = *self.player_id.read() {
+            let stage = progress
+                .and_then(breaking::destroy_stage)
+                .map_or(-1, |stage| stage as i8);
+            self.cracks.write().update(id, pos, stage);
+        }
+    }
+
+    /// Gathers what the local player is holding and affected by to work
+    /// out how fast they break blocks.
+    fn miner(&self) -> breaking::Miner {
+        let inventory_context = self.inventory_context.clone();
+        let inventory_context = inventory_context.read();
+        let inventory = inventory_context.player_inventory.read();
+        let held = inventory.get_item(36 + inventory_context.hotbar_index as i16);
+        let helmet = inventory.get_item(5);
+        let effects = self.player_effects.read();
+
+        let mut miner = breaking::Miner {
+            tool: held.as_ref().and_then(|item| item.material.tool()),
+            efficiency: held
+                .as_ref()
+                .map_or(0, |item| item.enchantment_level("efficiency", 32)),
+            haste: effects.get(&breaking::HASTE).copied(),
+            mining_fatigue: effects.get(&breaking::MINING_FATIGUE).copied(),
+            ..Default::default()
+        };
+        if let Some(player) = *self.player.clone().read() {
+            let entities = self.entities.clone();
+            let entities = entities.read();
+            miner.on_ground = entities
+                .get_component(player, self.gravity)
+                .is_some_and(|gravity| gravity.on_ground);
+            if let Some(position) = entities.get_component(player, self.target_position) {
+                let eyes = position.position + cgmath::Vector3::new(0.0, 1.62, 0.0);
+                let eyes = Position::new(
+                    eyes.x.floor() as i32,
+                    eyes.y.floor() as i32,
+                    eyes.z.floor() as i32,
+                );
+                miner.underwater = matches!(
+                    self.world.get_block(eyes),
+                    block::Water { .. } | block::FlowingWater { .. }
+                ) && helmet
+                    .as_ref()
+                    .is_none_or(|item| item.enchantment_level("aqua_affinity", 6) == 0);
             }
         }
+        miner
     }
 
     pub fn key_press(
@@ -1229,8 +1453,18 @@ impl Server {
                             .read()
                             .player_inventory
                             .clone();
+                        let inventory: Arc<RwLock<dyn Inventory + Send + Sync>> =
+                            match self.hud_context.read().get_game_mode() {
+                                GameMode::Creative => {
+                                    Arc::new(RwLock::new(CreativeInventory::new(
+                                        self.mapped_protocol_version,
+                                        player_inv,
+                                    )))
+                                }
+                                _ => player_inv,
+                            };
                         screen_sys.add_screen(Box::new(render::inventory::InventoryWindow::new(
-                            player_inv,
+                            inventory,
                             self.inventory_context.clone(),
                         )));
                         return true;
@@ -1267,7 +1501,7 @@ impl Server {
             Hand::MainHand,
         )
         .unwrap();
-        // TODO: Implement clientside animation.
+        self.hand.write().swing();
         if self.player.clone().read().is_some() {
             let world = self.world.clone();
             let gamemode = *self
@@ -1276,7 +1510,14 @@ impl Server {
                 .write()
                 .get_component(*self.player.clone().read().as_ref().unwrap(), self.gamemode)
                 .unwrap();
-            if !matches!(gamemode, GameMode::Spectator) && self.block_break_info.lock().delay == 0 {
+            if matches!(gamemode, GameMode::Spectator) {
+                return;
+            }
+            if let Some((id, _)) = self.targeted_entity(&renderer.read()) {
+                self.use_entity(id, packet::UseEntityType::Attack);
+                return;
+            }
+            if self.block_break_info.lock().delay == 0 {
                 // TODO: Check this
                 if let Some((pos, _, face, _)) = target::trace_ray(
                     &world,
@@ -1293,11 +1534,26 @@ impl Server {
                         face.index() as u8,
                     )
                     .unwrap();
-                    self.block_break_info.lock().break_face = face;
-                    self.block_break_info.lock().break_position = pos;
-                    self.block_break_info.lock().progress = 0.0;
-                    self.block_break_info.lock().hardness = 1.0; // TODO: Get actual hardness values depending on blocktype and version and tool in hands
-                    self.block_break_info.lock().active = true;
+                    // Blocks that break at once only need the start sent,
+                    // creative players wait a little before the next one.
+                    let mut info = self.block_break_info.lock();
+                    let miner = self.miner();
+                    let breaks = if matches!(gamemode, GameMode::Creative) {
+                        info.delay = 5;
+                        // Swords can't break blocks in creative
+                        !matches!(miner.tool, Some((block::Tool::Sword, _)))
+                    } else if breaking::progress_per_tick(world.get_block(pos), &miner) < 1.0 {
+                        info.break_face = face;
+                        info.break_position = pos;
+                        info.progress = 0.0;
+                        info.active = true;
+                        false
+                    } else {
+                        true
+                    };
+                    if breaks && !matches!(gamemode, GameMode::Adventure) {
+                        self.predictions.write().predict(&world, pos, block::Air {});
+                    }
                 }
             }
         }
@@ -1314,6 +1570,7 @@ impl Server {
             self.block_break_info.lock().delay = 5;
             let pos = self.block_break_info.lock().break_position;
             let face = self.block_break_info.lock().break_face;
+            self.show_crack(pos, None);
             packet::send_digging(
                 self.conn.clone().write().as_mut().unwrap(),
                 self.mapped_protocol_version,
@@ -1336,7 +1593,12 @@ impl Server {
                 .unwrap();
             if !matches!(gamemode, GameMode::Spectator) {
                 // TODO: Check this
-                if let Some((pos, _, face, at)) = target::trace_ray(
+                if let Some((id, at)) = self.targeted_entity(&renderer.read()) {
+                    // Like vanilla, try the exact spot first and then the
+                    // entity as a whole
+                    self.use_entity(id, packet::UseEntityType::InteractAt(at));
+                    self.use_entity(id, packet::UseEntityType::Interact);
+                } else if let Some((pos, _, face, at)) = target::trace_ray(
                     &world,
                     4.0,
                     renderer.read().camera.pos.to_vec(),
@@ -1372,11 +1634,155 @@ impl Server {
                         Hand::MainHand,
                     )
                     .unwrap();
+                    self.hand.write().swing();
+                    if !matches!(gamemode, GameMode::Adventure) {
+                        self.predict_placement(pos, face, at);
+                    }
                 }
             }
         }
     }
 
+    /// What the local player holds for drawing their hands in first
+    /// person, nothing when the hands aren't shown.
+    fn held_items(&self) -> Option<hand::Held> {
+        let player = (*self.player.clone().read())?;
+        if *self.dead.read() {
+            return None;
+        }
+        let gamemode = *self
+            .entities
+            .clone()
+            .read()
+            .get_component(player, self.gamemode)?;
+        let hud_context = self.hud_context.clone();
+        let hud_context = hud_context.read();
+        if !hud_context.enabled || matches!(gamemode, GameMode::Spectator) {
+            return None;
+        }
+        let inventory = hud_context.player_inventory.clone()?;
+        let inventory = inventory.read();
+        let slot = hud_context.get_slot_index();
+        Some(hand::Held {
+            main_hand: inventory.get_item(36 + slot as i16).clone(),
+            off_hand: PlayerInventory::window_slot(self.mapped_protocol_version, 40)
+                .and_then(|slot| inventory.get_item(slot).clone()),
+            slot,
+            skin_url: self
+                .players
+                .clone()
+                .read()
+                .get(&self.uuid)
+                .and_then(|info| info.skin_url.clone()),
+        })
+    }
+
+    /// The entity under the crosshair within reach, with where on it the
+    /// crosshair is, unless a block is in front of it.
+    fn targeted_entity(&self, renderer: &render::Renderer) -> Option<(i32, cgmath::Vector3<f64>)> {
+        let player = (*self.player.clone().read())?;
+        let entities = self.entities.clone();
+        let entities = entities.read();
+        let reach = match entities.get_component(player, self.gamemode)? {
+            GameMode::Creative => 6.0,
+            _ => 3.0,
+        };
+        let start = renderer.camera.pos.to_vec();
+        let dir: cgmath::Vector3<f64> = renderer.view_vector.cast().unwrap();
+        let entity_map = self.entity_map.clone();
+        let entity_map = entity_map.read();
+        let (id, distance, at) = target::trace_entity(
+            &entities,
+            entity_map
+                .iter()
+                .filter(|(_, entity)| **entity != player)
+                .map(|(id, entity)| (*id, *entity)),
+            reach,
+            start,
+            dir,
+        )?;
+        if let Some((pos, _, _, hit)) =
+            target::trace_ray(&self.world, distance, start, dir, target::test_block)
+        {
+            let hit = cgmath::Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64) + hit;
+            if (hit - start).magnitude() < distance {
+                return None;
+            }
+        }
+        Some((id, at))
+    }
+
+    fn use_entity(&self, id: i32, ty: packet::UseEntityType) {
+        let sneaking = self.player.clone().read().is_some_and(|player| {
+            self.entities
+                .clone()
+                .read()
+                .get_component(player, self.player_movement)
+                .is_some_and(|movement| movement.is_key_pressed(Actionkey::Sneak))
+        });
+        packet::send_use_entity(
+            self.conn.clone().write().as_mut().unwrap(),
+            self.mapped_protocol_version,
+            id,
+            ty,
+            Hand::MainHand,
+            sneaking,
+        )
+        .unwrap();
+    }
+
+    /// Places the held block before the server does, when it's clear
+    /// what the server is going to place.
+    fn predict_placement(&self, pos: Position, face: BlockDirection, at: cgmath::Vector3<f64>) {
+        let player = match *self.player.clone().read() {
+            Some(player) => player,
+            None => return,
+        };
+        let entities = self.entities.clone();
+        let entities = entities.read();
+        let sneaking = entities
+            .get_component(player, self.player_movement)
+            .is_some_and(|movement| movement.is_key_pressed(Actionkey::Sneak));
+        if !sneaking && prediction::is_interactable(self.world.get_block(pos)) {
+            return;
+        }
+        let (position, yaw) = match (
+            entities.get_component(player, self.target_position),
+            entities.get_component(player, self.rotation),
+        ) {
+            (Some(position), Some(rotation)) => (position.position, rotation.yaw),
+            _ => return,
+        };
+        let inventory_context = self.inventory_context.clone();
+        let inventory_context = inventory_context.read();
+        let inventory = inventory_context.player_inventory.read();
+        let held = match inventory.get_item(36 + inventory_context.hotbar_index as i16) {
+            Some(held) => held,
+            None => return,
+        };
+
+        let blocks = prediction::placement(
+            &*self.world,
+            held,
+            pos,
+            face,
+            at,
+            yaw,
+            self.mapped_protocol_version >= Version::V1_13,
+        );
+        let bounds = entity::physics::Body::new(position).bounds();
+        if blocks
+            .iter()
+            .any(|(pos, block)| entity::physics::block_intersects(*block, *pos, &bounds))
+        {
+            return;
+        }
+        let mut predictions = self.predictions.write();
+        for (pos, block) in blocks {
+            predictions.predict(&self.world, pos, block);
+        }
+    }
+
     pub fn write_packet<T: protocol::PacketType>(&self, p: T) {
         let conn = self.conn.clone();
         let mut conn = conn.write();
@@ -1526,6 +1932,32 @@ impl Server {
             .write_plugin_message(channel, data); // TODO handle errors
     }
 
+    fn on_dimension_codec(&self, codec: crate::nbt::NamedTag) {
+        self.world
+            .set_biome_registry(world::biome::Registry::from_codec(&codec.1));
+        *self.dimension_types.write() = world::dimension::Registry::from_codec(&codec.1);
+    }
+
+    /// Switches to the dimension described by whichever of the 1.16.2+
+    /// dimension type, the 1.16 dimension name or the pre-1.16 dimension
+    /// id the server sent.
+    fn on_dimension_change(
+        &self,
+        tag: Option<&crate::nbt::NamedTag>,
+        name: Option<&str>,
+        id: Option<i32>,
+    ) {
+        use crate::world::dimension::DimensionType;
+        let dimension = if let Some(tag) = tag {
+            DimensionType::from_tag(&tag.1)
+        } else if let Some(name) = name {
+            self.dimension_types.read().by_name(name)
+        } else {
+            DimensionType::by_id(id.unwrap_or(0))
+        };
+        self.world.set_dimension(dimension);
+    }
+
     fn on_game_join(&self, gamemode: u8, entity_id: i32) {
         let gamemode = GameMode::from_int((gamemode & 0x7) as i32);
         let player = entity::player::create_local(&mut self.entities.clone().write());
@@ -1554,6 +1986,7 @@ impl Server {
 
         self.entity_map.clone().write().insert(entity_id, player);
         self.player.clone().write().replace(player);
+        *self.player_id.write() = Some(entity_id);
 
         // Let the server know who we are
         let brand = plugin_messages::Brand {
@@ -1576,6 +2009,13 @@ impl Server {
 
     fn on_respawn(&self, respawn: mapped_packet::play::clientbound::Respawn) {
         let gamemode = GameMode::from_int((respawn.gamemode & 0x7) as i32);
+        self.on_dimension_change(
+            respawn.dimension_tag.as_ref(),
+            respawn.dimension_name.as_deref(),
+            respawn.dimension,
+        );
+        self.player_effects.write().clear();
+        self.predictions.write().clear();
 
         if let Some(player) = *self.player.clone().write() {
             self.hud_context.clone().write().update_game_mode(gamemode);
@@ -1620,6 +2060,282 @@ impl Server {
         self.disconnect(Some(disconnect.reason));
     }
 
+    fn on_window_open(&self, open: mapped_packet::play::clientbound::WindowOpen) {
+        let id = open.id as i8;
+        let title = open.title.to_string();
+        // Titles that are translation keys come through as the bare key
+        let name = match title.as_str() {
+            "container.chest" => "Chest",
+            "container.chestDouble" => "Large Chest",
+            "container.enderchest" => "Ender Chest",
+            "container.shulkerBox" => "Shulker Box",
+            "container.minecart" => "Minecart",
+            "container.crafting" => "Crafting",
+            "container.furnace" => "Furnace",
+            "container.blast_furnace" => "Blast Furnace",
+            "container.smoker" => "Smoker",
+            "container.dispenser" => "Dispenser",
+            "container.dropper" => "Dropper",
+            "container.hopper" => "Item Hopper",
+            "container.brewing" => "Brewing Stand",
+            title => title,
+        }
+        .to_string();
+        let player_inventory = self.inventory_context.read().player_inventory.clone();
+        let hud_context = self.hud_context.clone();
+        let version = self.mapped_protocol_version;
+        let inventory: Arc<RwLock<dyn Inventory + Send + Sync>> = match InventoryType::from_window(
+            version,
+            open.ty,
+            open.ty_name.as_deref(),
+            open.slot_count,
+        ) {
+            Some((InventoryType::Chest, slots)) => Arc::new(RwLock::new(SimpleContainer::new(
+                id,
+                name,
+                InventoryType::Chest,
+                Layout::chest(slots / 9),
+                player_inventory,
+                hud_context,
+            ))),
+            Some((InventoryType::CraftingTable, _)) => Arc::new(RwLock::new(
+                CraftingTableInventory::new(version, id, name, player_inventory, hud_context),
+            )),
+            Some((InventoryType::Furnace, _)) => Arc::new(RwLock::new(FurnaceInventory::new(
+                version,
+                id,
+                name,
+                player_inventory,
+                hud_context,
+            ))),
+            Some((InventoryType::Dropper, _)) => Arc::new(RwLock::new(SimpleContainer::new(
+                id,
+                name,
+                InventoryType::Dropper,
+                Layout::dropper(),
+                player_inventory,
+                hud_context,
+            ))),
+            Some((InventoryType::Hopper, _)) => Arc::new(RwLock::new(SimpleContainer::new(
+                id,
+                name,
+                InventoryType::Hopper,
+                Layout::hopper(),
+                player_inventory,
+                hud_context,
+            ))),
+            Some((InventoryType::Brewer, _)) => Arc::new(RwLock::new(BrewerInventory::new(
+                version,
+                id,
+                name,
+                player_inventory,
+                hud_context,
+            ))),
+            _ => {
+                warn!(
+                    "Closing window {} of unsupported type {:?}/{:?}",
+                    open.id, open.ty, open.ty_name
+                );
+                self.write_packet(packet::play::serverbound::CloseWindow { id: open.id as u8 });
+                return;
+            }
+        };
+        // The window's items follow right away, so they have to find it
+        // before the screen gets around to opening
+        {
+            let mut inventory_context = self.inventory_context.write();
+            inventory_context.inventory = Some(inventory.clone());
+            inventory_context.clear_transactions();
+        }
+        self.screen_sys.close_closable_screens();
+        self.screen_sys
+            .add_screen(Box::new(render::inventory::InventoryWindow::new(
+                inventory,
+                self.inventory_context.clone(),
+            )));
+    }
+
+    fn on_window_close(&self, close: mapped_packet::play::clientbound::WindowClose) {
+        let inventory = match self.window_inventory(close.id as i8) {
+            Some(inventory) => inventory,
+            None => return,
+        };
+        {
+            let mut inventory_context = self.inventory_context.write();
+            // It may have been replaced while we weren't holding the lock
+            if !inventory_context
+                .inventory
+                .as_ref()
+                .is_some_and(|open| Arc::ptr_eq(open, &inventory))
+            {
+                return;
+            }
+            inventory_context.inventory = None;
+            inventory_context.clear_transactions();
+        }
+        self.screen_sys.close_closable_screens();
+    }
+
+    /// The inventory a window id refers to: 0 is always the player's own,
+    /// anything else has to be the container that's open.
+    fn window_inventory(&self, id: i8) -> Option<Arc<RwLock<dyn Inventory + Send + Sync>>> {
+        // The inventory is locked after the context everywhere else, so the
+        // context has to be let go of before looking inside the inventory
+        let (player_inventory, open) = {
+            let inventory_context = self.inventory_context.read();
+            (
+                inventory_context.player_inventory.clone(),
+                inventory_context.inventory.clone(),
+            )
+        };
+        match open {
+            // The creative inventory stands in for the player's while open
+            Some(open) if open.read().id() == id => Some(open),
+            _ if id == 0 => Some(player_inventory),
+            _ => None,
+        }
+    }
+
+    /// Tells the server about a click in a window.
+    pub fn click_window(&self, click: WindowClick) {
+        if self.mapped_protocol_version < Version::V1_9 {
+            self.write_packet(packet::play::serverbound::ClickWindow_u8 {
+                id: click.id,
+                slot: click.slot,
+                button: click.button,
+                action_number: click.action_number as u16,
+                mode: click.mode,
+                clicked_item: click.clicked_item,
+            });
+        } else {
+            self.write_packet(packet::play::serverbound::ClickWindow {
+                id: click.id,
+                slot: click.slot,
+                button: click.button,
+                action_number: click.action_number as u16,
+                mode: protocol::VarInt(click.mode as i32),
+                clicked_item: click.clicked_item,
+            });
+        }
+    }
+
+    /// Sets a slot of the player's window in creative mode, slot -1 drops
+    /// the item instead.
+    pub fn creative_inventory_action(&self, slot: i16, item: Option<Stack>) {
+        self.write_packet(packet::play::serverbound::CreativeInventoryAction {
+            slot,
+            clicked_item: item,
+        });
+    }
+
+    fn on_confirm_transaction(
+        &self,
+        transaction: mapped_packet::play::clientbound::ConfirmTransaction,
+    ) {
+        let id = transaction.id as i8;
+        let items = self.inventory_context.write().confirm_transaction(
+            id,
+            transaction.action_number,
+            transaction.accepted,
+        );
+        if let (Some(items), Some(inventory)) = (items, self.window_inventory(id)) {
+            let mut inventory = inventory.write();
+            for (slot, item) in items.into_iter().enumerate() {
+                if (slot as i16) < inventory.size() {
+                    inventory.set_item(slot as i16, item);
+                }
+            }
+        }
+        // The server ignores the window until we apologise for the click
+        if !transaction.accepted {
+            self.write_packet(packet::play::serverbound::ConfirmTransactionServerbound {
+                id: transaction.id,
+                action_number: transaction.action_number,
+                accepted: true,
+            });
+        }
+    }
+
+    fn to_item(&self, stack: Option<Stack>) -> Option<Item> {
+        stack.map(|stack| {
+            let id = stack.id;
+            Item {
+                stack,
+                material: to_material(id as u16, self.mapped_protocol_version),
+            }
+        })
+    }
+
+    fn on_window_items(&self, window_items: mapped_packet::play::clientbound::WindowItems) {
+        let inventory = match self.window_inventory(window_items.id as i8) {
+            Some(inventory) => inventory,
+            None => return,
+        };
+        let mut inventory = inventory.write();
+        let size = inventory.size() as usize;
+        if window_items.items.len() > size {
+            warn!(
+                "The server sent {} items for window {} which only has {} slots",
+                window_items.items.len(),
+                window_items.id,
+                size
+            );
+        }
+        for (slot, stack) in window_items.items.into_iter().take(size).enumerate() {
+            inventory.set_item(slot as i16, self.to_item(stack));
+        }
+    }
+
+    fn on_window_set_slot(&self, set_slot: mapped_packet::play::clientbound::WindowSetSlot) {
+        let item = self.to_item(set_slot.item);
+        if set_slot.id == -1 && set_slot.slot == -1 {
+            let mut inventory_context = self.inventory_context.write();
+            inventory_context.cursor = item;
+            inventory_context.cursor_dirty = true;
+            return;
+        }
+        if set_slot.id == -2 && self.mapped_protocol_version >= Version::V1_9 {
+            self.set_player_slot(set_slot.slot, item);
+            return;
+        }
+        let inventory = match self.window_inventory(set_slot.id) {
+            Some(inventory) => inventory,
+            None => return,
+        };
+        let mut inventory = inventory.write();
+        let size = inventory.size();
+        if set_slot.slot < 0 || set_slot.slot >= size {
+            warn!("The server tried to set an item to slot {} but the current inventory only has {} slots. Did it try to crash you?", set_slot.slot, size);
+            return;
+        }
+        inventory.set_item(set_slot.slot, item);
+    }
+
+    /// Sets a slot of the player's inventory, numbered the way the server's
+    /// inventory numbers it, whichever window is open.
+    fn set_player_slot(&self, index: i16, item: Option<Item>) {
+        let slot = match PlayerInventory::window_slot(self.mapped_protocol_version, index) {
+            Some(slot) => slot,
+            None => {
+                warn!("The server tried to set player inventory slot {}", index);
+                return;
+            }
+        };
+        let open = self.inventory_context.read().inventory.clone();
+        // Containers show the main inventory and hotbar after their own
+        // slots, they get updated there as well
+        if let Some(open) = open.filter(|_| (9..45).contains(&slot)) {
+            let mut open = open.write();
+            if open.id() != 0 {
+                let container_slot = open.size() - 36 + slot - 9;
+                open.set_item(container_slot, item.clone());
+            }
+        }
+        if let Some(inventory) = self.window_inventory(0) {
+            inventory.write().set_item(slot, item);
+        }
+    }
+
     fn on_time_update(&self, time_update: mapped_packet::play::clientbound::TimeUpdate) {
         self.world_data.clone().write().world_age = time_update.time_of_day;
         self.world_data.clone().write().world_time_target =
@@ -1633,6 +2349,14 @@ impl Server {
     }
 
     fn on_game_state_change(&self, game_state: mapped_packet::play::clientbound::ChangeGameState) {
+        // Vanilla's names for 1 and 2 are swapped, 1 starts the rain
+        match game_state.reason {
+            1 => self.weather.write().set_raining(true),
+            2 => self.weather.write().set_raining(false),
+            7 => self.weather.write().set_rain_level(game_state.value),
+            8 => self.weather.write().set_thunder_level(game_state.value),
+            _ => {}
+        }
         if game_state.reason == 3 {
             if let Some(player) = *self.player.write() {
                 let gamemode = GameMode::from_int(game_state.value as i32);
@@ -1655,22 +2379,218 @@ impl Server {
 
     fn on_entity_spawn(
         &self,
-        ty: i16,
+        entity_type: EntityType,
         entity_id: i32,
         x: f64,
         y: f64,
         z: f64,
         yaw: f64,
         pitch: f64,
-    ) {
-        let entity_type = entity::versions::to_entity_type(ty, self.mapped_protocol_version);
-        if entity_type != EntityType::Unknown {
+    ) -> Option<ecs::Entity> {
+        if entity_type == EntityType::Lightning {
+            // Lightning is spawned like any other entity since 1.16
+            self.weather.write().strike_lightning(x, y, z);
+            None
+        } else if entity_type != EntityType::Unknown {
             let entity =
                 entity_type.create_entity(&mut self.entities.clone().write(), x, y, z, yaw, pitch);
             if let Some(entity) = entity {
                 self.entity_map.clone().write().insert(entity_id, entity);
-                println!("spawned {} {:?}", ty, entity_type);
             }
+            entity
+        } else {
+            None
+        }
+    }
+
+    fn on_object_spawn(&self, spawn: mapped_packet::play::clientbound::SpawnObject) {
+        use std::f64::consts::PI;
+        let entity_type =
+            entity::versions::object_to_entity_type(spawn.ty as i16, self.mapped_protocol_version);
+        let entity = self.on_entity_spawn(
+            entity_type,
+            spawn.entity_id,
+            spawn.x,
+            spawn.y,
+            spawn.z,
+            -(spawn.yaw as f64 / 256.0) * PI * 2.0,
+            -(spawn.pitch as f64 / 256.0) * PI * 2.0,
+        );
+        let entity = match entity {
+            Some(entity) => entity,
+            None => return,
+        };
+        if entity_type == EntityType::FallingBlock {
+            // The data holds the block, in the format block changes use
+            // since 1.13
+            let id = if self.mapped_protocol_version >= Version::V1_13 {
+                spawn.data as usize
+            } else if self.mapped_protocol_version == Version::V1_7 {
+                (((spawn.data & 0xfff) << 4) | ((spawn.data >> 16) & 0xf)) as usize
+            } else {
+                (((spawn.data & 0xfff) << 4) | ((spawn.data >> 12) & 0xf)) as usize
+            };
+            let world = self.world.clone();
+            let block = world
+                .id_map
+                .by_vanilla_id(id, world.modded_block_ids.clone());
+            if let Some(block_model) =
+                self.entities
+                    .clone()
+                    .write()
+                    .get_component_mut_direct::<entity::falling_block::BlockModel>(entity)
+            {
+                block_model.block = block;
+            }
+        } else if entity_type == EntityType::ItemFrame {
+            // The data holds the facing of the frame, which could only be
+            // horizontal before 1.13, and the position is its block
+            let facing = if self.mapped_protocol_version >= Version::V1_13 {
+                BlockDirection::from_index(spawn.data)
+            } else {
+                BlockDirection::from_horizontal_index(spawn.data)
+            };
+            if facing == BlockDirection::Invalid {
+                return;
+            }
+            let position = entity::hanging::hanging_position(
+                spawn.x.floor() as i32,
+                spawn.y.floor() as i32,
+                spawn.z.floor() as i32,
+                facing,
+                12,
+                12,
+            );
+            let entities = self.entities.clone();
+            let mut entities = entities.write();
+            Self::place_entity(&mut entities, entity, position);
+            if let Some(frame) =
+                entities.get_component_mut_direct::<entity::hanging::ItemFrameModel>(entity)
+            {
+                frame.facing = facing;
+            }
+        }
+    }
+
+    fn on_painting_spawn(&self, spawn: mapped_packet::play::clientbound::SpawnPainting) {
+        let motive = match (spawn.motive, spawn.title.as_ref()) {
+            (Some(id), _) => entity::hanging::Motive::by_id(id),
+            (None, Some(title)) => entity::hanging::Motive::by_name(title),
+            (None, None) => None,
+        };
+        let facing = BlockDirection::from_horizontal_index(spawn.direction);
+        let motive = match (motive, facing) {
+            (Some(motive), facing) if facing != BlockDirection::Invalid => motive,
+            _ => return,
+        };
+        let position = entity::hanging::hanging_position(
+            spawn.location.x,
+            spawn.location.y,
+            spawn.location.z,
+            facing,
+            motive.width,
+            motive.height,
+        );
+        let entities = self.entities.clone();
+        let mut entities = entities.write();
+        let entity = EntityType::Painting.create_entity(
+            &mut entities,
+            position.x,
+            position.y,
+            position.z,
+            0.0,
+            0.0,
+        );
+        if let Some(entity) = entity {
+            if let Some(painting) =
+                entities.get_component_mut_direct::<entity::hanging::PaintingModel>(entity)
+            {
+                painting.motive = Some(motive);
+                painting.facing = facing;
+            }
+            self.entity_map
+                .clone()
+                .write()
+                .insert(spawn.entity_id, entity);
+        }
+    }
+
+    /// Moves an entity that was just spawned without interpolating.
+    fn place_entity(
+        entities: &mut ecs::Manager,
+        entity: ecs::Entity,
+        position: cgmath::Vector3<f64>,
+    ) {
+        if let Some(pos) = entities.get_component_mut_direct::<entity::Position>(entity) {
+            pos.position = position;
+            pos.last_position = position;
+        }
+        if let Some(target) = entities.get_component_mut_direct::<entity::TargetPosition>(entity) {
+            target.position = position;
+        }
+        if let Some(server) = entities.get_component_mut_direct::<entity::ServerPosition>(entity) {
+            server.position = position;
+        }
+    }
+
+    fn on_maps(&self, maps: mapped_packet::play::clientbound::Maps) {
+        if let (Some(columns), Some(rows), Some(x), Some(z), Some(data)) =
+            (maps.columns, maps.rows, maps.x, maps.z, maps.data)
+        {
+            self.maps.write().update(
+                maps.item_damage,
+                x as usize,
+                z as usize,
+                columns as usize,
+                rows as usize,
+                &data,
+            );
+        }
+    }
+
+    fn on_experience_orb_spawn(&self, spawn: mapped_packet::play::clientbound::SpawnExperienceOrb) {
+        let entities = self.entities.clone();
+        let mut entities = entities.write();
+        let entity = EntityType::ExperienceOrb.create_entity(
+            &mut entities,
+            spawn.x,
+            spawn.y,
+            spawn.z,
+            0.0,
+            0.0,
+        );
+        if let Some(entity) = entity {
+            if let Some(orb) = entities
+                .get_component_mut_direct::<entity::experience_orb::ExperienceOrbModel>(entity)
+            {
+                orb.value = spawn.count as i32;
+            }
+            self.entity_map
+                .clone()
+                .write()
+                .insert(spawn.entity_id, entity);
+        }
+    }
+
+    /// Starts the animation of an entity flying towards the entity that
+    /// picked it up. The entity stays around until the animation is done,
+    /// even though the server destroys it right away.
+    fn on_collect_item(&self, collected_id: i32, collector_id: i32) {
+        let collected = match self.entity_map.clone().write().remove(&collected_id) {
+            Some(entity) => entity,
+            None => return,
+        };
+        let collector = self.entity_map.clone().read().get(&collector_id).cloned();
+        let entities = self.entities.clone();
+        let mut entities = entities.write();
+        let start = entities
+            .get_component_direct::<entity::Position>(collected)
+            .map(|pos| pos.position);
+        match (collector, start) {
+            (Some(collector), Some(start)) => {
+                entities.add_component_direct(collected, entity::Collected::new(collector, start));
+            }
+            _ => entities.remove_entity(collected),
         }
     }
 
@@ -1682,6 +2602,39 @@ impl Server {
         }
     }
 
+    fn on_entity_metadata(&self, entity_id: i32, metadata: &Metadata) {
+        if let Some(entity) = self.entity_map.clone().read().get(&entity_id) {
+            let entities = self.entities.clone();
+            let mut entities = entities.write();
+            let entity_type = match entities.get_component_direct::<EntityType>(*entity) {
+                Some(entity_type) => *entity_type,
+                None => return,
+            };
+            if let Some(state) = entities.get_component_mut_direct::<EntityState>(*entity) {
+                state.apply(metadata, entity_type, self.mapped_protocol_version);
+            }
+        }
+    }
+
+    /// Puts the item into the entity's hand, armor isn't shown yet.
+    fn on_entity_equipment(&self, entity_id: i32, slot: i32, stack: Option<Stack>) {
+        let item = self.to_item(stack);
+        if let Some(entity) = self.entity_map.clone().read().get(&entity_id) {
+            let entities = self.entities.clone();
+            let mut entities = entities.write();
+            if let Some(state) = entities.get_component_mut_direct::<EntityState>(*entity) {
+                // The off hand came in 1.9, before that the slots after the
+                // held item are the armor
+                match slot {
+                    0 => state.main_hand = item,
+                    1 if self.mapped_protocol_version >= Version::V1_9 => state.off_hand = item,
+                    _ => return,
+                }
+                state.dirty = true;
+            }
+        }
+    }
+
     fn on_entity_teleport(
         &self,
         entity_id: i32,
@@ -1694,21 +2647,13 @@ impl Server {
     ) {
         use std::f64::consts::PI;
         if let Some(entity) = self.entity_map.clone().read().get(&entity_id) {
-            let target_position = self
-                .entities
-                .clone()
-                .write()
-                .get_component_mut(*entity, self.target_position)
-                .unwrap();
+            self.move_entity(*entity, |_| cgmath::Vector3::new(x, y, z));
             let target_rotation = self
                 .entities
                 .clone()
                 .write()
                 .get_component_mut(*entity, self.target_rotation)
                 .unwrap();
-            target_position.position.x = x;
-            target_position.position.y = y;
-            target_position.position.z = z;
             target_rotation.yaw = -(yaw / 256.0) * PI * 2.0;
             target_rotation.pitch = -(pitch / 256.0) * PI * 2.0;
         }
@@ -1716,15 +2661,54 @@ impl Server {
 
     fn on_entity_move(&self, entity_move: mapped_packet::play::clientbound::EntityMove) {
         if let Some(entity) = self.entity_map.clone().read().get(&entity_move.entity_id) {
-            let position = self
+            let delta = cgmath::Vector3::new(
+                entity_move.delta_x,
+                entity_move.delta_y,
+                entity_move.delta_z,
+            );
+            self.move_entity(*entity, |position| position + delta);
+        }
+    }
+
+    /// Moves an entity to the position the server sent, computed from the
+    /// last one it sent. Whatever was predicted since is dropped.
+    fn move_entity(
+        &self,
+        entity: ecs::Entity,
+        update: impl FnOnce(cgmath::Vector3<f64>) -> cgmath::Vector3<f64>,
+    ) {
+        let entities = self.entities.clone();
+        let mut entities = entities.write();
+        let position = match entities.get_component_mut_direct::<entity::ServerPosition>(entity) {
+            Some(server_position) => {
+                server_position.position = update(server_position.position);
+                server_position.position
+            }
+            None => match entities.get_component(entity, self.target_position) {
+                Some(target_position) => update(target_position.position),
+                None => return,
+            },
+        };
+        if let Some(target_position) = entities.get_component_mut(entity, self.target_position) {
+            target_position.position = position;
+        }
+    }
+
+    fn on_entity_velocity(&self, velocity: mapped_packet::play::clientbound::EntityVelocity) {
+        if let Some(entity) = self.entity_map.clone().read().get(&velocity.entity_id) {
+            if let Some(entity_velocity) = self
                 .entities
                 .clone()
                 .write()
-                .get_component_mut(*entity, self.target_position)
-                .unwrap();
-            position.position.x += entity_move.delta_x;
-            position.position.y += entity_move.delta_y;
-            position.position.z += entity_move.delta_z;
+                .get_component_mut(*entity, self.velocity)
+            {
+                // Sent in 1/8000 of a block per tick
+                entity_velocity.velocity = cgmath::Vector3::new(
+                    velocity.velocity_x as f64 / 8000.0,
+                    velocity.velocity_y as f64 / 8000.0,
+                    velocity.velocity_z as f64 / 8000.0,
+                );
+            }
         }
     }
 
@@ -1753,21 +2737,14 @@ impl Server {
     ) {
         use std::f64::consts::PI;
         if let Some(entity) = self.entity_map.clone().read().get(&entity_id) {
-            let position = self
-                .entities
-                .clone()
-                .write()
-                .get_component_mut(*entity, self.target_position)
-                .unwrap();
+            let delta = cgmath::Vector3::new(delta_x, delta_y, delta_z);
+            self.move_entity(*entity, |position| position + delta);
             let rotation = self
                 .entities
                 .clone()
                 .write()
                 .get_component_mut(*entity, self.target_rotation)
                 .unwrap();
-            position.position.x += delta_x;
-            position.position.y += delta_y;
-            position.position.z += delta_z;
             rotation.yaw = -(yaw / 256.0) * PI * 2.0;
             rotation.pitch = -(pitch / 256.0) * PI * 2.0;
         }
@@ -1825,6 +2802,14 @@ impl Server {
         target_position.position.x = x;
         target_position.position.y = y;
         target_position.position.z = z;
+        if let Some(server_position) = self
+            .entities
+            .clone()
+            .write()
+            .get_component_mut_direct::<entity::ServerPosition>(entity)
+        {
+            server_position.position = target_position.position;
+        }
         rotation.yaw = -(yaw / 256.0) * PI * 2.0;
         rotation.pitch = -(pitch / 256.0) * PI * 2.0;
         target_rotation.yaw = rotation.yaw;
@@ -2168,6 +3153,11 @@ impl Server {
         &self,
         chunk_data: mapped_packet::play::clientbound::ChunkData_Biomes3D_i32,
     ) {
+        let biomes = if chunk_data.new {
+            Some(&chunk_data.biomes[..])
+        } else {
+            None
+        };
         self.world
             .clone()
             .load_chunk115(
@@ -2175,6 +3165,7 @@ impl Server {
                 chunk_data.chunk_z,
                 chunk_data.new,
                 chunk_data.bitmask as u16,
+                biomes,
                 chunk_data.data,
             )
             .unwrap();
@@ -2185,6 +3176,11 @@ impl Server {
         &self,
         chunk_data: mapped_packet::play::clientbound::ChunkData_Biomes3D_bool,
     ) {
+        let biomes = if chunk_data.new {
+            Some(&chunk_data.biomes.data[..])
+        } else {
+            None
+        };
         self.world
             .clone()
             .load_chunk115(
@@ -2192,6 +3188,7 @@ impl Server {
                 chunk_data.chunk_z,
                 chunk_data.new,
                 chunk_data.bitmask as u16,
+                biomes,
                 chunk_data.data,
             )
             .unwrap();
@@ -2202,6 +3199,11 @@ impl Server {
         &self,
         chunk_data: mapped_packet::play::clientbound::ChunkData_Biomes3D,
     ) {
+        let biomes = if chunk_data.new {
+            Some(&chunk_data.biomes.data[..])
+        } else {
+            None
+        };
         self.world
             .clone()
             .load_chunk115(
@@ -2209,6 +3211,7 @@ impl Server {
                 chunk_data.chunk_z,
                 chunk_data.new,
                 chunk_data.bitmask as u16,
+                biomes,
                 chunk_data.data,
             )
             .unwrap();
@@ -2326,6 +3329,7 @@ impl Server {
     }
 
     fn on_block_change_in_world(&self, location: Position, id: i32) {
+        self.predictions.write().confirm(location);
         let world = self.world.clone();
         let modded_block_ids = world.modded_block_ids.clone();
         let block = world.id_map.by_vanilla_id(id as usize, modded_block_ids);
@@ -2391,6 +3395,14 @@ enum TeleportFlag {
     RelPitch = 0b10000,
 }
 
+fn color_to_rgb(col: u32) -> (f32, f32, f32) {
+    (
+        ((col >> 16) & 0xFF) as f32 / 255.0,
+        ((col >> 8) & 0xFF) as f32 / 255.0,
+        (col & 0xFF) as f32 / 255.0,
+    )
+}
+
 fn calculate_relative_teleport(flag: TeleportFlag, flags: u8, base: f64, val: f64) -> f64 {
     if (flags & (flag as u8)) == 0 {
         val
@@ -0,0 +1,868 @@
+//! Guesses at what the server does when the local player places or breaks
+//! a block, so the world changes at once instead of a round trip later.
+
+use crate::inventory::{Item, Material};
+use crate::shared::{Axis, Direction, Position};
+use crate::world;
+use crate::world::block::{
+    Block, BlockHalf, DoorHalf, Side, StairShape, StoneSlabVariant, StoneVariant, TreeVariant,
+    WoodSlabVariant, WorldAccess,
+};
+use cgmath::Vector3;
+
+/// How many ticks a guess is kept without the server telling us what is
+/// really there before it is undone.
+const TIMEOUT_TICKS: u32 = 40;
+
+struct Pending {
+    pos: Position,
+    /// The block before the guess, put back if the server never confirms it
+    previous: Block,
+    ticks: u32,
+}
+
+/// The blocks set ahead of the server that it hasn't answered for yet.
+#[derive(Default)]
+pub struct Predictions {
+    pending: Vec<Pending>,
+}
+
+impl Predictions {
+    /// Sets the block the server is expected to set, keeping the old one
+    /// in case it doesn't.
+    pub fn predict(&mut self, world: &world::World, pos: Position, block: Block) {
+        // Guessing twice at a position still goes back to the block from
+        // before the first guess
+        match self.pending.iter_mut().find(|pending| pending.pos == pos) {
+            Some(pending) => pending.ticks = 0,
+            None => self.pending.push(Pending {
+                pos,
+                previous: world.get_block(pos),
+                ticks: 0,
+            }),
+        }
+        world.set_block(pos, block);
+    }
+
+    /// The server has said what is at the position, which replaces any
+    /// guess made there.
+    pub fn confirm(&mut self, pos: Position) {
+        self.pending.retain(|pending| pending.pos != pos);
+    }
+
+    /// Undoes the guesses the server has ignored for too long.
+    pub fn tick(&mut self, world: &world::World) {
+        self.pending.retain_mut(|pending| {
+            pending.ticks += 1;
+            if pending.ticks < TIMEOUT_TICKS {
+                return true;
+            }
+            world.set_block(pending.pos, pending.previous);
+            false
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// How a held item picks the state of the block it places.
+enum Kind {
+    Simple(Block),
+    Log(Box<dyn Fn(Axis) -> Block>),
+    Slab(Box<dyn Fn(BlockHalf) -> Block>),
+    Stairs(fn(Direction, BlockHalf) -> Block),
+    Door(fn(Direction, DoorHalf, Side) -> Block),
+    Torch,
+    Ladder,
+}
+
+macro_rules! stairs {
+    ($block:ident) => {
+        Kind::Stairs(|facing, half| Block::$block {
+            facing,
+            half,
+            shape: StairShape::Straight,
+            waterlogged: false,
+        })
+    };
+}
+
+macro_rules! door {
+    ($block:ident) => {
+        Kind::Door(|facing, half, hinge| Block::$block {
+            facing,
+            half,
+            hinge,
+            open: false,
+            powered: false,
+        })
+    };
+}
+
+/// The blocks placing the item against a face of the clicked block would
+/// set, or nothing if it isn't clear what the server will do.
+///
+/// `at` is where the block was hit relative to its corner, `yaw` is the
+/// player's and `flat` is whether the server is on 1.13 or later, which
+/// changed how slabs are stored.
+pub fn placement<W: WorldAccess>(
+    world: &W,
+    item: &Item,
+    clicked: Position,
+    face: Direction,
+    at: Vector3<f64>,
+    yaw: f64,
+    flat: bool,
+) -> Vec<(Position, Block)> {
+    let kind = match kind(item, flat) {
+        Some(kind) => kind,
+        None => return vec![],
+    };
+    let clicked_block = world.get_block(clicked);
+    // Slabs placed on slabs may turn into a double slab instead
+    if matches!(kind, Kind::Slab(_)) && is_slab(clicked_block) {
+        return vec![];
+    }
+    let (pos, face) = if is_replaceable(clicked_block) {
+        (clicked, Direction::Up)
+    } else {
+        (clicked.shift(face), face)
+    };
+    if !is_replaceable(world.get_block(pos)) {
+        return vec![];
+    }
+    let half = if face == Direction::Down || (face != Direction::Up && at.y > 0.5) {
+        BlockHalf::Top
+    } else {
+        BlockHalf::Bottom
+    };
+    let facing = horizontal_facing(yaw);
+    match kind {
+        Kind::Simple(block) => vec![(pos, block)],
+        Kind::Log(log) => vec![(pos, log(face.axis()))],
+        Kind::Slab(slab) => vec![(pos, slab(half))],
+        Kind::Stairs(stairs) => vec![(pos, stairs(facing, half))],
+        Kind::Door(door) => {
+            let upper = pos.shift(Direction::Up);
+            if !is_replaceable(world.get_block(upper)) {
+                return vec![];
+            }
+            let hinge = door_hinge(world, pos, facing);
+            vec![
+                (pos, door(facing, DoorHalf::Lower, hinge)),
+                (upper, door(facing, DoorHalf::Upper, hinge)),
+            ]
+        }
+        // Torches can't hang from the bottom of a block
+        Kind::Torch if face == Direction::Down => vec![],
+        Kind::Torch => vec![(pos, Block::Torch { facing: face })],
+        // Ladders only hang on the sides of solid blocks
+        Kind::Ladder
+            if face.axis() == Axis::Y
+                || !world
+                    .get_block(pos.shift(face.opposite()))
+                    .get_material()
+                    .should_cull_against =>
+        {
+            vec![]
+        }
+        Kind::Ladder => vec![(
+            pos,
+            Block::Ladder {
+                facing: face,
+                waterlogged: false,
+            },
+        )],
+    }
+}
+
+/// Blocks that do something when used, so clicking them doesn't place
+/// the held block unless sneaking.
+pub fn is_interactable(block: Block) -> bool {
+    matches!(
+        block,
+        Block::Chest { .. }
+            | Block::TrappedChest { .. }
+            | Block::EnderChest { .. }
+            | Block::Furnace { .. }
+            | Block::FurnaceLit { .. }
+            | Block::CraftingTable { .. }
+            | Block::Dispenser { .. }
+            | Block::Dropper { .. }
+            | Block::Hopper { .. }
+            | Block::BrewingStand { .. }
+            | Block::EnchantingTable { .. }
+            | Block::Anvil { .. }
+            | Block::Beacon { .. }
+            | Block::Bed { .. }
+            | Block::WoodenDoor { .. }
+            | Block::SpruceDoor { .. }
+            | Block::BirchDoor { .. }
+            | Block::JungleDoor { .. }
+            | Block::AcaciaDoor { .. }
+            | Block::DarkOakDoor { .. }
+            | Block::TrapDoor { .. }
+            | Block::FenceGate { .. }
+            | Block::SpruceFenceGate { .. }
+            | Block::BirchFenceGate { .. }
+            | Block::JungleFenceGate { .. }
+            | Block::DarkOakFenceGate { .. }
+            | Block::AcaciaFenceGate { .. }
+            | Block::Lever { .. }
+            | Block::StoneButton { .. }
+            | Block::WoodenButton { .. }
+            | Block::NoteBlock { .. }
+            | Block::Jukebox { .. }
+            | Block::Repeater { .. }
+            | Block::RepeaterPowered { .. }
+            | Block::ComparatorUnpowered { .. }
+            | Block::ComparatorPowered { .. }
+            | Block::DaylightDetector { .. }
+            | Block::DaylightDetectorInverted { .. }
+            | Block::Cake { .. }
+            | Block::FlowerPot { .. }
+            | Block::Cauldron { .. }
+            | Block::DragonEgg { .. }
+            | Block::CommandBlock { .. }
+            | Block::RepeatingCommandBlock { .. }
+            | Block::ChainCommandBlock { .. }
+            | Block::StructureBlock { .. }
+            | Block::ShulkerBox { .. }
+            | Block::WhiteShulkerBox { .. }
+            | Block::OrangeShulkerBox { .. }
+            | Block::MagentaShulkerBox { .. }
+            | Block::LightBlueShulkerBox { .. }
+            | Block::YellowShulkerBox { .. }
+            | Block::LimeShulkerBox { .. }
+            | Block::PinkShulkerBox { .. }
+            | Block::GrayShulkerBox { .. }
+            | Block::LightGrayShulkerBox { .. }
+            | Block::CyanShulkerBox { .. }
+            | Block::PurpleShulkerBox { .. }
+            | Block::BlueShulkerBox { .. }
+            | Block::BrownShulkerBox { .. }
+            | Block::GreenShulkerBox { .. }
+            | Block::RedShulkerBox { .. }
+            | Block::BlackShulkerBox { .. }
+    )
+}
+
+/// Blocks that are replaced by a block placed into them.
+fn is_replaceable(block: Block) -> bool {
+    matches!(
+        block,
+        Block::Air {}
+            | Block::CaveAir {}
+            | Block::VoidAir {}
+            | Block::Water { .. }
+            | Block::FlowingWater { .. }
+            | Block::Lava { .. }
+            | Block::FlowingLava { .. }
+            | Block::TallGrass { .. }
+            | Block::DeadBush { .. }
+            | Block::Seagrass { .. }
+            | Block::TallSeagrass { .. }
+            | Block::DoublePlant { .. }
+            | Block::Vine { .. }
+            | Block::Fire { .. }
+            | Block::SoulFire { .. }
+            | Block::StructureVoid { .. }
+            | Block::SnowLayer { layers: 1 }
+    )
+}
+
+fn is_slab(block: Block) -> bool {
+    matches!(
+        block,
+        Block::WoodenSlab { .. }
+            | Block::WoodenSlabFlat { .. }
+            | Block::StoneSlab { .. }
+            | Block::StoneSlab2 { .. }
+            | Block::StoneSlabFlat { .. }
+            | Block::PurpurSlab { .. }
+            | Block::PrismarineSlab { .. }
+    )
+}
+
+/// The horizontal direction the player looks in, as the server works it
+/// out for the blocks that face them.
+fn horizontal_facing(yaw: f64) -> Direction {
+    // The yaw is stored the other way around to the server's
+    let index = (-yaw.to_degrees() / 90.0 + 0.5).floor() as i32 & 3;
+    match index {
+        0 => Direction::South,
+        1 => Direction::West,
+        2 => Direction::North,
+        _ => Direction::East,
+    }
+}
+
+/// Doors open away from the wall next to them, or towards another door
+/// to make a double door.
+fn door_hinge<W: WorldAccess>(world: &W, pos: Position, facing: Direction) -> Side {
+    let left = pos.shift(facing.counter_clockwise());
+    let right = pos.shift(facing.clockwise());
+    let solid = |pos: Position| {
+        [pos, pos.shift(Direction::Up)]
+            .iter()
+            .filter(|pos| world.get_block(**pos).get_material().should_cull_against)
+            .count()
+    };
+    let door = |pos: Position| {
+        [pos, pos.shift(Direction::Up)]
+            .iter()
+            .any(|pos| is_door(world.get_block(*pos)))
+    };
+    if (door(left) && !door(right)) || solid(right) > solid(left) {
+        Side::Right
+    } else {
+        Side::Left
+    }
+}
+
+fn is_door(block: Block) -> bool {
+    matches!(
+        block,
+        Block::WoodenDoor { .. }
+            | Block::IronDoor { .. }
+            | Block::SpruceDoor { .. }
+            | Block::BirchDoor { .. }
+            | Block::JungleDoor { .. }
+            | Block::AcaciaDoor { .. }
+            | Block::DarkOakDoor { .. }
+    )
+}
+
+/// The blocks placed by the items whose placement is worth guessing,
+/// older versions tell variants apart by the item's damage.
+fn kind(item: &Item, flat: bool) -> Option<Kind> {
+    const TREES: [TreeVariant; 6] = [
+        TreeVariant::Oak,
+        TreeVariant::Spruce,
+        TreeVariant::Birch,
+        TreeVariant::Jungle,
+        TreeVariant::Acacia,
+        TreeVariant::DarkOak,
+    ];
+    let damage = item.stack.damage.unwrap_or(0) as usize;
+    let tree = |variant| Kind::Simple(Block::Planks { variant });
+    let stone = |variant| Kind::Simple(Block::Stone { variant });
+    Some(match item.material {
+        Material::Stone => stone(match damage {
+            1 => StoneVariant::Granite,
+            2 => StoneVariant::SmoothGranite,
+            3 => StoneVariant::Diorite,
+            4 => StoneVariant::SmoothDiorite,
+            5 => StoneVariant::Andesite,
+            6 => StoneVariant::SmoothAndesite,
+            _ => StoneVariant::Normal,
+        }),
+        Material::Granite => stone(StoneVariant::Granite),
+        Material::PolishedGranite => stone(StoneVariant::SmoothGranite),
+        Material::Diorite => stone(StoneVariant::Diorite),
+        Material::PolishedDiorite => stone(StoneVariant::SmoothDiorite),
+        Material::Andesite => stone(StoneVariant::Andesite),
+        Material::PolishedAndesite => stone(StoneVariant::SmoothAndesite),
+        Material::Cobblestone => Kind::Simple(Block::Cobblestone {}),
+        Material::MossyCobblestone => Kind::Simple(Block::MossyCobblestone {}),
+        Material::Brick | Material::Bricks => Kind::Simple(Block::BrickBlock {}),
+        Material::Obsidian => Kind::Simple(Block::Obsidian {}),
+        Material::Netherrack => Kind::Simple(Block::Netherrack {}),
+        Material::Glowstone => Kind::Simple(Block::Glowstone {}),
+        Material::Glass => Kind::Simple(Block::Glass {}),
+
+        Material::Wood => tree(*TREES.get(damage)?),
+        Material::OakPlanks => tree(TreeVariant::Oak),
+        Material::SprucePlanks => tree(TreeVariant::Spruce),
+        Material::BirchPlanks => tree(TreeVariant::Birch),
+        Material::JunglePlanks => tree(TreeVariant::Jungle),
+        Material::AcaciaPlanks => tree(TreeVariant::Acacia),
+        Material::DarkOakPlanks => tree(TreeVariant::DarkOak),
+
+        Material::Log => match damage & 3 {
+            0 => log(TreeVariant::Oak),
+            1 => log(TreeVariant::Spruce),
+            2 => log(TreeVariant::Birch),
+            _ => log(TreeVariant::Jungle),
+        },
+        Material::Log2 => match damage & 3 {
+            0 => Kind::Log(Box::new(|axis| Block::Log2 {
+                variant: TreeVariant::Acacia,
+                axis,
+            })),
+            1 => Kind::Log(Box::new(|axis| Block::Log2 {
+                variant: TreeVariant::DarkOak,
+                axis,
+            })),
+            _ => return None,
+        },
+        Material::OakLog => log(TreeVariant::Oak),
+        Material::SpruceLog => log(TreeVariant::Spruce),
+        Material::BirchLog => log(TreeVariant::Birch),
+        Material::JungleLog => log(TreeVariant::Jungle),
+        Material::AcaciaLog => log(TreeVariant::Acacia),
+        Material::DarkOakLog => log(TreeVariant::DarkOak),
+        Material::StrippedOakLog => log(TreeVariant::StrippedOak),
+        Material::StrippedSpruceLog => log(TreeVariant::StrippedSpruce),
+        Material::StrippedBirchLog => log(TreeVariant::StrippedBirch),
+        Material::StrippedJungleLog => log(TreeVariant::StrippedJungle),
+        Material::StrippedAcaciaLog => log(TreeVariant::StrippedAcacia),
+        Material::StrippedDarkOakLog => log(TreeVariant::StrippedDarkOak),
+
+        Material::WoodStep => wood_slab(
+            *[
+                WoodSlabVariant::Oak,
+                WoodSlabVariant::Spruce,
+                WoodSlabVariant::Birch,
+                WoodSlabVariant::Jungle,
+                WoodSlabVariant::Acacia,
+                WoodSlabVariant::DarkOak,
+            ]
+            .get(damage & 7)?,
+            flat,
+        ),
+        Material::OakSlab => wood_slab(WoodSlabVariant::Oak, flat),
+        Material::SpruceSlab => wood_slab(WoodSlabVariant::Spruce, flat),
+        Material::BirchSlab => wood_slab(WoodSlabVariant::Birch, flat),
+        Material::JungleSlab => wood_slab(WoodSlabVariant::Jungle, flat),
+        Material::AcaciaSlab => wood_slab(WoodSlabVariant::Acacia, flat),
+        Material::DarkOakSlab => wood_slab(WoodSlabVariant::DarkOak, flat),
+        Material::Step => stone_slab(
+            *[
+                StoneSlabVariant::Stone,
+                StoneSlabVariant::Sandstone,
+                StoneSlabVariant::PetrifiedWood,
+                StoneSlabVariant::Cobblestone,
+                StoneSlabVariant::Brick,
+                StoneSlabVariant::StoneBrick,
+                StoneSlabVariant::NetherBrick,
+                StoneSlabVariant::Quartz,
+            ]
+            .get(damage & 7)?,
+            flat,
+        ),
+        Material::StoneSlab => stone_slab(StoneSlabVariant::Stone, flat),
+        Material::SmoothStoneSlab => stone_slab(StoneSlabVariant::SmoothStone, flat),
+        Material::SandstoneSlab => stone_slab(StoneSlabVariant::Sandstone, flat),
+        Material::PetrifiedOakSlab => stone_slab(StoneSlabVariant::PetrifiedWood, flat),
+        Material::CobblestoneSlab => stone_slab(StoneSlabVariant::Cobblestone, flat),
+        Material::BrickSlab => stone_slab(StoneSlabVariant::Brick, flat),
+        Material::StoneBrickSlab => stone_slab(StoneSlabVariant::StoneBrick, flat),
+        Material::NetherBrickSlab => stone_slab(StoneSlabVariant::NetherBrick, flat),
+        Material::QuartzSlab => stone_slab(StoneSlabVariant::Quartz, flat),
+
+        Material::WoodStairs | Material::OakStairs => stairs!(OakStairs),
+        Material::SpruceWoodStairs | Material::SpruceStairs => stairs!(SpruceStairs),
+        Material::BirchWoodStairs | Material::BirchStairs => stairs!(BirchStairs),
+        Material::JungleWoodStairs | Material::JungleStairs => stairs!(JungleStairs),
+        Material::AcaciaStairs => stairs!(AcaciaStairs),
+        Material::DarkOakStairs => stairs!(DarkOakStairs),
+        Material::CobblestoneStairs => stairs!(StoneStairs),
+        Material::BrickStairs => stairs!(BrickStairs),
+        Material::SmoothStairs | Material::StoneBrickStairs => stairs!(StoneBrickStairs),
+        Material::SandstoneStairs => stairs!(SandstoneStairs),
+        Material::RedSandstoneStairs => stairs!(RedSandstoneStairs),
+        Material::NetherBrickStairs => stairs!(NetherBrickStairs),
+        Material::QuartzStairs => stairs!(QuartzStairs),
+        Material::PurpurStairs => stairs!(PurpurStairs),
+
+        Material::WoodDoor | Material::OakDoor => door!(WoodenDoor),
+        Material::SpruceDoor | Material::SpruceDoorItem => door!(SpruceDoor),
+        Material::BirchDoor | Material::BirchDoorItem => door!(BirchDoor),
+        Material::JungleDoor | Material::JungleDoorItem => door!(JungleDoor),
+        Material::AcaciaDoor | Material::AcaciaDoorItem => door!(AcaciaDoor),
+        Material::DarkOakDoor | Material::DarkOakDoorItem => door!(DarkOakDoor),
+        Material::IronDoor => door!(IronDoor),
+
+        Material::Torch => Kind::Torch,
+        Material::Ladder => Kind::Ladder,
+        _ => return None,
+    })
+}
+
+fn log(variant: TreeVariant) -> Kind {
+    Kind::Log(Box::new(move |axis| Block::Log { variant, axis }))
+}
+
+fn wood_slab(variant: WoodSlabVariant, flat: bool) -> Kind {
+    if flat {
+        Kind::Slab(Box::new(move |type_| Block::WoodenSlabFlat {
+            type_,
+            waterlogged: false,
+            variant,
+        }))
+    } else {
+        Kind::Slab(Box::new(move |half| Block::WoodenSlab { half, variant }))
+    }
+}
+
+fn stone_slab(variant: StoneSlabVariant, flat: bool) -> Kind {
+    if flat {
+        Kind::Slab(Box::new(move |type_| Block::StoneSlabFlat {
+            type_,
+            waterlogged: false,
+            variant,
+        }))
+    } else {
+        Kind::Slab(Box::new(move |half| Block::StoneSlab { half, variant }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::block::TallGrassVariant;
+    use crate::world::test_world::TestWorld;
+    use leafish_protocol::item::Stack;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    impl TestWorld {
+        /// A single block of stone at the origin to click on.
+        fn stone() -> TestWorld {
+            let mut world = TestWorld::default();
+            world.set(0, 0, 0, STONE);
+            world
+        }
+    }
+
+    const STONE: Block = Block::Stone {
+        variant: StoneVariant::Normal,
+    };
+    const ORIGIN: Position = Position { x: 0, y: 0, z: 0 };
+    /// The middle of the face of the block at the origin
+    const CENTER: Vector3<f64> = Vector3::new(0.5, 0.5, 0.5);
+
+    fn item(material: Material, damage: isize) -> Item {
+        Item {
+            stack: Stack {
+                id: 1,
+                count: 1,
+                damage: Some(damage),
+                tag: None,
+            },
+            material,
+        }
+    }
+
+    /// Places the item against the face of the stone at the origin, hit
+    /// at the height `y` and looking south.
+    fn place(
+        world: &TestWorld,
+        item: &Item,
+        face: Direction,
+        y: f64,
+        flat: bool,
+    ) -> Vec<(Position, Block)> {
+        let at = Vector3::new(0.5, y, 0.5);
+        placement(world, item, ORIGIN, face, at, 0.0, flat)
+    }
+
+    fn stone_slab(type_: BlockHalf) -> Block {
+        Block::StoneSlabFlat {
+            type_,
+            waterlogged: false,
+            variant: StoneSlabVariant::Stone,
+        }
+    }
+
+    #[test]
+    fn slab_half_from_hit() {
+        let world = TestWorld::stone();
+        let slab = item(Material::StoneSlab, 0);
+        assert_eq!(
+            place(&world, &slab, Direction::Up, 1.0, true),
+            vec![(Position::new(0, 1, 0), stone_slab(BlockHalf::Bottom))]
+        );
+        assert_eq!(
+            place(&world, &slab, Direction::Down, 0.0, true),
+            vec![(Position::new(0, -1, 0), stone_slab(BlockHalf::Top))]
+        );
+        // On the side the upper half of the face places a top slab
+        assert_eq!(
+            place(&world, &slab, Direction::North, 0.75, true),
+            vec![(Position::new(0, 0, -1), stone_slab(BlockHalf::Top))]
+        );
+        assert_eq!(
+            place(&world, &slab, Direction::North, 0.25, true),
+            vec![(Position::new(0, 0, -1), stone_slab(BlockHalf::Bottom))]
+        );
+    }
+
+    #[test]
+    fn legacy_slab_from_damage() {
+        let world = TestWorld::stone();
+        assert_eq!(
+            place(
+                &world,
+                &item(Material::Step, 3),
+                Direction::East,
+                0.75,
+                false
+            ),
+            vec![(
+                Position::new(1, 0, 0),
+                Block::StoneSlab {
+                    half: BlockHalf::Top,
+                    variant: StoneSlabVariant::Cobblestone,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn slab_on_slab_is_left_to_the_server() {
+        let mut world = TestWorld::default();
+        world.set(0, 0, 0, stone_slab(BlockHalf::Bottom));
+        assert!(place(
+            &world,
+            &item(Material::StoneSlab, 0),
+            Direction::Up,
+            0.5,
+            true
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn facing_from_yaw() {
+        assert_eq!(horizontal_facing(0.0), Direction::South);
+        assert_eq!(horizontal_facing(-FRAC_PI_2), Direction::West);
+        assert_eq!(horizontal_facing(PI), Direction::North);
+        assert_eq!(horizontal_facing(FRAC_PI_2), Direction::East);
+        // Rounds to the closest direction
+        assert_eq!(horizontal_facing(1.0), Direction::East);
+        assert_eq!(horizontal_facing(0.1), Direction::South);
+    }
+
+    #[test]
+    fn stairs_facing_and_half() {
+        let world = TestWorld::stone();
+        let stairs = item(Material::OakStairs, 0);
+        let placed = |face, y, yaw| {
+            placement(
+                &world,
+                &stairs,
+                ORIGIN,
+                face,
+                Vector3::new(0.5, y, 0.5),
+                yaw,
+                true,
+            )
+        };
+        let oak = |facing, half| Block::OakStairs {
+            facing,
+            half,
+            shape: StairShape::Straight,
+            waterlogged: false,
+        };
+        assert_eq!(
+            placed(Direction::Up, 1.0, PI),
+            vec![(
+                Position::new(0, 1, 0),
+                oak(Direction::North, BlockHalf::Bottom)
+            )]
+        );
+        assert_eq!(
+            placed(Direction::Down, 0.0, FRAC_PI_2),
+            vec![(
+                Position::new(0, -1, 0),
+                oak(Direction::East, BlockHalf::Top)
+            )]
+        );
+        assert_eq!(
+            placed(Direction::West, 0.9, 0.0),
+            vec![(
+                Position::new(-1, 0, 0),
+                oak(Direction::South, BlockHalf::Top)
+            )]
+        );
+    }
+
+    #[test]
+    fn log_axis_from_face() {
+        let world = TestWorld::stone();
+        let log = |axis| Block::Log {
+            variant: TreeVariant::Oak,
+            axis,
+        };
+        let oak = item(Material::OakLog, 0);
+        assert_eq!(
+            place(&world, &oak, Direction::Up, 1.0, true),
+            vec![(Position::new(0, 1, 0), log(Axis::Y))]
+        );
+        assert_eq!(
+            place(&world, &oak, Direction::East, 0.5, true),
+            vec![(Position::new(1, 0, 0), log(Axis::X))]
+        );
+        assert_eq!(
+            place(&world, &oak, Direction::South, 0.5, true),
+            vec![(Position::new(0, 0, 1), log(Axis::Z))]
+        );
+        // The damage picks the tree before 1.13
+        assert_eq!(
+            place(
+                &world,
+                &item(Material::Log, 2),
+                Direction::North,
+                0.5,
+                false
+            ),
+            vec![(
+                Position::new(0, 0, -1),
+                Block::Log {
+                    variant: TreeVariant::Birch,
+                    axis: Axis::Z,
+                }
+            )]
+        );
+    }
+
+    fn door(half: DoorHalf, hinge: Side) -> Block {
+        Block::WoodenDoor {
+            facing: Direction::South,
+            half,
+            hinge,
+            open: false,
+            powered: false,
+        }
+    }
+
+    /// Places a door on top of the stone, looking south.
+    fn place_door(world: &TestWorld) -> Vec<(Position, Block)> {
+        place(world, &item(Material::OakDoor, 0), Direction::Up, 1.0, true)
+    }
+
+    #[test]
+    fn door_has_upper_half() {
+        let world = TestWorld::stone();
+        assert_eq!(
+            place_door(&world),
+            vec![
+                (Position::new(0, 1, 0), door(DoorHalf::Lower, Side::Left)),
+                (Position::new(0, 2, 0), door(DoorHalf::Upper, Side::Left)),
+            ]
+        );
+    }
+
+    #[test]
+    fn door_hinge_away_from_wall() {
+        let mut world = TestWorld::stone();
+        let right = Position::new(0, 1, 0).shift(Direction::South.clockwise());
+        world.set(right.x, 1, right.z, STONE);
+        world.set(right.x, 2, right.z, STONE);
+        assert_eq!(place_door(&world)[0].1, door(DoorHalf::Lower, Side::Right));
+    }
+
+    #[test]
+    fn door_hinge_makes_double_door() {
+        let mut world = TestWorld::stone();
+        let left = Position::new(0, 1, 0).shift(Direction::South.counter_clockwise());
+        world.set(left.x, 1, left.z, door(DoorHalf::Lower, Side::Left));
+        world.set(left.x, 2, left.z, door(DoorHalf::Upper, Side::Left));
+        assert_eq!(place_door(&world)[0].1, door(DoorHalf::Lower, Side::Right));
+    }
+
+    #[test]
+    fn door_needs_room_for_upper_half() {
+        let mut world = TestWorld::stone();
+        world.set(0, 2, 0, STONE);
+        assert!(place_door(&world).is_empty());
+    }
+
+    #[test]
+    fn torch_facing() {
+        let world = TestWorld::stone();
+        let torch = item(Material::Torch, 0);
+        assert_eq!(
+            place(&world, &torch, Direction::East, 0.5, true),
+            vec![(
+                Position::new(1, 0, 0),
+                Block::Torch {
+                    facing: Direction::East
+                }
+            )]
+        );
+        assert_eq!(
+            place(&world, &torch, Direction::Up, 1.0, true),
+            vec![(
+                Position::new(0, 1, 0),
+                Block::Torch {
+                    facing: Direction::Up
+                }
+            )]
+        );
+        assert!(place(&world, &torch, Direction::Down, 0.0, true).is_empty());
+    }
+
+    #[test]
+    fn ladder_facing() {
+        let world = TestWorld::stone();
+        let ladder = item(Material::Ladder, 0);
+        assert_eq!(
+            place(&world, &ladder, Direction::North, 0.5, true),
+            vec![(
+                Position::new(0, 0, -1),
+                Block::Ladder {
+                    facing: Direction::North,
+                    waterlogged: false,
+                }
+            )]
+        );
+        assert!(place(&world, &ladder, Direction::Up, 1.0, true).is_empty());
+        assert!(place(&world, &ladder, Direction::Down, 0.0, true).is_empty());
+    }
+
+    #[test]
+    fn rejected_placements() {
+        let mut world = TestWorld::stone();
+        // Items that aren't guessed at
+        assert!(place(&world, &item(Material::Stick, 0), Direction::Up, 1.0, true).is_empty());
+        // Variants that don't exist
+        assert!(place(&world, &item(Material::Wood, 9), Direction::Up, 1.0, false).is_empty());
+        // Into a block that's in the way
+        world.set(0, 1, 0, STONE);
+        assert!(place(
+            &world,
+            &item(Material::Cobblestone, 0),
+            Direction::Up,
+            1.0,
+            true
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn replaces_clicked_plants() {
+        let mut world = TestWorld::stone();
+        let grass = Position::new(0, 1, 0);
+        world.set(
+            0,
+            1,
+            0,
+            Block::TallGrass {
+                variant: TallGrassVariant::TallGrass,
+            },
+        );
+        // The grass itself is replaced, even when clicking its side
+        assert_eq!(
+            placement(
+                &world,
+                &item(Material::OakLog, 0),
+                grass,
+                Direction::East,
+                CENTER,
+                0.0,
+                true
+            ),
+            vec![(
+                grass,
+                Block::Log {
+                    variant: TreeVariant::Oak,
+                    axis: Axis::Y,
+                }
+            )]
+        );
+    }
+}
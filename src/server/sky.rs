@@ -0,0 +1,80 @@
+use crate::render;
+use crate::render::model;
+use cgmath::{Matrix4, Vector3};
+
+/// The starless sky of the end, a textured box that follows the camera.
+pub struct EndSkyModel {
+    sky: model::ModelKey,
+}
+
+/// Half the width of the box, small enough that its corners stay within
+/// the far plane.
+const SIZE: f32 = 280.0;
+/// The number of times the texture repeats along each face
+const TILES: i32 = 16;
+/// Vanilla darkens the texture to this brightness
+const BRIGHTNESS: f32 = 40.0 / 255.0;
+
+impl EndSkyModel {
+    pub fn new(renderer: &mut render::Renderer) -> EndSkyModel {
+        let tex = render::Renderer::get_texture(renderer.get_textures_ref(), "environment/end_sky");
+        let step = (SIZE * 2.0) / TILES as f32;
+        let mut verts = Vec::with_capacity(6 * (TILES * TILES * 4) as usize);
+        for face in 0..6 {
+            for ta in 0..TILES {
+                for tb in 0..TILES {
+                    let a = -SIZE + ta as f32 * step;
+                    let b = -SIZE + tb as f32 * step;
+                    for &(oa, ob, tx, ty) in &[
+                        (0.0, 0.0, 0.0, 1.0),
+                        (0.0, step, 0.0, 0.0),
+                        (step, 0.0, 1.0, 1.0),
+                        (step, step, 1.0, 0.0),
+                    ] {
+                        let (x, y, z) = EndSkyModel::face_position(face, a + oa, b + ob);
+                        verts.push(model::Vertex {
+                            x,
+                            y,
+                            z,
+                            texture_x: tx,
+                            texture_y: ty,
+                            texture: tex.clone(),
+                            r: 255,
+                            g: 255,
+                            b: 255,
+                            a: 0,
+                            id: 0,
+                        });
+                    }
+                }
+            }
+        }
+        let sky = renderer.model.create_model(model::SUN, vec![verts]);
+        renderer.model.get_model(sky).unwrap().colors[0] =
+            [BRIGHTNESS, BRIGHTNESS, BRIGHTNESS, 1.0];
+        EndSkyModel { sky }
+    }
+
+    /// Maps a position on one of the faces of the box to the box's space.
+    fn face_position(face: usize, a: f32, b: f32) -> (f32, f32, f32) {
+        match face {
+            0 => (a, SIZE, b),
+            1 => (a, -SIZE, b),
+            2 => (a, b, SIZE),
+            3 => (a, b, -SIZE),
+            4 => (SIZE, a, b),
+            _ => (-SIZE, a, b),
+        }
+    }
+
+    pub fn tick(&mut self, renderer: &mut render::Renderer) {
+        let pos = renderer.camera.pos;
+        let sky = renderer.model.get_model(self.sky).unwrap();
+        sky.matrix[0] =
+            Matrix4::from_translation(Vector3::new(pos.x as f32, -pos.y as f32, pos.z as f32));
+    }
+
+    pub fn remove(&mut self, renderer: &mut render::Renderer) {
+        renderer.model.remove_model(self.sky);
+    }
+}
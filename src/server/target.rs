@@ -1,8 +1,11 @@
+use crate::ecs;
+use crate::entity::{Bounds, EntityType, Position as EntityPosition};
 use crate::render;
 use crate::render::model;
 use crate::shared::{Direction, Position};
 use crate::world;
 use crate::world::block;
+use cgmath::InnerSpace;
 use collision::{self, Aabb};
 
 pub struct Info {
@@ -47,7 +50,7 @@ impl Info {
         const LINE_SIZE: f64 = 1.0 / 128.0;
         let tex = render::Renderer::get_texture(renderer.get_textures_ref(), "leafish:solid");
 
-        for bound in bl.get_collision_boxes() {
+        for bound in bl.get_outline_boxes() {
             let bound = bound.add_v(cgmath::Vector3::new(
                 pos.x as f64,
                 pos.y as f64,
@@ -147,7 +150,7 @@ pub fn test_block(
 ) {
     let block = world.get_block(pos);
     let posf = cgmath::Vector3::new(pos.x as f64, pos.y as f64, pos.z as f64);
-    for bound in block.get_collision_boxes() {
+    for bound in block.get_outline_boxes() {
         let bound = bound.add_v(posf);
         if let Some(hit) = intersects_line(bound, s, d) {
             let cursor = hit - posf;
@@ -234,6 +237,44 @@ fn intersects_line(
     Some(coord)
 }
 
+/// Finds the closest of the entities the ray passes through within the
+/// distance, returning its id with the distance to it and where it was
+/// hit relative to its position.
+pub fn trace_entity<I>(
+    m: &ecs::Manager,
+    entities: I,
+    max: f64,
+    s: cgmath::Vector3<f64>,
+    d: cgmath::Vector3<f64>,
+) -> Option<(i32, f64, cgmath::Vector3<f64>)>
+where
+    I: IntoIterator<Item = (i32, ecs::Entity)>,
+{
+    let mut closest = None;
+    for (id, entity) in entities {
+        if !m
+            .get_component_direct::<EntityType>(entity)
+            .is_some_and(|ty| ty.can_be_targeted())
+        {
+            continue;
+        }
+        let (position, bounds) = match (
+            m.get_component_direct::<EntityPosition>(entity),
+            m.get_component_direct::<Bounds>(entity),
+        ) {
+            (Some(position), Some(bounds)) => (position.position, bounds.bounds),
+            _ => continue,
+        };
+        if let Some(hit) = intersects_line(bounds.add_v(position), s, d) {
+            let distance = (hit - s).magnitude();
+            if distance <= max && closest.is_none_or(|(_, closest, _)| distance < closest) {
+                closest = Some((id, distance, hit - position));
+            }
+        }
+    }
+    closest
+}
+
 pub fn trace_ray<F, R>(
     world: &world::World,
     max: f64,
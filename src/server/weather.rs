@@ -0,0 +1,309 @@
+use crate::render;
+use crate::render::model;
+use crate::shared::Position;
+use crate::world;
+use rand::{self, Rng};
+
+/// How far from the camera rain and snow are drawn
+const RADIUS: i32 = 10;
+/// The height of one repeat of the precipitation textures
+const SEGMENT: f32 = 4.0;
+/// How fast the rain and thunder levels change per tick when the server
+/// doesn't fade them itself
+const FADE_PER_TICK: f32 = 0.01;
+/// How many ticks a lightning bolt stays visible
+const BOLT_TICKS: f64 = 10.0;
+
+struct Bolt {
+    pos: (f64, f64, f64),
+    model: Option<model::ModelKey>,
+    ticks_left: f64,
+}
+
+/// Client side weather state and the models used to render it.
+#[derive(Default)]
+pub struct Weather {
+    rain_level: f32,
+    rain_target: f32,
+    thunder_level: f32,
+    thunder_target: f32,
+    ticks: f64,
+    model: Option<model::ModelKey>,
+    bolts: Vec<Bolt>,
+}
+
+impl Weather {
+    pub fn set_raining(&mut self, raining: bool) {
+        if raining {
+            self.rain_target = 1.0;
+        } else {
+            self.rain_target = 0.0;
+            self.thunder_target = 0.0;
+        }
+    }
+
+    pub fn set_rain_level(&mut self, level: f32) {
+        self.rain_target = level.clamp(0.0, 1.0);
+    }
+
+    pub fn set_thunder_level(&mut self, level: f32) {
+        self.thunder_target = level.clamp(0.0, 1.0);
+    }
+
+    /// Returns how much the sky is darkened by rain and thunder, as a
+    /// factor for the brightness of the sky.
+    pub fn sky_brightness(&self) -> f32 {
+        (1.0 - self.rain_level * 5.0 / 16.0) * (1.0 - self.thunder_level * 5.0 / 16.0)
+    }
+
+    /// Greys out a sky colour during storms.
+    pub fn tint_sky(&self, col: (f32, f32, f32)) -> (f32, f32, f32) {
+        let grey = (col.0 * 0.3 + col.1 * 0.59 + col.2 * 0.11) * 0.6;
+        let mix = |col: (f32, f32, f32), amount: f32, grey: f32| {
+            (
+                col.0 * (1.0 - amount) + grey * amount,
+                col.1 * (1.0 - amount) + grey * amount,
+                col.2 * (1.0 - amount) + grey * amount,
+            )
+        };
+        let col = mix(col, self.rain_level * 0.75, grey);
+        mix(col, self.thunder_level * 0.75, grey * 0.2)
+    }
+
+    pub fn strike_lightning(&mut self, x: f64, y: f64, z: f64) {
+        self.bolts.push(Bolt {
+            pos: (x, y, z),
+            model: None,
+            ticks_left: BOLT_TICKS,
+        });
+    }
+
+    fn create_bolt(renderer: &mut render::Renderer, x: f64, y: f64, z: f64) -> model::ModelKey {
+        let tex = render::Renderer::get_texture(renderer.get_textures_ref(), "leafish:solid");
+        let mut rng = rand::thread_rng();
+        let mut verts = vec![];
+        let (mut bx, mut bz) = (x as f32, z as f32);
+        // A jagged line of thin boxes reaching up into the sky
+        for i in 0..8 {
+            let size = 0.3 - i as f32 * 0.02;
+            model::append_box(
+                &mut verts,
+                bx - size / 2.0,
+                y as f32 + i as f32 * 16.0,
+                bz - size / 2.0,
+                size,
+                16.0,
+                size,
+                [
+                    Some(tex.clone()),
+                    Some(tex.clone()),
+                    Some(tex.clone()),
+                    Some(tex.clone()),
+                    Some(tex.clone()),
+                    Some(tex.clone()),
+                ],
+            );
+            bx += rng.gen_range(-1.5..1.5);
+            bz += rng.gen_range(-1.5..1.5);
+        }
+        for vert in &mut verts {
+            vert.r = 115;
+            vert.g = 115;
+            vert.b = 128;
+        }
+        let model = renderer.model.create_model(model::DEFAULT, vec![verts]);
+        let bolt = renderer.model.get_model(model).unwrap();
+        bolt.block_light = 15.0;
+        bolt.sky_light = 15.0;
+        model
+    }
+
+    pub fn tick(&mut self, renderer: &mut render::Renderer, world: &world::World, delta: f64) {
+        let ticks = delta / 3.0;
+        self.ticks += ticks;
+        let step = ticks as f32 * FADE_PER_TICK;
+        self.rain_level = approach(self.rain_level, self.rain_target, step);
+        self.thunder_level = approach(self.thunder_level, self.thunder_target, step);
+
+        self.update_precipitation(renderer, world);
+
+        for bolt in &mut self.bolts {
+            bolt.ticks_left -= ticks;
+            let (x, y, z) = bolt.pos;
+            let key = *bolt
+                .model
+                .get_or_insert_with(|| Weather::create_bolt(renderer, x, y, z));
+            if let Some(model) = renderer.model.get_model(key) {
+                // Flicker while fading out
+                let flicker = if (bolt.ticks_left as i32) % 2 == 0 {
+                    1.0
+                } else {
+                    0.6
+                };
+                model.colors[0][3] = (bolt.ticks_left / BOLT_TICKS) as f32 * flicker;
+            }
+        }
+        self.bolts.retain(|bolt| {
+            if bolt.ticks_left <= 0.0 {
+                if let Some(model) = bolt.model {
+                    renderer.model.remove_model(model);
+                }
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn update_precipitation(&mut self, renderer: &mut render::Renderer, world: &world::World) {
+        let verts = if self.rain_level > 0.0 && world.dimension().has_skylight {
+            self.build_precipitation(renderer, world)
+        } else {
+            vec![]
+        };
+        if verts.is_empty() {
+            if let Some(model) = self.model.take() {
+                renderer.model.remove_model(model);
+            }
+            return;
+        }
+        let key = match self.model {
+            Some(key) => {
+                renderer.model.set_model_verts(key, verts);
+                key
+            }
+            None => {
+                let key = renderer.model.create_model(model::DEFAULT, vec![verts]);
+                self.model = Some(key);
+                key
+            }
+        };
+        let camera = renderer.camera.pos;
+        let pos = Position::new(
+            camera.x.floor() as i32,
+            camera.y.floor() as i32,
+            camera.z.floor() as i32,
+        );
+        let block_light = world.get_block_light(pos) as f32;
+        let sky_light = world.get_sky_light(pos) as f32;
+        let model = renderer.model.get_model(key).unwrap();
+        model.block_light = block_light;
+        model.sky_light = sky_light;
+        model.colors[0][3] = self.rain_level;
+    }
+
+    /// Builds a quad facing the camera for every column around it that
+    /// has rain or snow falling in it.
+    fn build_precipitation(
+        &self,
+        renderer: &mut render::Renderer,
+        world: &world::World,
+    ) -> Vec<model::Vertex> {
+        let rain = render::Renderer::get_texture(renderer.get_textures_ref(), "environment/rain");
+        let snow = render::Renderer::get_texture(renderer.get_textures_ref(), "environment/snow");
+        let camera = renderer.camera.pos;
+        let (cx, cy, cz) = (
+            camera.x.floor() as i32,
+            camera.y as f32,
+            camera.z.floor() as i32,
+        );
+
+        let mut verts = vec![];
+        for dx in -RADIUS..=RADIUS {
+            for dz in -RADIUS..=RADIUS {
+                let dist = ((dx * dx + dz * dz) as f32).sqrt();
+                if dist > RADIUS as f32 {
+                    continue;
+                }
+                let (x, z) = (cx + dx, cz + dz);
+                let ground = match world.get_height(x, z) {
+                    Some(height) => height as f32 + 1.0,
+                    None => continue,
+                };
+                let bottom = ground.max(cy - RADIUS as f32);
+                let top = ground.max(cy + RADIUS as f32);
+                if bottom >= top {
+                    continue;
+                }
+                let biome = match world.get_biome(Position::new(x, ground as i32, z)) {
+                    Some(biome) if biome.has_precipitation => biome,
+                    _ => continue,
+                };
+                let snowing = biome.is_snowy_at(ground as i32);
+
+                // Give every column its own part of the texture and offset
+                let hash = (x.wrapping_mul(3121) ^ z.wrapping_mul(418_711)) as u32;
+                let column = (hash & 3) as f64 * 0.25;
+                let speed = if snowing { 0.05 } else { 0.4 };
+                let offset = ((hash >> 2) & 31) as f32 / 8.0;
+                let scroll = (self.ticks as f32 * speed + offset) % SEGMENT;
+
+                // Face the camera, like a cylindrical billboard
+                let (mut nx, mut nz) = (
+                    x as f32 + 0.5 - camera.x as f32,
+                    z as f32 + 0.5 - camera.z as f32,
+                );
+                let len = (nx * nx + nz * nz).sqrt().max(0.001);
+                nx /= len;
+                nz /= len;
+                let (sx, sz) = (-nz * 0.5, nx * 0.5);
+                let (mx, mz) = (x as f32 + 0.5, z as f32 + 0.5);
+
+                let alpha =
+                    (((1.0 - (dist * dist) / (RADIUS * RADIUS) as f32) * 0.5 + 0.5) * 255.0) as u8;
+                let tex = if snowing { &snow } else { &rain };
+
+                // Stack segments of the texture that slide downwards,
+                // cutting them off at the ends of the column
+                let mut start = ((bottom + scroll) / SEGMENT).floor() * SEGMENT - scroll;
+                while start < top {
+                    let end = start + SEGMENT;
+                    let (lo, hi) = (start.max(bottom), end.min(top));
+                    let (v_lo, v_hi) = (
+                        1.0 - ((lo - start) / SEGMENT) as f64,
+                        1.0 - ((hi - start) / SEGMENT) as f64,
+                    );
+                    for &(side, y, tx, ty) in &[
+                        (-1.0, lo, column, v_lo),
+                        (-1.0, hi, column, v_hi),
+                        (1.0, lo, column + 0.25, v_lo),
+                        (1.0, hi, column + 0.25, v_hi),
+                    ] {
+                        verts.push(model::Vertex {
+                            x: mx + sx * side,
+                            y,
+                            z: mz + sz * side,
+                            texture_x: tx,
+                            texture_y: ty,
+                            texture: tex.clone(),
+                            r: 255,
+                            g: 255,
+                            b: 255,
+                            a: alpha,
+                            id: 0,
+                        });
+                    }
+                    start = end;
+                }
+            }
+        }
+        verts
+    }
+
+    pub fn remove(&mut self, renderer: &mut render::Renderer) {
+        if let Some(model) = self.model.take() {
+            renderer.model.remove_model(model);
+        }
+        for model in self.bolts.drain(..).filter_map(|bolt| bolt.model) {
+            renderer.model.remove_model(model);
+        }
+    }
+}
+
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
+    }
+}
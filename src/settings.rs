@@ -30,6 +30,24 @@ pub const R_VSYNC: console::CVar<bool> = console::CVar {
     default: &|| false,
 };
 
+pub const R_SMOOTH_LIGHTING: console::CVar<bool> = console::CVar {
+    ty: PhantomData,
+    name: "r_smooth_lighting",
+    description: "Toggle to enable/disable smooth lighting and ambient occlusion",
+    mutable: true,
+    serializable: true,
+    default: &|| true,
+};
+
+pub const R_BIOME_BLEND: console::CVar<i64> = console::CVar {
+    ty: PhantomData,
+    name: "r_biome_blend",
+    description: "Radius in blocks over which biome colours are blended (0-7)",
+    mutable: true,
+    serializable: true,
+    default: &|| 2,
+};
+
 pub const CL_MASTER_VOLUME: console::CVar<i64> = console::CVar {
     ty: PhantomData,
     name: "cl_master_volume",
@@ -39,6 +57,15 @@ pub const CL_MASTER_VOLUME: console::CVar<i64> = console::CVar {
     default: &|| 100,
 };
 
+pub const CL_ADVANCED_TOOLTIPS: console::CVar<bool> = console::CVar {
+    ty: PhantomData,
+    name: "cl_advanced_tooltips",
+    description: "Show item ids and durability in item tooltips",
+    mutable: true,
+    serializable: true,
+    default: &|| false,
+};
+
 // https://github.com/SpigotMC/BungeeCord/blob/bda160562792a913cba3a65ba4996de60d0d6d68/proxy/src/main/java/net/md_5/bungee/PlayerSkinConfiguration.java#L20
 pub const S_CAPE: console::CVar<bool> = console::CVar {
     //
@@ -173,7 +200,10 @@ pub fn register_vars(vars: &mut console::Vars) {
     vars.register(R_MAX_FPS);
     vars.register(R_FOV);
     vars.register(R_VSYNC);
+    vars.register(R_SMOOTH_LIGHTING);
+    vars.register(R_BIOME_BLEND);
     vars.register(CL_MASTER_VOLUME);
+    vars.register(CL_ADVANCED_TOOLTIPS);
     vars.register(CL_KEYBIND_FORWARD);
     vars.register(CL_KEYBIND_BACKWARD);
     vars.register(CL_KEYBIND_LEFT);
@@ -15,6 +15,7 @@
 pub mod logo;
 
 use crate::format;
+use crate::model::ItemModel;
 use crate::render;
 use parking_lot::RwLock;
 use std::cell::{RefCell, RefMut};
@@ -838,17 +839,21 @@ element! {
         pub height: f64,
         pub colour: (u8, u8, u8, u8),
         pub texture_coords: (f64, f64, f64, f64),
+        pub model: Option<Arc<ItemModel>>,
         priv last_texture: String,
         priv last_colour: (u8, u8, u8, u8),
         priv last_texture_coords: (f64, f64, f64, f64),
+        priv last_model: Option<Arc<ItemModel>>,
     }
     builder ImageBuilder {
         hardcode last_texture = "".into(),
         hardcode last_colour = (0, 0, 0, 0),
         hardcode last_texture_coords = (0.0, 0.0, 0.0, 0.0),
+        hardcode last_model = None,
         simple texture: String,
         optional colour: (u8, u8, u8, u8) = (255, 255, 255, 255),
         optional texture_coords: (f64, f64, f64, f64) = (0.0, 0.0, 1.0, 1.0),
+        optional model: Option<Arc<ItemModel>> = None,
         noset width: f64 = |b| b.width.expect("Missing required field width"),
         noset height: f64 = |b| b.height.expect("Missing required field height"),
     }
@@ -875,27 +880,34 @@ impl UIElement for Image {
     ) -> &mut [u8] {
         if self.check_rebuild() {
             self.data.clear();
-            let texture = render::Renderer::get_texture(renderer.get_textures_ref(), &self.texture);
-            let mut element = render::ui::UIElement::new(
-                &texture,
-                r.x,
-                r.y,
-                r.w,
-                r.h,
-                self.texture_coords.0,
-                self.texture_coords.1,
-                self.texture_coords.2,
-                self.texture_coords.3,
-            );
-            element.r = self.colour.0;
-            element.g = self.colour.1;
-            element.b = self.colour.2;
-            element.a = self.colour.3;
-            self.data.extend_from_slice(&element.bytes(width, height));
+            let data = if let Some(model) = self.model.as_ref() {
+                Self::model_bytes(model, r, width, height)
+            } else {
+                let texture =
+                    render::Renderer::get_texture(renderer.get_textures_ref(), &self.texture);
+                let mut element = render::ui::UIElement::new(
+                    &texture,
+                    r.x,
+                    r.y,
+                    r.w,
+                    r.h,
+                    self.texture_coords.0,
+                    self.texture_coords.1,
+                    self.texture_coords.2,
+                    self.texture_coords.3,
+                );
+                element.r = self.colour.0;
+                element.g = self.colour.1;
+                element.b = self.colour.2;
+                element.a = self.colour.3;
+                element.bytes(width, height)
+            };
+            self.data.extend_from_slice(&data);
             self.super_draw(renderer, r, sw, sh, width, height, delta);
             self.last_texture = self.texture.clone();
             self.last_colour = self.colour;
             self.last_texture_coords = self.texture_coords;
+            self.last_model = self.model.clone();
         }
         &mut self.data
     }
@@ -908,6 +920,10 @@ impl UIElement for Image {
         self.last_texture != self.texture
             || self.last_colour != self.colour
             || self.last_texture_coords != self.texture_coords
+            || match (&self.last_model, &self.model) {
+                (Some(last), Some(model)) => !Arc::ptr_eq(last, model),
+                (last, model) => last.is_some() != model.is_some(),
+            }
     }
 
     fn tick(&mut self, renderer: &mut render::Renderer) {
@@ -915,6 +931,68 @@ impl UIElement for Image {
     }
 }
 
+impl Image {
+    /// Draws the faces of the model that point towards the screen as it's
+    /// shown in the GUI, furthest first.
+    fn model_bytes(model: &ItemModel, r: &Region, width: f64, height: f64) -> Vec<u8> {
+        let vertices = model.transformed("gui");
+        let mut faces = vec![];
+        for (quad, original) in vertices.chunks_exact(4).zip(model.vertices.chunks_exact(4)) {
+            let corners = [&quad[0], &quad[1], &quad[2], &quad[3]].map(|vert| {
+                (
+                    r.x + r.w * (0.5 + vert.x as f64),
+                    r.y + r.h * (0.5 - vert.y as f64),
+                    vert.texture_x,
+                    vert.texture_y,
+                )
+            });
+            let (a, b, c) = (corners[0], corners[1], corners[2]);
+            let area = (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1);
+            if area <= 0.0 {
+                continue;
+            }
+            let depth = quad.iter().map(|vert| vert.z).sum::<f32>();
+            faces.push((depth, corners, quad, original));
+        }
+        faces.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut data = vec![];
+        for (_, corners, quad, original) in faces {
+            // Tops are lit fully and the sides darker, on top of the sides
+            // along x being darker already
+            let up = {
+                let (a, b, c) = (&original[0], &original[1], &original[2]);
+                let u = (b.x - a.x, b.y - a.y, b.z - a.z);
+                let v = (c.x - a.x, c.y - a.y, c.z - a.z);
+                let n = (
+                    u.1 * v.2 - u.2 * v.1,
+                    u.2 * v.0 - u.0 * v.2,
+                    u.0 * v.1 - u.1 * v.0,
+                );
+                let len = (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt().max(0.0001);
+                (n.1 / len).abs()
+            };
+            let shade = 0.8 + 0.2 * up as f64;
+            let mut element = render::ui::UIElement::new(
+                &quad[0].texture,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+            );
+            element.r = (quad[0].r as f64 * shade) as u8;
+            element.g = (quad[0].g as f64 * shade) as u8;
+            element.b = (quad[0].b as f64 * shade) as u8;
+            data.extend_from_slice(&element.quad_bytes(&corners, width, height));
+        }
+        data
+    }
+}
+
 element! {
     ref BatchRef
     pub struct Batch {
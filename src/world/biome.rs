@@ -1,11 +1,55 @@
-use image::Rgba;
+use std::collections::HashMap;
+
+use image::{DynamicImage, GenericImageView};
 use lazy_static::lazy_static;
+use log::warn;
+
+use crate::nbt;
+
+/// The water colour used by most biomes since 1.13.
+pub const DEFAULT_WATER_COLOR: u32 = 0x3F76E4;
+
+/// Changes the grass colour of a biome after it has been picked from
+/// the colour map.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GrassColorModifier {
+    None,
+    DarkForest,
+    Swamp,
+}
+
+impl GrassColorModifier {
+    fn from_name(name: &str) -> GrassColorModifier {
+        match name {
+            "dark_forest" => GrassColorModifier::DarkForest,
+            "swamp" => GrassColorModifier::Swamp,
+            _ => GrassColorModifier::None,
+        }
+    }
+
+    fn apply(self, col: u32) -> u32 {
+        match self {
+            GrassColorModifier::None => col,
+            GrassColorModifier::DarkForest => ((col & 0xFEFEFE) + 0x28340A) >> 1,
+            // Vanilla picks between two colours using noise, use the
+            // more common one
+            GrassColorModifier::Swamp => 0x6A7039,
+        }
+    }
+}
 
 #[derive(Clone, Copy)]
 pub struct Biome {
     pub id: usize,
     pub temperature: i16,
     pub moisture: i16,
+    pub water_color: u32,
+    pub sky_color: Option<u32>,
+    pub fog_color: Option<u32>,
+    pub grass_color: Option<u32>,
+    pub foliage_color: Option<u32>,
+    pub grass_color_modifier: GrassColorModifier,
+    pub has_precipitation: bool,
 }
 
 impl Biome {
@@ -14,11 +58,63 @@ impl Biome {
             id,
             temperature: t,
             moisture: m * t,
+            water_color: DEFAULT_WATER_COLOR,
+            sky_color: None,
+            fog_color: None,
+            grass_color: None,
+            foliage_color: None,
+            grass_color_modifier: GrassColorModifier::None,
+            has_precipitation: true,
+        }
+    }
+
+    /// Marks a biome where it never rains or snows, like deserts,
+    /// savannas and the nether.
+    const fn dry(self) -> Biome {
+        Biome {
+            has_precipitation: false,
+            ..self
+        }
+    }
+
+    const fn water(self, water_color: u32) -> Biome {
+        Biome {
+            water_color,
+            ..self
+        }
+    }
+
+    const fn fog(self, fog_color: u32) -> Biome {
+        Biome {
+            fog_color: Some(fog_color),
+            ..self
+        }
+    }
+
+    const fn grass_modifier(self, grass_color_modifier: GrassColorModifier) -> Biome {
+        Biome {
+            grass_color_modifier,
+            ..self
         }
     }
 
+    /// Returns the vanilla biome with the given id. Unknown ids are
+    /// treated as plains so that they still get a sensible colour.
     pub fn by_id(id: usize) -> Biome {
-        *BY_ID.get(id).unwrap_or(&INVALID)
+        match BY_ID.get(id) {
+            Some(biome) if biome.id != INVALID.id => *biome,
+            _ => Biome { id, ..PLAINS },
+        }
+    }
+
+    /// Returns whether precipitation at the given height falls as snow.
+    pub fn is_snowy_at(self, y: i32) -> bool {
+        let mut temperature = self.temperature as f32 / 100.0;
+        // It gets colder higher up
+        if y > 64 {
+            temperature -= (y - 64) as f32 * 0.05 / 30.0;
+        }
+        temperature < 0.15
     }
 
     pub fn get_color_index(self) -> usize {
@@ -27,17 +123,131 @@ impl Biome {
         (((1.0 - t) * 255.0) as usize) | ((((1.0 - (m * t)) * 255.0) as usize) << 8)
     }
 
-    pub fn process_color(self, col: Rgba<u8>) -> Rgba<u8> {
-        if self.id == ROOFED_FOREST.id || self.id == ROOFED_FOREST_MOUNTAINS.id {
-            Rgba([
-                ((col.0[0] as u32 + 0x28) / 2) as u8,
-                ((col.0[1] as u32 + 0x34) / 2) as u8,
-                ((col.0[2] as u32 + 0x0A) / 2) as u8,
-                255,
-            ])
+    fn sample_color_map(self, img: &DynamicImage) -> u32 {
+        let color_index = self.get_color_index();
+        let ix = (color_index & 0xFF).min(255);
+        let iy = (color_index >> 8).min(255);
+        let col = img.get_pixel(ix as u32, iy as u32);
+        ((col.0[0] as u32) << 16) | ((col.0[1] as u32) << 8) | col.0[2] as u32
+    }
+
+    /// Returns the colour of grass in this biome, using the colour map
+    /// unless the biome overrides it.
+    pub fn get_grass_color(self, img: &DynamicImage) -> (u8, u8, u8) {
+        let col = self
+            .grass_color
+            .unwrap_or_else(|| self.sample_color_map(img));
+        split_color(self.grass_color_modifier.apply(col))
+    }
+
+    /// Returns the colour of leaves in this biome, using the colour map
+    /// unless the biome overrides it.
+    pub fn get_foliage_color(self, img: &DynamicImage) -> (u8, u8, u8) {
+        split_color(
+            self.foliage_color
+                .unwrap_or_else(|| self.sample_color_map(img)),
+        )
+    }
+
+    /// Returns the tint to apply to the water textures.
+    ///
+    /// The bundled water textures are already blue so the biome's water
+    /// colour (1.13+) is applied relative to the default water colour.
+    pub fn get_water_tint(self, protocol_version: i32) -> (u8, u8, u8) {
+        if protocol_version < 393 {
+            // Before 1.13 only swamps changed the colour of their water
+            if self.id == SWAMPLAND.id || self.id == SWAMPLAND_MOUNTAINS.id {
+                return (0xE0, 0xFF, 0xAE);
+            }
+            return (255, 255, 255);
+        }
+        let channel = |shift: u32| {
+            let col = (self.water_color >> shift) & 0xFF;
+            let def = (DEFAULT_WATER_COLOR >> shift) & 0xFF;
+            ((col * 255) / def).min(255) as u8
+        };
+        (channel(16), channel(8), channel(0))
+    }
+}
+
+fn split_color(col: u32) -> (u8, u8, u8) {
+    ((col >> 16) as u8, (col >> 8) as u8, col as u8)
+}
+
+/// The biomes of the current session.
+///
+/// Since 1.16.2 servers send their biomes as part of the dimension codec,
+/// which allows datapacks to add biomes with their own colours. Servers
+/// that don't send one use the vanilla biome table.
+#[derive(Default)]
+pub struct Registry {
+    biomes: HashMap<usize, Biome>,
+}
+
+impl Registry {
+    /// Parses the `minecraft:worldgen/biome` registry of the dimension codec.
+    pub fn from_codec(codec: &nbt::Tag) -> Registry {
+        let mut registry = Registry::default();
+        let entries = if codec.is_compound() {
+            codec
+                .get("minecraft:worldgen/biome")
+                .filter(|v| v.is_compound())
+                .and_then(|v| v.get("value"))
+                .and_then(|v| v.as_list())
         } else {
-            col
+            None
+        };
+        let entries = match entries {
+            Some(entries) => entries,
+            None => return registry,
+        };
+        for entry in entries {
+            match Registry::parse_biome(entry) {
+                Some(biome) => {
+                    registry.biomes.insert(biome.id, biome);
+                }
+                None => warn!("Skipping malformed biome in dimension codec"),
+            }
         }
+        registry
+    }
+
+    fn parse_biome(entry: &nbt::Tag) -> Option<Biome> {
+        if !entry.is_compound() {
+            return None;
+        }
+        let id = entry.get("id")?.as_int()?;
+        let element = entry.get("element").filter(|v| v.is_compound())?;
+        let effects = element.get("effects").filter(|v| v.is_compound())?;
+        let color = |name: &str| effects.get(name).and_then(|v| v.as_int()).map(|v| v as u32);
+
+        let temperature = element.get("temperature").and_then(|v| v.as_float())?;
+        let downfall = element.get("downfall").and_then(|v| v.as_float())?;
+        Some(Biome {
+            id: id as usize,
+            temperature: (temperature * 100.0) as i16,
+            moisture: (downfall * 100.0) as i16,
+            water_color: color("water_color").unwrap_or(DEFAULT_WATER_COLOR),
+            sky_color: color("sky_color"),
+            fog_color: color("fog_color"),
+            grass_color: color("grass_color"),
+            foliage_color: color("foliage_color"),
+            grass_color_modifier: effects
+                .get("grass_color_modifier")
+                .and_then(|v| v.as_str())
+                .map_or(GrassColorModifier::None, GrassColorModifier::from_name),
+            has_precipitation: element.get("precipitation").and_then(|v| v.as_str())
+                != Some("none"),
+        })
+    }
+
+    /// Returns the biome with the given id, falling back to the vanilla
+    /// table when the server didn't send it.
+    pub fn by_id(&self, id: usize) -> Biome {
+        self.biomes
+            .get(&id)
+            .copied()
+            .unwrap_or_else(|| Biome::by_id(id))
     }
 }
 
@@ -64,22 +274,22 @@ macro_rules! define_biomes {
 define_biomes! {
 pub const OCEAN: Biome = Biome::new(0, 50, 50);
 pub const PLAINS: Biome = Biome::new(1, 80, 40);
-pub const DESERT: Biome = Biome::new(2, 200, 0);
+pub const DESERT: Biome = Biome::new(2, 200, 0).dry();
 pub const EXTREME_HILLS: Biome = Biome::new(3, 20, 30);
 pub const FOREST: Biome = Biome::new(4, 70, 80);
 pub const TAIGA: Biome = Biome::new(5, 5, 80);
-pub const SWAMPLAND: Biome = Biome::new(6, 80, 90);
+pub const SWAMPLAND: Biome = Biome::new(6, 80, 90).water(0x617B64);
 pub const RIVER: Biome = Biome::new(7, 50, 50);
-pub const HELL: Biome = Biome::new(8, 200, 0);
-pub const THE_END: Biome = Biome::new(9, 50, 50);
-pub const FROZEN_OCEAN: Biome = Biome::new(10, 0, 50);
-pub const FROZEN_RIVER: Biome = Biome::new(11, 0, 50);
+pub const HELL: Biome = Biome::new(8, 200, 0).fog(0x330808).dry();
+pub const THE_END: Biome = Biome::new(9, 50, 50).dry();
+pub const FROZEN_OCEAN: Biome = Biome::new(10, 0, 50).water(0x3938C9);
+pub const FROZEN_RIVER: Biome = Biome::new(11, 0, 50).water(0x3938C9);
 pub const ICE_PLAINS: Biome = Biome::new(12, 0, 50);
 pub const ICE_MOUNTAINS: Biome = Biome::new(13, 0, 50);
 pub const MUSHROOM_ISLAND: Biome = Biome::new(14, 90, 100);
 pub const MUSHROOM_ISLAND_SHORE: Biome = Biome::new(15, 90, 100);
 pub const BEACH: Biome = Biome::new(16, 80, 40);
-pub const DESERT_HILLS: Biome = Biome::new(17, 200, 0);
+pub const DESERT_HILLS: Biome = Biome::new(17, 200, 0).dry();
 pub const FOREST_HILLS: Biome = Biome::new(18, 70, 80);
 pub const TAIGA_HILLS: Biome = Biome::new(19, 20, 70);
 pub const EXTREME_HILLS_EDGE: Biome = Biome::new(20, 20, 30);
@@ -91,39 +301,59 @@ pub const STONE_BEACH: Biome = Biome::new(25, 20, 30);
 pub const COLD_BEACH: Biome = Biome::new(26, 5, 30);
 pub const BIRCH_FOREST: Biome = Biome::new(27, 60, 60);
 pub const BIRCH_FOREST_HILLS: Biome = Biome::new(28, 60, 60);
-pub const ROOFED_FOREST: Biome = Biome::new(29, 70, 80);
+pub const ROOFED_FOREST: Biome =
+    Biome::new(29, 70, 80).grass_modifier(GrassColorModifier::DarkForest);
 pub const COLD_TAIGA: Biome = Biome::new(30, -50, 40);
 pub const COLD_TAIGA_HILLS: Biome = Biome::new(31, -50, 40);
 pub const MEGA_TAIGA: Biome = Biome::new(32, 30, 80);
 pub const MEGA_TAIGA_HILLS: Biome = Biome::new(33, 30, 80);
 pub const EXTREME_HILLS_PLUS: Biome = Biome::new(34, 20, 30);
-pub const SAVANNA: Biome = Biome::new(35, 120, 0);
-pub const SAVANNA_PLATEAU: Biome = Biome::new(36, 100, 0);
-pub const MESA: Biome = Biome::new(37, 200, 0);
-pub const MESA_PLATEAU_FOREST: Biome = Biome::new(38, 200, 0);
-pub const MESA_PLATEAU: Biome = Biome::new(39, 200, 0);
+pub const SAVANNA: Biome = Biome::new(35, 120, 0).dry();
+pub const SAVANNA_PLATEAU: Biome = Biome::new(36, 100, 0).dry();
+pub const MESA: Biome = Biome::new(37, 200, 0).dry();
+pub const MESA_PLATEAU_FOREST: Biome = Biome::new(38, 200, 0).dry();
+pub const MESA_PLATEAU: Biome = Biome::new(39, 200, 0).dry();
+pub const SMALL_END_ISLANDS: Biome = Biome::new(40, 50, 50).dry();
+pub const END_MIDLANDS: Biome = Biome::new(41, 50, 50).dry();
+pub const END_HIGHLANDS: Biome = Biome::new(42, 50, 50).dry();
+pub const END_BARRENS: Biome = Biome::new(43, 50, 50).dry();
+pub const WARM_OCEAN: Biome = Biome::new(44, 50, 50).water(0x43D5EE);
+pub const LUKEWARM_OCEAN: Biome = Biome::new(45, 50, 50).water(0x45ADF2);
+pub const COLD_OCEAN: Biome = Biome::new(46, 50, 50).water(0x3D57D6);
+pub const DEEP_WARM_OCEAN: Biome = Biome::new(47, 50, 50).water(0x43D5EE);
+pub const DEEP_LUKEWARM_OCEAN: Biome = Biome::new(48, 50, 50).water(0x45ADF2);
+pub const DEEP_COLD_OCEAN: Biome = Biome::new(49, 50, 50).water(0x3D57D6);
+pub const DEEP_FROZEN_OCEAN: Biome = Biome::new(50, 50, 50).water(0x3938C9);
+pub const THE_VOID: Biome = Biome::new(127, 50, 50).dry();
 
 pub const SUNFLOWER_PLAINS: Biome = Biome::new(129, 80, 40);
-pub const DESERT_MOUNTAIN: Biome = Biome::new(130, 200, 0);
+pub const DESERT_MOUNTAIN: Biome = Biome::new(130, 200, 0).dry();
 pub const EXTREME_HILLS_MOUNTAINS: Biome = Biome::new(131, 20, 30);
 pub const FLOWER_FOREST: Biome = Biome::new(132, 70, 80);
 pub const TAIGA_M: Biome = Biome::new(133, 5, 80);
-pub const SWAMPLAND_MOUNTAINS: Biome = Biome::new(134, 80, 90);
+pub const SWAMPLAND_MOUNTAINS: Biome = Biome::new(134, 80, 90).water(0x617B64);
 pub const ICE_PLAINS_SPIKES: Biome = Biome::new(140, 0, 50);
 pub const JUNGLE_MOUNTAINS: Biome = Biome::new(149, 120, 90);
 pub const JUNGLE_EDGE_MOUNTAINS: Biome = Biome::new(151, 95, 80);
 pub const BIRCH_FOREST_MOUNTAINS: Biome = Biome::new(155, 60, 60);
 pub const BIRCH_FOREST_HILLS_MOUNTAINS: Biome = Biome::new(156, 60, 60);
-pub const ROOFED_FOREST_MOUNTAINS: Biome = Biome::new(157, 70, 80);
+pub const ROOFED_FOREST_MOUNTAINS: Biome =
+    Biome::new(157, 70, 80).grass_modifier(GrassColorModifier::DarkForest);
 pub const COLD_TAIGA_MOUNTAINS: Biome = Biome::new(158, -50, 40);
 pub const MEGA_SPRUCE_TAIGA: Biome = Biome::new(160, 25, 80);
 pub const MEGA_SPRUCE_TAIGA_HILLS: Biome = Biome::new(161, 30, 80);
 pub const EXTREME_HILLS_PLUS_MOUNTAINS: Biome = Biome::new(162, 20, 30);
-pub const SAVANNA_MOUNTAINS: Biome = Biome::new(163, 120, 0);
-pub const SAVANNA_PLATEAU_MOUNTAINS: Biome = Biome::new(164, 100, 0);
-pub const MESA_BRYCE: Biome = Biome::new(165, 200, 0);
-pub const MESA_PLATEAU_FOREST_MOUNTAINS: Biome = Biome::new(166, 200, 0);
-pub const MESA_PLATEAU_MOUNTAINS: Biome = Biome::new(167, 200, 0);
+pub const SAVANNA_MOUNTAINS: Biome = Biome::new(163, 120, 0).dry();
+pub const SAVANNA_PLATEAU_MOUNTAINS: Biome = Biome::new(164, 100, 0).dry();
+pub const MESA_BRYCE: Biome = Biome::new(165, 200, 0).dry();
+pub const MESA_PLATEAU_FOREST_MOUNTAINS: Biome = Biome::new(166, 200, 0).dry();
+pub const MESA_PLATEAU_MOUNTAINS: Biome = Biome::new(167, 200, 0).dry();
+pub const BAMBOO_JUNGLE: Biome = Biome::new(168, 95, 90);
+pub const BAMBOO_JUNGLE_HILLS: Biome = Biome::new(169, 95, 90);
+pub const SOUL_SAND_VALLEY: Biome = Biome::new(170, 200, 0).fog(0x1B4745).dry();
+pub const CRIMSON_FOREST: Biome = Biome::new(171, 200, 0).fog(0x330303).dry();
+pub const WARPED_FOREST: Biome = Biome::new(172, 200, 0).fog(0x1A051A).dry();
+pub const BASALT_DELTAS: Biome = Biome::new(173, 200, 0).fog(0x685F70).dry();
 
 pub const INVALID: Biome = Biome::new(255, 0, 0);
 }
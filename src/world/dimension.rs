@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use log::warn;
+
+use crate::nbt;
+
+/// Selects how the sky of a dimension is rendered.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Effects {
+    Overworld,
+    Nether,
+    End,
+}
+
+impl Effects {
+    fn from_name(name: &str) -> Effects {
+        match name {
+            "minecraft:the_nether" => Effects::Nether,
+            "minecraft:the_end" => Effects::End,
+            _ => Effects::Overworld,
+        }
+    }
+}
+
+/// The properties of a dimension that change how it is rendered.
+#[derive(Clone, Copy, Debug)]
+pub struct DimensionType {
+    pub has_skylight: bool,
+    pub has_ceiling: bool,
+    /// The time of day the sun is frozen at, if any
+    pub fixed_time: Option<i64>,
+    /// The minimum brightness of unlit blocks
+    pub ambient_light: f32,
+    pub effects: Effects,
+}
+
+pub const OVERWORLD: DimensionType = DimensionType {
+    has_skylight: true,
+    has_ceiling: false,
+    fixed_time: None,
+    ambient_light: 0.0,
+    effects: Effects::Overworld,
+};
+
+pub const THE_NETHER: DimensionType = DimensionType {
+    has_skylight: false,
+    has_ceiling: true,
+    fixed_time: Some(18000),
+    ambient_light: 0.1,
+    effects: Effects::Nether,
+};
+
+pub const THE_END: DimensionType = DimensionType {
+    has_skylight: false,
+    has_ceiling: false,
+    fixed_time: Some(6000),
+    ambient_light: 0.0,
+    effects: Effects::End,
+};
+
+impl Default for DimensionType {
+    fn default() -> Self {
+        OVERWORLD
+    }
+}
+
+impl DimensionType {
+    /// Returns the dimension for the numeric ids used before 1.16.
+    pub fn by_id(id: i32) -> DimensionType {
+        match id {
+            -1 => THE_NETHER,
+            1 => THE_END,
+            _ => OVERWORLD,
+        }
+    }
+
+    /// Returns the vanilla dimension with the given name, if any.
+    pub fn by_name(name: &str) -> Option<DimensionType> {
+        match name {
+            "minecraft:overworld" | "minecraft:overworld_caves" => Some(OVERWORLD),
+            "minecraft:the_nether" => Some(THE_NETHER),
+            "minecraft:the_end" => Some(THE_END),
+            _ => None,
+        }
+    }
+
+    /// Parses a dimension type element as sent by 1.16+ servers.
+    ///
+    /// 1.16 and 1.16.1 don't send `effects` so the name of the dimension
+    /// (where present) is used instead.
+    pub fn from_tag(tag: &nbt::Tag) -> DimensionType {
+        if !tag.is_compound() {
+            return OVERWORLD;
+        }
+        let flag = |name: &str, def: bool| {
+            tag.get(name)
+                .and_then(|v| v.as_byte())
+                .map_or(def, |v| v != 0)
+        };
+        let fixed_time = tag
+            .get("fixed_time")
+            .and_then(|v| v.as_long().or_else(|| v.as_int().map(|v| v as i64)));
+        let effects = tag
+            .get("effects")
+            .or_else(|| tag.get("name"))
+            .and_then(|v| v.as_str())
+            .map_or(Effects::Overworld, Effects::from_name);
+        DimensionType {
+            has_skylight: flag("has_skylight", true),
+            has_ceiling: flag("has_ceiling", false),
+            fixed_time,
+            ambient_light: tag
+                .get("ambient_light")
+                .and_then(|v| v.as_float())
+                .unwrap_or(0.0),
+            effects,
+        }
+    }
+}
+
+/// The dimension types sent in the dimension codec of 1.16+ servers.
+#[derive(Default)]
+pub struct Registry {
+    types: HashMap<String, DimensionType>,
+}
+
+impl Registry {
+    /// Parses the dimension types of the codec. 1.16.2 moved them from
+    /// the `dimension` list into the `minecraft:dimension_type` registry.
+    pub fn from_codec(codec: &nbt::Tag) -> Registry {
+        let mut registry = Registry::default();
+        if !codec.is_compound() {
+            return registry;
+        }
+        if let Some(entries) = codec
+            .get("minecraft:dimension_type")
+            .filter(|v| v.is_compound())
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_list())
+        {
+            for entry in entries.iter().filter(|v| v.is_compound()) {
+                let name = entry.get("name").and_then(|v| v.as_str());
+                match (name, entry.get("element")) {
+                    (Some(name), Some(element)) => {
+                        registry
+                            .types
+                            .insert(name.to_owned(), DimensionType::from_tag(element));
+                    }
+                    _ => warn!("Skipping malformed dimension type in dimension codec"),
+                }
+            }
+        } else if let Some(entries) = codec.get("dimension").and_then(|v| v.as_list()) {
+            for entry in entries.iter().filter(|v| v.is_compound()) {
+                match entry.get("name").and_then(|v| v.as_str()) {
+                    Some(name) => {
+                        registry
+                            .types
+                            .insert(name.to_owned(), DimensionType::from_tag(entry));
+                    }
+                    None => warn!("Skipping malformed dimension type in dimension codec"),
+                }
+            }
+        }
+        registry
+    }
+
+    /// Returns the dimension type with the given name, falling back to
+    /// the vanilla dimensions and then the overworld.
+    pub fn by_name(&self, name: &str) -> DimensionType {
+        self.types
+            .get(name)
+            .copied()
+            .or_else(|| DimensionType::by_name(name))
+            .unwrap_or(OVERWORLD)
+    }
+}
@@ -37,7 +37,10 @@ use byteorder::ReadBytesExt;
 use instant::Instant;
 
 pub mod biome;
+pub mod dimension;
 mod storage;
+#[cfg(test)]
+pub mod test_world;
 
 use crate::chunk_builder::CullInfo;
 use crate::world::biome::Biome;
@@ -61,6 +64,8 @@ pub struct World {
     protocol_version: i32,
     pub modded_block_ids: Arc<RwLock<HashMap<usize, String>>>,
     pub id_map: Arc<block::VanillaIDMap>,
+    biome_registry: RwLock<Arc<biome::Registry>>,
+    dimension: RwLock<dimension::DimensionType>,
 }
 
 pub struct LightData {
@@ -127,6 +132,8 @@ impl World {
             light_updates: sender,
             render_list: Arc::new(Default::default()),
             block_entity_actions: unbounded(),
+            biome_registry: Default::default(),
+            dimension: Default::default(),
         }
     }
 
@@ -243,6 +250,42 @@ impl World {
         }
     }
 
+    /// Returns the height of the highest non-air block of the column.
+    pub fn get_height(&self, x: i32, z: i32) -> Option<i32> {
+        self.chunks
+            .clone()
+            .get(&CPos(x >> 4, z >> 4))
+            .map(|chunk| chunk.heightmap[(((z & 0xF) << 4) | (x & 0xF)) as usize] as i32)
+    }
+
+    pub fn get_biome(&self, pos: Position) -> Option<Biome> {
+        let id = self
+            .chunks
+            .clone()
+            .get(&CPos(pos.x >> 4, pos.z >> 4))?
+            .get_biome_id(pos.x & 0xF, pos.y, pos.z & 0xF);
+        Some(self.biome_registry().by_id(id))
+    }
+
+    pub fn biome_registry(&self) -> Arc<biome::Registry> {
+        self.biome_registry.read().clone()
+    }
+
+    pub fn dimension(&self) -> dimension::DimensionType {
+        *self.dimension.read()
+    }
+
+    pub fn set_dimension(&self, dimension: dimension::DimensionType) {
+        *self.dimension.write() = dimension;
+    }
+
+    /// Replaces the biomes of the session, rebuilding every chunk to
+    /// pick up the new colours.
+    pub fn set_biome_registry(&self, registry: biome::Registry) {
+        *self.biome_registry.write() = Arc::new(registry);
+        self.flag_dirty_all();
+    }
+
     fn update_light(&self, pos: Position, ty: LightType) {
         self.light_updates.send(LightUpdate { ty, pos }).unwrap();
     }
@@ -824,7 +867,7 @@ impl World {
         if sec.is_none() {
             return None;
         }
-        return Some(sec.as_ref().unwrap().capture_snapshot(chunk.biomes));
+        return Some(sec.as_ref().unwrap().capture_snapshot(&chunk.biomes));
     }
 
     pub fn unload_chunk(&self, x: i32, z: i32, m: &mut ecs::Manager) {
@@ -901,7 +944,9 @@ impl World {
 
             if new && read_biomes {
                 // read biomes is always true (as param) except for load_chunk_19
-                data.read_exact(&mut chunk.biomes)?;
+                let mut biomes = Box::new([0; 16 * 16]);
+                data.read_exact(&mut biomes[..])?;
+                chunk.biomes = ChunkBiomes::Flat(biomes);
             }
 
             chunk.calculate_heightmap();
@@ -979,7 +1024,12 @@ impl World {
             // Skylight in update skylight packet for 1.14+
         } else {
             data.read_exact(&mut section.block_light.data).unwrap();
-            data.read_exact(&mut section.sky_light.data).unwrap();
+            // Sky light is only sent for dimensions that have one
+            if self.dimension().has_skylight {
+                data.read_exact(&mut section.sky_light.data).unwrap();
+            } else {
+                section.sky_light = nibble::Array::new(16 * 16 * 16);
+            }
         }
     }
 
@@ -1369,9 +1419,20 @@ impl World {
         z: i32,
         new: bool,
         mask: u16,
+        biomes: Option<&[i32]>,
         data: Vec<u8>,
     ) -> Result<(), protocol::Error> {
-        self.load_chunk19_or_115(false, x, z, new, mask, data)
+        self.load_chunk19_or_115(false, x, z, new, mask, data)?;
+        if let Some(biomes) = biomes {
+            if let Some(mut chunk) = self.chunks.clone().get_mut(&CPos(x, z)) {
+                let mut cells = Box::new([0; 1024]);
+                for (cell, id) in cells.iter_mut().zip(biomes.iter()) {
+                    *cell = *id as u16;
+                }
+                chunk.biomes = ChunkBiomes::Volume(cells);
+            }
+        }
+        Ok(())
     }
 
     #[allow(clippy::or_fun_call)]
@@ -1424,7 +1485,7 @@ pub struct Chunk {
 
     pub(crate) sections: [Option<Section>; 16],
     sections_rendered_on: [u32; 16],
-    biomes: [u8; 16 * 16],
+    biomes: ChunkBiomes,
 
     heightmap: [u8; 16 * 16],
     heightmap_dirty: bool,
@@ -1441,7 +1502,7 @@ impl Chunk {
                 None, None,
             ],
             sections_rendered_on: [0; 16],
-            biomes: [0; 16 * 16],
+            biomes: ChunkBiomes::Flat(Box::new([0; 16 * 16])),
             heightmap: [0; 16 * 16],
             heightmap_dirty: true,
             block_entities: HashMap::with_hasher(BuildHasherDefault::default()),
@@ -1576,10 +1637,8 @@ impl Chunk {
         }
     }
 
-    // TODO: make use of "get_biome"
-    #[allow(dead_code)]
-    fn get_biome(&self, x: i32, z: i32) -> biome::Biome {
-        biome::Biome::by_id(self.biomes[((z << 4) | x) as usize] as usize)
+    fn get_biome_id(&self, x: i32, y: i32, z: i32) -> usize {
+        self.biomes.get(x, y, z)
     }
 
     pub fn capture_snapshot(&self) -> ChunkSnapshot {
@@ -1590,13 +1649,13 @@ impl Chunk {
         for section in self.sections.iter().enumerate() {
             if section.1.is_some() {
                 snapshot_sections[section.0] =
-                    Some(section.1.as_ref().unwrap().capture_snapshot(self.biomes));
+                    Some(section.1.as_ref().unwrap().capture_snapshot(&self.biomes));
             }
         }
         ChunkSnapshot {
             position: self.position,
             sections: snapshot_sections,
-            biomes: self.biomes,
+            biomes: self.biomes.clone(),
             heightmap: self.heightmap,
         }
     }
@@ -1605,10 +1664,45 @@ impl Chunk {
 pub struct ChunkSnapshot {
     pub position: CPos,
     pub sections: [Option<SectionSnapshot>; 16],
-    pub biomes: [u8; 16 * 16],
+    pub biomes: ChunkBiomes,
     pub heightmap: [u8; 16 * 16],
 }
 
+/// The biome ids of a chunk column.
+///
+/// Servers before 1.15 send a single biome per column, newer servers
+/// send a biome per 4x4x4 cell.
+#[derive(Clone)]
+pub enum ChunkBiomes {
+    Flat(Box<[u8; 16 * 16]>),
+    Volume(Box<[u16; 1024]>),
+}
+
+impl ChunkBiomes {
+    pub fn get(&self, x: i32, y: i32, z: i32) -> usize {
+        match self {
+            ChunkBiomes::Flat(biomes) => biomes[((z << 4) | x) as usize] as usize,
+            ChunkBiomes::Volume(biomes) => {
+                let y = y.clamp(0, 255);
+                biomes[(((y >> 2) << 4) | ((z >> 2) << 2) | (x >> 2)) as usize] as usize
+            }
+        }
+    }
+
+    /// Returns the biomes of a single section in the layout used by
+    /// `SectionSnapshot`.
+    fn capture_section(&self, section_y: u8) -> [u16; 16 * 16 * 4] {
+        let mut biomes = [0; 16 * 16 * 4];
+        for (i, biome) in biomes.iter_mut().enumerate() {
+            let x = (i & 0xF) as i32;
+            let z = ((i >> 4) & 0xF) as i32;
+            let y = ((section_y as i32) << 4) | (((i >> 8) as i32) << 2);
+            *biome = self.get(x, y, z) as u16;
+        }
+        biomes
+    }
+}
+
 pub struct Section {
     pub cull_info: chunk_builder::CullInfo,
     pub render_buffer: Arc<RwLock<render::ChunkBuffer>>,
@@ -1646,13 +1740,13 @@ impl Section {
         }
     }
 
-    pub fn capture_snapshot(&self, biomes: [u8; 16 * 16]) -> SectionSnapshot {
+    pub fn capture_snapshot(&self, biomes: &ChunkBiomes) -> SectionSnapshot {
         SectionSnapshot {
             y: self.y,
             blocks: self.blocks.clone(),
             block_light: self.block_light.clone(),
             sky_light: self.sky_light.clone(),
-            biomes,
+            biomes: biomes.capture_section(self.y),
         }
     }
 
@@ -1694,7 +1788,8 @@ pub struct SectionSnapshot {
     pub blocks: storage::BlockStorage,
     pub block_light: nibble::Array,
     pub sky_light: nibble::Array,
-    pub biomes: [u8; 16 * 16], // TODO: Remove this by using the chunk's biome!
+    /// Biome ids indexed by `(y >> 2) << 8 | z << 4 | x`
+    pub biomes: [u16; 16 * 16 * 4],
 }
 
 lazy_static! {
@@ -1703,7 +1798,7 @@ lazy_static! {
         blocks: storage::BlockStorage::new(16 * 16 * 16),
         block_light: nibble::Array::new(16 * 16 * 16),
         sky_light: nibble::Array::new_def(16 * 16 * 16, 0xF),
-        biomes: [0; 16 * 16], // TODO: Verify this!
+        biomes: [0; 16 * 16 * 4], // TODO: Verify this!
     };
 }
 
@@ -1720,8 +1815,8 @@ impl SectionSnapshot {
         self.sky_light.get(((y << 8) | (z << 4) | x) as usize)
     }
 
-    pub fn get_biome(&self, x: i32, z: i32) -> biome::Biome {
-        biome::Biome::by_id(self.biomes[((z << 4) | x) as usize] as usize)
+    pub fn get_biome_id(&self, x: i32, y: i32, z: i32) -> usize {
+        self.biomes[(((y >> 2) << 8) | (z << 4) | x) as usize] as usize
     }
 }
 
@@ -1729,6 +1824,8 @@ impl SectionSnapshot {
 #[allow(dead_code)]
 pub struct ComposedSection {
     sections: [Option<SectionSnapshot>; 27],
+    pub protocol_version: i32,
+    biome_registry: Arc<biome::Registry>,
     x: i32,
     y: i32,
     z: i32,
@@ -1753,7 +1850,7 @@ impl ComposedSection {
                         } else {
                             let section = &chunk.sections[(y + yo) as usize].as_ref();
                             if let Some(section) = section {
-                                Some(section.capture_snapshot(chunk.biomes))
+                                Some(section.capture_snapshot(&chunk.biomes))
                             } else {
                                 Some(EMPTY_SECTION.clone())
                             }
@@ -1767,6 +1864,8 @@ impl ComposedSection {
         }
         ComposedSection {
             sections,
+            protocol_version: world.protocol_version,
+            biome_registry: world.biome_registry(),
             x: -(expand_by as i32),
             y: -(expand_by as i32),
             z: -(expand_by as i32),
@@ -1812,13 +1911,20 @@ impl ComposedSection {
         section.map_or(16, |s| s.get_sky_light(x, y, z))
     }
 
-    pub fn get_biome(&self, x: i32, z: i32) -> biome::Biome {
+    pub fn get_biome(&self, x: i32, y: i32, z: i32) -> biome::Biome {
         let chunk_x = ComposedSection::cmp(x & !15, 0);
         let chunk_z = ComposedSection::cmp(z & !15, 0);
-        let section = self.sections[((chunk_x + 1) + (chunk_z + 1) * 3) as usize].as_ref();
+        let chunk_y = ComposedSection::cmp(y & !15, 0);
+        let section = self.sections
+            [((chunk_x + 1) + (chunk_z + 1) * 3 + (chunk_y + 1) * 3 * 3) as usize]
+            .as_ref()
+            // Sections outside of the world still need a biome for tinting
+            .or_else(|| self.sections[((chunk_x + 1) + (chunk_z + 1) * 3 + 9) as usize].as_ref());
         let x = if x < 0 { 16 + x } else { x & 15 };
+        let y = if y < 0 { 16 + y } else { y & 15 };
         let z = if z < 0 { 16 + z } else { z & 15 };
-        section.map_or(Biome::by_id(0), |s| s.get_biome(x, z))
+        let id = section.map_or(0, |s| s.get_biome_id(x, y, z));
+        self.biome_registry.by_id(id)
     }
 
     #[inline]
@@ -0,0 +1,23 @@
+//! A stand-in for the world in tests of code that only reads blocks.
+
+use crate::shared::Position;
+use crate::world::block::{Block, WorldAccess};
+use std::collections::HashMap;
+
+/// A world made of the given blocks, with air everywhere else.
+#[derive(Default)]
+pub struct TestWorld {
+    blocks: HashMap<Position, Block>,
+}
+
+impl TestWorld {
+    pub fn set(&mut self, x: i32, y: i32, z: i32, block: Block) {
+        self.blocks.insert(Position::new(x, y, z), block);
+    }
+}
+
+impl WorldAccess for TestWorld {
+    fn get_block(&self, pos: Position) -> Block {
+        self.blocks.get(&pos).cloned().unwrap_or(Block::Air {})
+    }
+}